@@ -0,0 +1,539 @@
+//! 命令管理模块
+//! 个人命令（config.json）和项目共享命令（.devpanel.json）的新建、编辑、删除、排序和转换
+
+use super::{AppMode, AppState, CommandFormField, CommandFormState, ConfirmAction};
+use crate::config::{RunTarget, PROJECT_CONFIG_FILE_NAME};
+use crate::i18n::Language;
+use crate::project::{parse_steps, CommandEntry, CommandSource, CommandType};
+use std::collections::BTreeMap;
+
+/// 解析环境变量输入（`KEY=value; KEY2=value`），出错时返回无效的条目
+/// 值中包含 `;` 时用引号括起来（`KEY="a;b"` 或 `KEY='a;b'`），两端的引号会被去掉
+pub fn parse_env(input: &str) -> Result<BTreeMap<String, String>, String> {
+    let mut env = BTreeMap::new();
+    for entry in split_env_entries(input)
+        .iter()
+        .map(|e| e.trim())
+        .filter(|e| !e.is_empty())
+    {
+        match entry.split_once('=') {
+            Some((key, value)) if !key.trim().is_empty() && !key.trim().contains(' ') => {
+                env.insert(key.trim().to_string(), unquote(value.trim()).to_string());
+            }
+            _ => return Err(entry.to_string()),
+        }
+    }
+    Ok(env)
+}
+
+/// 按引号外的 `;` 拆分条目
+fn split_env_entries(input: &str) -> Vec<String> {
+    let mut entries = vec![String::new()];
+    let mut quote = None;
+    for c in input.chars() {
+        match (quote, c) {
+            (None, ';') => entries.push(String::new()),
+            (None, '"' | '\'') => quote = Some(c),
+            (Some(q), _) if q == c => quote = None,
+            _ => {}
+        }
+        if quote.is_some() || c != ';' {
+            if let Some(entry) = entries.last_mut() {
+                entry.push(c);
+            }
+        }
+    }
+    entries
+}
+
+/// 去掉值两端成对的引号
+fn unquote(value: &str) -> &str {
+    ['"', '\'']
+        .into_iter()
+        .find_map(|q| {
+            value
+                .strip_prefix(q)
+                .and_then(|v| v.strip_suffix(q))
+                .filter(|_| value.len() >= 2)
+        })
+        .unwrap_or(value)
+}
+
+/// 环境变量转换为表单中的文本（包含 `;` 或以引号开头的值加上引号）
+pub fn format_env(env: &BTreeMap<String, String>) -> String {
+    env.iter()
+        .map(|(key, value)| {
+            if !value.contains(';') && !value.starts_with(['"', '\'']) {
+                format!("{}={}", key, value)
+            } else if value.contains('"') {
+                format!("{}='{}'", key, value)
+            } else {
+                format!("{}=\"{}\"", key, value)
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+impl AppState {
+    /// 命令管理中的条目：个人命令在前，项目共享命令在后
+    pub fn manager_entries(&self) -> Vec<(CommandSource, usize)> {
+        let Some(project) = self.active_project() else {
+            return Vec::new();
+        };
+        [CommandSource::Personal, CommandSource::Shared]
+            .into_iter()
+            .flat_map(|source| (0..project.commands(source).len()).map(move |idx| (source, idx)))
+            .collect()
+    }
+
+    /// 命令管理中条目对应的命令
+    pub fn manager_command(&self, source: CommandSource, idx: usize) -> Option<&CommandEntry> {
+        self.active_project()?.commands(source).get(idx)
+    }
+
+    /// 命令管理中选中的条目
+    pub fn selected_manager_entry(&self) -> Option<(CommandSource, usize)> {
+        self.manager_entries()
+            .get(self.command_manager_idx)
+            .copied()
+    }
+
+    /// 打开命令管理
+    pub fn open_command_manager(&mut self) {
+        if self.active_project().is_none() {
+            let msg = self.i18n().no_project().to_string();
+            self.set_status(&msg);
+            return;
+        }
+        self.command_manager_idx = 0;
+        self.mode = AppMode::CommandManager;
+    }
+
+    /// 命令管理选择下一项
+    pub fn command_manager_next(&mut self) {
+        let count = self.manager_entries().len();
+        if count > 0 {
+            self.command_manager_idx = (self.command_manager_idx + 1) % count;
+        }
+    }
+
+    /// 命令管理选择上一项
+    pub fn command_manager_prev(&mut self) {
+        let count = self.manager_entries().len();
+        if count > 0 {
+            self.command_manager_idx = (self.command_manager_idx + count - 1) % count;
+        }
+    }
+
+    /// 选中指定条目
+    fn select_manager_entry(&mut self, entry: (CommandSource, usize)) {
+        if let Some(pos) = self.manager_entries().iter().position(|e| *e == entry) {
+            self.command_manager_idx = pos;
+        }
+    }
+
+    /// 打开命令表单（editing 为 None 时新建个人命令）
+    pub fn open_command_form(
+        &mut self,
+        editing: Option<(CommandSource, usize)>,
+        from_manager: bool,
+    ) {
+        if self.active_project().is_none() {
+            let msg = self.i18n().no_project().to_string();
+            self.set_status(&msg);
+            return;
+        }
+        let existing = editing.and_then(|(source, idx)| self.manager_command(source, idx));
+        let form = match existing {
+            Some(cmd) => CommandFormState {
+                source: editing
+                    .map(|(source, _)| source)
+                    .unwrap_or(CommandSource::Personal),
+                editing: Some(cmd.name.clone()),
+                name: cmd.name.clone(),
+                cmd_type: cmd.cmd_type.clone(),
+                command: cmd.command.clone(),
+                cwd: cmd.cwd.clone().unwrap_or_default(),
+                target: cmd.target,
+                env: format_env(&cmd.env),
+                description: cmd.description.clone().unwrap_or_default(),
+                focus: 0,
+                from_manager,
+                error: None,
+            },
+            None => CommandFormState {
+                source: CommandSource::Personal,
+                editing: None,
+                name: String::new(),
+                cmd_type: CommandType::RawShell,
+                command: String::new(),
+                cwd: String::new(),
+                target: None,
+                env: String::new(),
+                description: String::new(),
+                focus: 0,
+                from_manager,
+                error: None,
+            },
+        };
+        self.command_form = Some(form);
+        self.mode = AppMode::CommandForm;
+    }
+
+    /// 表单切换到下一个字段
+    pub fn command_form_next_field(&mut self) {
+        if let Some(form) = self.command_form.as_mut() {
+            form.focus = (form.focus + 1) % CommandFormField::ALL.len();
+        }
+    }
+
+    /// 表单切换到上一个字段
+    pub fn command_form_prev_field(&mut self) {
+        if let Some(form) = self.command_form.as_mut() {
+            let count = CommandFormField::ALL.len();
+            form.focus = (form.focus + count - 1) % count;
+        }
+    }
+
+    /// 切换聚焦的选择字段
+    /// 类型：Shell → 顺序 → 并行；运行位置：自动 → Dev → Shell
+    pub fn command_form_cycle(&mut self, forward: bool) {
+        let Some(form) = self.command_form.as_mut() else {
+            return;
+        };
+        match form.focused_field() {
+            CommandFormField::Kind => {
+                form.cmd_type = match (&form.cmd_type, forward) {
+                    (CommandType::Sequence, true) | (CommandType::RawShell, false) => {
+                        CommandType::Parallel
+                    }
+                    (CommandType::Parallel, false) | (CommandType::RawShell, true) => {
+                        CommandType::Sequence
+                    }
+                    _ => CommandType::RawShell,
+                };
+                form.error = None;
+            }
+            CommandFormField::Target => {
+                form.target = match (form.target, forward) {
+                    (None, true) | (Some(RunTarget::Shell), false) => Some(RunTarget::Dev),
+                    (Some(RunTarget::Dev), true) | (None, false) => Some(RunTarget::Shell),
+                    (Some(RunTarget::Shell), true) | (Some(RunTarget::Dev), false) => None,
+                };
+            }
+            _ => {}
+        }
+    }
+
+    /// 编辑表单中聚焦的文本字段
+    pub fn command_form_edit(&mut self, f: impl FnOnce(&mut String)) {
+        if let Some(form) = self.command_form.as_mut() {
+            let field = form.focused_field();
+            if let Some(text) = form.text_mut(field) {
+                f(text);
+                form.error = None;
+            }
+        }
+    }
+
+    /// 取消表单
+    pub fn cancel_command_form(&mut self) {
+        let from_manager = self
+            .command_form
+            .take()
+            .map(|f| f.from_manager)
+            .unwrap_or(false);
+        self.exit_mode();
+        if from_manager {
+            self.mode = AppMode::CommandManager;
+        }
+    }
+
+    /// 校验并保存表单，失败时在表单中显示错误
+    pub fn save_command_form(&mut self) {
+        let Some(form) = self.command_form.clone() else {
+            return;
+        };
+        let name = form.name.trim().to_string();
+        let command = form.command.trim().to_string();
+        let error = if name.is_empty() {
+            Some(self.form_error_message(FormError::NameRequired))
+        } else if command.is_empty() {
+            Some(self.form_error_message(FormError::CommandRequired))
+        } else if self.is_duplicate_command_name(&name, form.source, form.editing.as_deref()) {
+            Some(self.form_error_message(FormError::DuplicateName(&name)))
+        } else if form
+            .cmd_type
+            .composite_mode()
+            .is_some_and(|mode| parse_steps(&command, mode).contains(&name))
+        {
+            Some(self.form_error_message(FormError::SelfStep))
+        } else {
+            None
+        };
+        if let Some(msg) = error {
+            self.set_form_error(msg);
+            return;
+        }
+        let env = match parse_env(&form.env) {
+            Ok(env) => env,
+            Err(entry) => {
+                let msg = self.form_error_message(FormError::InvalidEnv(&entry));
+                self.set_form_error(msg);
+                return;
+            }
+        };
+
+        let non_empty = |s: &str| Some(s.trim().to_string()).filter(|s| !s.is_empty());
+        let Some(project) = self.active_project_mut() else {
+            return;
+        };
+        let commands = project.commands_mut(form.source);
+        let editing_idx = form
+            .editing
+            .as_ref()
+            .and_then(|old| commands.iter().position(|cmd| &cmd.name == old));
+        let existing = editing_idx.map(|idx| &commands[idx]);
+        let entry = CommandEntry {
+            cwd: non_empty(&form.cwd),
+            target: form.target,
+            env,
+            description: non_empty(&form.description),
+            ..match existing {
+                // 保留原有 ID
+                Some(cmd) => CommandEntry {
+                    name: name.clone(),
+                    command,
+                    cmd_type: form.cmd_type,
+                    ..cmd.clone()
+                },
+                None => CommandEntry {
+                    cmd_type: form.cmd_type,
+                    ..CommandEntry::new_raw_shell(&name, &command)
+                },
+            }
+        };
+        let idx = match editing_idx {
+            Some(idx) => {
+                commands[idx] = entry;
+                idx
+            }
+            None => {
+                commands.push(entry);
+                commands.len() - 1
+            }
+        };
+
+        self.command_form = None;
+        self.persist_commands(form.source);
+        self.exit_mode();
+        if form.from_manager {
+            self.mode = AppMode::CommandManager;
+            self.select_manager_entry((form.source, idx));
+        }
+        let msg = match self.language() {
+            Language::English => format!("Saved command: {}", name),
+            Language::Chinese => format!("已保存命令: {}", name),
+        };
+        self.set_status(&msg);
+    }
+
+    fn set_form_error(&mut self, msg: String) {
+        if let Some(form) = self.command_form.as_mut() {
+            form.error = Some(msg);
+        }
+    }
+
+    /// 名称是否与项目中其他命令重复
+    fn is_duplicate_command_name(
+        &self,
+        name: &str,
+        source: CommandSource,
+        editing: Option<&str>,
+    ) -> bool {
+        let Some(project) = self.active_project() else {
+            return false;
+        };
+        let in_scripts = project.scripts.contains_key(name);
+        in_scripts
+            || [CommandSource::Personal, CommandSource::Shared]
+                .into_iter()
+                .any(|s| {
+                    project.commands(s).iter().any(|cmd| {
+                        cmd.name == name && !(s == source && Some(cmd.name.as_str()) == editing)
+                    })
+                })
+    }
+
+    fn form_error_message(&self, error: FormError) -> String {
+        match (self.language(), error) {
+            (Language::English, FormError::NameRequired) => "Name is required".to_string(),
+            (Language::English, FormError::CommandRequired) => "Command is required".to_string(),
+            (Language::English, FormError::DuplicateName(name)) => {
+                format!("A command named '{}' already exists", name)
+            }
+            (Language::English, FormError::InvalidEnv(entry)) => {
+                format!("Invalid env entry '{}', expected KEY=value", entry)
+            }
+            (Language::English, FormError::SelfStep) => {
+                "A composite command cannot include itself".to_string()
+            }
+            (Language::Chinese, FormError::NameRequired) => "名称不能为空".to_string(),
+            (Language::Chinese, FormError::CommandRequired) => "命令不能为空".to_string(),
+            (Language::Chinese, FormError::DuplicateName(name)) => {
+                format!("已存在名为 '{}' 的命令", name)
+            }
+            (Language::Chinese, FormError::InvalidEnv(entry)) => {
+                format!("环境变量 '{}' 无效，格式应为 KEY=value", entry)
+            }
+            (Language::Chinese, FormError::SelfStep) => "组合命令不能包含自身".to_string(),
+        }
+    }
+
+    /// 请求删除选中的命令（先弹出确认框）
+    pub fn request_delete_command(&mut self) {
+        let Some((source, idx)) = self.selected_manager_entry() else {
+            return;
+        };
+        let Some(name) = self.manager_command(source, idx).map(|c| c.name.clone()) else {
+            return;
+        };
+        let msg = match self.language() {
+            Language::English => format!("Delete command '{}'?", name),
+            Language::Chinese => format!("确定删除命令 '{}' 吗？", name),
+        };
+        self.confirm_action = ConfirmAction::DeleteCommand(source, name);
+        self.mode = AppMode::Confirm(msg);
+    }
+
+    /// 删除命令（按名称查找，确认期间命令列表可能已被重新加载）
+    pub fn delete_command(&mut self, source: CommandSource, name: &str) {
+        let Some(project) = self.active_project_mut() else {
+            return;
+        };
+        let commands = project.commands_mut(source);
+        let Some(idx) = commands.iter().position(|cmd| cmd.name == name) else {
+            return;
+        };
+        let removed = commands.remove(idx);
+        self.persist_commands(source);
+        let count = self.manager_entries().len();
+        self.command_manager_idx = self.command_manager_idx.min(count.saturating_sub(1));
+        let msg = match self.language() {
+            Language::English => format!("Deleted command: {}", removed.name),
+            Language::Chinese => format!("已删除命令: {}", removed.name),
+        };
+        self.set_status(&msg);
+    }
+
+    /// 在同一来源内移动选中的命令（delta 为 -1 上移，1 下移）
+    pub fn move_selected_command(&mut self, delta: isize) {
+        let Some((source, idx)) = self.selected_manager_entry() else {
+            return;
+        };
+        let Some(project) = self.active_project_mut() else {
+            return;
+        };
+        let commands = project.commands_mut(source);
+        let Some(new_idx) = idx
+            .checked_add_signed(delta)
+            .filter(|new_idx| *new_idx < commands.len())
+        else {
+            return;
+        };
+        commands.swap(idx, new_idx);
+        self.persist_commands(source);
+        self.select_manager_entry((source, new_idx));
+    }
+
+    /// 在个人命令和项目共享命令之间转换选中的命令
+    pub fn convert_selected_command(&mut self) {
+        let Some((source, idx)) = self.selected_manager_entry() else {
+            return;
+        };
+        let Some(project) = self.active_project_mut() else {
+            return;
+        };
+        if idx >= project.commands(source).len() {
+            return;
+        }
+        let cmd = project.commands_mut(source).remove(idx);
+        let name = cmd.name.clone();
+        let target = source.other();
+        project.commands_mut(target).push(cmd);
+        let new_idx = project.commands(target).len() - 1;
+        self.persist_commands(source);
+        self.persist_commands(target);
+        self.select_manager_entry((target, new_idx));
+        let msg = match (self.language(), target) {
+            (Language::English, CommandSource::Shared) => {
+                format!("{} moved to .devpanel.json", name)
+            }
+            (Language::English, CommandSource::Personal) => {
+                format!("{} moved to personal commands", name)
+            }
+            (Language::Chinese, CommandSource::Shared) => {
+                format!("{} 已移到 .devpanel.json", name)
+            }
+            (Language::Chinese, CommandSource::Personal) => format!("{} 已移到个人命令", name),
+        };
+        self.set_status(&msg);
+    }
+
+    /// 保存修改后的命令
+    /// 个人命令随配置在退出时保存，项目共享命令立即写入 .devpanel.json
+    fn persist_commands(&mut self, source: CommandSource) {
+        if source != CommandSource::Shared {
+            return;
+        }
+        let Some(project) = self.active_project() else {
+            return;
+        };
+        let path = project.path.join(PROJECT_CONFIG_FILE_NAME);
+        match project.save_local_config() {
+            // 自己写入的修改不触发重新加载
+            Ok(()) => self.file_watcher.mark_current(&path),
+            Err(e) => {
+                log::warn!("Failed to save .devpanel.json: {}", e);
+                self.set_status(&format!("Error: {}", e));
+            }
+        }
+    }
+}
+
+/// 表单校验错误
+enum FormError<'a> {
+    NameRequired,
+    CommandRequired,
+    DuplicateName(&'a str),
+    InvalidEnv(&'a str),
+    /// 组合命令的步骤中包含自身
+    SelfStep,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_env() {
+        let env = parse_env("API_URL=http://localhost:3000?a=b; DEBUG = 1;").unwrap();
+        assert_eq!(env["API_URL"], "http://localhost:3000?a=b");
+        assert_eq!(env["DEBUG"], "1");
+        assert_eq!(
+            format_env(&env),
+            "API_URL=http://localhost:3000?a=b; DEBUG=1"
+        );
+        assert!(parse_env("").unwrap().is_empty());
+        assert_eq!(parse_env("A=1; oops"), Err("oops".to_string()));
+        assert_eq!(parse_env("=1"), Err("=1".to_string()));
+
+        // 引号中的 `;` 不拆分，保存后再编辑时原样还原
+        let env = parse_env(r#"CLASSPATH="a.jar;b.jar"; MSG='say "hi"'; PLAIN=x"#).unwrap();
+        assert_eq!(env["CLASSPATH"], "a.jar;b.jar");
+        assert_eq!(env["MSG"], r#"say "hi""#);
+        assert_eq!(env["PLAIN"], "x");
+        assert_eq!(parse_env(&format_env(&env)), Ok(env));
+    }
+}
@@ -0,0 +1,115 @@
+//! 组合命令运行状态
+//! 处理组合命令步骤的退出、整体结果，以及 Dev 进程的停止
+
+use super::AppState;
+use crate::i18n::Language;
+use crate::project::CompositeResult;
+use crate::pty::StopOutcome;
+
+impl AppState {
+    /// 处理组合命令步骤的进程退出，返回 PTY 是否属于某个组合命令
+    pub fn composite_step_exited(&mut self, pty_id: &str, exit_code: Option<i32>) -> bool {
+        let Some(project_idx) = self.projects.iter_mut().position(|p| {
+            p.composite
+                .as_mut()
+                .is_some_and(|run| run.on_exit(pty_id, exit_code))
+        }) else {
+            return false;
+        };
+        self.finish_composite_if_done(project_idx);
+        true
+    }
+
+    /// 组合命令全部结束时记录整体结果
+    pub fn finish_composite_if_done(&mut self, project_idx: usize) {
+        let language = self.language();
+        let Some(project) = self.projects.get_mut(project_idx) else {
+            return;
+        };
+        let Some(run) = project.composite.as_ref() else {
+            return;
+        };
+        let exit_code = match run.result() {
+            CompositeResult::Running => return,
+            CompositeResult::Succeeded => Some(0),
+            CompositeResult::Failed(code) => Some(code.unwrap_or(1)),
+        };
+        let id = run.id.clone();
+        let msg = match (language, exit_code) {
+            (Language::English, Some(0)) => format!("{} succeeded", run.name),
+            (Language::English, _) => format!(
+                "{} failed: {}",
+                run.name,
+                run.first_error()
+                    .map(str::to_string)
+                    .unwrap_or(run.progress())
+            ),
+            (Language::Chinese, Some(0)) => format!("{} 全部成功", run.name),
+            (Language::Chinese, _) => format!(
+                "{} 失败: {}",
+                run.name,
+                run.first_error()
+                    .map(str::to_string)
+                    .unwrap_or(run.progress())
+            ),
+        };
+        project.mark_dev_stopped();
+        self.notify_exit(&id, exit_code);
+        self.finish_run(&id, exit_code);
+        self.set_status(&msg);
+    }
+
+    /// 停止项目 Dev Terminal 中的进程，返回是否有进程被停止
+    /// 进程在后台按停止序列停止，未结束的组合命令在运行历史中记为被终止
+    pub fn stop_dev(&mut self, project_idx: usize) -> bool {
+        let Some(project) = self.projects.get_mut(project_idx) else {
+            return false;
+        };
+        let unfinished = project
+            .composite
+            .as_ref()
+            .filter(|run| run.is_running())
+            .map(|run| run.id.clone());
+        let stopped = project.stop_dev(&self.config.settings.stop_sequence, &self.pty_tx);
+        if let Some(id) = unfinished {
+            self.finish_run(&id, None);
+        }
+        stopped
+    }
+
+    /// 停止中的进程树已退出：从项目中移除并报告结果
+    pub fn finish_stopping(&mut self, pty_id: &str, outcome: StopOutcome) {
+        // 已关闭的 Shell 标签：关闭时已经提示过
+        if self
+            .projects
+            .iter_mut()
+            .any(|p| p.finish_shell_stopping(pty_id))
+        {
+            return;
+        }
+        let language = self.language();
+        let Some(name) = self.projects.iter_mut().find_map(|p| {
+            p.finish_stopping(pty_id)
+                .then(|| p.display_name().to_string())
+        }) else {
+            return;
+        };
+        let secs = outcome.elapsed.as_secs_f32();
+        let msg = match (language, outcome.signal, outcome.gone) {
+            (_, None, _) => format!("{}: {}", name, self.i18n().dev_stopped()),
+            (Language::English, Some(signal), true) => {
+                format!("{}: stopped after {} ({:.1}s)", name, signal, secs)
+            }
+            (Language::Chinese, Some(signal), true) => {
+                format!("{}: 已停止（{}，{:.1} 秒）", name, signal, secs)
+            }
+            (Language::English, Some(signal), false) => {
+                format!("{}: processes still running after {}", name, signal)
+            }
+            (Language::Chinese, Some(signal), false) => {
+                format!("{}: 发送 {} 后仍有进程未退出", name, signal)
+            }
+        };
+        self.set_status(&msg);
+    }
+}
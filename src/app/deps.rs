@@ -0,0 +1,187 @@
+//! 依赖检查
+//! 定期在后台检查各项目的 node_modules 是否缺失或比 lock 文件旧，在 Dev Terminal 运行脚本前提示先安装依赖；
+//! 包管理器未安装时提示改用 corepack 或 npx 启动
+
+use super::{AppMode, AppState, CommandTarget, ConfirmAction, DependencyCheck};
+use crate::i18n::Language;
+use crate::project::{
+    check_dependencies, lockfile_modified, workspace_root, CommandType, DependencyState,
+    PackageRunner, Project,
+};
+
+impl AppState {
+    /// 项目使用的包管理器（没有 packageManager 字段和 lock 文件时使用默认包管理器设置）
+    pub fn package_runner(&self, project: &Project) -> PackageRunner {
+        PackageRunner {
+            fallback: project.runner_fallback,
+            ..PackageRunner::detect(
+                &project.path,
+                workspace_root(&project.path),
+                &self.config.settings.default_runner,
+            )
+        }
+    }
+
+    /// 在后台重新检查所有项目的依赖状态（需要读取文件元数据和 package.json，不阻塞界面）
+    pub fn refresh_dependency_states(&mut self) {
+        let default_runner = &self.config.settings.default_runner;
+        for project in &self.projects {
+            if !self.deps_checking.insert(project.path.clone()) {
+                continue;
+            }
+            let path = project.path.clone();
+            let acknowledged = project.dependencies_acknowledged;
+            let default_runner = default_runner.clone();
+            let tx = self.deps_tx.clone();
+            std::thread::spawn(move || {
+                // 工作区根目录只查找一次，检测包管理器和检查依赖共用
+                let root = workspace_root(&path);
+                let pm = PackageRunner::detect(&path, root, &default_runner).manager;
+                let state = check_dependencies(&path, root, pm, acknowledged);
+                let _ = tx.send(DependencyCheck {
+                    path,
+                    acknowledged,
+                    state,
+                });
+            });
+        }
+    }
+
+    /// 处理后台依赖检查的结果
+    pub fn handle_dependency_checks(&mut self) {
+        while let Ok(check) = self.deps_rx.try_recv() {
+            self.deps_checking.remove(&check.path);
+            if let Some(project) = self
+                .projects
+                .iter_mut()
+                .find(|p| p.path == check.path && p.dependencies_acknowledged == check.acknowledged)
+            {
+                project.dependencies = check.state;
+            }
+        }
+    }
+
+    /// 命令是否为 package.json 中的脚本
+    fn is_script(&self, command_idx: usize) -> bool {
+        self.active_commands()
+            .get(command_idx)
+            .is_some_and(|cmd| cmd.cmd_type == CommandType::NpmScript)
+    }
+
+    /// 在 Dev Terminal 运行命令前是否需要询问安装依赖（只针对 package.json 中的脚本）
+    pub fn needs_install_prompt(&mut self, command_idx: usize) -> bool {
+        if !self.is_script(command_idx) {
+            return false;
+        }
+        let Some(project) = self.active_project() else {
+            return false;
+        };
+        let root = workspace_root(&project.path);
+        let pm = PackageRunner::detect(&project.path, root, &self.config.settings.default_runner)
+            .manager;
+        // 运行前重新检查，拉取代码后不必等到下一次定期检查
+        let state = check_dependencies(&project.path, root, pm, project.dependencies_acknowledged);
+        if let Some(project) = self.active_project_mut() {
+            project.dependencies = state;
+        }
+        state != DependencyState::Installed
+    }
+
+    /// 弹出安装依赖的确认框
+    pub fn open_install_prompt(&mut self, command_idx: usize) {
+        let Some(project) = self.active_project() else {
+            return;
+        };
+        let install = self.package_runner(project).install_command();
+        let msg = match (self.language(), project.dependencies) {
+            (Language::English, DependencyState::Missing) => {
+                format!("node_modules is missing\nRun {} first?", install)
+            }
+            (Language::English, _) => {
+                format!("Dependencies are out of date\nRun {} first?", install)
+            }
+            (Language::Chinese, DependencyState::Missing) => {
+                format!("缺少 node_modules\n先运行 {}？", install)
+            }
+            (Language::Chinese, _) => format!("依赖已过期\n先运行 {}？", install),
+        };
+        self.confirm_action = ConfirmAction::InstallDependencies(command_idx);
+        self.mode = AppMode::Confirm(msg);
+    }
+
+    /// 用户已回应安装提示（安装或跳过）：当前 lock 文件不再提示依赖过期
+    pub fn acknowledge_dependencies(&mut self) {
+        let Some(project) = self.active_project() else {
+            return;
+        };
+        let root = workspace_root(&project.path);
+        let pm = PackageRunner::detect(&project.path, root, &self.config.settings.default_runner)
+            .manager;
+        let lockfile = lockfile_modified(&project.path, root, pm);
+        if let Some(project) = self.active_project_mut() {
+            project.dependencies_acknowledged = lockfile;
+        }
+    }
+
+    /// 确认安装：下一次在当前项目的 Dev Terminal 运行命令前先安装依赖
+    pub fn request_install_before_dev(&mut self) {
+        self.install_before_dev = self.active_project().map(|p| p.path.clone());
+    }
+
+    /// 取出指定项目的安装请求
+    pub fn take_install_request(&mut self, project_idx: usize) -> bool {
+        let requested = self
+            .projects
+            .get(project_idx)
+            .is_some_and(|p| self.install_before_dev.as_ref() == Some(&p.path));
+        if requested {
+            self.install_before_dev = None;
+        }
+        requested
+    }
+
+    /// 运行脚本前检查包管理器是否已安装
+    /// 未安装时询问改用 corepack 或 npx，都不可用时在状态栏给出提示，返回 false 表示不能直接运行
+    pub fn check_package_runner(&mut self, command_idx: usize, target: CommandTarget) -> bool {
+        if !self.is_script(command_idx) {
+            return true;
+        }
+        let Some(project) = self.active_project() else {
+            return true;
+        };
+        let runner = self.package_runner(project);
+        if runner.is_available() {
+            return true;
+        }
+        let name = runner.manager.name();
+        let Some(fallback) = runner.suggest_fallback() else {
+            let msg = match self.language() {
+                Language::English => format!(
+                    "{} is not installed. Install it, or install Node.js to run it through corepack or npx",
+                    name
+                ),
+                Language::Chinese => format!(
+                    "未安装 {}。请安装它，或安装 Node.js 以通过 corepack 或 npx 运行",
+                    name
+                ),
+            };
+            self.set_status(&msg);
+            return false;
+        };
+        let via = PackageRunner {
+            fallback: Some(fallback),
+            ..runner
+        };
+        let msg = match self.language() {
+            Language::English => format!(
+                "{} is not installed\nRun `{}` instead?",
+                name,
+                via.invocation()
+            ),
+            Language::Chinese => format!("未安装 {}\n改用 `{}` 运行？", name, via.invocation()),
+        };
+        self.confirm_action = ConfirmAction::UseRunnerFallback(command_idx, target, fallback);
+        self.mode = AppMode::Confirm(msg);
+        false
+    }
+}
@@ -142,7 +142,7 @@ impl DirectoryBrowser {
                 .collect();
 
             // 按名称排序
-            entries.sort_by(|a, b| a.name.to_lowercase().cmp(&b.name.to_lowercase()));
+            entries.sort_by_key(|e| e.name.to_lowercase());
 
             // 在顶部添加返回上级目录的选项（如果有上级目录）
             let has_parent = self.current_dir.parent().is_some();
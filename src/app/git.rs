@@ -0,0 +1,396 @@
+//! Git 状态
+//! 在后台线程中刷新各项目的仓库状态，并提供拉取、暂存、切换分支等快捷操作
+//! 仓库的其他工作树可以作为项目添加，继承所属项目的自定义命令和环境文件
+
+use super::{AppMode, AppState, GitEvent, GitTracker};
+use crate::i18n::Language;
+use crate::project::{
+    add_worktree, default_worktree_path, git_signature, list_worktrees, local_branches,
+    read_git_status, run_git_action, GitAction, Project,
+};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+/// 仓库文件没有变化时的刷新间隔（工作区文件的修改不会改变 .git 中的文件）
+const GIT_REFRESH_INTERVAL: Duration = Duration::from_secs(10);
+
+impl AppState {
+    /// 检查各项目是否需要刷新 Git 状态：HEAD、index、FETCH_HEAD 有变化或超过刷新间隔
+    pub fn refresh_git_statuses(&mut self) {
+        let projects: Vec<_> = self.projects.iter().map(|p| p.path.clone()).collect();
+        self.git_trackers.retain(|path, _| projects.contains(path));
+        for (idx, path) in projects.into_iter().enumerate() {
+            let signature = git_signature(&path);
+            if signature.is_empty() {
+                // 不是 Git 仓库
+                self.projects[idx].git = None;
+                continue;
+            }
+            let tracker = self.git_trackers.entry(path.clone()).or_default();
+            let due = tracker
+                .refreshed
+                .is_none_or(|t| t.elapsed() >= GIT_REFRESH_INTERVAL)
+                || tracker.signature != signature;
+            if tracker.pending || !due {
+                continue;
+            }
+            tracker.signature = signature;
+            tracker.pending = true;
+
+            let tx = self.git_tx.clone();
+            std::thread::spawn(move || {
+                let status = read_git_status(&path);
+                let _ = tx.send(GitEvent::Status { path, status });
+            });
+        }
+    }
+
+    /// 处理后台 git 任务的结果
+    pub fn handle_git_events(&mut self) {
+        while let Ok(event) = self.git_rx.try_recv() {
+            match event {
+                GitEvent::Status { path, status } => {
+                    if let Some(tracker) = self.git_trackers.get_mut(&path) {
+                        tracker.pending = false;
+                        tracker.refreshed = Some(Instant::now());
+                    }
+                    if let Some(project) = self.projects.iter_mut().find(|p| p.path == path) {
+                        project.git = status;
+                    }
+                    let count = self.active_git_files_len();
+                    self.git_popup.selected = self.git_popup.selected.min(count.saturating_sub(1));
+                }
+                GitEvent::Action {
+                    path,
+                    action,
+                    result,
+                } => {
+                    if self.git_popup.running.as_ref() == Some(&action) {
+                        self.git_popup.running = None;
+                    }
+                    let msg = match (self.language(), result) {
+                        (_, Ok(output)) if !output.is_empty() => {
+                            format!("{}: {}", action.command_line(), output)
+                        }
+                        (Language::English, Ok(_)) => format!("{}: done", action.command_line()),
+                        (Language::Chinese, Ok(_)) => format!("{}: 完成", action.command_line()),
+                        (_, Err(e)) => format!("{} ✗ {}", action.command_line(), e),
+                    };
+                    self.set_status(&msg);
+                    // 操作完成后立即刷新状态
+                    self.git_trackers.insert(path, GitTracker::default());
+                    self.refresh_git_statuses();
+                }
+                GitEvent::Branches { path, branches } => {
+                    // 列表读取期间已关闭详情或切换了项目时丢弃
+                    let Some(project) = self.git_popup_project(&path) else {
+                        continue;
+                    };
+                    let current = project.git.as_ref().map(|g| g.branch.as_str());
+                    self.git_popup.branch_idx = branches
+                        .iter()
+                        .position(|b| Some(b.as_str()) == current)
+                        .unwrap_or(0);
+                    self.git_popup.branches = Some(branches);
+                }
+                GitEvent::Worktrees { path, worktrees } => {
+                    if self.git_popup_project(&path).is_none() {
+                        continue;
+                    }
+                    self.git_popup.worktree_idx =
+                        worktrees.iter().position(|w| w.path == path).unwrap_or(0);
+                    self.git_popup.worktrees = Some(worktrees);
+                }
+                GitEvent::WorktreeAdded {
+                    parent,
+                    path,
+                    result,
+                } => match result {
+                    Ok(()) => self.add_worktree_project(&parent, path),
+                    Err(e) => {
+                        let msg = format!("git worktree add ✗ {}", e);
+                        self.set_status(&msg);
+                    }
+                },
+            }
+        }
+    }
+
+    /// 打开当前项目的 Git 详情
+    pub fn open_git(&mut self) {
+        match self.active_project() {
+            None => {
+                let msg = self.i18n().no_project().to_string();
+                self.set_status(&msg);
+            }
+            Some(project) if project.git.is_none() => {
+                let msg = self.i18n().not_git_repo().to_string();
+                self.set_status(&msg);
+            }
+            Some(_) => {
+                self.git_popup = Default::default();
+                self.mode = AppMode::Git;
+            }
+        }
+    }
+
+    /// Git 详情仍然打开且当前项目就是 path 时返回当前项目
+    fn git_popup_project(&self, path: &Path) -> Option<&Project> {
+        if self.mode != AppMode::Git {
+            return None;
+        }
+        self.active_project().filter(|p| p.path == path)
+    }
+
+    /// 当前项目的变更文件数
+    fn active_git_files_len(&self) -> usize {
+        self.active_project()
+            .and_then(|p| p.git.as_ref())
+            .map_or(0, |git| git.files.len())
+    }
+
+    /// Git 详情中选择下一项（分支列表、工作树列表或变更文件）
+    pub fn git_next(&mut self) {
+        let popup = &mut self.git_popup;
+        if let Some(branches) = &popup.branches {
+            if !branches.is_empty() {
+                popup.branch_idx = (popup.branch_idx + 1) % branches.len();
+            }
+            return;
+        }
+        if let Some(worktrees) = &popup.worktrees {
+            if !worktrees.is_empty() {
+                popup.worktree_idx = (popup.worktree_idx + 1) % worktrees.len();
+            }
+            return;
+        }
+        let count = self.active_git_files_len();
+        if count > 0 {
+            self.git_popup.selected = (self.git_popup.selected + 1) % count;
+        }
+    }
+
+    /// Git 详情中选择上一项
+    pub fn git_prev(&mut self) {
+        let popup = &mut self.git_popup;
+        if let Some(branches) = &popup.branches {
+            if !branches.is_empty() {
+                popup.branch_idx = (popup.branch_idx + branches.len() - 1) % branches.len();
+            }
+            return;
+        }
+        if let Some(worktrees) = &popup.worktrees {
+            if !worktrees.is_empty() {
+                popup.worktree_idx = (popup.worktree_idx + worktrees.len() - 1) % worktrees.len();
+            }
+            return;
+        }
+        let count = self.active_git_files_len();
+        if count > 0 {
+            self.git_popup.selected = (self.git_popup.selected + count - 1) % count;
+        }
+    }
+
+    /// 在编辑器中打开选中的变更文件
+    pub fn open_selected_git_file(&mut self) {
+        let file = self
+            .active_project()
+            .and_then(|p| p.git.as_ref())
+            .and_then(|git| git.files.get(self.git_popup.selected))
+            .map(|f| f.path.clone());
+        if let Some(file) = file {
+            self.request_open_in_editor(&file, 1, None);
+        }
+    }
+
+    /// 在后台读取分支列表，读取完成后打开（当前分支预选）
+    pub fn git_show_branches(&mut self) {
+        let Some(project) = self.active_project() else {
+            return;
+        };
+        let path = project.path.clone();
+        let tx = self.git_tx.clone();
+        std::thread::spawn(move || {
+            let branches = local_branches(&path);
+            let _ = tx.send(GitEvent::Branches { path, branches });
+        });
+    }
+
+    /// 切换到分支列表中选中的分支
+    pub fn git_switch_selected(&mut self) {
+        let Some(branches) = self.git_popup.branches.take() else {
+            return;
+        };
+        if let Some(branch) = branches.into_iter().nth(self.git_popup.branch_idx) {
+            self.run_git_action(GitAction::Switch(branch));
+        }
+    }
+
+    /// 在后台读取工作树列表，读取完成后打开（当前项目所在的工作树预选）
+    pub fn git_show_worktrees(&mut self) {
+        let Some(project) = self.active_project() else {
+            return;
+        };
+        let path = project.path.clone();
+        let tx = self.git_tx.clone();
+        std::thread::spawn(move || {
+            let worktrees = list_worktrees(&path);
+            let _ = tx.send(GitEvent::Worktrees { path, worktrees });
+        });
+    }
+
+    /// 切换到选中的工作树，尚未添加时作为当前项目的工作树项目添加
+    pub fn git_open_selected_worktree(&mut self) {
+        let Some(worktree) = self
+            .git_popup
+            .worktrees
+            .as_ref()
+            .and_then(|w| w.get(self.git_popup.worktree_idx))
+            .cloned()
+        else {
+            return;
+        };
+        self.exit_mode();
+        if let Some(idx) = self.projects.iter().position(|p| p.path == worktree.path) {
+            self.active_project_idx = idx;
+            return;
+        }
+        if let Some(parent) = self.worktree_parent() {
+            self.add_worktree_project(&parent, worktree.path);
+        }
+    }
+
+    /// 打开新建工作树的分支名输入框
+    pub fn start_new_worktree(&mut self) {
+        self.input_buffer.clear();
+        self.mode = AppMode::NewWorktree;
+    }
+
+    /// 在后台为输入的分支创建工作树，完成后作为项目添加
+    pub fn submit_new_worktree(&mut self) {
+        let branch = self.input_buffer.trim().to_string();
+        if branch.is_empty() {
+            return;
+        }
+        let (Some(project), Some(parent)) = (self.active_project(), self.worktree_parent()) else {
+            return;
+        };
+        let repo = project.path.clone();
+        let msg = format!("git worktree add {} …", branch);
+        self.set_status(&msg);
+        self.mode = AppMode::Normal;
+
+        let tx = self.git_tx.clone();
+        std::thread::spawn(move || {
+            // 新工作树放在主工作树旁边
+            let main_path = list_worktrees(&repo)
+                .into_iter()
+                .find(|w| w.is_main)
+                .map_or_else(|| repo.clone(), |w| w.path);
+            let path = default_worktree_path(&main_path, &branch);
+            let result = add_worktree(&repo, &path, &branch);
+            let _ = tx.send(GitEvent::WorktreeAdded {
+                parent,
+                path,
+                result,
+            });
+        });
+    }
+
+    /// 新工作树项目继承自哪个项目：当前项目本身是工作树项目时沿用它的所属项目
+    fn worktree_parent(&self) -> Option<PathBuf> {
+        let project = self.active_project()?;
+        let parent = project
+            .worktree_of
+            .as_ref()
+            .filter(|parent| self.projects.iter().any(|p| &p.path == *parent));
+        Some(parent.unwrap_or(&project.path).clone())
+    }
+
+    /// 添加工作树项目：继承所属项目的自定义命令，复制缺少的 .env 文件，并排在所属项目之后
+    fn add_worktree_project(&mut self, parent: &Path, path: PathBuf) {
+        if let Some(idx) = self.projects.iter().position(|p| p.path == path) {
+            self.active_project_idx = idx;
+            return;
+        }
+        let Some(parent_idx) = self.projects.iter().position(|p| p.path == parent) else {
+            return;
+        };
+        let mut project = match Project::load(path) {
+            Ok(project) => project,
+            Err(e) => {
+                self.set_status(&format!("Error: {}", e));
+                return;
+            }
+        };
+        project.worktree_of = Some(parent.to_path_buf());
+        project.custom_commands = self.projects[parent_idx].custom_commands.clone();
+        let copied = copy_env_files(parent, &project.path);
+
+        let msg = match self.language() {
+            Language::English if copied > 0 => format!(
+                "Added worktree: {} (copied {} env file(s))",
+                project.display_name(),
+                copied
+            ),
+            Language::Chinese if copied > 0 => format!(
+                "已添加工作树: {}（复制了 {} 个环境文件）",
+                project.display_name(),
+                copied
+            ),
+            Language::English => format!("Added worktree: {}", project.display_name()),
+            Language::Chinese => format!("已添加工作树: {}", project.display_name()),
+        };
+
+        // 插入到所属项目及其已有工作树项目之后
+        let idx = self
+            .projects
+            .iter()
+            .rposition(|p| p.path == parent || p.worktree_of.as_deref() == Some(parent))
+            .map_or(self.projects.len(), |i| i + 1);
+        self.watch_project_files(&project);
+        self.projects.insert(idx, project);
+        self.active_project_idx = idx;
+        self.set_status(&msg);
+    }
+
+    /// 在后台运行快捷操作（同一时间只运行一个）
+    pub fn run_git_action(&mut self, action: GitAction) {
+        if self.git_popup.running.is_some() {
+            return;
+        }
+        let Some(project) = self.active_project() else {
+            return;
+        };
+        let path = project.path.clone();
+        let msg = format!("{} …", action.command_line());
+        self.set_status(&msg);
+        self.git_popup.running = Some(action.clone());
+
+        let tx = self.git_tx.clone();
+        std::thread::spawn(move || {
+            let result = run_git_action(&path, &action);
+            let _ = tx.send(GitEvent::Action {
+                path,
+                action,
+                result,
+            });
+        });
+    }
+}
+
+/// 把所属项目根目录下的 .env 文件（通常未提交到仓库）复制到工作树中缺少的位置，返回复制数量
+fn copy_env_files(from: &Path, to: &Path) -> usize {
+    let Ok(entries) = std::fs::read_dir(from) else {
+        return 0;
+    };
+    entries
+        .flatten()
+        .filter(|entry| entry.file_type().is_ok_and(|t| t.is_file()))
+        .filter(|entry| entry.file_name().to_string_lossy().starts_with(".env"))
+        .filter(|entry| {
+            let target = to.join(entry.file_name());
+            !target.exists() && std::fs::copy(entry.path(), target).is_ok()
+        })
+        .count()
+}
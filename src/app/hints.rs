@@ -0,0 +1,113 @@
+//! 提示模式
+//! 为终端面板中可见的 file:line 引用或链接分配标签，输入标签即打开
+
+use super::{AppMode, AppState, Hint, HintKind, HintTarget};
+use crate::pty::{find_file_refs, hint_labels};
+
+impl AppState {
+    /// 进入提示模式，根据上一帧终端面板的可见内容生成目标
+    pub fn enter_hint_mode(&mut self, kind: HintKind) {
+        if self.active_project().is_none() {
+            let msg = self.i18n().no_project().to_string();
+            self.set_status(&msg);
+            return;
+        }
+        let targets: Vec<(u16, u16, HintTarget)> = self
+            .visible_rows
+            .iter()
+            .flat_map(|row| {
+                let targets: Vec<(usize, HintTarget)> = match kind {
+                    HintKind::Files => find_file_refs(&row.text)
+                        .into_iter()
+                        .map(|r| {
+                            let target = HintTarget::File {
+                                file: r.file,
+                                line: r.line,
+                                column: r.column,
+                            };
+                            (r.start, target)
+                        })
+                        .collect(),
+                    HintKind::Links => row
+                        .links
+                        .iter()
+                        .map(|u| (u.start, HintTarget::Url(u.url.clone())))
+                        .collect(),
+                };
+                targets
+                    .into_iter()
+                    .map(move |(start, target)| (row.x + start as u16, row.y, target))
+            })
+            .collect();
+        if targets.is_empty() {
+            let msg = match kind {
+                HintKind::Files => self.i18n().no_hints(),
+                HintKind::Links => self.i18n().no_links(),
+            };
+            self.set_status(msg);
+            return;
+        }
+
+        let labels = hint_labels(targets.len());
+        self.hints = targets
+            .into_iter()
+            .zip(labels)
+            .map(|((x, y, target), label)| Hint {
+                label,
+                x,
+                y,
+                target,
+            })
+            .collect();
+        self.hint_input.clear();
+        self.mode = AppMode::Hints;
+    }
+
+    /// 输入标签字符：完全匹配时打开目标，没有匹配时退出提示模式
+    pub fn hint_key(&mut self, c: char) {
+        self.hint_input.push(c);
+        let matched: Vec<&Hint> = self
+            .hints
+            .iter()
+            .filter(|h| h.label.starts_with(&self.hint_input))
+            .collect();
+        match matched.as_slice() {
+            [] => self.exit_hint_mode(),
+            [hint] if hint.label == self.hint_input => {
+                let target = hint.target.clone();
+                self.exit_hint_mode();
+                self.open_hint_target(target);
+            }
+            _ => {}
+        }
+    }
+
+    /// 点击标签时打开对应目标，点击其他地方退出提示模式
+    pub fn click_hint(&mut self, x: u16, y: u16) {
+        let clicked = self
+            .hints
+            .iter()
+            .find(|h| h.y == y && x >= h.x && x < h.x + h.label.len() as u16)
+            .map(|h| h.target.clone());
+        self.exit_hint_mode();
+        if let Some(target) = clicked {
+            self.open_hint_target(target);
+        }
+    }
+
+    /// 退出提示模式
+    pub fn exit_hint_mode(&mut self) {
+        self.hints.clear();
+        self.hint_input.clear();
+        self.mode = AppMode::Normal;
+    }
+
+    fn open_hint_target(&mut self, target: HintTarget) {
+        match target {
+            HintTarget::File { file, line, column } => {
+                self.request_open_in_editor(&file, line, column)
+            }
+            HintTarget::Url(url) => self.open_url(&url),
+        }
+    }
+}
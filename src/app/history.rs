@@ -0,0 +1,146 @@
+//! 运行历史模块
+//! 记录命令的开始和结束，并提供历史查看和重新运行所需的查询
+
+use super::{AppMode, AppState, CommandTarget};
+use crate::config::{save_history, unix_now, RunRecord, RunTarget};
+use crate::i18n::Language;
+use crate::project::CommandGroup;
+use std::time::Instant;
+
+impl From<CommandTarget> for RunTarget {
+    fn from(target: CommandTarget) -> Self {
+        match target {
+            CommandTarget::DevTerminal => RunTarget::Dev,
+            CommandTarget::ShellTerminal => RunTarget::Shell,
+        }
+    }
+}
+
+impl From<RunTarget> for CommandTarget {
+    fn from(target: RunTarget) -> Self {
+        match target {
+            RunTarget::Dev => CommandTarget::DevTerminal,
+            RunTarget::Shell => CommandTarget::ShellTerminal,
+        }
+    }
+}
+
+impl AppState {
+    /// 记录项目的一次命令运行
+    /// Dev 命令传入 pty_id，进程退出时由 finish_run 补充时长和退出码
+    pub fn record_run(
+        &mut self,
+        project_idx: usize,
+        source: CommandGroup,
+        name: &str,
+        command: &str,
+        target: CommandTarget,
+        pty_id: Option<String>,
+    ) {
+        let Some(project_path) = self.projects.get(project_idx).map(|p| p.path.clone()) else {
+            return;
+        };
+        let started = pty_id.as_ref().map(|_| Instant::now());
+        self.history.record(
+            &project_path,
+            RunRecord {
+                source,
+                name: name.to_string(),
+                command: command.to_string(),
+                target: target.into(),
+                started_at: unix_now(),
+                duration_ms: None,
+                exit_code: None,
+                pty_id,
+                started,
+            },
+        );
+        self.save_history_file();
+    }
+
+    /// 进程退出时补充运行记录
+    pub fn finish_run(&mut self, pty_id: &str, exit_code: Option<i32>) {
+        if self.history.finish(pty_id, exit_code) {
+            self.save_history_file();
+        }
+    }
+
+    /// 保存历史文件（失败只记录日志，不影响使用）
+    pub(super) fn save_history_file(&self) {
+        if let Err(e) = save_history(&self.history, &self.history_path) {
+            log::warn!("Failed to save history: {}", e);
+        }
+    }
+
+    /// 当前项目最近一次运行的记录
+    pub fn last_run(&self) -> Option<&RunRecord> {
+        let project = self.active_project()?;
+        self.history.last_run(&project.path)
+    }
+
+    /// 当前项目的运行记录（最新的在前）
+    pub fn history_runs(&self) -> Vec<&RunRecord> {
+        self.active_project()
+            .map(|p| self.history.runs(&p.path).iter().rev().collect())
+            .unwrap_or_default()
+    }
+
+    /// 按分组和名称查找命令在 get_all_commands() 中的索引
+    pub fn command_idx_by_name(&self, source: CommandGroup, name: &str) -> Option<usize> {
+        self.command_idx_in(self.active_project_idx, source, name)
+    }
+
+    /// 按分组和名称查找命令在指定项目的 get_all_commands() 中的索引
+    pub fn command_idx_in(
+        &self,
+        project_idx: usize,
+        source: CommandGroup,
+        name: &str,
+    ) -> Option<usize> {
+        let project = self.projects.get(project_idx)?;
+        self.project_commands(project_idx)
+            .iter()
+            .enumerate()
+            .position(|(idx, cmd)| cmd.name == name && project.command_group(idx) == source)
+    }
+
+    /// 命令已不存在时的提示
+    pub fn command_missing_message(&self, name: &str) -> String {
+        match self.language() {
+            Language::English => format!("Command '{}' no longer exists", name),
+            Language::Chinese => format!("命令 '{}' 已不存在", name),
+        }
+    }
+
+    /// 打开历史弹窗
+    pub fn open_history(&mut self) {
+        if self.active_project().is_none() {
+            let msg = self.i18n().no_project().to_string();
+            self.set_status(&msg);
+            return;
+        }
+        self.history_idx = 0;
+        self.mode = AppMode::History;
+    }
+
+    /// 历史弹窗选择下一项
+    pub fn history_next(&mut self) {
+        let count = self.history_runs().len();
+        if count > 0 {
+            self.history_idx = (self.history_idx + 1) % count;
+        }
+    }
+
+    /// 历史弹窗选择上一项
+    pub fn history_prev(&mut self) {
+        let count = self.history_runs().len();
+        if count > 0 {
+            self.history_idx = (self.history_idx + count - 1) % count;
+        }
+    }
+
+    /// 历史弹窗中选中的记录
+    pub fn selected_history_run(&self) -> Option<&RunRecord> {
+        self.history_runs().get(self.history_idx).copied()
+    }
+}
@@ -0,0 +1,113 @@
+//! 空闲自动暂停
+//! 长时间未被查看且没有输出的后台 Dev 进程自动暂停，选中项目或有请求访问其端口时恢复
+
+use super::AppState;
+use crate::i18n::Language;
+use crate::platform::has_pending_connections;
+use crate::pty::{url_port, PtyHandle};
+use std::time::{Duration, Instant};
+
+/// 设置中可选的空闲时长（分钟，0 表示关闭）
+const IDLE_SUSPEND_CHOICES: [u64; 6] = [0, 5, 10, 15, 30, 60];
+
+impl AppState {
+    /// 记录进程的最近输出时间
+    pub fn mark_output(&mut self, pty_id: &str) {
+        if let Some(pty) = self
+            .projects
+            .iter_mut()
+            .flat_map(|p| p.dev_ptys_mut())
+            .find(|pty| pty.id == pty_id)
+        {
+            pty.last_activity = Instant::now();
+        }
+    }
+
+    /// 暂停空闲的后台进程，恢复端口上有等待连接的自动暂停进程
+    /// 组合命令的各步骤（如 `dev:web + dev:api`）通常互相依赖，全部空闲时才一起暂停，收到请求时一起恢复
+    pub fn check_idle_processes(&mut self) {
+        let minutes = self.config.settings.idle_suspend_minutes;
+        let idle_for = Duration::from_secs(minutes * 60);
+        let lang = self.language();
+        let mut messages = Vec::new();
+        for (idx, project) in self.projects.iter_mut().enumerate() {
+            let port = project.dev_url.as_deref().and_then(url_port);
+            let name = project.display_name().to_string();
+            let mut ptys: Vec<_> = project.dev_ptys_mut().filter(|pty| pty.running).collect();
+            if ptys.is_empty() {
+                continue;
+            }
+            let auto_suspended = |pty: &PtyHandle| pty.suspended && pty.auto_suspended;
+            if ptys.iter().any(|pty| auto_suspended(pty)) {
+                if !port.is_some_and(has_pending_connections) {
+                    continue;
+                }
+                let mut resumed = false;
+                for pty in ptys.iter_mut().filter(|pty| auto_suspended(pty)) {
+                    if pty.resume().unwrap_or(false) {
+                        pty.auto_suspended = false;
+                        pty.last_activity = Instant::now();
+                        resumed = true;
+                    }
+                }
+                if resumed {
+                    let port = port.unwrap_or_default();
+                    log::info!("{} resumed: connection on port {}", name, port);
+                    messages.push(match lang {
+                        Language::English => {
+                            format!("{}: resumed for a request on port {}", name, port)
+                        }
+                        Language::Chinese => format!("{}: 端口 {} 收到请求，已恢复", name, port),
+                    });
+                }
+                continue;
+            }
+            let busy = ptys
+                .iter()
+                .any(|pty| !pty.suspended && pty.last_activity.elapsed() < idle_for);
+            if minutes == 0 || idx == self.active_project_idx || busy {
+                continue;
+            }
+            for pty in ptys.iter_mut().filter(|pty| !pty.suspended) {
+                if pty.suspend().unwrap_or(false) {
+                    pty.auto_suspended = true;
+                    log::info!("{} auto-paused after {} idle minutes", name, minutes);
+                }
+            }
+        }
+        if let Some(msg) = messages.pop() {
+            self.set_status(&msg);
+        }
+    }
+
+    /// 当前选中项目的活动时间，以及自动暂停后的恢复
+    pub fn wake_active_project(&mut self) {
+        let idx = self.active_project_idx;
+        let Some(project) = self.projects.get_mut(idx) else {
+            return;
+        };
+        let mut resumed = false;
+        for pty in project.dev_ptys_mut() {
+            pty.last_activity = Instant::now();
+            if !(pty.suspended && pty.auto_suspended) {
+                continue;
+            }
+            pty.auto_suspended = false;
+            resumed |= pty.resume().unwrap_or(false);
+        }
+        if resumed {
+            let msg = self.i18n().process_resumed().to_string();
+            self.set_status(&msg);
+        }
+    }
+
+    /// 设置中切换空闲时长（自定义的值切换到下一个更长的选项）
+    pub fn cycle_idle_suspend(&mut self) {
+        let settings = &mut self.config.settings;
+        settings.idle_suspend_minutes = IDLE_SUSPEND_CHOICES
+            .iter()
+            .copied()
+            .find(|&m| m > settings.idle_suspend_minutes)
+            .unwrap_or(0);
+    }
+}
@@ -0,0 +1,44 @@
+//! 面板布局
+//! 调整侧边栏宽度、Dev/Shell 分隔线位置和排列方向，设置保存在 AppSettings 中
+
+use super::{AppState, PanelLayout};
+use crate::ui::{clamp_dev_panel_percent, clamp_sidebar_width};
+
+impl AppState {
+    /// 设置侧边栏宽度（content_width 为侧边栏和工作区的总宽度）
+    pub fn set_sidebar_width(&mut self, width: u16, content_width: u16) {
+        let settings = &mut self.config.settings;
+        settings.sidebar_width = clamp_sidebar_width(width, content_width);
+        settings.sidebar_hidden = false;
+    }
+
+    /// 按列数加宽（delta 为正）或收窄侧边栏，侧边栏隐藏时先显示出来
+    pub fn resize_sidebar(&mut self, delta: i16, content_width: u16) {
+        let current = clamp_sidebar_width(self.config.settings.sidebar_width, content_width);
+        self.set_sidebar_width(current.saturating_add_signed(delta), content_width);
+    }
+
+    /// 设置 Dev Terminal 的占比，最大化时回到分屏布局
+    pub fn set_dev_panel_percent(&mut self, percent: u16) {
+        self.config.settings.dev_panel_percent = clamp_dev_panel_percent(percent);
+        self.panel_layout = PanelLayout::Split;
+    }
+
+    /// 按百分比扩大（delta 为正）或缩小 Dev Terminal
+    pub fn resize_dev_panel(&mut self, delta: i16) {
+        let current = clamp_dev_panel_percent(self.config.settings.dev_panel_percent);
+        self.set_dev_panel_percent(current.saturating_add_signed(delta));
+    }
+
+    /// 切换 Dev/Shell 上下排列和左右并排
+    pub fn toggle_panel_orientation(&mut self) {
+        let settings = &mut self.config.settings;
+        settings.panel_orientation = settings.panel_orientation.toggle();
+    }
+
+    /// 显示或隐藏侧边栏
+    pub fn toggle_sidebar(&mut self) {
+        let settings = &mut self.config.settings;
+        settings.sidebar_hidden = !settings.sidebar_hidden;
+    }
+}
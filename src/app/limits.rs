@@ -0,0 +1,174 @@
+//! 资源上限
+//! 每次刷新资源使用后检查 Dev 进程是否超过上限，按配置提示、通知、暂停或重启
+
+use super::{AppState, RestartRequest};
+use crate::config::NotifyEvent;
+use crate::i18n::Language;
+use crate::pty::{LimitAction, LimitBreach, ProcessResourceUsage, ResourceLimits};
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+/// 超限重启时，停止旧进程后至少等待多久再重新运行
+const RESTART_DELAY: Duration = Duration::from_secs(2);
+
+impl AppState {
+    /// 检查所有运行中的 Dev 进程和组合命令步骤（暂停的进程不检查），刚超过上限时执行配置的操作
+    pub fn check_resource_limits(&mut self) {
+        let now = Instant::now();
+        let mut breaches = Vec::new();
+        for (idx, project) in self.projects.iter_mut().enumerate() {
+            let Some(limits) = project
+                .resource_limits
+                .as_ref()
+                .or(self.config.resource_limits.as_ref())
+            else {
+                continue;
+            };
+            let limits = limits.clone();
+            for pty in project.dev_ptys_mut() {
+                if let Some(breach) = pty.check_limits(&limits, now) {
+                    breaches.push((idx, pty.id.clone(), breach, limits.clone()));
+                }
+            }
+        }
+        for (idx, pty_id, breach, limits) in breaches {
+            self.on_limit_breach(idx, &pty_id, breach, &limits);
+        }
+    }
+
+    /// 执行超限后的操作，并在状态栏提示（通知、暂停、重启时同时发送通知）
+    /// 组合命令的步骤超限时暂停该步骤，重启时重新运行整个组合命令
+    fn on_limit_breach(
+        &mut self,
+        project_idx: usize,
+        pty_id: &str,
+        breach: LimitBreach,
+        limits: &ResourceLimits,
+    ) {
+        let lang = self.language();
+        let mut detail = match (lang, breach) {
+            (Language::English, LimitBreach::Memory(bytes)) => format!(
+                "memory {} is over the {} MB limit",
+                format_memory(bytes),
+                limits.memory_mb.unwrap_or_default()
+            ),
+            (Language::Chinese, LimitBreach::Memory(bytes)) => format!(
+                "内存 {} 超过上限 {} MB",
+                format_memory(bytes),
+                limits.memory_mb.unwrap_or_default()
+            ),
+            (Language::English, LimitBreach::Cpu(cpu)) => format!(
+                "CPU {:.0}% has been above {}% for {}s",
+                cpu,
+                limits.cpu_percent.unwrap_or_default(),
+                limits.cpu_secs
+            ),
+            (Language::Chinese, LimitBreach::Cpu(cpu)) => format!(
+                "CPU {:.0}% 已持续 {} 秒超过 {}%",
+                cpu,
+                limits.cpu_secs,
+                limits.cpu_percent.unwrap_or_default()
+            ),
+        };
+
+        let Some(project) = self.projects.get_mut(project_idx) else {
+            return;
+        };
+        let name = match project.step_name(pty_id) {
+            Some(step) => format!("{} [{}]", project.display_name(), step),
+            None => project.display_name().to_string(),
+        };
+        // 组合命令的运行记录对应整个组合
+        let run_id = project.composite.as_ref().map(|run| run.id.as_str());
+        let command = [Some(pty_id), run_id]
+            .into_iter()
+            .flatten()
+            .find_map(|id| self.history.running(id))
+            .map(|run| (run.source, run.name.clone()));
+        log::warn!("{} over resource limit: {:?}", name, breach);
+
+        match limits.action {
+            LimitAction::Warn | LimitAction::Notify => {}
+            LimitAction::Suspend => {
+                let suspended = project
+                    .dev_ptys_mut()
+                    .find(|pty| pty.id == pty_id)
+                    .is_some_and(|pty| pty.suspend().unwrap_or(false));
+                if suspended {
+                    detail.push_str(match lang {
+                        Language::English => ", paused",
+                        Language::Chinese => "，已暂停",
+                    });
+                }
+            }
+            LimitAction::Restart => {
+                if let Some((source, command)) = command.clone() {
+                    self.stop_dev(project_idx);
+                    self.pending_restarts.push(RestartRequest {
+                        project_path: self.projects[project_idx].path.clone(),
+                        source,
+                        command,
+                        stopped_at: Instant::now(),
+                    });
+                    detail.push_str(match lang {
+                        Language::English => ", restarting",
+                        Language::Chinese => "，正在重启",
+                    });
+                }
+            }
+        }
+
+        self.set_status(&format!("{}: {}", name, detail));
+        if limits.action != LimitAction::Warn {
+            let command = command.map(|(_, name)| name).unwrap_or_default();
+            self.notify(project_idx, &command, NotifyEvent::OverLimit(detail));
+        }
+    }
+
+    /// 取出可以重新运行的命令：旧进程树已退出且停止足够久，期间没有在该项目运行其他命令
+    pub fn take_due_restart(&mut self) -> Option<RestartRequest> {
+        if self.is_waiting_for_cleanup() || !self.can_create_pty() {
+            return None;
+        }
+        let stopping = |path: &Path| {
+            self.projects
+                .iter()
+                .any(|p| p.path == path && p.is_stopping())
+        };
+        let pos = self
+            .pending_restarts
+            .iter()
+            .position(|r| r.stopped_at.elapsed() >= RESTART_DELAY && !stopping(&r.project_path))?;
+        let request = self.pending_restarts.remove(pos);
+        let idle = self
+            .projects
+            .iter()
+            .find(|p| p.path == request.project_path)
+            .is_some_and(|p| p.dev_pty.is_none() && p.composite.is_none());
+        idle.then_some(request)
+    }
+
+    /// 重新运行后的提示
+    pub fn restarted_message(&self, project_idx: usize, command: &str) -> String {
+        let name = self
+            .projects
+            .get(project_idx)
+            .map(|p| p.display_name())
+            .unwrap_or_default();
+        match self.language() {
+            Language::English => format!(
+                "{}: restarted {} after exceeding its resource limit",
+                name, command
+            ),
+            Language::Chinese => format!("{}: 超过资源上限，已重新运行 {}", name, command),
+        }
+    }
+}
+
+fn format_memory(bytes: u64) -> String {
+    ProcessResourceUsage {
+        cpu_percent: 0.0,
+        memory_bytes: bytes,
+    }
+    .format_memory()
+}
@@ -0,0 +1,71 @@
+//! 终端输出中的链接
+//! 记录 OSC 8 超链接和开发服务器的访问地址，并通过系统默认程序打开链接
+
+use super::AppState;
+use crate::config::strip_ansi;
+use crate::i18n::Language;
+use crate::platform::open_url;
+use crate::pty::local_url;
+
+impl AppState {
+    /// 解析进程输出中的 OSC 8 超链接，Dev 进程（包括组合命令步骤）还会记录本机访问地址
+    pub fn track_links(&mut self, pty_id: &str, data: &[u8]) {
+        for project in &mut self.projects {
+            let step_pty = project.composite.as_mut().and_then(|run| {
+                run.steps
+                    .iter_mut()
+                    .filter_map(|s| s.pty.as_mut())
+                    .find(|pty| pty.id == pty_id)
+            });
+            let (pty, is_dev) = match (project.dev_pty.as_mut(), step_pty) {
+                (Some(pty), _) if pty.id == pty_id => (pty, true),
+                (_, Some(pty)) => (pty, true),
+                _ => match project.find_shell_pty_mut(pty_id) {
+                    Some(pty) => (pty, false),
+                    None => continue,
+                },
+            };
+            pty.hyperlinks.feed(data);
+            if is_dev {
+                if let Some(url) = local_url(&strip_ansi(data)) {
+                    project.dev_url = Some(url);
+                }
+            }
+            return;
+        }
+    }
+
+    /// 用系统默认程序打开链接
+    pub fn open_url(&mut self, url: &str) {
+        let msg = match (open_url(url), self.language()) {
+            (Ok(()), Language::English) => format!("Opening {}", url),
+            (Ok(()), Language::Chinese) => format!("正在打开 {}", url),
+            (Err(e), _) => {
+                log::error!("Failed to open {}: {}", url, e);
+                format!("Error: {}: {}", url, e)
+            }
+        };
+        self.set_status(&msg);
+    }
+
+    /// 打开当前项目开发服务器的访问地址
+    pub fn open_dev_url(&mut self) {
+        match self.active_project().and_then(|p| p.dev_url.clone()) {
+            Some(url) => self.open_url(&url),
+            None => {
+                let msg = self.i18n().no_dev_url().to_string();
+                self.set_status(&msg);
+            }
+        }
+    }
+
+    /// 终端面板中指定位置的链接（用于 Ctrl+点击）
+    pub fn link_at(&self, x: u16, y: u16) -> Option<String> {
+        let row = self.visible_rows.iter().find(|row| row.y == y)?;
+        let col = x.checked_sub(row.x)? as usize;
+        row.links
+            .iter()
+            .find(|link| col >= link.start && col < link.start + link.len)
+            .map(|link| link.url.clone())
+    }
+}
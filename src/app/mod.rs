@@ -3,9 +3,29 @@
 
 #![allow(dead_code)]
 
+mod command_manager;
+mod composite;
+mod deps;
 mod dir_browser;
+mod git;
+mod hints;
+mod history;
+mod idle;
+mod layout;
+mod limits;
+mod links;
+mod node;
+mod notify;
+mod palette;
+mod problems;
+mod process_tree;
+mod prompt;
+mod reload;
+mod resources;
 mod scroll;
+mod shell_tabs;
 mod status;
+mod theme_picker;
 mod types;
 
 pub use dir_browser::*;
@@ -13,14 +33,23 @@ pub use scroll::*;
 pub use status::*;
 pub use types::*;
 
-use crate::config::AppConfig;
+use crate::config::{
+    get_config_path, get_history_path, load_history, AppConfig, CommandHistory, FileWatcher,
+};
 use crate::i18n::{I18n, Language};
-use crate::project::Project;
-use crate::pty::PtyEvent;
-use crate::ui::Spinner;
-use std::time::Instant;
+use crate::keymap::Keymap;
+use crate::project::{CommandEntry, NodeCheck, ProblemMatcher, Project};
+use crate::pty::{ProcessInfo, PtyEvent};
+use crate::ui::{Spinner, Theme, VisibleRow};
+use crossterm::event::KeyEvent;
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
 use tokio::sync::mpsc;
 
+/// 组合键两次按键之间的最长间隔，超过后丢弃已输入的部分
+const PENDING_KEYS_TIMEOUT: Duration = Duration::from_secs(1);
+
 /// 全局应用状态
 pub struct AppState {
     /// 项目列表
@@ -33,14 +62,18 @@ pub struct AppState {
     pub mode: AppMode,
     /// 应用配置
     pub config: AppConfig,
+    /// 上一次从配置文件读取的内容（热重载时只应用文件中发生变化的部分）
+    pub disk_config: AppConfig,
     /// 是否应该退出
     pub should_quit: bool,
     /// PTY 事件接收器
     pub pty_rx: mpsc::UnboundedReceiver<PtyEvent>,
     /// PTY 事件发送器（用于克隆给 PTY 任务）
     pub pty_tx: mpsc::UnboundedSender<PtyEvent>,
-    /// 命令面板选中索引
+    /// 命令面板选中索引（过滤结果中的位置）
     pub command_palette_idx: usize,
+    /// 命令面板过滤输入
+    pub command_filter: String,
     /// 命令执行目标（Dev Terminal 或 Shell Terminal）
     pub command_target: CommandTarget,
     /// 设置页面选中索引
@@ -63,6 +96,8 @@ pub struct AppState {
     resource_update_frame: u64,
     /// 右侧面板布局模式
     pub panel_layout: PanelLayout,
+    /// 正在用鼠标拖动的分隔线
+    pub layout_drag: Option<LayoutDrag>,
     /// 帮助弹窗平滑滚动状态
     pub help_scroll: SmoothScroll,
     /// PTY 资源清理状态（Windows 专用）
@@ -77,23 +112,110 @@ pub struct AppState {
     /// 待处理的 Shell 请求
     /// 当 PTY 创建锁被占用时，缓存用户的 Shell 启动请求
     pub pending_shell_request: Option<PendingShellRequest>,
+    /// 全局配置文件路径（用于热重载时识别变更来源）
+    pub config_path: PathBuf,
+    /// 配置文件和 package.json 变更监听器
+    pub file_watcher: FileWatcher,
+    /// 当前主题（按 settings.theme 加载）
+    pub theme: Theme,
+    /// 主题选择器状态
+    pub theme_picker: ThemePickerState,
+    /// 当前快捷键映射（默认绑定 + 配置覆盖）
+    pub keymap: Keymap,
+    /// 已输入但尚未完成的组合键
+    pub pending_keys: Vec<KeyEvent>,
+    /// 上一次组合键按键的时间和当时的焦点
+    pub pending_keys_at: Option<(Instant, FocusArea)>,
+    /// 命令运行历史
+    pub history: CommandHistory,
+    /// 历史文件路径
+    pub history_path: PathBuf,
+    /// 历史弹窗选中索引
+    pub history_idx: usize,
+    /// 命令占位符输入状态
+    pub command_prompt: Option<CommandPromptState>,
+    /// 命令管理选中索引
+    pub command_manager_idx: usize,
+    /// 命令表单状态
+    pub command_form: Option<CommandFormState>,
+    /// 确认对话框确认后执行的操作
+    pub confirm_action: ConfirmAction,
+    /// 输出匹配通知的上次发送时间（键为 PTY ID 和规则索引）
+    pub notify_cooldowns: HashMap<String, Instant>,
+    /// 编译后的自定义问题匹配器
+    pub problem_matchers: Vec<ProblemMatcher>,
+    /// 问题列表选中索引
+    pub problems_idx: usize,
+    /// 等待主循环打开的编辑器请求
+    pub pending_editor: Option<EditorRequest>,
+    /// 超过资源上限后等待重新运行的命令
+    pub pending_restarts: Vec<RestartRequest>,
+    /// 进程树弹窗中的进程（按树的先序排列）
+    pub process_tree: Vec<ProcessInfo>,
+    /// 进程树弹窗中选中的进程
+    pub process_tree_idx: usize,
+    /// 进程树显示的 Dev 进程（组合命令运行时在各步骤之间切换）
+    pub process_tree_root: usize,
+    /// 上一帧终端面板中可见的文本（提示模式和 Ctrl+点击链接使用）
+    pub visible_rows: Vec<VisibleRow>,
+    /// 提示模式的目标
+    pub hints: Vec<Hint>,
+    /// 提示模式中已输入的标签字符
+    pub hint_input: String,
+    /// 后台 git 任务结果接收器
+    pub git_rx: mpsc::UnboundedReceiver<GitEvent>,
+    /// 后台 git 任务结果发送器（克隆给后台线程）
+    pub git_tx: mpsc::UnboundedSender<GitEvent>,
+    /// 各项目的 Git 状态刷新记录（键为项目路径）
+    pub git_trackers: HashMap<PathBuf, GitTracker>,
+    /// Git 详情弹窗状态
+    pub git_popup: GitPopupState,
+    /// 确认安装依赖后，下一次在该项目的 Dev Terminal 运行命令前先安装
+    pub install_before_dev: Option<PathBuf>,
+    /// 后台 Node 版本检查结果接收器
+    pub node_rx: mpsc::UnboundedReceiver<(PathBuf, Option<NodeCheck>)>,
+    /// 后台 Node 版本检查结果发送器
+    pub node_tx: mpsc::UnboundedSender<(PathBuf, Option<NodeCheck>)>,
+    /// 各项目上次开始 Node 版本检查的时间（键为项目路径）
+    pub node_checked: HashMap<PathBuf, Instant>,
+    /// 后台依赖检查结果接收器
+    pub deps_rx: mpsc::UnboundedReceiver<DependencyCheck>,
+    /// 后台依赖检查结果发送器
+    pub deps_tx: mpsc::UnboundedSender<DependencyCheck>,
+    /// 正在后台检查依赖的项目路径（上一次检查完成前不重复检查）
+    pub deps_checking: HashSet<PathBuf>,
 }
 
 impl AppState {
     /// 创建新的应用状态
     pub fn new(config: AppConfig) -> Self {
         let (pty_tx, pty_rx) = mpsc::unbounded_channel();
+        let (git_tx, git_rx) = mpsc::unbounded_channel();
+        let (node_tx, node_rx) = mpsc::unbounded_channel();
+        let (deps_tx, deps_rx) = mpsc::unbounded_channel();
+
+        let config_path = get_config_path();
+        let mut file_watcher = FileWatcher::new();
+        file_watcher.watch(config_path.clone());
+
+        let history_path = get_history_path();
+        let history = load_history(&history_path).unwrap_or_else(|e| {
+            log::warn!("Failed to load history: {}", e);
+            CommandHistory::default()
+        });
 
-        Self {
+        let mut state = Self {
             projects: Vec::new(),
             active_project_idx: 0,
             focus: FocusArea::default(),
             mode: AppMode::default(),
+            disk_config: config.clone(),
             config,
             should_quit: false,
             pty_rx,
             pty_tx,
             command_palette_idx: 0,
+            command_filter: String::new(),
             command_target: CommandTarget::default(),
             settings_idx: 0,
             input_buffer: String::new(),
@@ -111,12 +233,57 @@ impl AppState {
             },
             resource_update_frame: 0,
             panel_layout: PanelLayout::default(),
+            layout_drag: None,
             help_scroll: SmoothScroll::new(),
             pty_cleanup: None,
             pending_dev_command: None,
             pty_creation_lock: None,
             pending_shell_request: None,
+            config_path,
+            file_watcher,
+            theme: Theme::default(),
+            theme_picker: ThemePickerState::default(),
+            keymap: Keymap::default(),
+            pending_keys: Vec::new(),
+            pending_keys_at: None,
+            history,
+            history_path,
+            history_idx: 0,
+            command_prompt: None,
+            command_manager_idx: 0,
+            command_form: None,
+            confirm_action: ConfirmAction::default(),
+            notify_cooldowns: HashMap::new(),
+            problem_matchers: Vec::new(),
+            problems_idx: 0,
+            pending_editor: None,
+            pending_restarts: Vec::new(),
+            process_tree: Vec::new(),
+            process_tree_idx: 0,
+            process_tree_root: 0,
+            visible_rows: Vec::new(),
+            hints: Vec::new(),
+            hint_input: String::new(),
+            git_rx,
+            git_tx,
+            git_trackers: HashMap::new(),
+            git_popup: GitPopupState::default(),
+            install_before_dev: None,
+            node_rx,
+            node_tx,
+            node_checked: HashMap::new(),
+            deps_rx,
+            deps_tx,
+            deps_checking: HashSet::new(),
+        };
+        state.apply_theme_setting();
+        if let Some(msg) = state.apply_keybindings() {
+            state.set_status(&msg);
+        }
+        if let Some(msg) = state.apply_problem_matchers() {
+            state.set_status(&msg);
         }
+        state
     }
 
     /// 进入目录浏览模式
@@ -141,11 +308,25 @@ impl AppState {
 
         // 每 30 帧（约每秒）更新一次进程资源信息
         // 这样可以避免频繁刷新系统信息带来的性能开销
+        // 同时检查配置文件和 package.json 是否被修改、依赖是否需要安装，以及 Git 状态是否需要刷新
         if self.frame_count - self.resource_update_frame >= 30 {
             self.update_resource_usage();
+            self.check_file_changes();
+            self.refresh_dependency_states();
+            self.refresh_node_checks();
+            self.refresh_git_statuses();
+            self.check_idle_processes();
             self.resource_update_frame = self.frame_count;
         }
 
+        // 选中的项目视为正在查看，自动暂停的进程立即恢复
+        self.wake_active_project();
+
+        // 接收后台 git 任务、依赖检查和 Node 版本检查的结果
+        self.handle_git_events();
+        self.handle_dependency_checks();
+        self.handle_node_checks();
+
         // 更新平滑滚动动画并返回是否仍在进行
         self.help_scroll.update()
     }
@@ -158,7 +339,7 @@ impl AppState {
         let pids: Vec<sysinfo::Pid> = self
             .projects
             .iter()
-            .filter_map(|p| p.dev_pty.as_ref())
+            .flat_map(|p| p.dev_ptys())
             .filter_map(|pty| pty.pid)
             .map(sysinfo::Pid::from_u32)
             .collect();
@@ -171,12 +352,16 @@ impl AppState {
         // 使用 All 来确保能获取到子进程信息
         self.system.refresh_processes(ProcessesToUpdate::All, true);
 
-        // 更新每个项目的资源使用信息
+        // 更新每个项目的资源使用信息（包括组合命令中正在运行的步骤）
         for project in &mut self.projects {
-            if let Some(ref mut pty) = project.dev_pty {
+            for pty in project.dev_ptys_mut() {
                 pty.update_resource_usage(&self.system);
             }
         }
+        self.check_resource_limits();
+        if self.mode == AppMode::ProcessTree {
+            self.refresh_process_tree();
+        }
     }
 
     /// 获取 spinner 当前帧
@@ -194,6 +379,38 @@ impl AppState {
         I18n::new(self.language())
     }
 
+    /// 取出过期的未完成组合键：超时、离开普通模式或焦点已切换（force 为 true 时直接取消）
+    /// 返回这些按键和输入时的焦点
+    pub fn take_expired_pending_keys(&mut self, force: bool) -> Option<(Vec<KeyEvent>, FocusArea)> {
+        let (at, focus) = self.pending_keys_at?;
+        if force
+            || at.elapsed() >= PENDING_KEYS_TIMEOUT
+            || self.mode != AppMode::Normal
+            || self.focus != focus
+        {
+            self.pending_keys_at = None;
+            let keys = std::mem::take(&mut self.pending_keys);
+            return Some((keys, focus)).filter(|(keys, _)| !keys.is_empty());
+        }
+        None
+    }
+
+    /// 按配置重建快捷键映射
+    /// 配置中有无效条目时跳过该条目，并返回错误提示
+    pub fn apply_keybindings(&mut self) -> Option<String> {
+        let (keymap, errors) = Keymap::from_config(&self.config.keybindings);
+        self.keymap = keymap;
+        self.pending_keys.clear();
+        if errors.is_empty() {
+            return None;
+        }
+        log::warn!("Invalid keybindings: {}", errors.join("; "));
+        Some(match self.language() {
+            Language::English => format!("Keybindings: {}", errors.join("; ")),
+            Language::Chinese => format!("快捷键配置有误: {}", errors.join("; ")),
+        })
+    }
+
     /// 切换语言
     pub fn toggle_language(&mut self) {
         self.config.settings.language = self.config.settings.language.toggle();
@@ -205,11 +422,36 @@ impl AppState {
         self.config.settings.windows_shell = self.config.settings.windows_shell.toggle();
     }
 
+    /// 执行命令使用的 Shell（Windows 上按设置选择 PowerShell 或 CMD）
+    pub fn command_shell(&self) -> String {
+        #[cfg(unix)]
+        {
+            crate::platform::get_default_shell()
+        }
+        #[cfg(windows)]
+        {
+            crate::platform::get_shell_with_config(self.config.settings.windows_shell)
+        }
+    }
+
     /// 获取当前激活的项目（如果有）
     pub fn active_project(&self) -> Option<&Project> {
         self.projects.get(self.active_project_idx)
     }
 
+    /// 当前项目的所有可执行命令（包括全局命令）
+    pub fn active_commands(&self) -> Vec<CommandEntry> {
+        self.project_commands(self.active_project_idx)
+    }
+
+    /// 指定项目的全部命令（与 get_all_commands() 的顺序一致）
+    pub fn project_commands(&self, project_idx: usize) -> Vec<CommandEntry> {
+        self.projects
+            .get(project_idx)
+            .map(|p| p.get_all_commands(&self.config.global_commands))
+            .unwrap_or_default()
+    }
+
     /// 获取当前激活的项目的可变引用
     pub fn active_project_mut(&mut self) -> Option<&mut Project> {
         self.projects.get_mut(self.active_project_idx)
@@ -245,13 +487,15 @@ impl AppState {
 
     /// 添加新项目
     pub fn add_project(&mut self, project: Project) {
+        self.watch_project_files(&project);
         self.projects.push(project);
     }
 
     /// 移除项目
     pub fn remove_project(&mut self, idx: usize) {
         if idx < self.projects.len() {
-            self.projects.remove(idx);
+            let project = self.projects.remove(idx);
+            self.unwatch_project_files(&project);
             // 调整当前选中索引
             if self.active_project_idx >= self.projects.len() && !self.projects.is_empty() {
                 self.active_project_idx = self.projects.len() - 1;
@@ -286,7 +530,20 @@ impl AppState {
     pub fn enter_command_palette(&mut self, target: CommandTarget) {
         self.mode = AppMode::CommandPalette;
         self.command_palette_idx = 0;
+        self.command_filter.clear();
         self.command_target = target;
+
+        // 预选最近一次运行的命令
+        let last_idx = self
+            .last_run()
+            .and_then(|run| self.command_idx_by_name(run.source, &run.name));
+        if let Some(command_idx) = last_idx {
+            self.command_palette_idx = self
+                .filtered_commands()
+                .iter()
+                .position(|m| m.command_idx == command_idx)
+                .unwrap_or(0);
+        }
     }
 
     /// 切换右侧面板布局
@@ -300,30 +557,6 @@ impl AppState {
         self.input_buffer.clear();
     }
 
-    /// 命令面板选择下一项
-    pub fn command_palette_next(&mut self) {
-        if let Some(project) = self.active_project() {
-            let commands = project.get_all_commands();
-            if !commands.is_empty() {
-                self.command_palette_idx = (self.command_palette_idx + 1) % commands.len();
-            }
-        }
-    }
-
-    /// 命令面板选择上一项
-    pub fn command_palette_prev(&mut self) {
-        if let Some(project) = self.active_project() {
-            let commands = project.get_all_commands();
-            if !commands.is_empty() {
-                if self.command_palette_idx == 0 {
-                    self.command_palette_idx = commands.len() - 1;
-                } else {
-                    self.command_palette_idx -= 1;
-                }
-            }
-        }
-    }
-
     /// 检查是否正在等待 PTY 资源释放
     pub fn is_waiting_for_cleanup(&self) -> bool {
         self.pty_cleanup.is_some()
@@ -371,6 +604,15 @@ impl AppState {
             return true;
         }
 
+        // 等待后台的停止线程终止进程树
+        if self
+            .projects
+            .get(cleanup.project_idx)
+            .is_some_and(|p| p.is_stopping())
+        {
+            return false;
+        }
+
         // 刷新进程信息
         self.system.refresh_processes(ProcessesToUpdate::All, false);
 
@@ -392,15 +634,26 @@ impl AppState {
         false
     }
 
-    /// 非 Windows 平台不需要轮询
+    /// 非 Windows 平台等待旧进程树按停止序列退出（收到 Stopped 事件后项目不再处于停止中）
     #[cfg(not(windows))]
     pub fn poll_pty_cleanup(&mut self) -> bool {
-        // 非 Windows 平台直接清除状态
-        if self.pty_cleanup.is_some() {
-            self.pty_cleanup = None;
-            return true;
+        let Some(cleanup) = &self.pty_cleanup else {
+            return false;
+        };
+        if self
+            .projects
+            .get(cleanup.project_idx)
+            .is_some_and(|p| p.is_stopping())
+        {
+            return false;
         }
-        false
+        log::info!(
+            "PTY process {} stopped after {}ms",
+            cleanup.old_pid,
+            cleanup.elapsed_ms()
+        );
+        self.pty_cleanup = None;
+        true
     }
 
     // ========== PTY 创建锁相关方法（ConPTY 竞态保护）==========
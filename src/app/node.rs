@@ -0,0 +1,73 @@
+//! Node 版本检查
+//! 在后台线程中检查各项目实际使用的 Node 版本是否满足项目固定的版本
+
+use super::AppState;
+use crate::i18n::Language;
+use crate::project::check_node;
+use std::time::{Duration, Instant};
+
+/// Node 版本检查间隔（版本文件很少变化，切换全局版本后最多一分钟内更新）
+const NODE_CHECK_INTERVAL: Duration = Duration::from_secs(60);
+
+impl AppState {
+    /// 在后台检查到期项目的 Node 版本
+    pub fn refresh_node_checks(&mut self) {
+        let use_manager = self.config.settings.node_version_manager;
+        let paths: Vec<_> = self.projects.iter().map(|p| p.path.clone()).collect();
+        self.node_checked.retain(|path, _| paths.contains(path));
+        for path in paths {
+            let due = self
+                .node_checked
+                .get(&path)
+                .is_none_or(|t| t.elapsed() >= NODE_CHECK_INTERVAL);
+            if !due {
+                continue;
+            }
+            self.node_checked.insert(path.clone(), Instant::now());
+            let tx = self.node_tx.clone();
+            std::thread::spawn(move || {
+                let check = check_node(&path, use_manager);
+                let _ = tx.send((path, check));
+            });
+        }
+    }
+
+    /// 处理 Node 版本检查结果，版本变为不满足要求时在状态栏提示
+    pub fn handle_node_checks(&mut self) {
+        while let Ok((path, check)) = self.node_rx.try_recv() {
+            let lang = self.language();
+            let Some(project) = self.projects.iter_mut().find(|p| p.path == path) else {
+                continue;
+            };
+            let was_mismatch = project.node.as_ref().is_some_and(|n| n.is_mismatch());
+            project.node = check;
+            let Some(node) = project.node.as_ref().filter(|n| n.is_mismatch()) else {
+                continue;
+            };
+            if was_mismatch {
+                continue;
+            }
+            let name = project.display_name().to_string();
+            let required = format!("{} ({})", node.required.source.label(), node.required.spec);
+            let msg = match (lang, &node.current) {
+                (Language::English, Some(current)) => {
+                    format!("{}: Node {} does not match {}", name, current, required)
+                }
+                (Language::English, None) => format!("{}: node not found, {}", name, required),
+                (Language::Chinese, Some(current)) => {
+                    format!("{}: Node {} 不满足 {}", name, current, required)
+                }
+                (Language::Chinese, None) => format!("{}: 找不到 node，需要 {}", name, required),
+            };
+            self.set_status(&msg);
+        }
+    }
+
+    /// 切换是否通过版本管理器使用固定的 Node 版本，并立即重新检查
+    pub fn toggle_node_version_manager(&mut self) {
+        let settings = &mut self.config.settings;
+        settings.node_version_manager = !settings.node_version_manager;
+        self.node_checked.clear();
+        self.refresh_node_checks();
+    }
+}
@@ -0,0 +1,119 @@
+//! 进程事件通知
+//! 在 handle_pty_events 中检查进程退出和输出，按配置发送通知
+
+use super::AppState;
+use crate::config::{strip_ansi, NotifyEvent};
+use crate::i18n::Language;
+use crate::platform::send_notification;
+use std::time::{Duration, Instant};
+
+/// 同一进程的同一条输出匹配规则，两次通知之间的最小间隔
+const PATTERN_COOLDOWN: Duration = Duration::from_secs(30);
+
+impl AppState {
+    /// 进程退出时按配置通知（需在 finish_run 之前调用，以便获取命令名称和运行时长）
+    pub fn notify_exit(&mut self, pty_id: &str, exit_code: Option<i32>) {
+        self.notify_cooldowns
+            .retain(|key, _| !key.starts_with(&format!("{}#", pty_id)));
+        let Some(project_idx) = self.project_idx_by_pty(pty_id) else {
+            return;
+        };
+        let Some(run) = self.history.running(pty_id) else {
+            return;
+        };
+        let elapsed = run.started.map(|started| started.elapsed());
+        let name = run.name.clone();
+        if let Some(event) = self.config.notifications.exit_event(exit_code, elapsed) {
+            self.notify(project_idx, &name, event);
+        }
+    }
+
+    /// 进程输出匹配规则时按配置通知
+    pub fn notify_output(&mut self, pty_id: &str, data: &[u8]) {
+        if self.config.notifications.patterns.is_empty() {
+            return;
+        }
+        let Some(project_idx) = self.project_idx_by_pty(pty_id) else {
+            return;
+        };
+        let Some(NotifyEvent::Matched(pattern_idx)) =
+            self.config.notifications.match_output(&strip_ansi(data))
+        else {
+            return;
+        };
+        let key = format!("{}#{}", pty_id, pattern_idx);
+        if self
+            .notify_cooldowns
+            .get(&key)
+            .is_some_and(|last| last.elapsed() < PATTERN_COOLDOWN)
+        {
+            return;
+        }
+        self.notify_cooldowns.insert(key, Instant::now());
+        let name = self
+            .history
+            .running(pty_id)
+            .map(|run| run.name.clone())
+            .unwrap_or_default();
+        self.notify(project_idx, &name, NotifyEvent::Matched(pattern_idx));
+    }
+
+    /// 发送通知（按项目过滤和只通知后台项目的设置）
+    pub(super) fn notify(&self, project_idx: usize, name: &str, event: NotifyEvent) {
+        let config = &self.config.notifications;
+        let Some(project) = self.projects.get(project_idx) else {
+            return;
+        };
+        if config.background_only && project_idx == self.active_project_idx {
+            return;
+        }
+        let path = project.path.to_string_lossy();
+        let ids = [project.name.as_str(), project.display_name(), path.as_ref()];
+        if !config.allows_project(&ids) {
+            return;
+        }
+
+        let title = format!("DevPanel · {}", project.display_name());
+        let body = match (self.language(), event) {
+            (Language::English, NotifyEvent::Failed(Some(code))) => {
+                format!("{} failed (exit {})", name, code)
+            }
+            (Language::English, NotifyEvent::Failed(None)) => format!("{} was terminated", name),
+            (Language::English, NotifyEvent::Finished(elapsed)) => {
+                format!("{} finished in {}s", name, elapsed.as_secs())
+            }
+            (Language::Chinese, NotifyEvent::Failed(Some(code))) => {
+                format!("{} 失败（退出码 {}）", name, code)
+            }
+            (Language::Chinese, NotifyEvent::Failed(None)) => format!("{} 被终止", name),
+            (Language::Chinese, NotifyEvent::Finished(elapsed)) => {
+                format!("{} 已完成，用时 {}s", name, elapsed.as_secs())
+            }
+            (_, NotifyEvent::OverLimit(text)) => text,
+            (_, NotifyEvent::Matched(idx)) => {
+                let pattern = &config.patterns[idx];
+                let text = pattern.message.as_deref().unwrap_or(&pattern.pattern);
+                if name.is_empty() {
+                    text.to_string()
+                } else {
+                    format!("{}: {}", name, text)
+                }
+            }
+        };
+        log::info!("Notification: {} - {}", title, body);
+        send_notification(&config.channels, &title, &body);
+    }
+
+    /// PTY 所属的项目（Dev Server、组合命令步骤或 Shell）
+    fn project_idx_by_pty(&self, pty_id: &str) -> Option<usize> {
+        self.projects.iter().position(|p| {
+            let is = |pty: Option<&crate::pty::PtyHandle>| pty.is_some_and(|pty| pty.id == pty_id);
+            is(p.dev_pty.as_ref())
+                || p.shells.iter().any(|tab| is(tab.pty.as_ref()))
+                || p.composite
+                    .as_ref()
+                    .is_some_and(|run| run.steps.iter().any(|s| is(s.pty.as_ref())))
+                || p.composite.as_ref().is_some_and(|run| run.id == pty_id)
+        })
+    }
+}
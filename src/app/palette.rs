@@ -0,0 +1,221 @@
+//! 命令面板过滤模块
+//! 输入即过滤：对命令名称和命令内容做模糊匹配，并记录匹配位置用于高亮
+
+use super::AppState;
+use crate::config::unix_now;
+
+/// 模糊匹配结果
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FuzzyMatch {
+    /// 匹配得分（越高越好）
+    pub score: i32,
+    /// 匹配到的字符位置（按字符计，不是字节）
+    pub positions: Vec<usize>,
+}
+
+/// 命令面板中的一条过滤结果
+#[derive(Debug, Clone)]
+pub struct PaletteMatch {
+    /// 在 get_all_commands() 中的索引
+    pub command_idx: usize,
+    /// 综合得分
+    pub score: i32,
+    /// 运行历史的 frecency 得分
+    pub frecency: u32,
+    /// 命令名称中匹配的字符位置
+    pub name_positions: Vec<usize>,
+    /// 命令内容中匹配的字符位置
+    pub command_positions: Vec<usize>,
+}
+
+/// 名称匹配比命令内容匹配更重要
+const NAME_MATCH_BONUS: i32 = 20;
+
+/// 模糊匹配：query 的每个字符需按顺序出现在 text 中（忽略大小写）
+/// 连续匹配、单词开头匹配得分更高，间隔越大得分越低
+pub fn fuzzy_match(query: &str, text: &str) -> Option<FuzzyMatch> {
+    let query: Vec<char> = query
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .flat_map(char::to_lowercase)
+        .collect();
+    if query.is_empty() {
+        return Some(FuzzyMatch {
+            score: 0,
+            positions: Vec::new(),
+        });
+    }
+
+    let chars: Vec<char> = text.chars().collect();
+    let lower: Vec<char> = chars
+        .iter()
+        .map(|c| c.to_lowercase().next().unwrap_or(*c))
+        .collect();
+
+    // 从首字符的每个出现位置开始贪心匹配，取得分最高的一次
+    let mut best: Option<FuzzyMatch> = None;
+    for start in (0..lower.len()).filter(|&i| lower[i] == query[0]) {
+        let Some(positions) = match_from(&query, &lower, start) else {
+            // 从更靠后的位置开始只会更难匹配
+            break;
+        };
+        let score = score_positions(&chars, &positions);
+        if best.as_ref().is_none_or(|b| score > b.score) {
+            best = Some(FuzzyMatch { score, positions });
+        }
+    }
+    best
+}
+
+/// 从指定位置开始贪心匹配，返回每个查询字符的位置
+fn match_from(query: &[char], text: &[char], start: usize) -> Option<Vec<usize>> {
+    let mut positions = Vec::with_capacity(query.len());
+    let mut idx = start;
+    for q in query {
+        let offset = text[idx..].iter().position(|c| c == q)?;
+        positions.push(idx + offset);
+        idx += offset + 1;
+    }
+    Some(positions)
+}
+
+/// 根据匹配位置计算得分
+fn score_positions(chars: &[char], positions: &[usize]) -> i32 {
+    let mut score = 0;
+    let mut prev: Option<usize> = None;
+    for &pos in positions {
+        score += 1;
+        let at_boundary = pos == 0
+            || matches!(chars[pos - 1], '-' | '_' | ':' | ' ' | '/' | '.')
+            || (chars[pos - 1].is_lowercase() && chars[pos].is_uppercase());
+        if at_boundary {
+            score += 8;
+        }
+        match prev {
+            Some(p) if pos == p + 1 => score += 6,
+            Some(p) => score -= (pos - p - 1).min(5) as i32,
+            None => score -= pos.min(5) as i32,
+        }
+        prev = Some(pos);
+    }
+    // 同等匹配下更短的文本更相关
+    score - (chars.len() as i32 / 10)
+}
+
+impl AppState {
+    /// 当前项目按过滤条件筛选后的命令
+    /// 按匹配得分排序，得分相同时按运行历史的 frecency 排序，再保持原顺序
+    pub fn filtered_commands(&self) -> Vec<PaletteMatch> {
+        let Some(project) = self.active_project() else {
+            return Vec::new();
+        };
+        let frecency = self.history.frecency(&project.path, unix_now());
+
+        let mut matches: Vec<PaletteMatch> = self
+            .active_commands()
+            .iter()
+            .enumerate()
+            .filter_map(|(command_idx, cmd)| {
+                let name = fuzzy_match(&self.command_filter, &cmd.name);
+                let command = fuzzy_match(&self.command_filter, &cmd.command);
+                let score = match (&name, &command) {
+                    (None, None) => return None,
+                    (Some(n), Some(c)) => (n.score + NAME_MATCH_BONUS).max(c.score),
+                    (Some(n), None) => n.score + NAME_MATCH_BONUS,
+                    (None, Some(c)) => c.score,
+                };
+                Some(PaletteMatch {
+                    command_idx,
+                    score,
+                    frecency: frecency
+                        .get(&(project.command_group(command_idx), cmd.name.clone()))
+                        .copied()
+                        .unwrap_or(0),
+                    name_positions: name.map(|m| m.positions).unwrap_or_default(),
+                    command_positions: command.map(|m| m.positions).unwrap_or_default(),
+                })
+            })
+            .collect();
+
+        matches.sort_by_key(|m| std::cmp::Reverse((m.score, m.frecency)));
+        matches
+    }
+
+    /// 当前选中命令在 get_all_commands() 中的索引
+    pub fn selected_command_idx(&self) -> Option<usize> {
+        self.filtered_commands()
+            .get(self.command_palette_idx)
+            .map(|m| m.command_idx)
+    }
+
+    /// 输入过滤字符
+    pub fn command_filter_push(&mut self, c: char) {
+        self.command_filter.push(c);
+        self.command_palette_idx = 0;
+    }
+
+    /// 删除最后一个过滤字符
+    pub fn command_filter_pop(&mut self) {
+        self.command_filter.pop();
+        self.command_palette_idx = 0;
+    }
+
+    /// 清空过滤条件
+    pub fn command_filter_clear(&mut self) {
+        self.command_filter.clear();
+        self.command_palette_idx = 0;
+    }
+
+    /// 命令面板选择下一项
+    pub fn command_palette_next(&mut self) {
+        let count = self.filtered_commands().len();
+        if count > 0 {
+            self.command_palette_idx = (self.command_palette_idx + 1) % count;
+        }
+    }
+
+    /// 命令面板选择上一项
+    pub fn command_palette_prev(&mut self) {
+        let count = self.filtered_commands().len();
+        if count > 0 {
+            self.command_palette_idx = (self.command_palette_idx + count - 1) % count;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fuzzy_match_subsequence() {
+        let m = fuzzy_match("tsc", "typecheck:src").unwrap();
+        assert_eq!(m.positions, vec![0, 10, 12]);
+        assert!(fuzzy_match("xyz", "build").is_none());
+        assert!(fuzzy_match("BLD", "build").is_some());
+        assert_eq!(
+            fuzzy_match("", "build").unwrap().positions,
+            Vec::<usize>::new()
+        );
+    }
+
+    #[test]
+    fn test_fuzzy_match_prefers_boundaries_and_runs() {
+        // "dev" 在 "dev" 中连续匹配，得分高于在 "dump-env-vars" 中分散匹配
+        let exact = fuzzy_match("dev", "dev").unwrap();
+        let scattered = fuzzy_match("dev", "dump-env-vars").unwrap();
+        assert!(exact.score > scattered.score);
+
+        // 单词开头匹配优于中间匹配
+        let boundary = fuzzy_match("tw", "test:watch").unwrap();
+        let middle = fuzzy_match("tw", "nottwice").unwrap();
+        assert!(boundary.score > middle.score);
+    }
+
+    #[test]
+    fn test_fuzzy_match_picks_best_start() {
+        // 第一个 "b" 之后无法连续匹配，应选择 "build" 处的匹配
+        let m = fuzzy_match("build", "lib:build").unwrap();
+        assert_eq!(m.positions, vec![4, 5, 6, 7, 8]);
+    }
+}
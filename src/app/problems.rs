@@ -0,0 +1,98 @@
+//! 问题列表
+//! 收集 Dev 进程输出中的错误和警告，在弹窗中浏览并在编辑器中打开
+
+use super::{AppMode, AppState, EditorRequest};
+use crate::i18n::Language;
+use crate::project::{compile_matchers, Problem};
+use std::path::Path;
+
+impl AppState {
+    /// 按配置编译自定义问题匹配器，返回配置中的错误提示
+    pub fn apply_problem_matchers(&mut self) -> Option<String> {
+        let (matchers, errors) = compile_matchers(&self.config.problem_matchers);
+        self.problem_matchers = matchers;
+        if errors.is_empty() {
+            return None;
+        }
+        log::warn!("Invalid problem matchers: {}", errors.join("; "));
+        Some(match self.language() {
+            Language::English => format!("Problem matchers: {}", errors.join("; ")),
+            Language::Chinese => format!("问题匹配器配置有误: {}", errors.join("; ")),
+        })
+    }
+
+    /// 解析 Dev 进程（包括组合命令步骤）的输出
+    pub fn collect_problems(&mut self, pty_id: &str, data: &[u8]) {
+        let project = self.projects.iter_mut().find(|p| {
+            p.dev_pty.as_ref().is_some_and(|pty| pty.id == pty_id)
+                || p.composite.as_ref().is_some_and(|run| {
+                    run.steps
+                        .iter()
+                        .any(|s| s.pty.as_ref().is_some_and(|pty| pty.id == pty_id))
+                })
+        });
+        if let Some(project) = project {
+            project.problems.feed(pty_id, data, &self.problem_matchers);
+        }
+    }
+
+    /// 当前项目的问题
+    pub fn active_problems(&self) -> &[Problem] {
+        self.active_project()
+            .map(|p| p.problems.problems())
+            .unwrap_or_default()
+    }
+
+    /// 打开问题列表
+    pub fn open_problems(&mut self) {
+        if self.active_project().is_none() {
+            let msg = self.i18n().no_project().to_string();
+            self.set_status(&msg);
+            return;
+        }
+        self.problems_idx = 0;
+        self.mode = AppMode::Problems;
+    }
+
+    /// 问题列表选择下一项
+    pub fn problems_next(&mut self) {
+        let count = self.active_problems().len();
+        if count > 0 {
+            self.problems_idx = (self.problems_idx + 1) % count;
+        }
+    }
+
+    /// 问题列表选择上一项
+    pub fn problems_prev(&mut self) {
+        let count = self.active_problems().len();
+        if count > 0 {
+            self.problems_idx = (self.problems_idx + count - 1) % count;
+        }
+    }
+
+    /// 在编辑器中打开选中的问题
+    pub fn open_selected_problem(&mut self) {
+        let Some(problem) = self.active_problems().get(self.problems_idx).cloned() else {
+            return;
+        };
+        self.request_open_in_editor(&problem.file, problem.line, problem.column);
+    }
+
+    /// 请求在编辑器中打开文件（相对路径相对项目根目录），由主循环执行
+    pub fn request_open_in_editor(&mut self, file: &str, line: u32, column: Option<u32>) {
+        let Some(project) = self.active_project() else {
+            return;
+        };
+        let path = Path::new(file);
+        let path = if path.is_absolute() {
+            path.to_path_buf()
+        } else {
+            project.path.join(path)
+        };
+        self.pending_editor = Some(EditorRequest {
+            path,
+            line,
+            column: column.unwrap_or(1),
+        });
+    }
+}
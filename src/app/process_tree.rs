@@ -0,0 +1,149 @@
+//! 进程树
+//! 列出当前项目 Dev 进程的所有子进程，可以单独终止、暂停或调整优先级
+
+use super::{AppMode, AppState};
+use crate::i18n::Language;
+use crate::pty::{process_tree_info, renice, ProcessInfo};
+use sysinfo::{Pid, ProcessesToUpdate, Signal};
+
+impl AppState {
+    /// 打开当前项目的进程树
+    pub fn open_process_tree(&mut self) {
+        let result = match self.active_project() {
+            None => Err(self.i18n().no_project()),
+            Some(_) if self.process_tree_roots().is_empty() => Err(self.i18n().dev_not_running()),
+            Some(_) => Ok(()),
+        };
+        match result {
+            Ok(()) => {
+                self.system.refresh_processes(ProcessesToUpdate::All, true);
+                self.process_tree_root = 0;
+                self.process_tree_idx = 0;
+                self.refresh_process_tree();
+                self.mode = AppMode::ProcessTree;
+            }
+            Err(msg) => self.set_status(msg),
+        }
+    }
+
+    /// 当前项目正在运行的 Dev 进程（组合命令的步骤带上步骤名称）
+    pub fn process_tree_roots(&self) -> Vec<(Option<&str>, u32)> {
+        let Some(project) = self.active_project() else {
+            return Vec::new();
+        };
+        project
+            .dev_ptys()
+            .filter_map(|pty| Some((project.step_name(&pty.id), pty.pid?)))
+            .collect()
+    }
+
+    /// 进程树当前显示的 Dev 进程
+    pub fn process_tree_current_root(&self) -> Option<(Option<&str>, u32)> {
+        let roots = self.process_tree_roots();
+        let idx = self.process_tree_root.min(roots.len().saturating_sub(1));
+        roots.get(idx).copied()
+    }
+
+    /// 切换到下一个步骤的进程树
+    pub fn process_tree_cycle_root(&mut self) {
+        let count = self.process_tree_roots().len();
+        if count > 1 {
+            self.process_tree_root = (self.process_tree_root + 1) % count;
+            self.process_tree_idx = 0;
+            self.process_tree.clear();
+            self.refresh_process_tree();
+        }
+    }
+
+    /// 按最新的进程信息重建列表（选中的进程仍在时保持选中）
+    pub fn refresh_process_tree(&mut self) {
+        let selected = self.selected_process().map(|p| p.pid);
+        let root = self.process_tree_current_root().map(|(_, pid)| pid);
+        self.process_tree = root
+            .map(|pid| process_tree_info(&self.system, Pid::from_u32(pid)))
+            .unwrap_or_default();
+        if let Some(idx) =
+            selected.and_then(|pid| self.process_tree.iter().position(|p| p.pid == pid))
+        {
+            self.process_tree_idx = idx;
+        }
+        self.process_tree_idx = self
+            .process_tree_idx
+            .min(self.process_tree.len().saturating_sub(1));
+    }
+
+    pub fn selected_process(&self) -> Option<&ProcessInfo> {
+        self.process_tree.get(self.process_tree_idx)
+    }
+
+    pub fn process_tree_next(&mut self) {
+        let count = self.process_tree.len();
+        if count > 0 {
+            self.process_tree_idx = (self.process_tree_idx + 1) % count;
+        }
+    }
+
+    pub fn process_tree_prev(&mut self) {
+        let count = self.process_tree.len();
+        if count > 0 {
+            self.process_tree_idx = (self.process_tree_idx + count - 1) % count;
+        }
+    }
+
+    /// 终止选中的进程（force 为 true 时发送 SIGKILL）
+    pub fn kill_selected_process(&mut self, force: bool) {
+        let signal = if force { Signal::Kill } else { Signal::Term };
+        self.signal_selected_process(signal);
+    }
+
+    /// 暂停或恢复选中的进程
+    pub fn toggle_selected_process(&mut self) {
+        let signal = match self.selected_process() {
+            Some(p) if p.is_stopped() => Signal::Continue,
+            Some(_) => Signal::Stop,
+            None => return,
+        };
+        self.signal_selected_process(signal);
+    }
+
+    fn signal_selected_process(&mut self, signal: Signal) {
+        let Some(pid) = self.selected_process().map(|p| p.pid) else {
+            return;
+        };
+        let result = self
+            .system
+            .process(Pid::from_u32(pid))
+            .and_then(|process| process.kill_with(signal));
+        let msg = match (self.language(), result) {
+            (Language::English, Some(true)) => format!("Sent {} to {}", signal, pid),
+            (Language::Chinese, Some(true)) => format!("已向 {} 发送 {}", pid, signal),
+            (Language::English, Some(false)) => format!("Failed to send {} to {}", signal, pid),
+            (Language::Chinese, Some(false)) => format!("向 {} 发送 {} 失败", pid, signal),
+            (Language::English, None) => format!("{} is not supported on this platform", signal),
+            (Language::Chinese, None) => format!("当前平台不支持 {}", signal),
+        };
+        self.set_status(&msg);
+        self.refresh_selected_process(pid);
+    }
+
+    /// 调整选中进程的 nice 值
+    pub fn renice_selected_process(&mut self, delta: i32) {
+        let Some(pid) = self.selected_process().map(|p| p.pid) else {
+            return;
+        };
+        let msg = match (self.language(), renice(pid, delta)) {
+            (Language::English, Ok(nice)) => format!("{} nice is now {}", pid, nice),
+            (Language::Chinese, Ok(nice)) => format!("{} 的 nice 值已调整为 {}", pid, nice),
+            (_, Err(e)) => format!("Error: {}: {}", pid, e),
+        };
+        self.set_status(&msg);
+        self.refresh_selected_process(pid);
+    }
+
+    /// 操作后立即刷新进程状态
+    fn refresh_selected_process(&mut self, pid: u32) {
+        self.system
+            .refresh_processes(ProcessesToUpdate::Some(&[Pid::from_u32(pid)]), true);
+        self.refresh_process_tree();
+    }
+}
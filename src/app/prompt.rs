@@ -0,0 +1,128 @@
+//! 命令占位符输入模块
+//! 运行带 `${input:..}` / `${pick:..}` 的命令前，逐个询问并记住回答
+
+use super::{AppMode, AppState, CommandPromptState, CommandTarget};
+use crate::project::{command_prompts, CommandType, Prompt};
+
+impl AppState {
+    /// 命令需要回答占位符时打开输入弹窗并返回 true，否则返回 false
+    pub fn open_command_prompt(&mut self, command_idx: usize, target: CommandTarget) -> bool {
+        let Some(project) = self.active_project() else {
+            return false;
+        };
+        let Some(cmd) = self.active_commands().into_iter().nth(command_idx) else {
+            return false;
+        };
+        if cmd.cmd_type != CommandType::RawShell {
+            return false;
+        }
+        let prompts = command_prompts(&cmd.command);
+        if prompts.is_empty() {
+            return false;
+        }
+
+        let source = project.command_group(command_idx);
+        let answers = self
+            .history
+            .answers(&project.path, source, &cmd.name)
+            .cloned()
+            .unwrap_or_default();
+        self.command_prompt = Some(CommandPromptState {
+            command_idx,
+            target,
+            command_source: source,
+            command_name: cmd.name,
+            prompts,
+            step: 0,
+            answers,
+            pick_idx: 0,
+        });
+        self.load_prompt_step();
+        self.mode = AppMode::CommandPrompt;
+        true
+    }
+
+    /// 当前需要回答的占位符
+    pub fn current_prompt(&self) -> Option<&Prompt> {
+        let prompt = self.command_prompt.as_ref()?;
+        prompt.prompts.get(prompt.step)
+    }
+
+    /// 进入当前步骤：预填上次的回答
+    fn load_prompt_step(&mut self) {
+        let Some(prompt) = self.command_prompt.as_mut() else {
+            return;
+        };
+        let Some(current) = prompt.prompts.get(prompt.step) else {
+            return;
+        };
+        let last = prompt.answers.get(current.label());
+        match current {
+            Prompt::Input { .. } => {
+                self.input_buffer = last.cloned().unwrap_or_default();
+            }
+            Prompt::Pick { options, .. } => {
+                prompt.pick_idx = last
+                    .and_then(|last| options.iter().position(|o| o == last))
+                    .unwrap_or(0);
+            }
+        }
+    }
+
+    /// 确认当前回答
+    /// 全部回答完成后记住回答、关闭弹窗，并返回要运行的命令和位置
+    pub fn prompt_submit(&mut self) -> Option<(usize, CommandTarget)> {
+        let prompt = self.command_prompt.as_mut()?;
+        let current = prompt.prompts.get(prompt.step)?;
+        let answer = match current {
+            Prompt::Input { .. } => self.input_buffer.clone(),
+            Prompt::Pick { options, .. } => options.get(prompt.pick_idx)?.clone(),
+        };
+        prompt.answers.insert(current.label().to_string(), answer);
+        prompt.step += 1;
+        if prompt.step < prompt.prompts.len() {
+            self.load_prompt_step();
+            return None;
+        }
+
+        let prompt = self.command_prompt.take()?;
+        if let Some(path) = self.active_project().map(|p| p.path.clone()) {
+            self.history.remember_answers(
+                &path,
+                prompt.command_source,
+                &prompt.command_name,
+                prompt.answers,
+            );
+            self.save_history_file();
+        }
+        self.exit_mode();
+        Some((prompt.command_idx, prompt.target))
+    }
+
+    /// 取消输入
+    pub fn prompt_cancel(&mut self) {
+        self.command_prompt = None;
+        self.exit_mode();
+    }
+
+    /// pick 选择下一项
+    pub fn prompt_pick_next(&mut self) {
+        self.move_pick(1);
+    }
+
+    /// pick 选择上一项
+    pub fn prompt_pick_prev(&mut self) {
+        self.move_pick(-1);
+    }
+
+    fn move_pick(&mut self, delta: isize) {
+        let count = match self.current_prompt() {
+            Some(Prompt::Pick { options, .. }) => options.len(),
+            _ => return,
+        };
+        if let Some(prompt) = self.command_prompt.as_mut() {
+            prompt.pick_idx =
+                (prompt.pick_idx as isize + delta).rem_euclid(count as isize) as usize;
+        }
+    }
+}
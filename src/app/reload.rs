@@ -0,0 +1,304 @@
+//! 配置热重载模块
+//! 检测全局配置、项目本地配置和 package.json 的变化，
+//! 并合并到运行中的 AppState（不影响正在运行的 PTY）
+
+use super::{AppMode, AppState};
+use crate::config::{load_config, merge_changed_fields};
+use crate::i18n::Language;
+use crate::project::{same_commands, Project};
+use std::path::{Path, PathBuf};
+
+impl AppState {
+    /// 检查被监听文件的变化并重新加载
+    /// 由 tick() 定期调用，变更摘要会显示在状态栏
+    pub fn check_file_changes(&mut self) {
+        let changed = self.file_watcher.poll();
+        if changed.is_empty() {
+            return;
+        }
+
+        let mut summaries = Vec::new();
+        for path in changed {
+            if path == self.config_path {
+                if let Some(summary) = self.reload_global_config() {
+                    summaries.push(summary);
+                }
+            } else if let Some(summary) = self.reload_project_file(&path) {
+                summaries.push(summary);
+            }
+        }
+
+        // 命令列表可能变短，避免命令面板索引越界
+        if self.mode == AppMode::CommandPalette {
+            let count = self.filtered_commands().len();
+            self.command_palette_idx = self.command_palette_idx.min(count.saturating_sub(1));
+        }
+
+        if !summaries.is_empty() {
+            self.set_status(&summaries.join(" · "));
+        }
+    }
+
+    /// 开始监听项目相关文件
+    pub(super) fn watch_project_files(&mut self, project: &Project) {
+        for path in project.watched_files() {
+            self.file_watcher.watch(path);
+        }
+    }
+
+    /// 停止监听项目相关文件
+    pub(super) fn unwatch_project_files(&mut self, project: &Project) {
+        for path in project.watched_files() {
+            self.file_watcher.unwatch(&path);
+        }
+    }
+
+    /// 重新加载全局配置（~/.devpanel/config.json）
+    /// 配置文件只在退出时写入，这里只应用文件中相对上一次读取发生变化的部分，
+    /// 本次会话中尚未保存的修改（主题、命令、布局等）不会被覆盖
+    /// - 设置按字段合并
+    /// - 已有项目更新文件中变化的别名、自定义命令、资源上限和终端标签
+    /// - 文件中新增的项目加载后追加
+    /// - 配置中删除的项目，仅在没有运行中 PTY 时移除
+    fn reload_global_config(&mut self) -> Option<String> {
+        let lang = self.language();
+        let config = match load_config(&self.config_path) {
+            Ok(config) => config,
+            Err(e) => {
+                // 编辑过程中可能保存了不完整的 JSON，保留当前状态
+                log::warn!("Failed to reload config: {}", e);
+                return Some(match lang {
+                    Language::English => format!("Config not reloaded: {}", e),
+                    Language::Chinese => format!("配置未重新加载: {}", e),
+                });
+            }
+        };
+
+        let mut changes = Vec::new();
+        let base = std::mem::take(&mut self.disk_config);
+
+        if config.settings != base.settings {
+            let settings =
+                merge_changed_fields(&self.config.settings, &base.settings, &config.settings);
+            if settings != self.config.settings {
+                let theme_changed = settings.theme != self.config.settings.theme;
+                self.config.settings = settings;
+                if theme_changed {
+                    self.apply_theme_setting();
+                }
+                changes.push(match self.language() {
+                    Language::English => "settings".to_string(),
+                    Language::Chinese => "设置".to_string(),
+                });
+            }
+        }
+
+        if config.keybindings != base.keybindings {
+            self.config.keybindings = config.keybindings.clone();
+            match self.apply_keybindings() {
+                Some(msg) => changes.push(msg),
+                None => changes.push(match self.language() {
+                    Language::English => "keybindings".to_string(),
+                    Language::Chinese => "快捷键".to_string(),
+                }),
+            }
+        }
+
+        if !same_commands(&config.global_commands, &base.global_commands) {
+            self.config.global_commands = config.global_commands.clone();
+            changes.push(match self.language() {
+                Language::English => "global commands".to_string(),
+                Language::Chinese => "全局命令".to_string(),
+            });
+        }
+
+        if config.problem_matchers != base.problem_matchers {
+            self.config.problem_matchers = config.problem_matchers.clone();
+            match self.apply_problem_matchers() {
+                Some(msg) => changes.push(msg),
+                None => changes.push(match self.language() {
+                    Language::English => "problem matchers".to_string(),
+                    Language::Chinese => "问题匹配器".to_string(),
+                }),
+            }
+        }
+
+        if config.notifications != base.notifications {
+            self.config.notifications = config.notifications.clone();
+            changes.push(match self.language() {
+                Language::English => "notifications".to_string(),
+                Language::Chinese => "通知".to_string(),
+            });
+        }
+
+        if config.resource_limits != base.resource_limits {
+            self.config.resource_limits = config.resource_limits.clone();
+            changes.push(match self.language() {
+                Language::English => "resource limits".to_string(),
+                Language::Chinese => "资源上限".to_string(),
+            });
+        }
+
+        // 语言设置可能刚刚被修改，后续提示使用新语言
+        let lang = self.language();
+
+        let mut updated = 0;
+        let mut added = 0;
+        for project_config in &config.projects {
+            let path = PathBuf::from(&project_config.path);
+            let old = base.projects.iter().find(|p| p.path == project_config.path);
+            if let Some(project) = self.projects.iter_mut().find(|p| p.path == path) {
+                let mut changed = false;
+                if old.is_none_or(|old| old.alias != project_config.alias)
+                    && project.alias != project_config.alias
+                {
+                    project.alias = project_config.alias.clone();
+                    changed = true;
+                }
+                if old.is_none_or(|old| old.custom_commands != project_config.custom_commands)
+                    && project.custom_commands != project_config.custom_commands
+                {
+                    project.custom_commands = project_config.custom_commands.clone();
+                    changed = true;
+                }
+                if old.is_none_or(|old| old.resource_limits != project_config.resource_limits)
+                    && project.resource_limits != project_config.resource_limits
+                {
+                    project.resource_limits = project_config.resource_limits.clone();
+                    changed = true;
+                }
+                if old.is_none_or(|old| old.shell_tabs != project_config.shell_tabs)
+                    && project.shell_tab_configs() != project_config.shell_tabs
+                {
+                    project.set_shell_tabs(&project_config.shell_tabs);
+                    changed = true;
+                }
+                if changed {
+                    updated += 1;
+                }
+            } else if old.is_none() && path.exists() {
+                // 只添加文件中新增的项目，本次会话中删除的项目不会被加回来
+                match Project::load(path) {
+                    Ok(mut project) => {
+                        project.alias = project_config.alias.clone();
+                        project.custom_commands = project_config.custom_commands.clone();
+                        project.worktree_of =
+                            project_config.worktree_of.as_ref().map(PathBuf::from);
+                        project.resource_limits = project_config.resource_limits.clone();
+                        project.set_shell_tabs(&project_config.shell_tabs);
+                        self.add_project(project);
+                        added += 1;
+                    }
+                    Err(e) => {
+                        log::warn!("Failed to load project {}: {}", project_config.path, e);
+                    }
+                }
+            }
+        }
+
+        // 移除从配置中删除、且没有运行中进程的项目（从后往前删除以保持索引有效）
+        // 只比较上一次读取的配置，本次会话中新添加的项目尚未写入文件，不能误删
+        let mut removed = 0;
+        for idx in (0..self.projects.len()).rev() {
+            let project = &self.projects[idx];
+            let path = project.path.to_string_lossy();
+            let was_in_config = base.projects.iter().any(|p| p.path == path);
+            let in_config = config.projects.iter().any(|p| p.path == path);
+            if was_in_config && !in_config && !project.has_running_pty() {
+                self.remove_project(idx);
+                removed += 1;
+            }
+        }
+        self.disk_config = config;
+
+        for (count, en, zh) in [
+            (added, "added", "新增"),
+            (updated, "updated", "更新"),
+            (removed, "removed", "移除"),
+        ] {
+            if count > 0 {
+                changes.push(match lang {
+                    Language::English => format!("{} project(s) {}", count, en),
+                    Language::Chinese => format!("{} {} 个项目", zh, count),
+                });
+            }
+        }
+
+        // 内容未变（例如只是保存了一次），不打扰用户
+        if changes.is_empty() {
+            return None;
+        }
+
+        Some(match lang {
+            Language::English => format!("Config reloaded: {}", changes.join(", ")),
+            Language::Chinese => format!("配置已重新加载: {}", changes.join("，")),
+        })
+    }
+
+    /// 重新加载项目的 package.json 或 .devpanel.json
+    fn reload_project_file(&mut self, path: &Path) -> Option<String> {
+        let lang = self.language();
+        let project = self
+            .projects
+            .iter_mut()
+            .find(|p| p.watched_files().iter().any(|f| f == path))?;
+
+        let is_package_json = path.file_name().is_some_and(|n| n == "package.json");
+
+        if is_package_json {
+            match project.reload_package_json() {
+                Ok(diff) if diff.is_empty() => None,
+                Ok(diff) => Some(match lang {
+                    Language::English => format!(
+                        "{}: scripts reloaded (+{} / -{} / ~{})",
+                        project.display_name(),
+                        diff.added,
+                        diff.removed,
+                        diff.changed
+                    ),
+                    Language::Chinese => format!(
+                        "{}: 脚本已重新加载 (+{} / -{} / ~{})",
+                        project.display_name(),
+                        diff.added,
+                        diff.removed,
+                        diff.changed
+                    ),
+                }),
+                Err(e) => {
+                    log::warn!("Failed to reload {}: {}", path.display(), e);
+                    Some(match lang {
+                        Language::English => {
+                            format!("{}: package.json not reloaded", project.display_name())
+                        }
+                        Language::Chinese => {
+                            format!("{}: package.json 未重新加载", project.display_name())
+                        }
+                    })
+                }
+            }
+        } else {
+            match project.reload_local_config() {
+                Ok(false) => None,
+                Ok(true) => Some(match lang {
+                    Language::English => {
+                        format!("{}: project commands reloaded", project.display_name())
+                    }
+                    Language::Chinese => {
+                        format!("{}: 项目命令已重新加载", project.display_name())
+                    }
+                }),
+                Err(e) => {
+                    log::warn!("Failed to reload {}: {}", path.display(), e);
+                    Some(match lang {
+                        Language::English => {
+                            format!("{}: .devpanel.json not reloaded", project.display_name())
+                        }
+                        Language::Chinese => {
+                            format!("{}: .devpanel.json 未重新加载", project.display_name())
+                        }
+                    })
+                }
+            }
+        }
+    }
+}
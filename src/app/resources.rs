@@ -0,0 +1,19 @@
+//! 资源占用
+//! 查看当前项目 Dev 进程最近 15 分钟的 CPU 和内存历史
+
+use super::{AppMode, AppState};
+
+impl AppState {
+    /// 打开资源占用弹窗（Dev 进程未运行时在状态栏提示）
+    pub fn open_resources(&mut self) {
+        let msg = match self.active_project() {
+            None => self.i18n().no_project(),
+            Some(project) if project.dev_pty.is_none() => self.i18n().dev_not_running(),
+            Some(_) => {
+                self.mode = AppMode::Resources;
+                return;
+            }
+        };
+        self.set_status(msg);
+    }
+}
@@ -0,0 +1,114 @@
+//! Shell 标签页
+//! 新建、重命名、关闭和切换当前项目的 Shell 标签
+
+use super::{AppMode, AppState, FocusArea};
+use crate::i18n::Language;
+use crate::project::ShellTabConfig;
+
+impl AppState {
+    /// 输入新标签的名称、目录和初始命令
+    pub fn open_new_shell_tab(&mut self) {
+        if self.active_project().is_none() {
+            let msg = self.i18n().no_project().to_string();
+            self.set_status(&msg);
+            return;
+        }
+        self.input_buffer.clear();
+        self.mode = AppMode::NewShellTab;
+    }
+
+    /// 按输入新建标签并选中它，返回是否已创建（之后由调用方启动 Shell）
+    pub fn submit_new_shell_tab(&mut self) -> bool {
+        let Some(config) = ShellTabConfig::parse(&self.input_buffer) else {
+            return false;
+        };
+        // 标签按名称保存和恢复，名称不能重复
+        if self.shell_tab_name_taken(&config.name, None) {
+            let msg = self.i18n().shell_tab_exists(&config.name);
+            self.set_status(&msg);
+            return false;
+        }
+        self.exit_mode();
+        match self.active_project_mut() {
+            Some(project) => {
+                project.add_shell(config);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// 重命名当前标签（输入框预填当前名称）
+    pub fn open_rename_shell_tab(&mut self) {
+        let Some(name) = self
+            .active_project()
+            .and_then(|p| p.active_shell())
+            .map(|tab| tab.config.name.clone())
+        else {
+            let msg = self.i18n().no_shell_tab().to_string();
+            self.set_status(&msg);
+            return;
+        };
+        self.input_buffer = name;
+        self.mode = AppMode::RenameShellTab;
+    }
+
+    pub fn submit_rename_shell_tab(&mut self) {
+        let name = self.input_buffer.trim().to_string();
+        if name.is_empty() {
+            return;
+        }
+        let active = self.active_project().map(|p| p.active_shell_idx);
+        if self.shell_tab_name_taken(&name, active) {
+            let msg = self.i18n().shell_tab_exists(&name);
+            self.set_status(&msg);
+            return;
+        }
+        self.exit_mode();
+        if let Some(tab) = self
+            .active_project_mut()
+            .and_then(|p| p.shells.get_mut(p.active_shell_idx))
+        {
+            tab.config.name = name;
+        }
+    }
+
+    /// 当前项目中是否已有同名标签（except 为排除的标签索引）
+    fn shell_tab_name_taken(&self, name: &str, except: Option<usize>) -> bool {
+        self.active_project().is_some_and(|p| {
+            p.shells
+                .iter()
+                .enumerate()
+                .any(|(idx, tab)| tab.config.name == name && Some(idx) != except)
+        })
+    }
+
+    /// 关闭当前标签并终止其中的 Shell，没有标签时焦点回到侧边栏
+    pub fn close_shell_tab(&mut self) {
+        let lang = self.language();
+        let tx = self.pty_tx.clone();
+        let idx = self.active_project_idx;
+        let Some(project) = self.projects.get_mut(idx) else {
+            return;
+        };
+        let Some(name) = project.close_shell(&self.config.settings.stop_sequence, &tx) else {
+            let msg = self.i18n().no_shell_tab().to_string();
+            self.set_status(&msg);
+            return;
+        };
+        if project.shells.is_empty() && self.focus == FocusArea::ShellTerminal {
+            self.focus = FocusArea::Sidebar;
+        }
+        self.set_status(&match lang {
+            Language::English => format!("Closed shell tab {}", name),
+            Language::Chinese => format!("已关闭终端标签 {}", name),
+        });
+    }
+
+    /// 切换到下一个（delta 为 1）或上一个（delta 为 -1）标签
+    pub fn cycle_shell_tab(&mut self, delta: isize) {
+        if let Some(project) = self.active_project_mut() {
+            project.cycle_shell(delta);
+        }
+    }
+}
@@ -0,0 +1,90 @@
+//! 主题切换模块
+//! 负责按设置加载主题，以及主题选择器的实时预览
+
+use super::{AppMode, AppState, ThemePickerState};
+use crate::i18n::Language;
+use crate::ui::{available_themes, load_theme, Theme};
+
+impl AppState {
+    /// 按 settings.theme 加载主题
+    /// 主题不存在或文件有误时回退到默认主题，并在状态栏提示
+    pub fn apply_theme_setting(&mut self) {
+        let name = self.config.settings.theme.clone();
+        match load_theme(&name) {
+            Ok(theme) => self.theme = theme,
+            Err(e) => {
+                log::warn!("Failed to load theme '{}': {:#}", name, e);
+                self.theme = Theme::default();
+                let msg = match self.language() {
+                    Language::English => format!("Theme not loaded: {:#}", e),
+                    Language::Chinese => format!("主题加载失败: {:#}", e),
+                };
+                self.set_status(&msg);
+            }
+        }
+    }
+
+    /// 打开主题选择器，选中当前主题
+    pub fn open_theme_picker(&mut self) {
+        let names = available_themes();
+        let selected = names
+            .iter()
+            .position(|n| *n == self.theme.name)
+            .unwrap_or(0);
+        self.theme_picker = ThemePickerState {
+            names,
+            selected,
+            original: Some(self.theme.clone()),
+        };
+        self.mode = AppMode::ThemePicker;
+    }
+
+    /// 选择下一个主题并预览
+    pub fn theme_picker_next(&mut self) {
+        let count = self.theme_picker.names.len();
+        if count > 0 {
+            self.theme_picker.selected = (self.theme_picker.selected + 1) % count;
+            self.preview_selected_theme();
+        }
+    }
+
+    /// 选择上一个主题并预览
+    pub fn theme_picker_prev(&mut self) {
+        let count = self.theme_picker.names.len();
+        if count > 0 {
+            self.theme_picker.selected = (self.theme_picker.selected + count - 1) % count;
+            self.preview_selected_theme();
+        }
+    }
+
+    /// 确认选择：保存到设置并返回设置页
+    pub fn confirm_theme_picker(&mut self) {
+        if let Some(name) = self.theme_picker.names.get(self.theme_picker.selected) {
+            self.config.settings.theme = name.clone();
+        }
+        self.theme_picker.original = None;
+        self.mode = AppMode::Settings;
+    }
+
+    /// 取消选择：恢复原主题并返回设置页
+    pub fn cancel_theme_picker(&mut self) {
+        if let Some(original) = self.theme_picker.original.take() {
+            self.theme = original;
+        }
+        self.mode = AppMode::Settings;
+    }
+
+    /// 实时预览选中的主题
+    fn preview_selected_theme(&mut self) {
+        let Some(name) = self.theme_picker.names.get(self.theme_picker.selected) else {
+            return;
+        };
+        match load_theme(name) {
+            Ok(theme) => self.theme = theme,
+            Err(e) => {
+                let msg = format!("{:#}", e);
+                self.set_status(&msg);
+            }
+        }
+    }
+}
@@ -1,7 +1,15 @@
 //! 应用类型定义模块
 //! 包含各种枚举和简单类型定义
 
-use std::time::Instant;
+use crate::config::RunTarget;
+use crate::project::{
+    CommandGroup, CommandSource, CommandType, DependencyState, GitAction, GitStatus, GitWorktree,
+    Prompt, RunnerFallback,
+};
+use crate::ui::Theme;
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::time::{Instant, SystemTime};
 
 /// 焦点区域枚举
 /// 用于追踪当前用户焦点所在的 UI 区域
@@ -62,6 +70,15 @@ impl PanelLayout {
     }
 }
 
+/// 鼠标拖动调整的分隔线
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LayoutDrag {
+    /// 侧边栏右边框（调整侧边栏宽度）
+    Sidebar,
+    /// Dev Terminal 和 Shell Terminal 之间的分隔线（调整占比）
+    Divider,
+}
+
 /// 应用模式枚举
 /// 用于处理不同的交互模式（普通模式、弹窗模式等）
 #[derive(Debug, Clone, PartialEq, Default)]
@@ -69,15 +86,250 @@ pub enum AppMode {
     #[default]
     Normal,
     CommandPalette,
-    AddCommand,
+    CommandManager,  // 命令管理（编辑、删除、排序、转换）
+    CommandForm,     // 新建/编辑命令表单
     AddProject,      // 旧的手动输入模式（保留）
     BrowseDirectory, // 新的目录浏览器模式
     EditAlias,
     Help,
     Settings,
+    ThemePicker,     // 主题选择（从设置页进入，实时预览）
+    History,         // 命令运行历史
+    CommandPrompt,   // 运行前回答命令中的 input/pick 占位符
+    Problems,        // 问题列表（从 Dev 输出中解析的错误和警告）
+    Hints,           // 提示模式：为终端面板中的 file:line 引用显示标签
+    Git,             // Git 详情（变更文件和快捷操作）
+    NewWorktree,     // 输入新工作树的分支名
+    NewShellTab,     // 输入新 Shell 标签的名称、目录和初始命令
+    RenameShellTab,  // 重命名当前 Shell 标签
+    Resources,       // Dev 进程的 CPU 和内存历史曲线
+    ProcessTree,     // Dev 进程的进程树（终止、暂停、调整优先级）
     Confirm(String), // 确认对话框，参数为确认消息
 }
 
+/// 主题选择器状态
+#[derive(Debug, Clone, Default)]
+pub struct ThemePickerState {
+    /// 可选主题名称列表
+    pub names: Vec<String>,
+    /// 当前选中索引
+    pub selected: usize,
+    /// 打开选择器前的主题（取消时恢复）
+    pub original: Option<Theme>,
+}
+
+/// 确认对话框确认后执行的操作
+#[derive(Debug, Clone, PartialEq, Default)]
+pub enum ConfirmAction {
+    #[default]
+    DeleteProject,
+    /// 删除命令（来源和命令名称，确认时按名称重新查找）
+    DeleteCommand(CommandSource, String),
+    /// 运行命令前安装依赖（命令在命令面板中的索引）
+    InstallDependencies(usize),
+    /// 包管理器未安装，改用替代方式运行命令
+    UseRunnerFallback(usize, CommandTarget, RunnerFallback),
+}
+
+/// 命令表单字段
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CommandFormField {
+    Name,
+    Kind,
+    Command,
+    Cwd,
+    Target,
+    Env,
+    Description,
+}
+
+impl CommandFormField {
+    /// 表单中字段的显示顺序
+    pub const ALL: [CommandFormField; 7] = [
+        CommandFormField::Name,
+        CommandFormField::Kind,
+        CommandFormField::Command,
+        CommandFormField::Cwd,
+        CommandFormField::Target,
+        CommandFormField::Env,
+        CommandFormField::Description,
+    ];
+}
+
+/// 命令表单状态
+#[derive(Debug, Clone)]
+pub struct CommandFormState {
+    /// 命令来源
+    pub source: CommandSource,
+    /// 正在编辑的命令原来的名称（None 表示新建）
+    /// 保存时按名称重新查找，编辑期间 .devpanel.json 被重新加载也不会改错命令
+    pub editing: Option<String>,
+    pub name: String,
+    /// 命令类型（Shell 命令、顺序组合或并行组合）
+    pub cmd_type: CommandType,
+    pub command: String,
+    pub cwd: String,
+    pub target: Option<RunTarget>,
+    /// 环境变量（`KEY=value; KEY2=value`）
+    pub env: String,
+    pub description: String,
+    /// 当前聚焦的字段（CommandFormField::ALL 中的索引）
+    pub focus: usize,
+    /// 保存或取消后是否回到命令管理
+    pub from_manager: bool,
+    /// 校验错误
+    pub error: Option<String>,
+}
+
+impl CommandFormState {
+    /// 当前聚焦的字段
+    pub fn focused_field(&self) -> CommandFormField {
+        CommandFormField::ALL[self.focus]
+    }
+
+    /// 字段对应的文本（Kind 和 Target 不是文本字段）
+    pub fn text_mut(&mut self, field: CommandFormField) -> Option<&mut String> {
+        match field {
+            CommandFormField::Name => Some(&mut self.name),
+            CommandFormField::Command => Some(&mut self.command),
+            CommandFormField::Cwd => Some(&mut self.cwd),
+            CommandFormField::Env => Some(&mut self.env),
+            CommandFormField::Description => Some(&mut self.description),
+            CommandFormField::Kind | CommandFormField::Target => None,
+        }
+    }
+}
+
+/// 命令占位符输入状态
+/// 运行带 `${input:..}` / `${pick:..}` 的命令前逐个询问
+#[derive(Debug, Clone)]
+pub struct CommandPromptState {
+    /// 命令在 get_all_commands() 中的索引
+    pub command_idx: usize,
+    /// 回答完成后的运行位置
+    pub target: CommandTarget,
+    /// 命令所在的分组
+    pub command_source: CommandGroup,
+    /// 命令名称
+    pub command_name: String,
+    /// 需要回答的占位符
+    pub prompts: Vec<Prompt>,
+    /// 当前回答到第几个
+    pub step: usize,
+    /// 回答（预填上次的回答，键为标签）
+    pub answers: BTreeMap<String, String>,
+    /// pick 占位符当前选中的选项
+    pub pick_idx: usize,
+}
+
+/// 在编辑器中打开文件的请求
+#[derive(Debug, Clone, PartialEq)]
+pub struct EditorRequest {
+    pub path: PathBuf,
+    pub line: u32,
+    pub column: u32,
+}
+
+/// 提示模式的种类
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HintKind {
+    /// file:line 引用，在编辑器中打开
+    Files,
+    /// URL 和 OSC 8 超链接，用系统默认程序打开
+    Links,
+}
+
+/// 提示模式中的目标
+#[derive(Debug, Clone, PartialEq)]
+pub enum HintTarget {
+    File {
+        file: String,
+        line: u32,
+        column: Option<u32>,
+    },
+    Url(String),
+}
+
+/// 提示模式中的一个目标及其标签
+#[derive(Debug, Clone, PartialEq)]
+pub struct Hint {
+    pub label: String,
+    /// 标签在屏幕上的位置
+    pub x: u16,
+    pub y: u16,
+    pub target: HintTarget,
+}
+
+/// 后台依赖检查的结果
+#[derive(Debug)]
+pub struct DependencyCheck {
+    pub path: PathBuf,
+    /// 检查时使用的已确认 lock 文件修改时间（检查期间用户又回应了安装提示时丢弃结果）
+    pub acknowledged: Option<SystemTime>,
+    pub state: DependencyState,
+}
+
+/// 后台 git 任务的结果
+#[derive(Debug)]
+pub enum GitEvent {
+    /// 仓库状态（不是 Git 仓库或读取失败时为 None）
+    Status {
+        path: PathBuf,
+        status: Option<GitStatus>,
+    },
+    /// 快捷操作完成（成功时为 git 输出的最后一行，失败时为错误信息）
+    Action {
+        path: PathBuf,
+        action: GitAction,
+        result: Result<String, String>,
+    },
+    /// 本地分支列表读取完成
+    Branches {
+        path: PathBuf,
+        branches: Vec<String>,
+    },
+    /// 工作树列表读取完成
+    Worktrees {
+        path: PathBuf,
+        worktrees: Vec<GitWorktree>,
+    },
+    /// 新工作树创建完成
+    WorktreeAdded {
+        /// 发起创建的项目路径
+        parent: PathBuf,
+        path: PathBuf,
+        result: Result<(), String>,
+    },
+}
+
+/// 项目的 Git 状态刷新记录
+#[derive(Debug, Clone, Default)]
+pub struct GitTracker {
+    /// 上次刷新时 HEAD、index、FETCH_HEAD 的修改时间
+    pub signature: Vec<Option<SystemTime>>,
+    /// 上次刷新完成的时间
+    pub refreshed: Option<Instant>,
+    /// 是否正在后台刷新
+    pub pending: bool,
+}
+
+/// Git 详情弹窗状态
+#[derive(Debug, Clone, Default)]
+pub struct GitPopupState {
+    /// 变更文件列表选中索引
+    pub selected: usize,
+    /// 切换分支时的本地分支列表
+    pub branches: Option<Vec<String>>,
+    /// 分支列表选中索引
+    pub branch_idx: usize,
+    /// 仓库的工作树列表
+    pub worktrees: Option<Vec<GitWorktree>>,
+    /// 工作树列表选中索引
+    pub worktree_idx: usize,
+    /// 正在运行的快捷操作
+    pub running: Option<GitAction>,
+}
+
 /// PTY 资源清理状态（Windows 专用）
 /// 用于追踪 ConPTY 资源释放进度
 #[derive(Debug, Clone)]
@@ -112,6 +364,19 @@ impl PtyCleanupState {
     pub const MAX_WAIT_MS: u64 = 3000;
 }
 
+/// 超过资源上限后等待重新运行的命令
+#[derive(Debug, Clone)]
+pub struct RestartRequest {
+    /// 项目路径
+    pub project_path: PathBuf,
+    /// 命令所在的分组
+    pub source: CommandGroup,
+    /// 命令名称
+    pub command: String,
+    /// 停止旧进程的时间
+    pub stopped_at: Instant,
+}
+
 /// 待执行的命令
 /// 当 PTY 资源正在释放时，缓存用户请求的命令
 #[derive(Debug, Clone)]
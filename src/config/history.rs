@@ -0,0 +1,294 @@
+//! 命令运行历史模块
+//! 按项目记录每次运行的命令（持久化到 ~/.devpanel/history.json），
+//! 用于命令面板的 frecency 排序、历史查看和重新运行，
+//! 同时记住带占位符命令上次的回答
+
+use super::get_config_dir;
+use crate::project::CommandGroup;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+/// 历史文件名（位于配置目录下）
+pub const HISTORY_FILE_NAME: &str = "history.json";
+
+/// 每个项目最多保留的运行记录数
+pub const MAX_RUNS_PER_PROJECT: usize = 200;
+
+/// 命令运行位置
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RunTarget {
+    Dev,
+    Shell,
+}
+
+/// 单次运行记录
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RunRecord {
+    /// 命令所在的分组（与名称一起匹配命令列表中的条目）
+    #[serde(default)]
+    pub source: CommandGroup,
+    /// 命令名称（用于匹配命令列表中的条目）
+    pub name: String,
+    /// 实际执行的命令
+    pub command: String,
+    /// 运行位置
+    pub target: RunTarget,
+    /// 开始时间（Unix 时间戳，秒）
+    pub started_at: u64,
+    /// 运行时长（毫秒），Shell 中运行或尚未结束时为空
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub duration_ms: Option<u64>,
+    /// 退出码，Shell 中运行或尚未结束时为空
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exit_code: Option<i32>,
+    /// 对应的 PTY ID（仅运行期间有效，不持久化）
+    #[serde(skip)]
+    pub pty_id: Option<String>,
+    /// 开始时刻（用于计算时长，不持久化）
+    #[serde(skip)]
+    pub started: Option<Instant>,
+}
+
+/// 单个项目的运行历史
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ProjectHistory {
+    /// 运行记录（按时间顺序，最新的在最后）
+    #[serde(default)]
+    pub runs: Vec<RunRecord>,
+    /// 带占位符的命令上次的回答（命令分组 → 命令名称 → 标签 → 回答）
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub answers: BTreeMap<CommandGroup, BTreeMap<String, BTreeMap<String, String>>>,
+}
+
+/// 全部项目的运行历史（键为项目路径）
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CommandHistory {
+    #[serde(default)]
+    pub projects: BTreeMap<String, ProjectHistory>,
+}
+
+impl CommandHistory {
+    /// 获取项目的运行记录（按时间顺序）
+    pub fn runs(&self, project_path: &Path) -> &[RunRecord] {
+        self.projects
+            .get(&*project_path.to_string_lossy())
+            .map(|h| h.runs.as_slice())
+            .unwrap_or_default()
+    }
+
+    /// 追加运行记录，超出上限时丢弃最早的记录
+    pub fn record(&mut self, project_path: &Path, record: RunRecord) {
+        let history = self
+            .projects
+            .entry(project_path.to_string_lossy().into_owned())
+            .or_default();
+        history.runs.push(record);
+        if history.runs.len() > MAX_RUNS_PER_PROJECT {
+            let excess = history.runs.len() - MAX_RUNS_PER_PROJECT;
+            history.runs.drain(..excess);
+        }
+    }
+
+    /// 按 PTY ID 查找尚未结束的运行记录
+    pub fn running(&self, pty_id: &str) -> Option<&RunRecord> {
+        self.projects
+            .values()
+            .flat_map(|h| h.runs.iter())
+            .find(|r| r.pty_id.as_deref() == Some(pty_id))
+    }
+
+    /// 标记运行结束：按 PTY ID 找到记录并写入时长和退出码
+    /// 返回是否找到对应记录
+    pub fn finish(&mut self, pty_id: &str, exit_code: Option<i32>) -> bool {
+        let record = self
+            .projects
+            .values_mut()
+            .flat_map(|h| h.runs.iter_mut())
+            .find(|r| r.pty_id.as_deref() == Some(pty_id));
+        match record {
+            Some(record) => {
+                record.pty_id = None;
+                record.duration_ms = record
+                    .started
+                    .take()
+                    .map(|started| started.elapsed().as_millis() as u64);
+                record.exit_code = exit_code;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// 命令上次的占位符回答
+    pub fn answers(
+        &self,
+        project_path: &Path,
+        source: CommandGroup,
+        name: &str,
+    ) -> Option<&BTreeMap<String, String>> {
+        self.projects
+            .get(&*project_path.to_string_lossy())?
+            .answers
+            .get(&source)?
+            .get(name)
+    }
+
+    /// 记住命令的占位符回答
+    pub fn remember_answers(
+        &mut self,
+        project_path: &Path,
+        source: CommandGroup,
+        name: &str,
+        answers: BTreeMap<String, String>,
+    ) {
+        self.projects
+            .entry(project_path.to_string_lossy().into_owned())
+            .or_default()
+            .answers
+            .entry(source)
+            .or_default()
+            .insert(name.to_string(), answers);
+    }
+
+    /// 最近一次运行的记录
+    pub fn last_run(&self, project_path: &Path) -> Option<&RunRecord> {
+        self.runs(project_path).last()
+    }
+
+    /// 计算项目中每个命令（按分组和名称区分）的 frecency 得分（频率 × 时间衰减）
+    pub fn frecency(&self, project_path: &Path, now: u64) -> HashMap<(CommandGroup, String), u32> {
+        let mut scores: HashMap<(CommandGroup, String), u32> = HashMap::new();
+        for run in self.runs(project_path) {
+            let age_days = now.saturating_sub(run.started_at) / 86_400;
+            let weight = match age_days {
+                0..=3 => 100,
+                4..=13 => 70,
+                14..=30 => 50,
+                31..=90 => 30,
+                _ => 10,
+            };
+            *scores.entry((run.source, run.name.clone())).or_default() += weight;
+        }
+        scores
+    }
+}
+
+/// 当前 Unix 时间戳（秒）
+pub fn unix_now() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// 获取历史文件路径
+/// 返回 ~/.devpanel/history.json
+pub fn get_history_path() -> PathBuf {
+    get_config_dir().join(HISTORY_FILE_NAME)
+}
+
+/// 从文件加载运行历史，文件不存在时返回空历史
+pub fn load_history(path: &Path) -> anyhow::Result<CommandHistory> {
+    if path.exists() {
+        let content = std::fs::read_to_string(path)?;
+        Ok(serde_json::from_str(&content)?)
+    } else {
+        Ok(CommandHistory::default())
+    }
+}
+
+/// 保存运行历史
+pub fn save_history(history: &CommandHistory, path: &Path) -> anyhow::Result<()> {
+    if let Some(parent) = path.parent() {
+        if !parent.exists() {
+            std::fs::create_dir_all(parent)?;
+        }
+    }
+    let content = serde_json::to_string_pretty(history)?;
+    std::fs::write(path, content)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(name: &str, started_at: u64) -> RunRecord {
+        RunRecord {
+            source: CommandGroup::Script,
+            name: name.to_string(),
+            command: format!("pnpm run {}", name),
+            target: RunTarget::Dev,
+            started_at,
+            duration_ms: None,
+            exit_code: None,
+            pty_id: None,
+            started: None,
+        }
+    }
+
+    #[test]
+    fn test_frecency_prefers_recent_and_frequent() {
+        let path = Path::new("/tmp/app");
+        let now = 100 * 86_400;
+        let mut history = CommandHistory::default();
+        // build 很久以前运行过两次，dev 最近运行过一次
+        history.record(path, run("build", 0));
+        history.record(path, run("build", 0));
+        history.record(path, run("dev", now - 60));
+
+        // 同名的全局命令单独计分
+        history.record(
+            path,
+            RunRecord {
+                source: CommandGroup::Global,
+                ..run("build", now)
+            },
+        );
+
+        let scores = history.frecency(path, now);
+        let score = |source, name: &str| scores[&(source, name.to_string())];
+        assert_eq!(score(CommandGroup::Script, "build"), 20);
+        assert_eq!(score(CommandGroup::Global, "build"), 100);
+        assert_eq!(score(CommandGroup::Script, "dev"), 100);
+        assert_eq!(history.last_run(path).unwrap().source, CommandGroup::Global);
+        assert!(history.runs(Path::new("/other")).is_empty());
+    }
+
+    #[test]
+    fn test_answers_keyed_by_source() {
+        let path = Path::new("/tmp/app");
+        let mut history = CommandHistory::default();
+        let answers = BTreeMap::from([("branch".to_string(), "main".to_string())]);
+        history.remember_answers(path, CommandGroup::Personal, "deploy", answers.clone());
+        assert_eq!(
+            history.answers(path, CommandGroup::Personal, "deploy"),
+            Some(&answers)
+        );
+        assert_eq!(history.answers(path, CommandGroup::Global, "deploy"), None);
+    }
+
+    #[test]
+    fn test_record_caps_and_finish() {
+        let path = Path::new("/tmp/app");
+        let mut history = CommandHistory::default();
+        for i in 0..MAX_RUNS_PER_PROJECT + 5 {
+            history.record(path, run(&format!("cmd{}", i), i as u64));
+        }
+        assert_eq!(history.runs(path).len(), MAX_RUNS_PER_PROJECT);
+        assert_eq!(history.runs(path)[0].name, "cmd5");
+
+        let mut running = run("dev", 1);
+        running.pty_id = Some("dev-1".to_string());
+        running.started = Some(Instant::now());
+        history.record(path, running);
+        assert!(history.finish("dev-1", Some(1)));
+        assert!(!history.finish("dev-1", Some(1)));
+        let last = history.last_run(path).unwrap();
+        assert!(last.duration_ms.is_some());
+        assert_eq!(last.exit_code, Some(1));
+    }
+}
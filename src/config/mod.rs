@@ -2,13 +2,21 @@
 
 #![allow(dead_code)]
 
+mod history;
+mod notify;
 mod persistence;
+mod watcher;
 
+pub use history::*;
+pub use notify::*;
 pub use persistence::*;
+pub use watcher::*;
 
 use crate::i18n::Language;
-use crate::project::ProjectConfig;
+use crate::project::{CommandEntry, ProblemMatcherConfig, ProjectConfig};
+use crate::pty::{default_stop_sequence, ResourceLimits, StopStep};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
 /// Windows Shell 选项
 #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Default)]
@@ -36,8 +44,28 @@ impl WindowsShell {
     }
 }
 
+/// Dev Terminal 和 Shell Terminal 的排列方向
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Default)]
+pub enum PanelOrientation {
+    /// 上下排列
+    #[default]
+    Vertical,
+    /// 左右并排
+    Horizontal,
+}
+
+impl PanelOrientation {
+    /// 切换到另一个方向
+    pub fn toggle(&self) -> Self {
+        match self {
+            PanelOrientation::Vertical => PanelOrientation::Horizontal,
+            PanelOrientation::Horizontal => PanelOrientation::Vertical,
+        }
+    }
+}
+
 /// 应用设置
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct AppSettings {
     /// 主题名称
     pub theme: String,
@@ -55,6 +83,38 @@ pub struct AppSettings {
     /// 最后一次浏览的目录（用于记住 Windows 盘符）
     #[serde(default)]
     pub last_browse_dir: Option<String>,
+    /// 打开 file:line 的编辑器命令（支持 `{file}`、`{line}`、`{col}`，为空时使用 $VISUAL / $EDITOR）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub editor: Option<String>,
+    /// 通过检测到的版本管理器（fnm、nvm、volta、mise）使用项目固定的 Node 版本运行命令
+    #[serde(default)]
+    pub node_version_manager: bool,
+    /// 后台 Dev 进程空闲（未被查看且没有输出）多少分钟后自动暂停（0 表示关闭）
+    #[serde(default)]
+    pub idle_suspend_minutes: u64,
+    /// 停止 Dev 进程时依次发送的信号和每步的等待时间（命令可以单独配置）
+    #[serde(default = "default_stop_sequence")]
+    pub stop_sequence: Vec<StopStep>,
+    /// 侧边栏宽度（列数）
+    #[serde(default = "default_sidebar_width")]
+    pub sidebar_width: u16,
+    /// 是否隐藏侧边栏
+    #[serde(default)]
+    pub sidebar_hidden: bool,
+    /// Dev Terminal 占工作区的百分比（平分布局下，其余为 Shell Terminal）
+    #[serde(default = "default_dev_panel_percent")]
+    pub dev_panel_percent: u16,
+    /// Dev Terminal 和 Shell Terminal 的排列方向
+    #[serde(default)]
+    pub panel_orientation: PanelOrientation,
+}
+
+fn default_sidebar_width() -> u16 {
+    38
+}
+
+fn default_dev_panel_percent() -> u16 {
+    50
 }
 
 impl Default for AppSettings {
@@ -66,10 +126,63 @@ impl Default for AppSettings {
             windows_shell: WindowsShell::default(),
             first_run_shown: false,
             last_browse_dir: None,
+            editor: None,
+            node_version_manager: false,
+            idle_suspend_minutes: 0,
+            stop_sequence: default_stop_sequence(),
+            sidebar_width: default_sidebar_width(),
+            sidebar_hidden: false,
+            dev_panel_percent: default_dev_panel_percent(),
+            panel_orientation: PanelOrientation::default(),
         }
     }
 }
 
+/// 快捷键绑定：单个按键或按键列表（空列表表示解除绑定）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum KeyList {
+    One(String),
+    Many(Vec<String>),
+}
+
+impl KeyList {
+    /// 获取所有按键字符串
+    pub fn keys(&self) -> Vec<&str> {
+        match self {
+            KeyList::One(key) => vec![key.as_str()],
+            KeyList::Many(keys) => keys.iter().map(String::as_str).collect(),
+        }
+    }
+}
+
+/// 快捷键配置（按模式覆盖默认绑定，键为动作名称）
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct KeybindingsConfig {
+    /// Leader 键，可在绑定中用 "<leader>" 引用
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub leader: Option<String>,
+    /// 侧边栏模式
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub normal: BTreeMap<String, KeyList>,
+    /// 日志查看模式
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub dev_log: BTreeMap<String, KeyList>,
+    /// 交互终端模式
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub shell: BTreeMap<String, KeyList>,
+}
+
+impl KeybindingsConfig {
+    /// 是否没有任何自定义
+    pub fn is_empty(&self) -> bool {
+        self.leader.is_none()
+            && self.normal.is_empty()
+            && self.dev_log.is_empty()
+            && self.shell.is_empty()
+    }
+}
+
 /// 应用配置（持久化到 ~/.devpanel/config.json）
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct AppConfig {
@@ -78,6 +191,21 @@ pub struct AppConfig {
     /// 应用设置
     #[serde(default)]
     pub settings: AppSettings,
+    /// 自定义快捷键
+    #[serde(default, skip_serializing_if = "KeybindingsConfig::is_empty")]
+    pub keybindings: KeybindingsConfig,
+    /// 全局命令（显示在所有项目的命令面板中）
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub global_commands: Vec<CommandEntry>,
+    /// 进程事件通知
+    #[serde(default, skip_serializing_if = "NotificationConfig::is_default")]
+    pub notifications: NotificationConfig,
+    /// 自定义问题匹配器（正则）
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub problem_matchers: Vec<ProblemMatcherConfig>,
+    /// 默认资源上限（项目没有单独配置时使用）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resource_limits: Option<ResourceLimits>,
 }
 
 impl AppConfig {
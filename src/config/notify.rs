@@ -0,0 +1,224 @@
+//! 通知配置模块
+//! 决定进程事件（失败退出、长时间命令完成、输出匹配）是否需要通知以及通过哪些方式通知
+
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// 通知方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NotifyChannel {
+    /// 桌面通知（Linux 使用 notify-send 或 D-Bus，macOS 使用 osascript）
+    Desktop,
+    /// OSC 9 终端通知（iTerm2、Windows Terminal、WezTerm 等）
+    Osc9,
+    /// OSC 777 终端通知（foot、rxvt-unicode、Ghostty 等）
+    Osc777,
+    /// 终端响铃
+    Bell,
+}
+
+/// 输出匹配规则：进程输出中出现指定文本时通知
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OutputPattern {
+    /// 要匹配的文本（区分大小写）
+    pub pattern: String,
+    /// 通知内容（为空时使用匹配到的文本）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+/// 需要通知的事件
+#[derive(Debug, Clone, PartialEq)]
+pub enum NotifyEvent {
+    /// 进程以失败退出（被终止时退出码为 None）
+    Failed(Option<i32>),
+    /// 长时间运行的命令成功完成
+    Finished(Duration),
+    /// 输出匹配规则（规则在 patterns 中的索引）
+    Matched(usize),
+    /// Dev 进程超过资源上限（参数为通知内容）
+    OverLimit(String),
+}
+
+/// 通知配置
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NotificationConfig {
+    /// 是否启用通知
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// 通知方式
+    #[serde(default = "default_channels")]
+    pub channels: Vec<NotifyChannel>,
+    /// 进程失败退出时通知
+    #[serde(default = "default_true")]
+    pub on_failure: bool,
+    /// 运行超过多少秒的命令完成时通知（0 表示关闭）
+    #[serde(default = "default_long_running_secs")]
+    pub long_running_secs: u64,
+    /// 只通知非当前选中项目的事件
+    #[serde(default = "default_true")]
+    pub background_only: bool,
+    /// 输出匹配规则
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub patterns: Vec<OutputPattern>,
+    /// 只通知这些项目（名称、别名或路径，为空表示所有项目）
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub projects: Vec<String>,
+    /// 不通知这些项目（名称、别名或路径）
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub muted_projects: Vec<String>,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_channels() -> Vec<NotifyChannel> {
+    vec![NotifyChannel::Bell]
+}
+
+fn default_long_running_secs() -> u64 {
+    60
+}
+
+impl Default for NotificationConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            channels: default_channels(),
+            on_failure: true,
+            long_running_secs: default_long_running_secs(),
+            background_only: true,
+            patterns: Vec::new(),
+            projects: Vec::new(),
+            muted_projects: Vec::new(),
+        }
+    }
+}
+
+impl NotificationConfig {
+    /// 是否为默认配置（保存时省略）
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// 项目是否需要通知（ids 为项目的名称、别名和路径）
+    pub fn allows_project(&self, ids: &[&str]) -> bool {
+        let listed = |list: &[String]| list.iter().any(|p| ids.contains(&p.as_str()));
+        self.enabled
+            && !self.channels.is_empty()
+            && (self.projects.is_empty() || listed(&self.projects))
+            && !listed(&self.muted_projects)
+    }
+
+    /// 进程退出是否需要通知（elapsed 为命令运行时长）
+    pub fn exit_event(
+        &self,
+        exit_code: Option<i32>,
+        elapsed: Option<Duration>,
+    ) -> Option<NotifyEvent> {
+        match exit_code {
+            Some(0) => elapsed
+                .filter(|e| self.long_running_secs > 0 && e.as_secs() >= self.long_running_secs)
+                .map(NotifyEvent::Finished),
+            code => self.on_failure.then_some(NotifyEvent::Failed(code)),
+        }
+    }
+
+    /// 输出中匹配到的规则
+    pub fn match_output(&self, output: &str) -> Option<NotifyEvent> {
+        self.patterns
+            .iter()
+            .position(|p| !p.pattern.is_empty() && output.contains(&p.pattern))
+            .map(NotifyEvent::Matched)
+    }
+}
+
+/// 去掉输出中的 ANSI 转义序列（颜色等），便于匹配文本
+pub fn strip_ansi(data: &[u8]) -> String {
+    let text = String::from_utf8_lossy(data);
+    let mut result = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != '\x1b' {
+            result.push(c);
+            continue;
+        }
+        match chars.next() {
+            // CSI：以 0x40-0x7e 之间的字符结束
+            Some('[') => {
+                for c in chars.by_ref() {
+                    if ('@'..='~').contains(&c) {
+                        break;
+                    }
+                }
+            }
+            // OSC：以 BEL 或 ST 结束
+            Some(']') => {
+                while let Some(c) = chars.next() {
+                    if c == '\x07' || (c == '\x1b' && chars.next_if_eq(&'\\').is_some()) {
+                        break;
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_notification_rules() {
+        let config = NotificationConfig {
+            patterns: vec![OutputPattern {
+                pattern: "Compiled successfully".to_string(),
+                message: None,
+            }],
+            muted_projects: vec!["docs".to_string()],
+            ..Default::default()
+        };
+        assert_eq!(
+            config.exit_event(Some(1), None),
+            Some(NotifyEvent::Failed(Some(1)))
+        );
+        assert_eq!(
+            config.exit_event(None, None),
+            Some(NotifyEvent::Failed(None))
+        );
+        assert_eq!(
+            config.exit_event(Some(0), Some(Duration::from_secs(5))),
+            None
+        );
+        assert_eq!(
+            config.exit_event(Some(0), Some(Duration::from_secs(90))),
+            Some(NotifyEvent::Finished(Duration::from_secs(90)))
+        );
+        assert_eq!(
+            config.match_output("webpack: Compiled successfully in 2s"),
+            Some(NotifyEvent::Matched(0))
+        );
+        assert_eq!(config.match_output("Compiling..."), None);
+        assert!(config.allows_project(&["web", "/work/web"]));
+        assert!(!config.allows_project(&["docs", "/work/docs"]));
+
+        let only_api = NotificationConfig {
+            projects: vec!["/work/api".to_string()],
+            ..Default::default()
+        };
+        assert!(only_api.allows_project(&["api", "/work/api"]));
+        assert!(!only_api.allows_project(&["web", "/work/web"]));
+    }
+
+    #[test]
+    fn test_strip_ansi() {
+        assert_eq!(
+            strip_ansi(b"\x1b[32mready\x1b[0m in \x1b]0;title\x07120ms"),
+            "ready in 120ms"
+        );
+    }
+}
@@ -2,6 +2,9 @@
 //! 负责配置文件的读写
 
 use super::AppConfig;
+use crate::project::ProjectLocalConfig;
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::Value;
 use std::path::{Path, PathBuf};
 
 /// 配置文件夹名称（位于用户主目录下）
@@ -10,6 +13,12 @@ pub const CONFIG_DIR_NAME: &str = ".devpanel";
 /// 默认配置文件名
 pub const CONFIG_FILE_NAME: &str = "config.json";
 
+/// 自定义主题目录名（位于配置目录下）
+pub const THEMES_DIR_NAME: &str = "themes";
+
+/// 项目本地配置文件名（位于项目根目录）
+pub const PROJECT_CONFIG_FILE_NAME: &str = ".devpanel.json";
+
 /// 从文件加载配置
 pub fn load_config(path: &Path) -> anyhow::Result<AppConfig> {
     if path.exists() {
@@ -37,6 +46,57 @@ pub fn save_config(config: &AppConfig, path: &Path) -> anyhow::Result<()> {
     Ok(())
 }
 
+/// 三方合并：只采用 new 相对 base 发生变化的字段，其余字段保留 current
+/// 热重载时 base 为上一次从文件读取的内容，current 中本次会话尚未保存的修改不会被覆盖
+pub fn merge_changed_fields<T: Serialize + DeserializeOwned + Clone>(
+    current: &T,
+    base: &T,
+    new: &T,
+) -> T {
+    let (Ok(Value::Object(mut merged)), Ok(Value::Object(base)), Ok(Value::Object(new))) = (
+        serde_json::to_value(current),
+        serde_json::to_value(base),
+        serde_json::to_value(new),
+    ) else {
+        return new.clone();
+    };
+    // 文件中删除的字段（序列化时省略的可选项）
+    for key in base.keys() {
+        if !new.contains_key(key) {
+            merged.remove(key);
+        }
+    }
+    for (key, value) in new {
+        if base.get(&key) != Some(&value) {
+            merged.insert(key, value);
+        }
+    }
+    serde_json::from_value(Value::Object(merged)).unwrap_or_else(|_| current.clone())
+}
+
+/// 从项目目录加载本地配置（.devpanel.json）
+/// 文件不存在时返回默认配置
+pub fn load_project_local_config(project_path: &Path) -> anyhow::Result<ProjectLocalConfig> {
+    let path = project_path.join(PROJECT_CONFIG_FILE_NAME);
+    if path.exists() {
+        let content = std::fs::read_to_string(path)?;
+        let config: ProjectLocalConfig = serde_json::from_str(&content)?;
+        Ok(config)
+    } else {
+        Ok(ProjectLocalConfig::default())
+    }
+}
+
+/// 保存项目本地配置（.devpanel.json）
+pub fn save_project_local_config(
+    project_path: &Path,
+    config: &ProjectLocalConfig,
+) -> anyhow::Result<()> {
+    let content = serde_json::to_string_pretty(config)?;
+    std::fs::write(project_path.join(PROJECT_CONFIG_FILE_NAME), content + "\n")?;
+    Ok(())
+}
+
 /// 获取配置文件目录路径
 /// 返回 ~/.devpanel/
 pub fn get_config_dir() -> PathBuf {
@@ -51,6 +111,12 @@ pub fn get_config_path() -> PathBuf {
     get_config_dir().join(CONFIG_FILE_NAME)
 }
 
+/// 获取自定义主题目录路径
+/// 返回 ~/.devpanel/themes/
+pub fn get_themes_dir() -> PathBuf {
+    get_config_dir().join(THEMES_DIR_NAME)
+}
+
 /// 确保配置目录存在
 /// 如果不存在则创建
 pub fn ensure_config_dir() -> anyhow::Result<PathBuf> {
@@ -79,4 +145,47 @@ mod tests {
 
         assert_eq!(loaded.settings.theme, "test-theme");
     }
+
+    #[test]
+    fn test_merge_changed_fields() {
+        let base = AppConfig::new().settings;
+        let mut current = base.clone();
+        current.theme = "nord".to_string();
+        current.sidebar_width = 50;
+        let mut new = base.clone();
+        new.default_runner = "bun".to_string();
+        new.editor = Some("code -g {file}:{line}".to_string());
+
+        let merged = merge_changed_fields(&current, &base, &new);
+        assert_eq!(merged.theme, "nord");
+        assert_eq!(merged.sidebar_width, 50);
+        assert_eq!(merged.default_runner, "bun");
+        assert_eq!(merged.editor, new.editor);
+
+        // 文件中删除的可选字段也会生效
+        let merged = merge_changed_fields(&merged, &new, &base);
+        assert_eq!(merged.editor, None);
+        assert_eq!(merged.default_runner, base.default_runner);
+        assert_eq!(merged.theme, "nord");
+    }
+
+    #[test]
+    fn test_project_local_config_defaults() {
+        let dir = tempdir().unwrap();
+        assert!(load_project_local_config(dir.path())
+            .unwrap()
+            .custom_commands
+            .is_empty());
+
+        // 手写配置可以省略 id 和 cmd_type
+        std::fs::write(
+            dir.path().join(PROJECT_CONFIG_FILE_NAME),
+            r#"{ "custom_commands": [{ "name": "db", "command": "docker compose up db" }] }"#,
+        )
+        .unwrap();
+        let config = load_project_local_config(dir.path()).unwrap();
+        assert_eq!(config.custom_commands.len(), 1);
+        assert_eq!(config.custom_commands[0].name, "db");
+        assert!(!config.custom_commands[0].id.is_empty());
+    }
 }
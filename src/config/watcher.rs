@@ -0,0 +1,116 @@
+//! 文件变更监听模块
+//! 通过轮询文件修改时间检测配置文件和 package.json 的变化
+//!
+//! 不依赖系统文件通知机制：主循环本身就是定时轮询的，
+//! 每秒比较一次 mtime 足够及时，且在 macOS/Linux/Windows 上行为一致
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+/// 文件变更监听器
+/// 记录每个被监听文件的最后修改时间（文件不存在时为 None）
+#[derive(Debug, Default)]
+pub struct FileWatcher {
+    files: HashMap<PathBuf, Option<SystemTime>>,
+}
+
+impl FileWatcher {
+    /// 创建空的监听器
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 开始监听文件（以当前状态作为基线，不会立即触发变更）
+    pub fn watch(&mut self, path: PathBuf) {
+        let mtime = modified_time(&path);
+        self.files.insert(path, mtime);
+    }
+
+    /// 停止监听文件
+    pub fn unwatch(&mut self, path: &Path) {
+        self.files.remove(path);
+    }
+
+    /// 是否正在监听指定文件
+    pub fn is_watching(&self, path: &Path) -> bool {
+        self.files.contains_key(path)
+    }
+
+    /// 将文件当前状态记为基线
+    /// 用于程序自己写入文件后，避免把自己的修改当成外部变更
+    pub fn mark_current(&mut self, path: &Path) {
+        if let Some(mtime) = self.files.get_mut(path) {
+            *mtime = modified_time(path);
+        }
+    }
+
+    /// 检查所有被监听的文件，返回自上次检查以来发生变化的文件
+    /// 文件被创建、修改或删除都算作变化
+    pub fn poll(&mut self) -> Vec<PathBuf> {
+        let mut changed = Vec::new();
+        for (path, last) in self.files.iter_mut() {
+            let current = modified_time(path);
+            if current != *last {
+                *last = current;
+                changed.push(path.clone());
+            }
+        }
+        changed.sort();
+        changed
+    }
+}
+
+/// 获取文件修改时间（文件不存在或无法读取时返回 None）
+fn modified_time(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+    use tempfile::tempdir;
+
+    fn touch(path: &Path, time: SystemTime) {
+        let file = std::fs::OpenOptions::new().write(true).open(path).unwrap();
+        file.set_modified(time).unwrap();
+    }
+
+    #[test]
+    fn test_poll_detects_modify_create_and_delete() {
+        let dir = tempdir().unwrap();
+        let existing = dir.path().join("package.json");
+        let missing = dir.path().join(".devpanel.json");
+        std::fs::write(&existing, "{}").unwrap();
+
+        let mut watcher = FileWatcher::new();
+        watcher.watch(existing.clone());
+        watcher.watch(missing.clone());
+        assert!(watcher.poll().is_empty());
+
+        touch(&existing, SystemTime::now() + Duration::from_secs(5));
+        assert_eq!(watcher.poll(), vec![existing.clone()]);
+        assert!(watcher.poll().is_empty());
+
+        std::fs::write(&missing, "{}").unwrap();
+        assert_eq!(watcher.poll(), vec![missing.clone()]);
+
+        std::fs::remove_file(&missing).unwrap();
+        assert_eq!(watcher.poll(), vec![missing]);
+    }
+
+    #[test]
+    fn test_mark_current_suppresses_own_writes() {
+        let dir = tempdir().unwrap();
+        let config = dir.path().join("config.json");
+        std::fs::write(&config, "{}").unwrap();
+
+        let mut watcher = FileWatcher::new();
+        watcher.watch(config.clone());
+
+        touch(&config, SystemTime::now() + Duration::from_secs(5));
+        watcher.mark_current(&config);
+        assert!(watcher.poll().is_empty());
+    }
+}
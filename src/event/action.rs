@@ -0,0 +1,233 @@
+//! 快捷键动作执行模块
+//! 按键经过 keymap 解析为 Action 后，在这里执行对应操作
+
+use crate::app::{AppMode, AppState, CommandTarget, ConfirmAction, FocusArea, HintKind};
+use crate::keymap::Action;
+use crate::pty::PtyManager;
+
+use super::command::rerun_history_command;
+use super::helpers::start_shell_for_active_project;
+
+/// 执行快捷键动作
+pub fn execute_action(
+    state: &mut AppState,
+    action: Action,
+    pty_manager: &PtyManager,
+) -> anyhow::Result<()> {
+    match action {
+        Action::Quit => {
+            state.should_quit = true;
+        }
+        Action::Help => {
+            state.mode = AppMode::Help;
+        }
+        Action::NextProject => {
+            state.select_next_project();
+        }
+        Action::PrevProject => {
+            state.select_prev_project();
+        }
+        // 快速切换项目
+        Action::SelectProject(n) => {
+            let idx = n as usize - 1;
+            if idx < state.projects.len() {
+                state.active_project_idx = idx;
+            }
+        }
+        // 打开命令面板（在 Dev Terminal 运行）
+        Action::RunInDev => open_command_palette(state, CommandTarget::DevTerminal),
+        // 打开命令面板（在 Interactive Shell 运行）
+        Action::RunInShell => open_command_palette(state, CommandTarget::ShellTerminal),
+        // 添加项目（进入目录浏览器）
+        Action::AddProject => {
+            state.enter_browse_mode();
+        }
+        // 添加自定义命令
+        Action::AddCommand => {
+            state.open_command_form(None, false);
+        }
+        // 管理自定义命令
+        Action::ManageCommands => {
+            state.open_command_manager();
+        }
+        // 停止 Dev Server
+        Action::StopDev => {
+            if state.stop_dev(state.active_project_idx) {
+                let msg = state.i18n().dev_stopping().to_string();
+                state.set_status(&msg);
+            }
+        }
+        // 进入 Shell Terminal
+        Action::EnterShell => {
+            start_shell_for_active_project(state, pty_manager)?;
+        }
+        // 删除项目
+        Action::DeleteProject => {
+            if state.active_project().is_some() {
+                let msg = state.i18n().delete_project().to_string();
+                state.confirm_action = ConfirmAction::DeleteProject;
+                state.mode = AppMode::Confirm(msg);
+            }
+        }
+        // 发送 Ctrl+C 给 Dev Server（无需切换焦点）
+        Action::InterruptDev => {
+            if let Some(project) = state.active_project_mut() {
+                // 组合命令中所有正在运行的步骤都会收到 Ctrl+C
+                let composite = project
+                    .composite
+                    .iter_mut()
+                    .flat_map(|c| c.running_ptys_mut());
+                let ptys: Vec<_> = project.dev_pty.iter_mut().chain(composite).collect();
+                if !ptys.is_empty() {
+                    for pty in ptys {
+                        pty.send_input(&[0x03])?; // Ctrl+C
+                    }
+                    let msg = state.i18n().sent_interrupt().to_string();
+                    state.set_status(&msg);
+                }
+            }
+        }
+        // 暂停/恢复 Dev Server（冻结进程节省资源）
+        Action::ToggleSuspend => {
+            if let Some(project) = state.active_project_mut() {
+                if let Some(ref mut pty) = project.dev_pty {
+                    let was_suspended = pty.suspended;
+                    match pty.toggle_suspend() {
+                        Ok(true) => {
+                            let msg = if was_suspended {
+                                state.i18n().process_resumed().to_string()
+                            } else {
+                                state.i18n().process_suspended().to_string()
+                            };
+                            state.set_status(&msg);
+                        }
+                        Ok(false) => {
+                            let msg = state.i18n().suspend_not_supported().to_string();
+                            state.set_status(&msg);
+                        }
+                        Err(e) => {
+                            state.set_status(&format!("Error: {}", e));
+                        }
+                    }
+                }
+            }
+        }
+        // 在原来的位置重新运行最近一次的命令
+        Action::RerunLast => {
+            if let Some(run) = state.last_run() {
+                let (source, name, target) = (run.source, run.name.clone(), run.target.into());
+                rerun_history_command(state, pty_manager, source, &name, target)?;
+            } else {
+                let msg = state.i18n().no_history().to_string();
+                state.set_status(&msg);
+            }
+        }
+        Action::OpenHistory => {
+            state.open_history();
+        }
+        Action::OpenProblems => {
+            state.open_problems();
+        }
+        Action::ShowHints => {
+            state.enter_hint_mode(HintKind::Files);
+        }
+        Action::ShowLinkHints => {
+            state.enter_hint_mode(HintKind::Links);
+        }
+        Action::OpenDevUrl => {
+            state.open_dev_url();
+        }
+        Action::NewShellTab => {
+            state.open_new_shell_tab();
+        }
+        Action::CloseShellTab => {
+            state.close_shell_tab();
+        }
+        Action::RenameShellTab => {
+            state.open_rename_shell_tab();
+        }
+        // 在 Shell Terminal 中切换时，尚未启动的标签立即启动
+        Action::NextShellTab | Action::PrevShellTab => {
+            let delta = if action == Action::NextShellTab {
+                1
+            } else {
+                -1
+            };
+            state.cycle_shell_tab(delta);
+            if state.focus == FocusArea::ShellTerminal {
+                start_shell_for_active_project(state, pty_manager)?;
+            }
+        }
+        Action::OpenResources => {
+            state.open_resources();
+        }
+        Action::OpenProcessTree => {
+            state.open_process_tree();
+        }
+        Action::OpenGit => {
+            state.open_git();
+        }
+        // 打开设置
+        Action::OpenSettings => {
+            state.mode = AppMode::Settings;
+            state.settings_idx = 0;
+        }
+        // 编辑项目别名
+        Action::EditAlias => {
+            if let Some(project) = state.active_project() {
+                // 预填充当前别名
+                state.input_buffer = project.alias.clone().unwrap_or_default();
+                state.mode = AppMode::EditAlias;
+            } else {
+                let msg = state.i18n().no_project().to_string();
+                state.set_status(&msg);
+            }
+        }
+        // 切换面板布局（最大化/平分）
+        Action::ToggleLayout => {
+            state.toggle_panel_layout();
+        }
+        Action::TogglePanelOrientation => state.toggle_panel_orientation(),
+        Action::ToggleSidebar => state.toggle_sidebar(),
+        Action::ShrinkSidebar | Action::GrowSidebar => {
+            let step = if action == Action::GrowSidebar { 2 } else { -2 };
+            let (width, _) = crossterm::terminal::size().unwrap_or((80, 24));
+            state.resize_sidebar(step, width);
+        }
+        Action::ShrinkDevPanel => state.resize_dev_panel(-5),
+        Action::GrowDevPanel => state.resize_dev_panel(5),
+        // === 日志滚动（偏移量越大越靠前）===
+        Action::ScrollUp => scroll_dev_log(state, |offset| offset.saturating_add(1)),
+        Action::ScrollDown => scroll_dev_log(state, |offset| offset.saturating_sub(1)),
+        Action::PageUp => scroll_dev_log(state, |offset| offset.saturating_add(10)),
+        Action::PageDown => scroll_dev_log(state, |offset| offset.saturating_sub(10)),
+        Action::ScrollToLatest => scroll_dev_log(state, |_| 0),
+        // 返回侧边栏（不关闭 shell），离开日志查看时重置滚动位置
+        Action::FocusSidebar => {
+            if state.focus == FocusArea::DevTerminal {
+                if let Some(project) = state.active_project_mut() {
+                    project.dev_scroll_offset = 0;
+                }
+            }
+            state.focus = FocusArea::Sidebar;
+        }
+    }
+    Ok(())
+}
+
+/// 打开命令面板，没有项目时提示
+fn open_command_palette(state: &mut AppState, target: CommandTarget) {
+    if state.active_project().is_some() {
+        state.enter_command_palette(target);
+    } else {
+        let msg = state.i18n().no_project().to_string();
+        state.set_status(&msg);
+    }
+}
+
+/// 调整 Dev 日志的滚动偏移
+fn scroll_dev_log(state: &mut AppState, f: impl FnOnce(usize) -> usize) {
+    if let Some(project) = state.active_project_mut() {
+        project.dev_scroll_offset = f(project.dev_scroll_offset);
+    }
+}
@@ -1,16 +1,75 @@
 //! 命令执行模块
 //! 负责在 Dev Terminal 和 Shell Terminal 中执行命令
 
-use crate::app::{AppState, PendingDevCommand, PtyCleanupState};
-use crate::project::{detect_package_manager, CommandType};
-use crate::pty::PtyManager;
+use super::helpers::spawn_active_shell;
+use crate::app::{AppState, CommandTarget, PendingDevCommand, PtyCleanupState};
+use crate::config::RunTarget;
+use crate::platform::shell_input_with_context;
+use crate::project::{
+    prepend_node_bin, CommandEntry, CommandGroup, CompositeMode, CompositeRun, CompositeStep,
+};
+use crate::pty::{PtyManager, StopStep};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+/// 解析后可直接执行的命令
+struct ResolvedCommand {
+    /// 命令所在的分组
+    source: CommandGroup,
+    name: String,
+    /// 实际执行的命令行（占位符已替换）
+    full_command: String,
+    project_path: PathBuf,
+    working_dir: PathBuf,
+    env: BTreeMap<String, String>,
+    target: Option<RunTarget>,
+    /// 命令配置的停止序列
+    stop: Vec<StopStep>,
+}
+
+/// 解析命令的工作目录、实际执行的命令行和环境变量
+/// 占位符使用该命令上次记住的回答替换，通过版本管理器使用固定的 Node 版本时 PATH 优先指向该版本
+fn resolve_command(
+    state: &AppState,
+    project_idx: usize,
+    command_idx: usize,
+) -> Option<ResolvedCommand> {
+    let cmd = state
+        .project_commands(project_idx)
+        .into_iter()
+        .nth(command_idx)?;
+    let source = state.projects.get(project_idx)?.command_group(command_idx);
+    resolve_entry(state, project_idx, source, cmd)
+}
+
+/// 解析指定项目的命令（组合命令的步骤也通过这里解析）
+fn resolve_entry(
+    state: &AppState,
+    project_idx: usize,
+    source: CommandGroup,
+    cmd: CommandEntry,
+) -> Option<ResolvedCommand> {
+    let project = state.projects.get(project_idx)?;
+    let answers = state.history.answers(&project.path, source, &cmd.name);
+    let runner = state.package_runner(project);
+    let node_bin = project.node.as_ref().and_then(|n| n.bin_dir.as_deref());
+    Some(ResolvedCommand {
+        full_command: project.command_line(&cmd, answers, &runner, &state.command_shell()),
+        project_path: project.path.clone(),
+        working_dir: project.command_dir(&cmd),
+        source,
+        name: cmd.name,
+        env: prepend_node_bin(cmd.env, node_bin),
+        target: cmd.target,
+        stop: cmd.stop,
+    })
+}
 
 /// 请求在 Dev Terminal 执行命令
 /// 如果有旧进程正在运行，会启动资源释放流程并缓存命令
 /// 如果 PTY 创建锁被占用，也会缓存命令等待执行
 /// 返回 true 表示命令已开始执行，false 表示命令已缓存等待执行
-pub fn request_execute_in_dev(state: &mut AppState) -> bool {
-    let command_idx = state.command_palette_idx;
+pub fn request_execute_in_dev(state: &mut AppState, command_idx: usize) -> bool {
     let project_idx = state.active_project_idx;
 
     // 检查是否已经在等待资源释放
@@ -40,19 +99,23 @@ pub fn request_execute_in_dev(state: &mut AppState) -> bool {
         return false;
     }
 
-    // 检查是否有旧进程需要清理
-    let old_pid = state
-        .active_project()
-        .and_then(|p| p.dev_pty.as_ref())
-        .and_then(|pty| pty.pid);
+    // 检查是否有旧进程需要清理（组合命令取任一仍在运行的步骤，也包括仍在停止中的进程）
+    let old_pid = state.active_project().and_then(|p| {
+        p.dev_pty
+            .as_ref()
+            .and_then(|pty| pty.pid)
+            .or_else(|| {
+                p.composite
+                    .as_ref()
+                    .and_then(|c| c.steps.iter().find_map(|s| s.pty.as_ref()?.pid))
+            })
+            .or_else(|| p.stopping.iter().find_map(|pty| pty.pid))
+    });
 
     if let Some(pid) = old_pid {
         // 有旧进程，启动清理流程
-        // 先停止旧进程
-        if let Some(project) = state.active_project_mut() {
-            project.dev_pty = None; // 触发 Drop，调用 kill()
-            project.mark_dev_stopped();
-        }
+        // 先在后台按停止序列停止旧进程，进程树退出后再执行新命令
+        state.stop_dev(project_idx);
 
         // 设置清理状态和待执行命令
         state.pty_cleanup = Some(PtyCleanupState::new(pid, project_idx));
@@ -77,38 +140,39 @@ pub fn request_execute_in_dev(state: &mut AppState) -> bool {
 }
 
 /// 实际执行 Dev 命令（内部使用）
-/// 在确认资源已释放后调用
+/// 在确认资源已释放后调用，project_idx 为运行命令的项目（不一定是当前选中的项目）
 pub fn do_execute_command_in_dev(
     state: &mut AppState,
     pty_manager: &PtyManager,
+    project_idx: usize,
     command_idx: usize,
 ) -> anyhow::Result<()> {
-    let command_info = {
-        if let Some(project) = state.active_project() {
-            let commands = project.get_all_commands();
-            commands.get(command_idx).map(|cmd| {
-                let working_dir = project.path.clone();
-                let full_command = match cmd.cmd_type {
-                    CommandType::NpmScript => {
-                        let pm = detect_package_manager(&working_dir);
-                        format!("{} {}", pm.run_prefix(), cmd.name)
-                    }
-                    CommandType::RawShell => cmd.command.clone(),
-                };
-                (working_dir, full_command, cmd.name.clone())
-            })
-        } else {
-            None
-        }
-    };
-
-    if let Some((working_dir, full_command, cmd_name)) = command_info {
+    // 组合命令由主循环逐步启动各步骤
+    if start_composite(state, project_idx, command_idx) {
+        return Ok(());
+    }
+    // 确认过安装依赖时，先安装再运行
+    if state.take_install_request(project_idx)
+        && start_with_install(state, project_idx, command_idx)
+    {
+        return Ok(());
+    }
+    if let Some(resolved) = resolve_command(state, project_idx, command_idx) {
+        let ResolvedCommand {
+            source,
+            name: cmd_name,
+            full_command,
+            working_dir,
+            env,
+            stop,
+            ..
+        } = resolved;
         // 尝试获取 PTY 创建锁
         if !state.try_acquire_pty_lock("dev") {
             // 锁被占用，缓存命令
             state.pending_dev_command = Some(PendingDevCommand {
                 command_idx,
-                project_idx: state.active_project_idx,
+                project_idx,
             });
             match state.language() {
                 crate::i18n::Language::English => state.set_status("Waiting for PTY ready..."),
@@ -127,6 +191,7 @@ pub fn do_execute_command_in_dev(
             &pty_id,
             &full_command,
             &working_dir,
+            &env,
             24,
             80,
             pty_tx,
@@ -135,13 +200,23 @@ pub fn do_execute_command_in_dev(
         );
 
         match result {
-            Ok(handle) => {
-                if let Some(project) = state.active_project_mut() {
+            Ok(mut handle) => {
+                handle.stop_steps = stop;
+                if let Some(project) = state.projects.get_mut(project_idx) {
+                    project.composite = None;
                     project.dev_pty = Some(handle);
                     project.mark_dev_started();
                 }
                 // 创建成功，开始冷却期
                 state.mark_pty_created("dev");
+                state.record_run(
+                    project_idx,
+                    source,
+                    &cmd_name,
+                    &full_command,
+                    CommandTarget::DevTerminal,
+                    Some(pty_id),
+                );
                 state.set_status(&format!("Running: {}", cmd_name));
             }
             Err(e) => {
@@ -154,14 +229,203 @@ pub fn do_execute_command_in_dev(
     Ok(())
 }
 
+/// 组合命令：解析各步骤并放入 Dev Terminal，步骤由 advance_composites 启动
+/// 返回 false 表示不是组合命令
+fn start_composite(state: &mut AppState, project_idx: usize, command_idx: usize) -> bool {
+    let commands = state.project_commands(project_idx);
+    let Some(cmd) = commands.get(command_idx) else {
+        return false;
+    };
+    let Some(mode) = cmd.cmd_type.composite_mode() else {
+        return false;
+    };
+
+    let Some(project) = state.projects.get(project_idx) else {
+        return false;
+    };
+    let source = project.command_group(command_idx);
+    let steps = crate::project::parse_steps(&cmd.command, mode)
+        .into_iter()
+        .map(|step_name| {
+            let step_cmd = commands
+                .iter()
+                .enumerate()
+                .find(|(_, c)| c.name == step_name);
+            match step_cmd {
+                Some((_, c)) if c.cmd_type.composite_mode().is_some() => CompositeStep::invalid(
+                    &step_name,
+                    composite_step_error(state, &step_name, true),
+                ),
+                Some((idx, c)) => {
+                    match resolve_entry(state, project_idx, project.command_group(idx), c.clone()) {
+                        Some(r) => CompositeStep {
+                            stop: r.stop,
+                            ..CompositeStep::new(&step_name, r.full_command, r.working_dir, r.env)
+                        },
+                        None => CompositeStep::invalid(
+                            &step_name,
+                            composite_step_error(state, &step_name, false),
+                        ),
+                    }
+                }
+                None => CompositeStep::invalid(
+                    &step_name,
+                    composite_step_error(state, &step_name, false),
+                ),
+            }
+        })
+        .collect();
+    let run = CompositeRun::new(&cmd.name, mode, steps);
+    let run_id = run.id.clone();
+
+    if let Some(project) = state.projects.get_mut(project_idx) {
+        project.dev_pty = None;
+        project.composite = Some(run);
+        project.dev_scroll_offset = 0;
+        project.mark_dev_started();
+    }
+    state.record_run(
+        project_idx,
+        source,
+        &cmd.name,
+        &cmd.command,
+        CommandTarget::DevTerminal,
+        Some(run_id),
+    );
+    state.set_status(&format!("Running: {}", cmd.name));
+    // 所有步骤都无法运行时直接结束
+    state.finish_composite_if_done(project_idx);
+    true
+}
+
+/// 先安装依赖再运行命令：作为两步的顺序组合放入 Dev Terminal，安装失败时不会运行命令
+fn start_with_install(state: &mut AppState, project_idx: usize, command_idx: usize) -> bool {
+    let Some(resolved) = resolve_command(state, project_idx, command_idx) else {
+        return false;
+    };
+    let Some(project) = state.projects.get(project_idx) else {
+        return false;
+    };
+    let install = state.package_runner(project).install_command();
+    let node_bin = project.node.as_ref().and_then(|n| n.bin_dir.as_deref());
+    let steps = vec![
+        CompositeStep::new(
+            &install,
+            install.clone(),
+            resolved.project_path.clone(),
+            prepend_node_bin(BTreeMap::new(), node_bin),
+        ),
+        CompositeStep {
+            stop: resolved.stop,
+            ..CompositeStep::new(
+                &resolved.name,
+                resolved.full_command.clone(),
+                resolved.working_dir,
+                resolved.env,
+            )
+        },
+    ];
+    let run = CompositeRun::new(&resolved.name, CompositeMode::Sequence, steps);
+    let run_id = run.id.clone();
+
+    if let Some(project) = state.projects.get_mut(project_idx) {
+        project.dev_pty = None;
+        project.composite = Some(run);
+        project.dev_scroll_offset = 0;
+        project.mark_dev_started();
+    }
+    state.record_run(
+        project_idx,
+        resolved.source,
+        &resolved.name,
+        &resolved.full_command,
+        CommandTarget::DevTerminal,
+        Some(run_id),
+    );
+    state.set_status(&format!("Running: {} → {}", install, resolved.name));
+    true
+}
+
+/// 组合命令步骤无法运行的原因
+fn composite_step_error(state: &AppState, step_name: &str, nested: bool) -> String {
+    match (state.language(), nested) {
+        (crate::i18n::Language::English, false) => format!("Command not found: {}", step_name),
+        (crate::i18n::Language::English, true) => {
+            format!("{} is a composite command and cannot be nested", step_name)
+        }
+        (crate::i18n::Language::Chinese, false) => format!("找不到命令: {}", step_name),
+        (crate::i18n::Language::Chinese, true) => {
+            format!("{} 是组合命令，不能嵌套", step_name)
+        }
+    }
+}
+
+/// 启动组合命令中应该运行的步骤
+/// 由主循环调用：顺序组合在上一步成功后启动下一步，
+/// 并行组合的各步骤受 PTY 创建锁限制，逐个启动
+pub fn advance_composites(state: &mut AppState, pty_manager: &PtyManager) {
+    for project_idx in 0..state.projects.len() {
+        let to_start = match &state.projects[project_idx].composite {
+            Some(run) => run.steps_to_start(),
+            None => continue,
+        };
+        for step_idx in to_start {
+            if !state.try_acquire_pty_lock("composite") {
+                return;
+            }
+            let Some(step) = state.projects[project_idx]
+                .composite
+                .as_ref()
+                .and_then(|run| run.steps.get(step_idx))
+            else {
+                break;
+            };
+            let pty_id = format!("dev-{}", uuid::Uuid::new_v4());
+
+            #[cfg(windows)]
+            let shell_config = state.config.settings.windows_shell;
+
+            let result = pty_manager.run_shell_command(
+                &pty_id,
+                &step.command,
+                &step.working_dir,
+                &step.env,
+                24,
+                80,
+                state.pty_tx.clone(),
+                #[cfg(windows)]
+                shell_config,
+            );
+
+            let Some(run) = state.projects[project_idx].composite.as_mut() else {
+                break;
+            };
+            match result {
+                Ok(mut handle) => {
+                    handle.stop_steps = run.steps[step_idx].stop.clone();
+                    run.start_step(step_idx, handle);
+                    state.mark_pty_created("composite");
+                }
+                Err(e) => {
+                    log::error!("Failed to start composite step: {}", e);
+                    run.steps[step_idx].error = Some(e.to_string());
+                    run.fail_step(step_idx, None);
+                    state.pty_creation_lock = None;
+                }
+            }
+        }
+        state.finish_composite_if_done(project_idx);
+    }
+}
+
 /// 在 Dev Terminal 执行命令（覆盖现有进程）
 /// 这是原有的直接执行版本，用于没有旧进程时的情况
 pub fn execute_command_in_dev(
     state: &mut AppState,
     pty_manager: &PtyManager,
+    command_idx: usize,
 ) -> anyhow::Result<()> {
-    let command_idx = state.command_palette_idx;
-    do_execute_command_in_dev(state, pty_manager, command_idx)
+    do_execute_command_in_dev(state, pty_manager, state.active_project_idx, command_idx)
 }
 
 /// 执行待处理的 Dev 命令（如果有）
@@ -171,16 +435,20 @@ pub fn execute_pending_dev_command(
     pty_manager: &PtyManager,
 ) -> anyhow::Result<bool> {
     if let Some(pending) = state.pending_dev_command.take() {
-        // 确保项目索引仍然有效
-        if pending.project_idx == state.active_project_idx {
+        // 确保项目索引仍然有效（命令在请求时的项目中运行，之后切换了项目也不影响）
+        if pending.project_idx < state.projects.len() {
             log::info!("Executing pending command (idx: {})", pending.command_idx);
-            do_execute_command_in_dev(state, pty_manager, pending.command_idx)?;
+            do_execute_command_in_dev(
+                state,
+                pty_manager,
+                pending.project_idx,
+                pending.command_idx,
+            )?;
             return Ok(true);
         } else {
             log::warn!(
-                "Pending command project mismatch: {} vs {}",
-                pending.project_idx,
-                state.active_project_idx
+                "Pending command project {} no longer exists",
+                pending.project_idx
             );
         }
     }
@@ -191,39 +459,28 @@ pub fn execute_pending_dev_command(
 pub fn execute_command_in_shell(
     state: &mut AppState,
     pty_manager: &PtyManager,
+    command_idx: usize,
 ) -> anyhow::Result<()> {
     use crate::app::FocusArea;
 
-    let command_idx = state.command_palette_idx;
-
     // 获取命令信息
-    let command_info = {
-        if let Some(project) = state.active_project() {
-            let commands = project.get_all_commands();
-            commands.get(command_idx).map(|cmd| {
-                let working_dir = project.path.clone();
-                let full_command = match cmd.cmd_type {
-                    CommandType::NpmScript => {
-                        let pm = detect_package_manager(&working_dir);
-                        format!("{} {}", pm.run_prefix(), cmd.name)
-                    }
-                    CommandType::RawShell => cmd.command.clone(),
-                };
-                (working_dir, full_command, cmd.name.clone())
-            })
-        } else {
-            None
-        }
-    };
-
-    if let Some((project_path, full_command, cmd_name)) = command_info {
-        // 检查是否需要先启动 Shell
-        let needs_shell = {
-            if let Some(project) = state.active_project() {
-                project.shell_pty.is_none()
-            } else {
-                false
+    if let Some(resolved) = resolve_command(state, state.active_project_idx, command_idx) {
+        let ResolvedCommand {
+            source,
+            name: cmd_name,
+            full_command,
+            project_path,
+            working_dir,
+            env,
+            ..
+        } = resolved;
+        // 检查是否需要先启动 Shell（在当前选中的标签中运行）
+        let needs_shell = match state.active_project_mut() {
+            Some(project) => {
+                project.ensure_shell();
+                project.shell_pty().is_none()
             }
+            None => false,
         };
 
         // 如果 Shell 不存在，先启动（带竞态保护）
@@ -237,52 +494,160 @@ pub fn execute_command_in_shell(
                 }
                 return Ok(());
             }
-
-            let pty_id = format!("shell-{}", uuid::Uuid::new_v4());
-            let pty_tx = state.pty_tx.clone();
-
-            #[cfg(windows)]
-            let shell_config = state.config.settings.windows_shell;
-
-            let result = pty_manager.create_shell(
-                &pty_id,
-                &project_path,
-                24,
-                80,
-                pty_tx,
-                #[cfg(windows)]
-                shell_config,
-            );
-
-            match result {
-                Ok(handle) => {
-                    if let Some(project) = state.active_project_mut() {
-                        project.shell_pty = Some(handle);
-                    }
-                    // 创建成功，开始冷却期
-                    state.mark_pty_created("shell-for-cmd");
-                }
-                Err(e) => {
-                    // 创建失败，释放锁
-                    state.pty_creation_lock = None;
-                    state.set_status(&format!("Failed to start shell: {}", e));
-                    return Ok(());
-                }
+            if let Err(e) = spawn_active_shell(state, pty_manager, "shell-for-cmd") {
+                state.set_status(&format!("Failed to start shell: {}", e));
+                return Ok(());
             }
         }
 
+        // 命令配置了工作目录或环境变量时，按当前 Shell 的语法附加
+        let shell = state.command_shell();
+        // Shell 从标签的工作目录启动，命令的工作目录与之不同时才切换
+        let shell_dir = state
+            .active_project()
+            .and_then(|p| Some(p.shell_dir(&p.active_shell()?.config)))
+            .unwrap_or(project_path);
+        let cwd = (working_dir != shell_dir).then_some(working_dir.as_path());
+        let input = shell_input_with_context(&full_command, cwd, &env, &shell);
+
         // 向 Shell 发送命令（加上回车执行）
         if let Some(project) = state.active_project_mut() {
-            if let Some(ref mut pty) = project.shell_pty {
+            if let Some(pty) = project.shell_pty_mut() {
                 // 发送命令文本 + 回车
-                let command_with_newline = format!("{}\r", full_command);
+                let command_with_newline = format!("{}\r", input);
                 pty.send_input(command_with_newline.as_bytes())?;
             }
         }
 
-        // 切换焦点到 Shell Terminal
+        // 切换焦点到 Shell Terminal（Shell 中运行无法得知退出码，只记录开始）
         state.focus = FocusArea::ShellTerminal;
+        state.record_run(
+            state.active_project_idx,
+            source,
+            &cmd_name,
+            &full_command,
+            CommandTarget::ShellTerminal,
+            None,
+        );
         state.set_status(&format!("Shell: {}", cmd_name));
     }
     Ok(())
 }
+
+/// 在指定位置运行命令（命令面板、历史和重新运行共用）
+pub fn run_command(
+    state: &mut AppState,
+    pty_manager: &PtyManager,
+    command_idx: usize,
+    target: CommandTarget,
+) -> anyhow::Result<()> {
+    // 组合命令总是在 Dev Terminal 运行，命令固定了运行位置时优先使用
+    let is_composite = state
+        .active_commands()
+        .get(command_idx)
+        .is_some_and(|cmd| cmd.cmd_type.composite_mode().is_some());
+    let target = if is_composite {
+        CommandTarget::DevTerminal
+    } else {
+        resolve_command(state, state.active_project_idx, command_idx)
+            .and_then(|cmd| cmd.target)
+            .map(CommandTarget::from)
+            .unwrap_or(target)
+    };
+    // 包管理器未安装时先询问改用 corepack 或 npx
+    if !state.check_package_runner(command_idx, target) {
+        return Ok(());
+    }
+    match target {
+        // 依赖缺失或过期时先询问是否安装
+        CommandTarget::DevTerminal if !is_composite && state.needs_install_prompt(command_idx) => {
+            state.open_install_prompt(command_idx);
+        }
+        CommandTarget::DevTerminal => run_in_dev(state, pty_manager, command_idx)?,
+        CommandTarget::ShellTerminal => {
+            execute_command_in_shell(state, pty_manager, command_idx)?;
+        }
+    }
+    Ok(())
+}
+
+/// 在 Dev Terminal 运行命令
+/// 使用智能等待机制：如果有旧进程，会缓存命令等待资源释放后自动执行
+pub fn run_in_dev(
+    state: &mut AppState,
+    pty_manager: &PtyManager,
+    command_idx: usize,
+) -> anyhow::Result<()> {
+    if request_execute_in_dev(state, command_idx) {
+        // 没有旧进程，直接执行
+        execute_command_in_dev(state, pty_manager, command_idx)?;
+    }
+    Ok(())
+}
+
+/// 从命令面板运行命令
+/// 命令包含 input/pick 占位符时先弹出输入框，回答完成后再运行
+pub fn start_command(
+    state: &mut AppState,
+    pty_manager: &PtyManager,
+    command_idx: usize,
+    target: CommandTarget,
+) -> anyhow::Result<()> {
+    if state.open_command_prompt(command_idx, target) {
+        return Ok(());
+    }
+    run_command(state, pty_manager, command_idx, target)
+}
+
+/// 重新运行历史中的命令（按分组和名称匹配当前命令列表）
+pub fn rerun_history_command(
+    state: &mut AppState,
+    pty_manager: &PtyManager,
+    source: CommandGroup,
+    name: &str,
+    target: CommandTarget,
+) -> anyhow::Result<()> {
+    match state.command_idx_by_name(source, name) {
+        Some(command_idx) => run_command(state, pty_manager, command_idx, target),
+        None => {
+            let msg = state.command_missing_message(name);
+            state.set_status(&msg);
+            Ok(())
+        }
+    }
+}
+
+/// 重新运行因超过资源上限而停止的命令（在该命令所属的项目中运行，不切换当前项目）
+pub fn restart_over_limit(state: &mut AppState, pty_manager: &PtyManager) {
+    let Some(request) = state.take_due_restart() else {
+        return;
+    };
+    let Some(project_idx) = state
+        .projects
+        .iter()
+        .position(|p| p.path == request.project_path)
+    else {
+        return;
+    };
+    let command_idx = state.command_idx_in(project_idx, request.source, &request.command);
+    let result = match command_idx {
+        Some(command_idx) => {
+            do_execute_command_in_dev(state, pty_manager, project_idx, command_idx)
+        }
+        None => {
+            let msg = state.command_missing_message(&request.command);
+            state.set_status(&msg);
+            return;
+        }
+    };
+    match result {
+        Ok(()) => {
+            let msg = state.restarted_message(project_idx, &request.command);
+            state.set_status(&msg);
+        }
+        Err(e) => {
+            log::error!("Failed to restart {}: {}", request.command, e);
+            state.set_status(&format!("Error: {}", e));
+        }
+    }
+}
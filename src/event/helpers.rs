@@ -5,71 +5,89 @@ use crate::pty::PtyManager;
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 
 /// 为当前项目启动交互式 Shell（带 ConPTY 竞态保护）
+/// 没有标签时创建默认标签，选中的标签没有运行中的 Shell 时启动它
 pub fn start_shell_for_active_project(
     state: &mut AppState,
     pty_manager: &PtyManager,
 ) -> anyhow::Result<()> {
     // 检查是否有项目选中
-    if state.projects.is_empty() {
+    let Some(project) = state.active_project_mut() else {
         let msg = state.i18n().no_project().to_string();
         state.set_status(&msg);
         return Ok(());
+    };
+    project.ensure_shell();
+
+    if project.shell_pty().is_none() {
+        // 尝试获取 PTY 创建锁
+        if !state.try_acquire_pty_lock("shell") {
+            // 锁被占用，缓存请求
+            state.queue_shell_request(state.active_project_idx);
+            return Ok(());
+        }
+        if let Err(e) = spawn_active_shell(state, pty_manager, "shell") {
+            state.set_status(&format!("Failed to start shell: {}", e));
+            return Ok(());
+        }
+        let msg = state.i18n().shell_started().to_string();
+        state.set_status(&msg);
     }
+    state.focus = FocusArea::ShellTerminal;
+    Ok(())
+}
 
-    let (needs_shell, project_path) = {
-        if let Some(project) = state.active_project() {
-            (project.shell_pty.is_none(), Some(project.path.clone()))
-        } else {
-            (false, None)
-        }
+/// 为当前项目选中的 Shell 标签创建 PTY（调用前需已获取 PTY 创建锁）
+/// 标签配置了初始命令时，Shell 启动后立即执行
+pub fn spawn_active_shell(
+    state: &mut AppState,
+    pty_manager: &PtyManager,
+    reason: &str,
+) -> anyhow::Result<()> {
+    let Some((dir, command)) = state.active_project().and_then(|p| {
+        let tab = p.active_shell()?;
+        Some((p.shell_dir(&tab.config), tab.config.command.clone()))
+    }) else {
+        state.pty_creation_lock = None;
+        return Ok(());
     };
 
-    if let Some(path) = project_path {
-        if needs_shell {
-            // 尝试获取 PTY 创建锁
-            if !state.try_acquire_pty_lock("shell") {
-                // 锁被占用，缓存请求
-                state.queue_shell_request(state.active_project_idx);
-                return Ok(());
+    let pty_id = format!("shell-{}", uuid::Uuid::new_v4());
+    let pty_tx = state.pty_tx.clone();
+
+    #[cfg(windows)]
+    let shell_config = state.config.settings.windows_shell;
+
+    let result = pty_manager.create_shell(
+        &pty_id,
+        &dir,
+        24,
+        80,
+        pty_tx,
+        #[cfg(windows)]
+        shell_config,
+    );
+
+    match result {
+        Ok(mut handle) => {
+            if let Some(command) = command {
+                handle.send_input(format!("{}\r", command).as_bytes())?;
             }
-
-            let pty_id = format!("shell-{}", uuid::Uuid::new_v4());
-            let pty_tx = state.pty_tx.clone();
-
-            #[cfg(windows)]
-            let shell_config = state.config.settings.windows_shell;
-
-            let result = pty_manager.create_shell(
-                &pty_id,
-                &path,
-                24,
-                80,
-                pty_tx,
-                #[cfg(windows)]
-                shell_config,
-            );
-
-            match result {
-                Ok(handle) => {
-                    if let Some(project) = state.active_project_mut() {
-                        project.shell_pty = Some(handle);
-                    }
-                    // 创建成功，开始冷却期
-                    state.mark_pty_created("shell");
-                    let msg = state.i18n().shell_started().to_string();
-                    state.set_status(&msg);
-                }
-                Err(e) => {
-                    // 创建失败，释放锁
-                    state.pty_creation_lock = None;
-                    state.set_status(&format!("Failed to start shell: {}", e));
-                    return Ok(());
-                }
+            if let Some(tab) = state
+                .active_project_mut()
+                .and_then(|p| p.shells.get_mut(p.active_shell_idx))
+            {
+                tab.pty = Some(handle);
             }
+            // 创建成功，开始冷却期
+            state.mark_pty_created(reason);
+            Ok(())
+        }
+        Err(e) => {
+            // 创建失败，释放锁
+            state.pty_creation_lock = None;
+            Err(e)
         }
-        state.focus = FocusArea::ShellTerminal;
     }
-    Ok(())
 }
 
 /// 执行待处理的 Shell 请求（如果有）
@@ -1,12 +1,15 @@
 //! 键盘事件处理模块
 
-use crate::app::{AppMode, AppState, CommandTarget, FocusArea};
-use crate::project::Project;
+use crate::app::{AppMode, AppState, CommandFormField, ConfirmAction, FocusArea};
+use crate::keymap::{Action, KeyPress, KeyResolution, KeymapMode};
+use crate::project::{GitAction, Project, Prompt};
 use crate::pty::PtyManager;
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use std::path::PathBuf;
+use std::time::Instant;
 
-use super::command::{execute_command_in_dev, execute_command_in_shell, request_execute_in_dev};
+use super::action::execute_action;
+use super::command::{rerun_history_command, run_command, run_in_dev, start_command};
 use super::helpers::{key_to_bytes, start_shell_for_active_project};
 
 /// 处理键盘事件
@@ -20,296 +23,391 @@ pub fn handle_key_event(
         AppMode::CommandPalette => handle_command_palette_mode(state, key, pty_manager),
         AppMode::AddProject => handle_add_project_mode(state, key),
         AppMode::BrowseDirectory => handle_browse_directory_mode(state, key),
-        AppMode::AddCommand => handle_add_command_mode(state, key),
+        AppMode::CommandManager => handle_command_manager_mode(state, key),
+        AppMode::CommandForm => handle_command_form_mode(state, key),
         AppMode::EditAlias => handle_edit_alias_mode(state, key),
         AppMode::Help => handle_help_mode(state, key),
         AppMode::Settings => handle_settings_mode(state, key),
-        AppMode::Confirm(_) => handle_confirm_mode(state, key),
+        AppMode::ThemePicker => handle_theme_picker_mode(state, key),
+        AppMode::History => handle_history_mode(state, key, pty_manager),
+        AppMode::CommandPrompt => handle_command_prompt_mode(state, key, pty_manager),
+        AppMode::Problems => handle_problems_mode(state, key),
+        AppMode::Resources => handle_resources_mode(state, key),
+        AppMode::ProcessTree => handle_process_tree_mode(state, key),
+        AppMode::Hints => handle_hints_mode(state, key),
+        AppMode::Git => handle_git_mode(state, key),
+        AppMode::NewWorktree => handle_new_worktree_mode(state, key),
+        AppMode::NewShellTab => handle_new_shell_tab_mode(state, key, pty_manager),
+        AppMode::RenameShellTab => handle_rename_shell_tab_mode(state, key),
+        AppMode::Confirm(_) => handle_confirm_mode(state, key, pty_manager),
     }
 }
 
 /// 处理普通模式下的键盘事件
+/// 按键先按当前焦点对应的 keymap 解析，支持多键组合
 fn handle_normal_mode(
     state: &mut AppState,
     key: KeyEvent,
     pty_manager: &PtyManager,
 ) -> anyhow::Result<bool> {
-    // === Shell Terminal 交互模式 ===
-    // 只有 Shell Terminal 是完全交互式的
-    if state.focus == FocusArea::ShellTerminal {
-        match key.code {
-            // Esc 返回侧边栏（不关闭 shell）
-            KeyCode::Esc => {
-                state.focus = FocusArea::Sidebar;
-                return Ok(true);
-            }
-            // 其他所有按键转发给 Shell PTY
-            _ => {
-                let data = key_to_bytes(&key);
-                if !data.is_empty() {
-                    if let Some(project) = state.active_project_mut() {
-                        // 用户输入时自动回到底部（重置滚动偏移）
-                        project.shell_scroll_offset = 0;
-                        if let Some(ref mut pty) = project.shell_pty {
-                            pty.send_input(&data)?;
-                        }
+    let mode = match state.focus {
+        FocusArea::Sidebar => KeymapMode::Normal,
+        FocusArea::DevTerminal => KeymapMode::DevLog,
+        FocusArea::ShellTerminal => KeymapMode::Shell,
+    };
+
+    expire_pending_keys(state, false)?;
+    state.pending_keys.push(key);
+    let keys: Vec<KeyPress> = state.pending_keys.iter().map(KeyPress::from).collect();
+
+    match state.keymap.resolve(mode, &keys) {
+        KeyResolution::Action(action) => {
+            state.pending_keys.clear();
+            state.pending_keys_at = None;
+            execute_action(state, action, pty_manager)?;
+        }
+        // 组合键尚未输入完整，等待后续按键
+        KeyResolution::Pending => {
+            state.pending_keys_at = Some((Instant::now(), state.focus));
+        }
+        KeyResolution::Unbound => {
+            state.pending_keys_at = None;
+            let keys = std::mem::take(&mut state.pending_keys);
+            match mode {
+                // Shell Terminal 完全交互式：未绑定的按键（包括未完成的组合键）转发给 Shell PTY
+                KeymapMode::Shell => send_keys_to_shell(state, &keys)?,
+                // Dev Terminal 只读：其他按键返回侧边栏
+                KeymapMode::DevLog => {
+                    if keys.len() == 1 {
+                        state.focus = FocusArea::Sidebar;
                     }
                 }
-                return Ok(true);
+                KeymapMode::Normal => {}
             }
         }
     }
 
-    // === Dev Terminal 只读模式（查看 log）===
-    if state.focus == FocusArea::DevTerminal {
-        match key.code {
-            // Esc 返回侧边栏
-            KeyCode::Esc => {
-                // 重置滚动位置
-                if let Some(project) = state.active_project_mut() {
-                    project.dev_scroll_offset = 0;
-                }
-                state.focus = FocusArea::Sidebar;
-                return Ok(true);
-            }
-            // j/k 或方向键滚动
-            KeyCode::Char('j') | KeyCode::Down => {
-                if let Some(project) = state.active_project_mut() {
-                    project.dev_scroll_offset = project.dev_scroll_offset.saturating_sub(1);
-                }
-                return Ok(true);
-            }
-            KeyCode::Char('k') | KeyCode::Up => {
-                if let Some(project) = state.active_project_mut() {
-                    project.dev_scroll_offset = project.dev_scroll_offset.saturating_add(1);
-                }
-                return Ok(true);
-            }
-            // Page Up/Down 快速滚动
-            KeyCode::PageUp => {
-                if let Some(project) = state.active_project_mut() {
-                    project.dev_scroll_offset = project.dev_scroll_offset.saturating_add(10);
-                }
-                return Ok(true);
-            }
-            KeyCode::PageDown => {
-                if let Some(project) = state.active_project_mut() {
-                    project.dev_scroll_offset = project.dev_scroll_offset.saturating_sub(10);
-                }
-                return Ok(true);
-            }
-            // Home 跳到最新
-            KeyCode::Home => {
-                if let Some(project) = state.active_project_mut() {
-                    project.dev_scroll_offset = 0;
-                }
-                return Ok(true);
-            }
-            // z 切换面板布局（最大化/平分）
-            KeyCode::Char('z') => {
-                state.toggle_panel_layout();
-                return Ok(true);
-            }
-            _ => {
-                // 其他按键返回侧边栏
-                state.focus = FocusArea::Sidebar;
-                return Ok(true);
-            }
-        }
-    }
+    Ok(true)
+}
 
-    // === 侧边栏模式（全局快捷键）===
-    // Dev Terminal 不需要焦点，所有操作都在侧边栏完成
+/// 处理过期或被取消的未完成组合键
+/// 在 Shell Terminal 中输入的按键转发给 Shell PTY，不会被吞掉
+pub fn expire_pending_keys(state: &mut AppState, force: bool) -> anyhow::Result<()> {
+    if let Some((keys, FocusArea::ShellTerminal)) = state.take_expired_pending_keys(force) {
+        send_keys_to_shell(state, &keys)?;
+    }
+    Ok(())
+}
 
-    // Ctrl+C 或 Ctrl+Q 退出程序
-    if key.modifiers.contains(KeyModifiers::CONTROL) {
-        match key.code {
-            KeyCode::Char('c') | KeyCode::Char('q') => {
-                state.should_quit = true;
-                return Ok(true);
-            }
-            _ => {}
+/// 把按键发送给当前项目的 Shell PTY
+fn send_keys_to_shell(state: &mut AppState, keys: &[KeyEvent]) -> anyhow::Result<()> {
+    let data: Vec<u8> = keys.iter().flat_map(key_to_bytes).collect();
+    if data.is_empty() {
+        return Ok(());
+    }
+    if let Some(project) = state.active_project_mut() {
+        // 用户输入时自动回到底部（重置滚动偏移）
+        project.shell_scroll_offset = 0;
+        if let Some(pty) = project.shell_pty_mut() {
+            pty.send_input(&data)?;
         }
     }
+    Ok(())
+}
 
+/// 处理命令面板模式
+fn handle_command_palette_mode(
+    state: &mut AppState,
+    key: KeyEvent,
+    pty_manager: &PtyManager,
+) -> anyhow::Result<bool> {
+    let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
     match key.code {
-        // 退出
-        KeyCode::Char('q') => {
-            state.should_quit = true;
+        KeyCode::Esc => {
+            state.exit_mode();
         }
-        // 帮助
-        KeyCode::Char('?') => {
-            state.mode = AppMode::Help;
+        // 输入过滤时用 Ctrl-N/P 或方向键移动
+        KeyCode::Down | KeyCode::Tab => {
+            state.command_palette_next();
         }
-        // Tab 切换项目
-        KeyCode::Tab => {
-            state.select_next_project();
+        KeyCode::Char('n') if ctrl => {
+            state.command_palette_next();
         }
-        KeyCode::BackTab => {
-            state.select_prev_project();
+        KeyCode::Up | KeyCode::BackTab => {
+            state.command_palette_prev();
         }
-        // j/k 或方向键切换项目
-        KeyCode::Char('j') | KeyCode::Down => {
-            state.select_next_project();
+        KeyCode::Char('p') if ctrl => {
+            state.command_palette_prev();
         }
-        KeyCode::Char('k') | KeyCode::Up => {
-            state.select_prev_project();
+        // Ctrl-U 清空过滤
+        KeyCode::Char('u') if ctrl => {
+            state.command_filter_clear();
         }
-        // r 打开命令面板（在 Dev Terminal 运行）
-        KeyCode::Char('r') => {
-            if state.active_project().is_some() {
-                state.enter_command_palette(CommandTarget::DevTerminal);
-            } else {
-                let msg = state.i18n().no_project().to_string();
-                state.set_status(&msg);
-            }
+        KeyCode::Char(c) if !ctrl => {
+            state.command_filter_push(c);
         }
-        // R (Shift+r) 打开命令面板（在 Interactive Shell 运行）
-        KeyCode::Char('R') => {
-            if state.active_project().is_some() {
-                state.enter_command_palette(CommandTarget::ShellTerminal);
-            } else {
-                let msg = state.i18n().no_project().to_string();
-                state.set_status(&msg);
+        KeyCode::Backspace => {
+            state.command_filter_pop();
+        }
+        KeyCode::Enter => {
+            let selected = state.selected_command_idx();
+            state.exit_mode();
+            if let Some(command_idx) = selected {
+                let target = state.command_target;
+                start_command(state, pty_manager, command_idx, target)?;
             }
         }
-        // a 添加项目（进入目录浏览器）
-        KeyCode::Char('a') => {
-            state.enter_browse_mode();
+        _ => {}
+    }
+    Ok(true)
+}
+
+/// 处理历史弹窗模式
+fn handle_history_mode(
+    state: &mut AppState,
+    key: KeyEvent,
+    pty_manager: &PtyManager,
+) -> anyhow::Result<bool> {
+    match key.code {
+        KeyCode::Esc | KeyCode::Char('q') => {
+            state.exit_mode();
         }
-        // c 添加自定义命令
-        KeyCode::Char('c') => {
-            if state.active_project().is_some() {
-                state.mode = AppMode::AddCommand;
-                state.input_buffer.clear();
-            } else {
-                let msg = state.i18n().no_project().to_string();
-                state.set_status(&msg);
-            }
+        KeyCode::Char('j') | KeyCode::Down => {
+            state.history_next();
         }
-        // s 停止 Dev Server
-        KeyCode::Char('s') => {
-            if let Some(project) = state.active_project_mut() {
-                if project.dev_pty.is_some() {
-                    project.dev_pty = None;
-                    project.mark_dev_stopped();
-                    let msg = state.i18n().dev_stopped().to_string();
-                    state.set_status(&msg);
-                }
+        KeyCode::Char('k') | KeyCode::Up => {
+            state.history_prev();
+        }
+        // Enter 在原来的位置重新运行选中的命令
+        KeyCode::Enter => {
+            if let Some(run) = state.selected_history_run() {
+                let (source, name, target) = (run.source, run.name.clone(), run.target.into());
+                state.exit_mode();
+                rerun_history_command(state, pty_manager, source, &name, target)?;
             }
         }
-        // Enter 进入 Shell Terminal
+        _ => {}
+    }
+    Ok(true)
+}
+
+/// 处理问题列表模式
+fn handle_problems_mode(state: &mut AppState, key: KeyEvent) -> anyhow::Result<bool> {
+    match key.code {
+        KeyCode::Esc | KeyCode::Char('q') => {
+            state.exit_mode();
+        }
+        KeyCode::Char('j') | KeyCode::Down => {
+            state.problems_next();
+        }
+        KeyCode::Char('k') | KeyCode::Up => {
+            state.problems_prev();
+        }
+        // Enter 在编辑器中打开问题所在位置
         KeyCode::Enter => {
-            start_shell_for_active_project(state, pty_manager)?;
+            state.open_selected_problem();
         }
-        // d 删除项目
-        KeyCode::Char('d') => {
-            if state.active_project().is_some() {
-                let msg = state.i18n().delete_project().to_string();
-                state.mode = AppMode::Confirm(msg);
-            }
+        _ => {}
+    }
+    Ok(true)
+}
+
+/// 处理资源占用弹窗
+fn handle_resources_mode(state: &mut AppState, key: KeyEvent) -> anyhow::Result<bool> {
+    if matches!(
+        key.code,
+        KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('u')
+    ) {
+        state.exit_mode();
+    }
+    Ok(true)
+}
+
+/// 处理进程树弹窗
+fn handle_process_tree_mode(state: &mut AppState, key: KeyEvent) -> anyhow::Result<bool> {
+    match key.code {
+        KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('t') => {
+            state.exit_mode();
+        }
+        KeyCode::Char('j') | KeyCode::Down => {
+            state.process_tree_next();
+        }
+        KeyCode::Char('k') | KeyCode::Up => {
+            state.process_tree_prev();
+        }
+        // Tab 在组合命令的各步骤进程之间切换
+        KeyCode::Tab => {
+            state.process_tree_cycle_root();
         }
-        // x 发送 Ctrl+C 给 Dev Server（无需切换焦点）
+        // x 发送 SIGTERM，X 发送 SIGKILL
         KeyCode::Char('x') => {
-            if let Some(project) = state.active_project_mut() {
-                if let Some(ref mut pty) = project.dev_pty {
-                    pty.send_input(&[0x03])?; // Ctrl+C
-                    let msg = state.i18n().sent_interrupt().to_string();
-                    state.set_status(&msg);
-                }
-            }
+            state.kill_selected_process(false);
+        }
+        KeyCode::Char('X') => {
+            state.kill_selected_process(true);
         }
-        // p 暂停/恢复 Dev Server（冻结进程节省资源）
         KeyCode::Char('p') => {
-            if let Some(project) = state.active_project_mut() {
-                if let Some(ref mut pty) = project.dev_pty {
-                    let was_suspended = pty.suspended;
-                    match pty.toggle_suspend() {
-                        Ok(true) => {
-                            let msg = if was_suspended {
-                                state.i18n().process_resumed().to_string()
-                            } else {
-                                state.i18n().process_suspended().to_string()
-                            };
-                            state.set_status(&msg);
-                        }
-                        Ok(false) => {
-                            let msg = state.i18n().suspend_not_supported().to_string();
-                            state.set_status(&msg);
-                        }
-                        Err(e) => {
-                            state.set_status(&format!("Error: {}", e));
-                        }
-                    }
-                }
-            }
+            state.toggle_selected_process();
         }
-        // 数字键 1-9 快速切换项目
-        KeyCode::Char(c @ '1'..='9') => {
-            let idx = (c as usize) - ('1' as usize);
-            if idx < state.projects.len() {
-                state.active_project_idx = idx;
-            }
+        // + 提高优先级（nice 值减 1），- 降低优先级（nice 值加 1）
+        KeyCode::Char('+') | KeyCode::Char('=') => {
+            state.renice_selected_process(-1);
         }
-        // , 打开设置
-        KeyCode::Char(',') => {
-            state.mode = AppMode::Settings;
-            state.settings_idx = 0;
-        }
-        // e 编辑项目别名
-        KeyCode::Char('e') => {
-            if let Some(project) = state.active_project() {
-                // 预填充当前别名
-                state.input_buffer = project.alias.clone().unwrap_or_default();
-                state.mode = AppMode::EditAlias;
-            } else {
-                let msg = state.i18n().no_project().to_string();
-                state.set_status(&msg);
-            }
+        KeyCode::Char('-') => {
+            state.renice_selected_process(1);
+        }
+        _ => {}
+    }
+    Ok(true)
+}
+
+/// 处理 Git 详情模式
+fn handle_git_mode(state: &mut AppState, key: KeyEvent) -> anyhow::Result<bool> {
+    // 分支列表：Enter 切换，Esc 返回变更文件列表
+    if state.git_popup.branches.is_some() {
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => state.git_popup.branches = None,
+            KeyCode::Char('j') | KeyCode::Down => state.git_next(),
+            KeyCode::Char('k') | KeyCode::Up => state.git_prev(),
+            KeyCode::Enter => state.git_switch_selected(),
+            _ => {}
         }
-        // z 切换面板布局（最大化/平分）
-        KeyCode::Char('z') => {
-            state.toggle_panel_layout();
+        return Ok(true);
+    }
+    // 工作树列表：Enter 切换或添加为项目，n 新建，Esc 返回变更文件列表
+    if state.git_popup.worktrees.is_some() {
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => state.git_popup.worktrees = None,
+            KeyCode::Char('j') | KeyCode::Down => state.git_next(),
+            KeyCode::Char('k') | KeyCode::Up => state.git_prev(),
+            KeyCode::Char('n') => state.start_new_worktree(),
+            KeyCode::Enter => state.git_open_selected_worktree(),
+            _ => {}
         }
+        return Ok(true);
+    }
+    match key.code {
+        KeyCode::Esc | KeyCode::Char('q') => state.exit_mode(),
+        KeyCode::Char('j') | KeyCode::Down => state.git_next(),
+        KeyCode::Char('k') | KeyCode::Up => state.git_prev(),
+        KeyCode::Char('f') => state.run_git_action(GitAction::Fetch),
+        KeyCode::Char('p') => state.run_git_action(GitAction::Pull),
+        KeyCode::Char('s') => state.run_git_action(GitAction::Stash),
+        KeyCode::Char('b') => state.git_show_branches(),
+        KeyCode::Char('w') => state.git_show_worktrees(),
+        // Enter 在编辑器中打开选中的变更文件
+        KeyCode::Enter => state.open_selected_git_file(),
         _ => {}
     }
+    Ok(true)
+}
 
+/// 处理新建工作树的分支名输入
+fn handle_new_worktree_mode(state: &mut AppState, key: KeyEvent) -> anyhow::Result<bool> {
+    match key.code {
+        // 返回工作树列表
+        KeyCode::Esc => state.mode = AppMode::Git,
+        KeyCode::Enter => state.submit_new_worktree(),
+        // 分支名中不能有空白字符
+        KeyCode::Char(c) if !c.is_whitespace() => state.input_buffer.push(c),
+        KeyCode::Backspace => {
+            state.input_buffer.pop();
+        }
+        _ => {}
+    }
     Ok(true)
 }
 
-/// 处理命令面板模式
-fn handle_command_palette_mode(
+/// 处理新建 Shell 标签的输入，创建后立即启动并聚焦
+fn handle_new_shell_tab_mode(
     state: &mut AppState,
     key: KeyEvent,
     pty_manager: &PtyManager,
 ) -> anyhow::Result<bool> {
     match key.code {
-        KeyCode::Esc => {
-            state.exit_mode();
+        KeyCode::Esc => state.exit_mode(),
+        KeyCode::Enter => {
+            let created = state.submit_new_shell_tab();
+            if created {
+                start_shell_for_active_project(state, pty_manager)?;
+            }
         }
-        KeyCode::Char('j') | KeyCode::Down => {
-            state.command_palette_next();
+        KeyCode::Char(c) => state.input_buffer.push(c),
+        KeyCode::Backspace => {
+            state.input_buffer.pop();
         }
-        KeyCode::Char('k') | KeyCode::Up => {
-            state.command_palette_prev();
+        _ => {}
+    }
+    Ok(true)
+}
+
+/// 处理重命名 Shell 标签的输入
+fn handle_rename_shell_tab_mode(state: &mut AppState, key: KeyEvent) -> anyhow::Result<bool> {
+    match key.code {
+        KeyCode::Esc => state.exit_mode(),
+        KeyCode::Enter => state.submit_rename_shell_tab(),
+        KeyCode::Char(c) => state.input_buffer.push(c),
+        KeyCode::Backspace => {
+            state.input_buffer.pop();
+        }
+        _ => {}
+    }
+    Ok(true)
+}
+
+/// 处理提示模式：输入标签字符选择目标
+fn handle_hints_mode(state: &mut AppState, key: KeyEvent) -> anyhow::Result<bool> {
+    match key.code {
+        KeyCode::Char(c) if !key.modifiers.contains(KeyModifiers::CONTROL) => {
+            state.hint_key(c.to_ascii_lowercase());
+        }
+        KeyCode::Backspace => {
+            state.hint_input.pop();
+        }
+        _ => state.exit_hint_mode(),
+    }
+    Ok(true)
+}
+
+/// 处理命令占位符输入模式
+fn handle_command_prompt_mode(
+    state: &mut AppState,
+    key: KeyEvent,
+    pty_manager: &PtyManager,
+) -> anyhow::Result<bool> {
+    let is_pick = matches!(state.current_prompt(), Some(Prompt::Pick { .. }));
+    let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+    match key.code {
+        KeyCode::Esc => {
+            state.prompt_cancel();
         }
         KeyCode::Enter => {
-            // 根据 command_target 决定执行位置
-            match state.command_target {
-                CommandTarget::DevTerminal => {
-                    // 使用智能等待机制：如果有旧进程，会缓存命令等待资源释放
-                    let can_execute_now = request_execute_in_dev(state);
-                    if can_execute_now {
-                        // 没有旧进程，直接执行
-                        execute_command_in_dev(state, pty_manager)?;
-                    }
-                    // 如果返回 false，命令已被缓存，会在资源释放后自动执行
-                }
-                CommandTarget::ShellTerminal => {
-                    execute_command_in_shell(state, pty_manager)?;
-                }
+            if let Some((command_idx, target)) = state.prompt_submit() {
+                run_command(state, pty_manager, command_idx, target)?;
             }
-            state.exit_mode();
+        }
+        // 选择列表
+        KeyCode::Down | KeyCode::Tab | KeyCode::Char('j') if is_pick => {
+            state.prompt_pick_next();
+        }
+        KeyCode::Char('n') if is_pick && ctrl => {
+            state.prompt_pick_next();
+        }
+        KeyCode::Up | KeyCode::BackTab | KeyCode::Char('k') if is_pick => {
+            state.prompt_pick_prev();
+        }
+        KeyCode::Char('p') if is_pick && ctrl => {
+            state.prompt_pick_prev();
+        }
+        // 文本输入
+        KeyCode::Char('u') if ctrl && !is_pick => {
+            state.input_buffer.clear();
+        }
+        KeyCode::Char(c) if !is_pick && !ctrl => {
+            state.input_buffer.push(c);
+        }
+        KeyCode::Backspace if !is_pick => {
+            state.input_buffer.pop();
         }
         _ => {}
     }
@@ -452,31 +550,84 @@ fn handle_dir_browser_select(state: &mut AppState) -> anyhow::Result<()> {
     Ok(())
 }
 
-/// 处理添加命令模式
-fn handle_add_command_mode(state: &mut AppState, key: KeyEvent) -> anyhow::Result<bool> {
+/// 处理命令管理模式
+fn handle_command_manager_mode(state: &mut AppState, key: KeyEvent) -> anyhow::Result<bool> {
     match key.code {
-        KeyCode::Esc => {
+        KeyCode::Esc | KeyCode::Char('q') => {
             state.exit_mode();
         }
-        KeyCode::Enter => {
-            let input = state.input_buffer.clone();
-            if let Some((name, command)) = input.split_once(':') {
-                let name = name.trim().to_string();
-                let command = command.trim().to_string();
-                if let Some(project) = state.active_project_mut() {
-                    project.add_custom_command(&name, &command);
-                }
-                state.set_status(&format!("Added command: {}", name));
-            } else {
-                state.set_status("Format: name:command");
+        KeyCode::Char('j') | KeyCode::Down => {
+            state.command_manager_next();
+        }
+        KeyCode::Char('k') | KeyCode::Up => {
+            state.command_manager_prev();
+        }
+        // J/K 调整顺序
+        KeyCode::Char('J') => {
+            state.move_selected_command(1);
+        }
+        KeyCode::Char('K') => {
+            state.move_selected_command(-1);
+        }
+        KeyCode::Char('a') => {
+            state.open_command_form(None, true);
+        }
+        KeyCode::Enter | KeyCode::Char('e') => {
+            if let Some(entry) = state.selected_manager_entry() {
+                state.open_command_form(Some(entry), true);
             }
-            state.exit_mode();
         }
-        KeyCode::Char(c) => {
-            state.input_buffer.push(c);
+        KeyCode::Char('d') | KeyCode::Delete => {
+            state.request_delete_command();
+        }
+        // 在个人命令和项目共享命令之间转换
+        KeyCode::Char('m') => {
+            state.convert_selected_command();
+        }
+        _ => {}
+    }
+    Ok(true)
+}
+
+/// 处理命令表单模式
+fn handle_command_form_mode(state: &mut AppState, key: KeyEvent) -> anyhow::Result<bool> {
+    let on_choice = state.command_form.as_ref().is_some_and(|f| {
+        matches!(
+            f.focused_field(),
+            CommandFormField::Kind | CommandFormField::Target
+        )
+    });
+    let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+    match key.code {
+        KeyCode::Esc => {
+            state.cancel_command_form();
+        }
+        KeyCode::Enter => {
+            state.save_command_form();
+        }
+        KeyCode::Tab | KeyCode::Down => {
+            state.command_form_next_field();
+        }
+        KeyCode::BackTab | KeyCode::Up => {
+            state.command_form_prev_field();
+        }
+        // 类型和运行位置字段：左右或空格切换
+        KeyCode::Right | KeyCode::Char(' ') if on_choice => {
+            state.command_form_cycle(true);
+        }
+        KeyCode::Left if on_choice => {
+            state.command_form_cycle(false);
+        }
+        KeyCode::Char('u') if ctrl => {
+            state.command_form_edit(|text| text.clear());
+        }
+        KeyCode::Char(c) if !ctrl => {
+            state.command_form_edit(|text| text.push(c));
         }
         KeyCode::Backspace => {
-            state.input_buffer.pop();
+            state.command_form_edit(|text| {
+                text.pop();
+            });
         }
         _ => {}
     }
@@ -515,8 +666,20 @@ fn handle_help_mode(state: &mut AppState, key: KeyEvent) -> anyhow::Result<bool>
     const SCROLL_STEP: f32 = 1.0; // 普通滚动步进（每次 1 行）
     const PAGE_STEP: f32 = 10.0; // 翻页步进
 
+    // 绑定到帮助的单键同样可以关闭帮助
+    let is_help_key = state
+        .keymap
+        .keys_for(KeymapMode::Normal, Action::Help)
+        .iter()
+        .any(|seq| seq.0 == [KeyPress::from(&key)]);
+    if is_help_key {
+        state.help_scroll.reset();
+        state.exit_mode();
+        return Ok(true);
+    }
+
     match key.code {
-        KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('?') => {
+        KeyCode::Esc | KeyCode::Char('q') => {
             state.help_scroll.reset(); // 退出时重置滚动位置
             state.exit_mode();
         }
@@ -577,6 +740,15 @@ fn handle_settings_mode(state: &mut AppState, key: KeyEvent) -> anyhow::Result<b
                     SettingItem::Language => {
                         state.toggle_language();
                     }
+                    SettingItem::Theme => {
+                        state.open_theme_picker();
+                    }
+                    SettingItem::NodeVersionManager => {
+                        state.toggle_node_version_manager();
+                    }
+                    SettingItem::IdleSuspend => {
+                        state.cycle_idle_suspend();
+                    }
                     #[cfg(windows)]
                     SettingItem::WindowsShell => {
                         state.toggle_windows_shell();
@@ -589,20 +761,100 @@ fn handle_settings_mode(state: &mut AppState, key: KeyEvent) -> anyhow::Result<b
     Ok(true)
 }
 
+/// 处理主题选择模式（上下移动即预览）
+fn handle_theme_picker_mode(state: &mut AppState, key: KeyEvent) -> anyhow::Result<bool> {
+    match key.code {
+        KeyCode::Esc => {
+            state.cancel_theme_picker();
+        }
+        KeyCode::Char('j') | KeyCode::Down => {
+            state.theme_picker_next();
+        }
+        KeyCode::Char('k') | KeyCode::Up => {
+            state.theme_picker_prev();
+        }
+        KeyCode::Enter => {
+            state.confirm_theme_picker();
+        }
+        _ => {}
+    }
+    Ok(true)
+}
+
 /// 处理确认对话框模式
-fn handle_confirm_mode(state: &mut AppState, key: KeyEvent) -> anyhow::Result<bool> {
+fn handle_confirm_mode(
+    state: &mut AppState,
+    key: KeyEvent,
+    pty_manager: &PtyManager,
+) -> anyhow::Result<bool> {
+    // 安装依赖：y 先安装再运行，n 直接运行，Esc 取消
+    if let ConfirmAction::InstallDependencies(command_idx) = state.confirm_action {
+        match key.code {
+            KeyCode::Char('y') | KeyCode::Char('Y') | KeyCode::Enter => {
+                state.exit_mode();
+                state.confirm_action = ConfirmAction::default();
+                state.acknowledge_dependencies();
+                state.request_install_before_dev();
+                run_in_dev(state, pty_manager, command_idx)?;
+            }
+            KeyCode::Char('n') | KeyCode::Char('N') => {
+                state.exit_mode();
+                state.confirm_action = ConfirmAction::default();
+                state.acknowledge_dependencies();
+                run_in_dev(state, pty_manager, command_idx)?;
+            }
+            KeyCode::Esc => {
+                state.exit_mode();
+                state.confirm_action = ConfirmAction::default();
+            }
+            _ => {}
+        }
+        return Ok(true);
+    }
+    // 包管理器未安装：确认后记住替代方式并重新运行命令
+    if let ConfirmAction::UseRunnerFallback(command_idx, target, fallback) = state.confirm_action {
+        match key.code {
+            KeyCode::Char('y') | KeyCode::Char('Y') | KeyCode::Enter => {
+                state.exit_mode();
+                state.confirm_action = ConfirmAction::default();
+                if let Some(project) = state.active_project_mut() {
+                    project.runner_fallback = Some(fallback);
+                }
+                run_command(state, pty_manager, command_idx, target)?;
+            }
+            KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => {
+                state.exit_mode();
+                state.confirm_action = ConfirmAction::default();
+            }
+            _ => {}
+        }
+        return Ok(true);
+    }
     match key.code {
         // y/Y 或 Enter 确认删除
         KeyCode::Char('y') | KeyCode::Char('Y') | KeyCode::Enter => {
-            let idx = state.active_project_idx;
-            let msg = state.i18n().project_removed().to_string();
-            state.remove_project(idx);
-            state.set_status(&msg);
             state.exit_mode();
+            match std::mem::take(&mut state.confirm_action) {
+                ConfirmAction::DeleteProject => {
+                    let idx = state.active_project_idx;
+                    let msg = state.i18n().project_removed().to_string();
+                    state.remove_project(idx);
+                    state.set_status(&msg);
+                }
+                ConfirmAction::DeleteCommand(source, name) => {
+                    state.delete_command(source, &name);
+                    state.mode = AppMode::CommandManager;
+                }
+                // 在上方单独处理
+                ConfirmAction::InstallDependencies(_) | ConfirmAction::UseRunnerFallback(..) => {}
+            }
         }
         // n/N 或 Esc 取消
         KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => {
             state.exit_mode();
+            if let ConfirmAction::DeleteCommand(..) = std::mem::take(&mut state.confirm_action) {
+                state.mode = AppMode::CommandManager;
+            }
         }
         _ => {}
     }
@@ -6,6 +6,7 @@
 //! - Dev Server: 只显示命令输出，不需要焦点，r 运行命令，s 停止
 //! - Interactive Shell: 完全交互式，Enter 进入
 
+mod action;
 pub mod command;
 pub mod helpers;
 mod keyboard;
@@ -16,10 +17,7 @@ pub use mouse::*;
 
 use crate::app::AppState;
 use crate::pty::PtyManager;
-use crossterm::event::{Event, KeyEventKind};
-
-/// 侧边栏宽度常量（增加以显示 CPU/内存信息）
-pub const SIDEBAR_WIDTH: u16 = 38;
+use crossterm::event::{Event, KeyEventKind, MouseEventKind};
 
 /// 处理 crossterm 事件
 pub fn handle_event(
@@ -36,7 +34,13 @@ pub fn handle_event(
             }
             handle_key_event(state, key, pty_manager)
         }
-        Event::Mouse(mouse) => handle_mouse_event(state, mouse, pty_manager),
+        Event::Mouse(mouse) => {
+            // 点击会改变焦点，取消未完成的组合键
+            if matches!(mouse.kind, MouseEventKind::Down(_)) {
+                expire_pending_keys(state, true)?;
+            }
+            handle_mouse_event(state, mouse, pty_manager)
+        }
         Event::Resize(_cols, _rows) => {
             // TODO: 处理终端大小变化，通知 PTY resize
             Ok(true)
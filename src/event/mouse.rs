@@ -1,13 +1,13 @@
 //! 鼠标事件处理模块
 
-use crate::app::{AppMode, AppState, FocusArea};
+use crate::app::{AppMode, AppState, FocusArea, LayoutDrag, PanelLayout};
+use crate::config::PanelOrientation;
 use crate::pty::PtyManager;
-use crate::ui::centered_rect;
-use crossterm::event::{MouseButton, MouseEvent, MouseEventKind};
+use crate::ui::{app_layout, centered_rect, sidebar_item_height, AppLayout};
+use crossterm::event::{KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
 use ratatui::layout::{Constraint, Direction, Layout, Rect};
 
 use super::helpers::start_shell_for_active_project;
-use super::SIDEBAR_WIDTH;
 
 /// 计算居中矩形区域（用于弹窗点击检测）
 /// 使用与渲染相同的 centered_rect 函数确保一致性
@@ -40,13 +40,26 @@ pub fn handle_mouse_event(
         return handle_popup_mouse_event(state, mouse, term_width, term_height);
     }
 
-    let content_height = term_height.saturating_sub(2);
-    let half_height = content_height / 2;
+    // 与绘制使用同一份布局计算
+    let layout = app_layout(state, Rect::new(0, 0, term_width, term_height));
 
     match mouse.kind {
         MouseEventKind::Down(MouseButton::Left) => {
-            handle_left_click(state, mouse, pty_manager, half_height, term_height)
+            // 按住侧边栏边框或面板分隔线开始拖动
+            if let Some(drag) = drag_target(state, &layout, mouse.column, mouse.row) {
+                state.layout_drag = Some(drag);
+                return Ok(true);
+            }
+            handle_left_click(state, mouse, pty_manager, &layout)
         }
+        MouseEventKind::Drag(MouseButton::Left) => match state.layout_drag {
+            Some(drag) => {
+                drag_layout(state, drag, &layout, mouse.column, mouse.row);
+                Ok(true)
+            }
+            None => Ok(false),
+        },
+        MouseEventKind::Up(MouseButton::Left) => Ok(state.layout_drag.take().is_some()),
         MouseEventKind::ScrollUp => {
             handle_scroll_up(state);
             Ok(true)
@@ -81,10 +94,25 @@ fn handle_popup_mouse_event(
                 }
                 AppMode::Help => Some(calc_centered_rect(55, 80, term_width, term_height)),
                 AppMode::Settings => Some(calc_centered_rect(50, 40, term_width, term_height)),
+                AppMode::ThemePicker => Some(calc_centered_rect(40, 50, term_width, term_height)),
+                AppMode::History | AppMode::Problems => {
+                    Some(calc_centered_rect(70, 60, term_width, term_height))
+                }
+                AppMode::Resources | AppMode::ProcessTree => {
+                    Some(calc_centered_rect(70, 70, term_width, term_height))
+                }
+                AppMode::CommandManager | AppMode::CommandForm | AppMode::Git => {
+                    Some(calc_centered_rect(60, 60, term_width, term_height))
+                }
+                AppMode::CommandPrompt => Some(calc_centered_rect(60, 40, term_width, term_height)),
                 AppMode::CommandPalette => {
                     Some(calc_centered_rect(60, 50, term_width, term_height))
                 }
-                AppMode::AddProject | AppMode::AddCommand | AppMode::EditAlias => {
+                AppMode::AddProject
+                | AppMode::EditAlias
+                | AppMode::NewWorktree
+                | AppMode::NewShellTab
+                | AppMode::RenameShellTab => {
                     Some(calc_centered_rect(60, 20, term_width, term_height))
                 }
                 AppMode::Confirm(_) => {
@@ -95,13 +123,23 @@ fn handle_popup_mouse_event(
                     let py = (term_height.saturating_sub(height)) / 2;
                     Some((px, py, width, height))
                 }
+                // 提示模式：点击标签打开对应位置，点击其他地方退出
+                AppMode::Hints => {
+                    state.click_hint(x, y);
+                    return Ok(true);
+                }
                 AppMode::Normal => None,
             };
 
             if let Some((rx, ry, rw, rh)) = popup_area {
                 if !is_point_in_rect(x, y, rx, ry, rw, rh) {
-                    // 点击在弹窗区域外，关闭弹窗
-                    state.exit_mode();
+                    // 点击在弹窗区域外，关闭弹窗（主题选择需要恢复预览前的主题，占位符输入需要清除状态）
+                    match state.mode {
+                        AppMode::ThemePicker => state.cancel_theme_picker(),
+                        AppMode::CommandPrompt => state.prompt_cancel(),
+                        AppMode::CommandForm => state.cancel_command_form(),
+                        _ => state.exit_mode(),
+                    }
                     return Ok(true);
                 }
 
@@ -138,7 +176,23 @@ fn handle_popup_mouse_event(
             }
             AppMode::CommandPalette => {
                 // 命令面板向上滚动
-                state.command_palette_idx = state.command_palette_idx.saturating_sub(1);
+                state.command_palette_prev();
+                return Ok(true);
+            }
+            AppMode::History => {
+                state.history_prev();
+                return Ok(true);
+            }
+            AppMode::Problems => {
+                state.problems_prev();
+                return Ok(true);
+            }
+            AppMode::Git => {
+                state.git_prev();
+                return Ok(true);
+            }
+            AppMode::ProcessTree => {
+                state.process_tree_prev();
                 return Ok(true);
             }
             _ => {}
@@ -164,8 +218,24 @@ fn handle_popup_mouse_event(
                 return Ok(true);
             }
             AppMode::CommandPalette => {
-                // 命令面板向下滚动（需要知道最大索引，这里简单 +1）
-                state.command_palette_idx = state.command_palette_idx.saturating_add(1);
+                // 命令面板向下滚动
+                state.command_palette_next();
+                return Ok(true);
+            }
+            AppMode::History => {
+                state.history_next();
+                return Ok(true);
+            }
+            AppMode::Problems => {
+                state.problems_next();
+                return Ok(true);
+            }
+            AppMode::Git => {
+                state.git_next();
+                return Ok(true);
+            }
+            AppMode::ProcessTree => {
+                state.process_tree_next();
                 return Ok(true);
             }
             _ => {}
@@ -200,11 +270,9 @@ fn handle_dir_browser_click(
     let help_items_count = 6u16;
     let avg_item_width = 15u16;
     let total_width_needed = help_items_count * avg_item_width;
-    let help_lines_needed = if inner.width > 0 {
-        (total_width_needed / inner.width).max(1) + 1
-    } else {
-        2
-    };
+    let help_lines_needed = total_width_needed
+        .checked_div(inner.width)
+        .map_or(2, |lines| lines.max(1) + 1);
     let help_height = help_lines_needed.min(3);
 
     // 使用 Layout 分割内部区域（与渲染完全一致）
@@ -256,46 +324,99 @@ fn handle_dir_browser_click(
     Ok(false)
 }
 
+/// 点击位置是否在可拖动的分隔线上
+/// 侧边栏右边框和工作区左边框都可以拖动；分隔线只在分屏布局下可拖动
+fn drag_target(state: &AppState, layout: &AppLayout, x: u16, y: u16) -> Option<LayoutDrag> {
+    if let Some(sidebar) = layout.sidebar {
+        let on_border = x + 1 == sidebar.right() || x == layout.work.x;
+        if on_border && y >= sidebar.y && y < sidebar.bottom() {
+            return Some(LayoutDrag::Sidebar);
+        }
+    }
+
+    if state.panel_layout != PanelLayout::Split {
+        return None;
+    }
+    let work = layout.work;
+    let in_work = is_point_in_rect(x, y, work.x, work.y, work.width, work.height);
+    let on_divider = match state.config.settings.panel_orientation {
+        PanelOrientation::Vertical => y + 1 == layout.dev.bottom() || y == layout.shell.y,
+        PanelOrientation::Horizontal => x + 1 == layout.dev.right() || x == layout.shell.x,
+    };
+    (in_work && on_divider).then_some(LayoutDrag::Divider)
+}
+
+/// 拖动分隔线：侧边栏右边框或 Dev Terminal 的下（右）边框跟随鼠标
+fn drag_layout(state: &mut AppState, drag: LayoutDrag, layout: &AppLayout, x: u16, y: u16) {
+    let work = layout.work;
+    match drag {
+        LayoutDrag::Sidebar => {
+            let content_x = layout.sidebar.map(|s| s.x).unwrap_or(work.x);
+            let content_width = work.right().saturating_sub(content_x);
+            state.set_sidebar_width((x + 1).saturating_sub(content_x), content_width);
+        }
+        LayoutDrag::Divider => {
+            let (offset, total) = match state.config.settings.panel_orientation {
+                PanelOrientation::Vertical => ((y + 1).saturating_sub(work.y), work.height),
+                PanelOrientation::Horizontal => ((x + 1).saturating_sub(work.x), work.width),
+            };
+            if total > 0 {
+                let percent = (offset as u32 * 100 / total as u32) as u16;
+                state.set_dev_panel_percent(percent);
+            }
+        }
+    }
+}
+
 /// 处理普通模式下的左键点击
 fn handle_left_click(
     state: &mut AppState,
     mouse: MouseEvent,
     pty_manager: &PtyManager,
-    half_height: u16,
-    term_height: u16,
+    layout: &AppLayout,
 ) -> anyhow::Result<bool> {
     let x = mouse.column;
     let y = mouse.row;
 
-    if x < SIDEBAR_WIDTH {
+    // Ctrl+点击终端面板中的链接：用系统默认程序打开
+    if mouse.modifiers.contains(KeyModifiers::CONTROL) {
+        if let Some(url) = state.link_at(x, y) {
+            state.open_url(&url);
+            return Ok(true);
+        }
+    }
+
+    let contains = |area: Rect| is_point_in_rect(x, y, area.x, area.y, area.width, area.height);
+
+    if let Some(sidebar) = layout.sidebar.filter(|area| contains(*area)) {
         // 点击侧边栏
         state.focus = FocusArea::Sidebar;
 
         // 计算"添加项目"行的位置
         // 侧边栏布局：边框(1) + 项目列表/无项目提示 + 空行(1) + 添加项目(1)
-        let list_start_y = 2u16; // 边框+标题后的起始行
-        let list_len = if state.projects.is_empty() {
-            1
-        } else {
-            state.projects.len()
-        };
-        let add_project_y = list_start_y + list_len as u16 + 1; // +1 是空行
-
-        if y >= 2 {
-            let clicked_idx = (y - 2) as usize;
+        // Git 仓库和有资源历史的运行中项目占两行（第二行显示分支和内存趋势）
+        let list_start_y = sidebar.y + 1; // 边框+标题后的起始行
+        let mut row_ends = Vec::new();
+        let mut list_len = 0u16;
+        for project in &state.projects {
+            list_len += sidebar_item_height(project);
+            row_ends.push(list_start_y + list_len);
+        }
+        let add_project_y = list_start_y + list_len.max(1) + 1; // +1 是空行
 
+        if y >= list_start_y {
             if y == add_project_y {
                 // 点击了"添加项目"行
                 state.enter_browse_mode();
-            } else if !state.projects.is_empty() && clicked_idx < state.projects.len() {
+            } else if let Some(clicked_idx) = row_ends.iter().position(|end| y < *end) {
                 // 点击了项目列表中的项目
                 state.active_project_idx = clicked_idx;
             }
         }
-    } else if y > 0 && y <= half_height + 1 {
+    } else if contains(layout.dev) {
         // 点击 Dev Terminal - 聚焦（只读模式，用于滚动查看 log）
         state.focus = FocusArea::DevTerminal;
-    } else if y > half_height + 1 && y < term_height - 1 {
+    } else if contains(layout.shell) {
         // 点击 Shell Terminal - 进入交互模式
         start_shell_for_active_project(state, pty_manager)?;
     }
@@ -3,6 +3,10 @@
 
 #![allow(dead_code)]
 
+use crate::app::CommandFormField;
+use crate::config::RunTarget;
+use crate::keymap::{Action, HelpSection};
+use crate::project::{CommandSource, CommandType};
 use serde::{Deserialize, Serialize};
 
 /// 语言枚举
@@ -105,22 +109,159 @@ impl I18n {
         }
     }
 
-    // === 状态栏提示 ===
-    pub fn status_hint_sidebar(&self) -> &'static str {
-        match self.lang {
-            Language::English => {
-                " Tab: Project | Enter: Shell | r/R: Run | s: Stop | z: Layout | ?: Help"
-            }
-            Language::Chinese => {
-                " Tab: 切换 | Enter: 终端 | r/R: 运行 | s: 停止 | z: 布局 | ?: 帮助"
-            }
-        }
-    }
-
-    pub fn status_hint_shell(&self) -> &'static str {
-        match self.lang {
-            Language::English => " Interactive Shell - type freely (Esc: back)",
-            Language::Chinese => " 交互终端 - 自由输入 (Esc: 返回)",
+    // === 快捷键说明（帮助弹窗和状态栏由 keymap 生成）===
+    pub fn help_section_title(&self, section: HelpSection) -> &'static str {
+        match (self.lang, section) {
+            (Language::English, HelpSection::Navigation) => "PROJECT NAVIGATION",
+            (Language::English, HelpSection::DevServer) => "DEV SERVER",
+            (Language::English, HelpSection::Shell) => "INTERACTIVE SHELL",
+            (Language::English, HelpSection::Management) => "PROJECT MANAGEMENT",
+            (Language::English, HelpSection::General) => "GENERAL",
+            (Language::Chinese, HelpSection::Navigation) => "项目导航",
+            (Language::Chinese, HelpSection::DevServer) => "开发服务",
+            (Language::Chinese, HelpSection::Shell) => "交互终端",
+            (Language::Chinese, HelpSection::Management) => "项目管理",
+            (Language::Chinese, HelpSection::General) => "通用",
+        }
+    }
+
+    pub fn help_dev_log_title(&self) -> &'static str {
+        match self.lang {
+            Language::English => "DEV LOG VIEW (click Dev panel)",
+            Language::Chinese => "日志查看 (点击开发服务面板)",
+        }
+    }
+
+    pub fn help_shell_passthrough(&self) -> (&'static str, &'static str) {
+        match self.lang {
+            Language::English => ("All keys", "Sent to shell directly"),
+            Language::Chinese => ("所有按键", "直接发送给终端"),
+        }
+    }
+
+    /// 帮助弹窗中的动作描述
+    pub fn action_description(&self, action: Action) -> &'static str {
+        match self.lang {
+            Language::English => match action {
+                Action::NextProject => "Next project",
+                Action::PrevProject => "Previous project",
+                Action::SelectProject(_) => "Quick switch to project",
+                Action::EnterShell => "Enter Interactive Shell",
+                Action::RunInDev => "Run command (opens palette)",
+                Action::StopDev => "Stop dev server",
+                Action::InterruptDev => "Send interrupt (Ctrl+C)",
+                Action::ToggleSuspend => "Pause/Resume (freeze)",
+                Action::RerunLast => "Re-run last command",
+                Action::OpenHistory => "Command history",
+                Action::OpenProblems => "Problems (errors and warnings)",
+                Action::ShowHints => "Open file:line from output in editor",
+                Action::ShowLinkHints => "Open a link from output",
+                Action::OpenDevUrl => "Open dev server URL in browser",
+                Action::OpenResources => "CPU and memory over the last 15 minutes",
+                Action::OpenProcessTree => "Process tree (kill, pause, renice children)",
+                Action::RunInShell => "Run command in shell",
+                Action::NewShellTab => "New shell tab",
+                Action::CloseShellTab => "Close shell tab",
+                Action::RenameShellTab => "Rename shell tab",
+                Action::NextShellTab => "Next shell tab",
+                Action::PrevShellTab => "Previous shell tab",
+                Action::AddProject => "Add new project",
+                Action::EditAlias => "Edit project alias",
+                Action::AddCommand => "Add custom command",
+                Action::ManageCommands => "Manage custom commands",
+                Action::DeleteProject => "Delete project",
+                Action::OpenGit => "Git status, fetch, pull, stash, switch branch",
+                Action::ToggleLayout => "Toggle panel layout",
+                Action::TogglePanelOrientation => "Stack or place panels side by side",
+                Action::ToggleSidebar => "Show/hide sidebar",
+                Action::ShrinkSidebar => "Narrow sidebar",
+                Action::GrowSidebar => "Widen sidebar",
+                Action::ShrinkDevPanel => "Shrink Dev Terminal",
+                Action::GrowDevPanel => "Enlarge Dev Terminal",
+                Action::OpenSettings => "Open settings",
+                Action::Quit => "Quit application",
+                Action::Help => "Toggle this help",
+                Action::ScrollUp => "Scroll up",
+                Action::ScrollDown => "Scroll down",
+                Action::PageUp => "Page up",
+                Action::PageDown => "Page down",
+                Action::ScrollToLatest => "Jump to latest",
+                Action::FocusSidebar => "Return to sidebar",
+            },
+            Language::Chinese => match action {
+                Action::NextProject => "下一个项目",
+                Action::PrevProject => "上一个项目",
+                Action::SelectProject(_) => "快速切换项目",
+                Action::EnterShell => "进入交互终端",
+                Action::RunInDev => "运行命令",
+                Action::StopDev => "停止服务",
+                Action::InterruptDev => "发送中断 (Ctrl+C)",
+                Action::ToggleSuspend => "暂停/恢复 (冻结进程)",
+                Action::RerunLast => "重新运行上一个命令",
+                Action::OpenHistory => "命令历史",
+                Action::OpenProblems => "问题列表（错误和警告）",
+                Action::ShowHints => "在编辑器中打开输出中的 file:line",
+                Action::ShowLinkHints => "打开输出中的链接",
+                Action::OpenDevUrl => "在浏览器中打开开发服务器地址",
+                Action::OpenResources => "最近 15 分钟的 CPU 和内存占用",
+                Action::OpenProcessTree => "进程树（终止、暂停、调整子进程优先级）",
+                Action::RunInShell => "在终端运行命令",
+                Action::NewShellTab => "新建终端标签",
+                Action::CloseShellTab => "关闭终端标签",
+                Action::RenameShellTab => "重命名终端标签",
+                Action::NextShellTab => "下一个终端标签",
+                Action::PrevShellTab => "上一个终端标签",
+                Action::AddProject => "添加项目",
+                Action::EditAlias => "编辑别名",
+                Action::AddCommand => "添加自定义命令",
+                Action::ManageCommands => "管理自定义命令",
+                Action::DeleteProject => "删除项目",
+                Action::OpenGit => "Git 状态、拉取、暂存、切换分支",
+                Action::ToggleLayout => "切换面板布局",
+                Action::TogglePanelOrientation => "切换面板上下/左右排列",
+                Action::ToggleSidebar => "显示/隐藏侧边栏",
+                Action::ShrinkSidebar => "收窄侧边栏",
+                Action::GrowSidebar => "加宽侧边栏",
+                Action::ShrinkDevPanel => "缩小 Dev Terminal",
+                Action::GrowDevPanel => "扩大 Dev Terminal",
+                Action::OpenSettings => "打开设置",
+                Action::Quit => "退出程序",
+                Action::Help => "帮助",
+                Action::ScrollUp => "向上滚动",
+                Action::ScrollDown => "向下滚动",
+                Action::PageUp => "向上翻页",
+                Action::PageDown => "向下翻页",
+                Action::ScrollToLatest => "跳到最新",
+                Action::FocusSidebar => "返回侧边栏",
+            },
+        }
+    }
+
+    /// 状态栏中的简短动作描述
+    pub fn action_hint(&self, action: Action) -> &'static str {
+        match (self.lang, action) {
+            (Language::English, Action::NextProject) => "Project",
+            (Language::English, Action::EnterShell) => "Shell",
+            (Language::English, Action::RunInDev | Action::RunInShell) => "Run",
+            (Language::English, Action::StopDev) => "Stop",
+            (Language::English, Action::ToggleLayout) => "Layout",
+            (Language::English, Action::Help) => "Help",
+            (Language::English, Action::FocusSidebar) => "Back",
+            (Language::Chinese, Action::NextProject) => "切换项目",
+            (Language::Chinese, Action::EnterShell) => "终端",
+            (Language::Chinese, Action::RunInDev | Action::RunInShell) => "运行",
+            (Language::Chinese, Action::StopDev) => "停止",
+            (Language::Chinese, Action::ToggleLayout) => "布局",
+            (Language::Chinese, Action::Help) => "帮助",
+            (Language::Chinese, Action::FocusSidebar) => "返回",
+            _ => self.action_description(action),
+        }
+    }
+
+    pub fn pending_keys(&self) -> &'static str {
+        match self.lang {
+            Language::English => "waiting for next key…",
+            Language::Chinese => "等待后续按键…",
         }
     }
 
@@ -139,6 +280,72 @@ impl I18n {
         }
     }
 
+    pub fn node_version_manager(&self) -> &'static str {
+        match self.lang {
+            Language::English => "Node version manager",
+            Language::Chinese => "Node 版本管理器",
+        }
+    }
+
+    pub fn idle_suspend(&self) -> &'static str {
+        match self.lang {
+            Language::English => "Auto-pause idle servers",
+            Language::Chinese => "自动暂停空闲进程",
+        }
+    }
+
+    pub fn minutes_or_off(&self, minutes: u64) -> String {
+        match (self.lang, minutes) {
+            (_, 0) => self.on_off(false).to_string(),
+            (Language::English, m) => format!("After {} min", m),
+            (Language::Chinese, m) => format!("{} 分钟后", m),
+        }
+    }
+
+    pub fn on_off(&self, on: bool) -> &'static str {
+        match (self.lang, on) {
+            (Language::English, true) => "On",
+            (Language::English, false) => "Off",
+            (Language::Chinese, true) => "开启",
+            (Language::Chinese, false) => "关闭",
+        }
+    }
+
+    pub fn theme(&self) -> &'static str {
+        match self.lang {
+            Language::English => "Theme",
+            Language::Chinese => "主题",
+        }
+    }
+
+    pub fn theme_picker_title(&self) -> &'static str {
+        match self.lang {
+            Language::English => " Theme ",
+            Language::Chinese => " 主题 ",
+        }
+    }
+
+    pub fn theme_hint_preview(&self) -> &'static str {
+        match self.lang {
+            Language::English => ": Preview | ",
+            Language::Chinese => ": 预览 | ",
+        }
+    }
+
+    pub fn theme_hint_apply(&self) -> &'static str {
+        match self.lang {
+            Language::English => ": Apply | ",
+            Language::Chinese => ": 应用 | ",
+        }
+    }
+
+    pub fn theme_hint_cancel(&self) -> &'static str {
+        match self.lang {
+            Language::English => ": Cancel",
+            Language::Chinese => ": 取消",
+        }
+    }
+
     pub fn settings_hint(&self) -> &'static str {
         match self.lang {
             Language::English => "↑/↓: Navigate | Enter: Toggle | Esc: Close",
@@ -168,10 +375,240 @@ impl I18n {
         }
     }
 
-    pub fn command_format_hint(&self) -> &'static str {
+    pub fn edit_command(&self) -> &'static str {
+        match self.lang {
+            Language::English => "Edit Command",
+            Language::Chinese => "编辑命令",
+        }
+    }
+
+    pub fn command_field_label(&self, field: CommandFormField) -> &'static str {
+        match (self.lang, field) {
+            (Language::English, CommandFormField::Name) => "Name",
+            (Language::English, CommandFormField::Kind) => "Type",
+            (Language::English, CommandFormField::Command) => "Command",
+            (Language::English, CommandFormField::Cwd) => "Working dir",
+            (Language::English, CommandFormField::Target) => "Run in",
+            (Language::English, CommandFormField::Env) => "Env",
+            (Language::English, CommandFormField::Description) => "Description",
+            (Language::Chinese, CommandFormField::Name) => "名称",
+            (Language::Chinese, CommandFormField::Kind) => "类型",
+            (Language::Chinese, CommandFormField::Command) => "命令",
+            (Language::Chinese, CommandFormField::Cwd) => "工作目录",
+            (Language::Chinese, CommandFormField::Target) => "运行位置",
+            (Language::Chinese, CommandFormField::Env) => "环境变量",
+            (Language::Chinese, CommandFormField::Description) => "说明",
+        }
+    }
+
+    /// 字段为空时显示的提示
+    pub fn command_field_placeholder(
+        &self,
+        field: CommandFormField,
+        cmd_type: &CommandType,
+    ) -> &'static str {
+        match (self.lang, field, cmd_type) {
+            (Language::English, CommandFormField::Command, CommandType::Sequence) => {
+                "lint -> typecheck -> test"
+            }
+            (Language::English, CommandFormField::Command, CommandType::Parallel) => {
+                "dev:web + dev:api"
+            }
+            (Language::English, CommandFormField::Command, _) => {
+                "supports ${input:..} placeholders"
+            }
+            (Language::English, CommandFormField::Cwd, _) => "project root",
+            (Language::English, CommandFormField::Env, _) => "KEY=value; KEY2=value",
+            (Language::Chinese, CommandFormField::Command, CommandType::Sequence) => {
+                "lint -> typecheck -> test"
+            }
+            (Language::Chinese, CommandFormField::Command, CommandType::Parallel) => {
+                "dev:web + dev:api"
+            }
+            (Language::Chinese, CommandFormField::Command, _) => "支持 ${input:..} 等占位符",
+            (Language::Chinese, CommandFormField::Cwd, _) => "项目根目录",
+            (Language::Chinese, CommandFormField::Env, _) => "KEY=value; KEY2=value",
+            _ => "",
+        }
+    }
+
+    /// 命令类型（表单中的类型字段）
+    pub fn command_type_label(&self, cmd_type: &CommandType) -> &'static str {
+        match (self.lang, cmd_type) {
+            (Language::English, CommandType::Sequence) => "Sequence (stops on first failure)",
+            (Language::English, CommandType::Parallel) => "Parallel (split panels)",
+            (Language::English, _) => "Shell command",
+            (Language::Chinese, CommandType::Sequence) => "顺序组合（失败即停止）",
+            (Language::Chinese, CommandType::Parallel) => "并行组合（分屏显示）",
+            (Language::Chinese, _) => "Shell 命令",
+        }
+    }
+
+    pub fn run_target_label(&self, target: Option<RunTarget>) -> &'static str {
+        match (self.lang, target) {
+            (Language::English, None) => "Auto (r: Dev / R: Shell)",
+            (Language::Chinese, None) => "自动（r: Dev / R: 终端）",
+            (_, Some(RunTarget::Dev)) => "Dev Terminal",
+            (_, Some(RunTarget::Shell)) => "Interactive Shell",
+        }
+    }
+
+    pub fn form_hint_field(&self) -> &'static str {
         match self.lang {
-            Language::English => "Format: name:command",
-            Language::Chinese => "格式：名称:命令",
+            Language::English => ": Field | ",
+            Language::Chinese => ": 切换字段 | ",
+        }
+    }
+
+    pub fn form_hint_change(&self) -> &'static str {
+        match self.lang {
+            Language::English => ": Change | ",
+            Language::Chinese => ": 切换 | ",
+        }
+    }
+
+    pub fn form_hint_save(&self) -> &'static str {
+        match self.lang {
+            Language::English => ": Save | ",
+            Language::Chinese => ": 保存 | ",
+        }
+    }
+
+    pub fn command_manager_title(&self) -> &'static str {
+        match self.lang {
+            Language::English => " Custom Commands ",
+            Language::Chinese => " 自定义命令 ",
+        }
+    }
+
+    pub fn command_source_label(&self, source: CommandSource) -> &'static str {
+        match (self.lang, source) {
+            (Language::English, CommandSource::Personal) => "Personal",
+            (Language::English, CommandSource::Shared) => "Shared (.devpanel.json)",
+            (Language::Chinese, CommandSource::Personal) => "个人",
+            (Language::Chinese, CommandSource::Shared) => "项目共享（.devpanel.json）",
+        }
+    }
+
+    pub fn no_custom_commands(&self) -> &'static str {
+        match self.lang {
+            Language::English => "No custom commands yet, press a to add one",
+            Language::Chinese => "还没有自定义命令，按 a 添加",
+        }
+    }
+
+    /// 命令管理底部提示（按键, 描述）
+    pub fn command_manager_hints(&self) -> [(&'static str, &'static str); 6] {
+        match self.lang {
+            Language::English => [
+                ("a", ": Add | "),
+                ("Enter", ": Edit | "),
+                ("d", ": Delete | "),
+                ("J/K", ": Move | "),
+                ("m", ": Personal/Shared | "),
+                ("Esc", ": Close"),
+            ],
+            Language::Chinese => [
+                ("a", ": 添加 | "),
+                ("Enter", ": 编辑 | "),
+                ("d", ": 删除 | "),
+                ("J/K", ": 移动 | "),
+                ("m", ": 个人/共享 | "),
+                ("Esc", ": 关闭"),
+            ],
+        }
+    }
+
+    pub fn not_git_repo(&self) -> &'static str {
+        match self.lang {
+            Language::English => "This project is not a Git repository",
+            Language::Chinese => "此项目不是 Git 仓库",
+        }
+    }
+
+    pub fn git_clean(&self) -> &'static str {
+        match self.lang {
+            Language::English => "Working tree clean",
+            Language::Chinese => "工作区没有修改",
+        }
+    }
+
+    pub fn git_no_upstream(&self) -> &'static str {
+        match self.lang {
+            Language::English => "no upstream",
+            Language::Chinese => "没有上游分支",
+        }
+    }
+
+    pub fn git_switch_branch(&self) -> &'static str {
+        match self.lang {
+            Language::English => "Switch branch",
+            Language::Chinese => "切换分支",
+        }
+    }
+
+    pub fn git_worktrees(&self) -> &'static str {
+        match self.lang {
+            Language::English => "Worktrees",
+            Language::Chinese => "工作树",
+        }
+    }
+
+    pub fn new_worktree(&self) -> &'static str {
+        match self.lang {
+            Language::English => "New Worktree",
+            Language::Chinese => "新建工作树",
+        }
+    }
+
+    pub fn new_worktree_hint(&self) -> &'static str {
+        match self.lang {
+            Language::English => "Branch name (existing or new):",
+            Language::Chinese => "分支名（已有分支或新分支）：",
+        }
+    }
+
+    /// Git 详情底部提示（按键, 描述）
+    pub fn git_hints(&self) -> [(&'static str, &'static str); 7] {
+        match self.lang {
+            Language::English => [
+                ("f", ": Fetch | "),
+                ("p", ": Pull | "),
+                ("s", ": Stash | "),
+                ("b", ": Branch | "),
+                ("w", ": Worktrees | "),
+                ("Enter", ": Open | "),
+                ("Esc", ": Close"),
+            ],
+            Language::Chinese => [
+                ("f", ": 获取 | "),
+                ("p", ": 拉取 | "),
+                ("s", ": 暂存 | "),
+                ("b", ": 分支 | "),
+                ("w", ": 工作树 | "),
+                ("Enter", ": 打开 | "),
+                ("Esc", ": 关闭"),
+            ],
+        }
+    }
+
+    /// 工作树列表底部提示（按键, 描述）
+    pub fn git_worktree_hints(&self) -> [(&'static str, &'static str); 3] {
+        match self.lang {
+            Language::English => [("Enter", ": Open | "), ("n", ": New | "), ("Esc", ": Back")],
+            Language::Chinese => [
+                ("Enter", ": 打开 | "),
+                ("n", ": 新建 | "),
+                ("Esc", ": 返回"),
+            ],
+        }
+    }
+
+    /// 分支列表底部提示（按键, 描述）
+    pub fn git_branch_hints(&self) -> [(&'static str, &'static str); 2] {
+        match self.lang {
+            Language::English => [("Enter", ": Switch | "), ("Esc", ": Back")],
+            Language::Chinese => [("Enter", ": 切换 | "), ("Esc", ": 返回")],
         }
     }
 
@@ -231,6 +668,13 @@ impl I18n {
         }
     }
 
+    pub fn install_yes_no(&self) -> &'static str {
+        match self.lang {
+            Language::English => "[y] Install  [n] Skip  [Esc] Cancel",
+            Language::Chinese => "[y] 安装  [n] 跳过  [Esc] 取消",
+        }
+    }
+
     pub fn yes_no(&self) -> &'static str {
         match self.lang {
             Language::English => "[Enter] Confirm  [Esc] Cancel",
@@ -245,6 +689,27 @@ impl I18n {
         }
     }
 
+    pub fn filter_placeholder(&self) -> &'static str {
+        match self.lang {
+            Language::English => "Type to filter (Ctrl-N/P to move)",
+            Language::Chinese => "输入以过滤 (Ctrl-N/P 移动)",
+        }
+    }
+
+    pub fn no_commands(&self) -> &'static str {
+        match self.lang {
+            Language::English => "No commands available",
+            Language::Chinese => "没有可用的命令",
+        }
+    }
+
+    pub fn no_matching_commands(&self) -> &'static str {
+        match self.lang {
+            Language::English => "No matching commands",
+            Language::Chinese => "没有匹配的命令",
+        }
+    }
+
     // === 提示消息 ===
     pub fn no_project(&self) -> &'static str {
         match self.lang {
@@ -253,6 +718,160 @@ impl I18n {
         }
     }
 
+    pub fn prompt_hint_select(&self) -> &'static str {
+        match self.lang {
+            Language::English => ": Select | ",
+            Language::Chinese => ": 选择 | ",
+        }
+    }
+
+    pub fn prompt_hint_next(&self) -> &'static str {
+        match self.lang {
+            Language::English => ": Next | ",
+            Language::Chinese => ": 下一步 | ",
+        }
+    }
+
+    pub fn prompt_hint_run(&self) -> &'static str {
+        match self.lang {
+            Language::English => ": Run | ",
+            Language::Chinese => ": 运行 | ",
+        }
+    }
+
+    pub fn no_history(&self) -> &'static str {
+        match self.lang {
+            Language::English => "No commands have been run in this project yet",
+            Language::Chinese => "此项目还没有运行过命令",
+        }
+    }
+
+    pub fn history_title(&self) -> &'static str {
+        match self.lang {
+            Language::English => " Command History ",
+            Language::Chinese => " 命令历史 ",
+        }
+    }
+
+    pub fn history_hint_rerun(&self) -> &'static str {
+        match self.lang {
+            Language::English => ": Re-run | ",
+            Language::Chinese => ": 重新运行 | ",
+        }
+    }
+
+    pub fn history_hint_close(&self) -> &'static str {
+        match self.lang {
+            Language::English => ": Close",
+            Language::Chinese => ": 关闭",
+        }
+    }
+
+    pub fn problems_title(&self, errors: usize, warnings: usize) -> String {
+        match self.lang {
+            Language::English => format!(" Problems · {} errors, {} warnings ", errors, warnings),
+            Language::Chinese => format!(" 问题 · {} 个错误，{} 个警告 ", errors, warnings),
+        }
+    }
+
+    pub fn no_problems(&self) -> &'static str {
+        match self.lang {
+            Language::English => "No errors or warnings in the dev output",
+            Language::Chinese => "Dev 输出中没有错误或警告",
+        }
+    }
+
+    pub fn no_hints(&self) -> &'static str {
+        match self.lang {
+            Language::English => "No file:line references in the terminal panels",
+            Language::Chinese => "终端面板中没有 file:line 引用",
+        }
+    }
+
+    pub fn no_links(&self) -> &'static str {
+        match self.lang {
+            Language::English => "No links in the terminal panels",
+            Language::Chinese => "终端面板中没有链接",
+        }
+    }
+
+    pub fn no_dev_url(&self) -> &'static str {
+        match self.lang {
+            Language::English => "The dev server hasn't printed a local URL",
+            Language::Chinese => "开发服务器还没有输出本机访问地址",
+        }
+    }
+
+    pub fn resources_title(&self, name: &str) -> String {
+        match self.lang {
+            Language::English => format!(" Resources · {} ", name),
+            Language::Chinese => format!(" 资源占用 · {} ", name),
+        }
+    }
+
+    pub fn process_tree_title(&self, name: &str, count: usize) -> String {
+        match self.lang {
+            Language::English => format!(" Processes · {} · {} ", name, count),
+            Language::Chinese => format!(" 进程树 · {} · {} 个进程 ", name, count),
+        }
+    }
+
+    pub fn process_tree_hints(&self) -> [(&'static str, &'static str); 6] {
+        match self.lang {
+            Language::English => [
+                ("Tab", ": Step | "),
+                ("x/X", ": Term/Kill | "),
+                ("p", ": Pause/Resume | "),
+                ("+/-", ": Priority | "),
+                ("j/k", ": Select | "),
+                ("Esc", ": Close"),
+            ],
+            Language::Chinese => [
+                ("Tab", ": 切换步骤 | "),
+                ("x/X", ": 终止/强制结束 | "),
+                ("p", ": 暂停/恢复 | "),
+                ("+/-", ": 优先级 | "),
+                ("j/k", ": 选择 | "),
+                ("Esc", ": 关闭"),
+            ],
+        }
+    }
+
+    pub fn resources_peak(&self) -> &'static str {
+        match self.lang {
+            Language::English => "peak",
+            Language::Chinese => "峰值",
+        }
+    }
+
+    pub fn resources_memory(&self) -> &'static str {
+        match self.lang {
+            Language::English => "Memory",
+            Language::Chinese => "内存",
+        }
+    }
+
+    pub fn dev_not_running(&self) -> &'static str {
+        match self.lang {
+            Language::English => "The dev server is not running",
+            Language::Chinese => "开发服务器未运行",
+        }
+    }
+
+    pub fn problems_hint_open(&self) -> &'static str {
+        match self.lang {
+            Language::English => ": Open in editor | ",
+            Language::Chinese => ": 在编辑器中打开 | ",
+        }
+    }
+
+    pub fn history_running(&self) -> &'static str {
+        match self.lang {
+            Language::English => "running",
+            Language::Chinese => "运行中",
+        }
+    }
+
     pub fn no_projects(&self) -> &'static str {
         match self.lang {
             Language::English => "  No projects",
@@ -302,6 +921,21 @@ impl I18n {
         }
     }
 
+    pub fn dev_stopping(&self) -> &'static str {
+        match self.lang {
+            Language::English => "Stopping dev server…",
+            Language::Chinese => "正在停止开发服务…",
+        }
+    }
+
+    /// 侧边栏中正在停止的状态
+    pub fn stopping(&self) -> &'static str {
+        match self.lang {
+            Language::English => "stopping…",
+            Language::Chinese => "停止中…",
+        }
+    }
+
     pub fn sent_interrupt(&self) -> &'static str {
         match self.lang {
             Language::English => "Sent interrupt to dev server",
@@ -309,6 +943,48 @@ impl I18n {
         }
     }
 
+    pub fn new_shell_tab(&self) -> &'static str {
+        match self.lang {
+            Language::English => "New Shell Tab",
+            Language::Chinese => "新建终端标签",
+        }
+    }
+
+    pub fn new_shell_tab_hint(&self) -> &'static str {
+        match self.lang {
+            Language::English => "Name [@dir] [: command], e.g. repl: node",
+            Language::Chinese => "名称 [@目录] [: 命令]，如 repl: node",
+        }
+    }
+
+    pub fn rename_shell_tab(&self) -> &'static str {
+        match self.lang {
+            Language::English => "Rename Shell Tab",
+            Language::Chinese => "重命名终端标签",
+        }
+    }
+
+    pub fn rename_shell_tab_hint(&self) -> &'static str {
+        match self.lang {
+            Language::English => "Tab name:",
+            Language::Chinese => "标签名：",
+        }
+    }
+
+    pub fn no_shell_tab(&self) -> &'static str {
+        match self.lang {
+            Language::English => "No shell tab open",
+            Language::Chinese => "没有打开的终端标签",
+        }
+    }
+
+    pub fn shell_tab_exists(&self, name: &str) -> String {
+        match self.lang {
+            Language::English => format!("Shell tab {} already exists", name),
+            Language::Chinese => format!("终端标签 {} 已存在", name),
+        }
+    }
+
     pub fn shell_started(&self) -> &'static str {
         match self.lang {
             Language::English => "Shell started - type commands here",
@@ -0,0 +1,225 @@
+//! 快捷键动作定义
+//! 每个可绑定的操作对应一个 Action，配置文件中使用 snake_case 名称引用
+
+/// 可绑定快捷键的动作
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Action {
+    // === 项目导航 ===
+    NextProject,
+    PrevProject,
+    /// 快速切换到第 n 个项目（1-9）
+    SelectProject(u8),
+    EnterShell,
+    // === 开发服务 ===
+    RunInDev,
+    StopDev,
+    InterruptDev,
+    ToggleSuspend,
+    RerunLast,
+    OpenHistory,
+    OpenProblems,
+    ShowHints,
+    ShowLinkHints,
+    OpenDevUrl,
+    OpenResources,
+    OpenProcessTree,
+    // === 交互终端 ===
+    RunInShell,
+    NewShellTab,
+    CloseShellTab,
+    RenameShellTab,
+    NextShellTab,
+    PrevShellTab,
+    // === 项目管理 ===
+    AddProject,
+    EditAlias,
+    AddCommand,
+    ManageCommands,
+    DeleteProject,
+    OpenGit,
+    // === 通用 ===
+    ToggleLayout,
+    TogglePanelOrientation,
+    ToggleSidebar,
+    ShrinkSidebar,
+    GrowSidebar,
+    ShrinkDevPanel,
+    GrowDevPanel,
+    OpenSettings,
+    Quit,
+    Help,
+    // === 日志查看 / 终端 ===
+    ScrollUp,
+    ScrollDown,
+    PageUp,
+    PageDown,
+    ScrollToLatest,
+    FocusSidebar,
+}
+
+/// 帮助弹窗中的分组
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HelpSection {
+    Navigation,
+    DevServer,
+    Shell,
+    Management,
+    General,
+}
+
+impl HelpSection {
+    /// 帮助弹窗中分组的显示顺序
+    pub const ALL: [HelpSection; 5] = [
+        HelpSection::Navigation,
+        HelpSection::DevServer,
+        HelpSection::Shell,
+        HelpSection::Management,
+        HelpSection::General,
+    ];
+}
+
+/// 不带参数的动作与配置名称的对应表
+const NAMED_ACTIONS: &[(Action, &str)] = &[
+    (Action::NextProject, "next_project"),
+    (Action::PrevProject, "prev_project"),
+    (Action::EnterShell, "enter_shell"),
+    (Action::RunInDev, "run_in_dev"),
+    (Action::StopDev, "stop_dev"),
+    (Action::InterruptDev, "interrupt_dev"),
+    (Action::ToggleSuspend, "toggle_suspend"),
+    (Action::RerunLast, "rerun_last"),
+    (Action::OpenHistory, "open_history"),
+    (Action::OpenProblems, "open_problems"),
+    (Action::ShowHints, "show_hints"),
+    (Action::ShowLinkHints, "show_link_hints"),
+    (Action::OpenDevUrl, "open_dev_url"),
+    (Action::OpenResources, "open_resources"),
+    (Action::OpenProcessTree, "open_process_tree"),
+    (Action::RunInShell, "run_in_shell"),
+    (Action::NewShellTab, "new_shell_tab"),
+    (Action::CloseShellTab, "close_shell_tab"),
+    (Action::RenameShellTab, "rename_shell_tab"),
+    (Action::NextShellTab, "next_shell_tab"),
+    (Action::PrevShellTab, "prev_shell_tab"),
+    (Action::AddProject, "add_project"),
+    (Action::EditAlias, "edit_alias"),
+    (Action::AddCommand, "add_command"),
+    (Action::ManageCommands, "manage_commands"),
+    (Action::DeleteProject, "delete_project"),
+    (Action::OpenGit, "open_git"),
+    (Action::ToggleLayout, "toggle_layout"),
+    (Action::TogglePanelOrientation, "toggle_panel_orientation"),
+    (Action::ToggleSidebar, "toggle_sidebar"),
+    (Action::ShrinkSidebar, "shrink_sidebar"),
+    (Action::GrowSidebar, "grow_sidebar"),
+    (Action::ShrinkDevPanel, "shrink_dev_panel"),
+    (Action::GrowDevPanel, "grow_dev_panel"),
+    (Action::OpenSettings, "open_settings"),
+    (Action::Quit, "quit"),
+    (Action::Help, "help"),
+    (Action::ScrollUp, "scroll_up"),
+    (Action::ScrollDown, "scroll_down"),
+    (Action::PageUp, "page_up"),
+    (Action::PageDown, "page_down"),
+    (Action::ScrollToLatest, "scroll_to_latest"),
+    (Action::FocusSidebar, "focus_sidebar"),
+];
+
+const SELECT_PROJECT_PREFIX: &str = "select_project_";
+
+impl Action {
+    /// 配置文件中使用的名称
+    pub fn name(&self) -> String {
+        if let Action::SelectProject(n) = self {
+            return format!("{}{}", SELECT_PROJECT_PREFIX, n);
+        }
+        NAMED_ACTIONS
+            .iter()
+            .find(|(action, _)| action == self)
+            .map(|(_, name)| name.to_string())
+            .unwrap_or_default()
+    }
+
+    /// 根据配置名称查找动作
+    pub fn from_name(name: &str) -> Option<Self> {
+        if let Some(n) = name.strip_prefix(SELECT_PROJECT_PREFIX) {
+            return match n.parse::<u8>() {
+                Ok(n @ 1..=9) => Some(Action::SelectProject(n)),
+                _ => None,
+            };
+        }
+        NAMED_ACTIONS
+            .iter()
+            .find(|(_, n)| *n == name)
+            .map(|(action, _)| *action)
+    }
+
+    /// 在帮助弹窗中所属的分组（日志查看和终端模式的动作单独展示）
+    pub fn help_section(&self) -> HelpSection {
+        match self {
+            Action::NextProject
+            | Action::PrevProject
+            | Action::SelectProject(_)
+            | Action::EnterShell => HelpSection::Navigation,
+            Action::RunInDev
+            | Action::StopDev
+            | Action::InterruptDev
+            | Action::ToggleSuspend
+            | Action::RerunLast
+            | Action::OpenHistory
+            | Action::OpenProblems
+            | Action::ShowHints
+            | Action::ShowLinkHints
+            | Action::OpenDevUrl
+            | Action::OpenResources
+            | Action::OpenProcessTree => HelpSection::DevServer,
+            Action::RunInShell
+            | Action::NewShellTab
+            | Action::CloseShellTab
+            | Action::RenameShellTab
+            | Action::NextShellTab
+            | Action::PrevShellTab => HelpSection::Shell,
+            Action::AddProject
+            | Action::EditAlias
+            | Action::AddCommand
+            | Action::ManageCommands
+            | Action::DeleteProject
+            | Action::OpenGit => HelpSection::Management,
+            Action::ToggleLayout
+            | Action::TogglePanelOrientation
+            | Action::ToggleSidebar
+            | Action::ShrinkSidebar
+            | Action::GrowSidebar
+            | Action::ShrinkDevPanel
+            | Action::GrowDevPanel
+            | Action::OpenSettings
+            | Action::Quit
+            | Action::Help
+            | Action::ScrollUp
+            | Action::ScrollDown
+            | Action::PageUp
+            | Action::PageDown
+            | Action::ScrollToLatest
+            | Action::FocusSidebar => HelpSection::General,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_action_names_round_trip() {
+        for (action, name) in NAMED_ACTIONS {
+            assert_eq!(Action::from_name(name), Some(*action));
+            assert_eq!(action.name(), *name);
+        }
+        assert_eq!(
+            Action::from_name("select_project_3"),
+            Some(Action::SelectProject(3))
+        );
+        assert_eq!(Action::from_name("select_project_0"), None);
+        assert_eq!(Action::from_name("launch_rockets"), None);
+    }
+}
@@ -0,0 +1,264 @@
+//! 按键解析模块
+//! 负责按键字符串（如 "ctrl+c"、"shift+tab"、"<leader> d"）与按键事件之间的转换
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use std::fmt;
+
+/// 单个按键（按键码 + 修饰键）
+/// 字符键的大小写已经体现在字符本身，因此会去掉 Shift 修饰
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct KeyPress {
+    pub code: KeyCode,
+    pub modifiers: KeyModifiers,
+}
+
+impl KeyPress {
+    /// 创建按键并规范化修饰键
+    pub fn new(code: KeyCode, modifiers: KeyModifiers) -> Self {
+        // 只关心 Ctrl/Alt/Shift，忽略 Super 等平台相关修饰键
+        let mut modifiers =
+            modifiers & (KeyModifiers::CONTROL | KeyModifiers::ALT | KeyModifiers::SHIFT);
+        let code = match code {
+            // Shift+Tab 在部分终端上报告为 BackTab + SHIFT
+            KeyCode::BackTab => {
+                modifiers.remove(KeyModifiers::SHIFT);
+                KeyCode::BackTab
+            }
+            KeyCode::Char(c) => {
+                modifiers.remove(KeyModifiers::SHIFT);
+                // Ctrl 组合统一使用小写字母
+                if modifiers.contains(KeyModifiers::CONTROL) {
+                    KeyCode::Char(c.to_ascii_lowercase())
+                } else {
+                    KeyCode::Char(c)
+                }
+            }
+            other => other,
+        };
+        Self { code, modifiers }
+    }
+
+    /// 不带修饰键的按键
+    pub fn plain(code: KeyCode) -> Self {
+        Self::new(code, KeyModifiers::NONE)
+    }
+
+    /// 解析单个按键字符串，例如 "q"、"ctrl+c"、"shift+tab"、"pgup"、"f5"
+    pub fn parse(s: &str) -> Result<Self, String> {
+        // 单个字符（包括 "+" 本身）直接作为字符键
+        if s.chars().count() == 1 {
+            let c = s.chars().next().unwrap_or(' ');
+            return Ok(Self::plain(KeyCode::Char(c)));
+        }
+
+        let mut modifiers = KeyModifiers::NONE;
+        let mut parts: Vec<&str> = s.split('+').collect();
+        let key = parts.pop().unwrap_or_default();
+        for part in parts {
+            match part.to_ascii_lowercase().as_str() {
+                "ctrl" | "control" => modifiers |= KeyModifiers::CONTROL,
+                "alt" | "meta" | "option" => modifiers |= KeyModifiers::ALT,
+                "shift" => modifiers |= KeyModifiers::SHIFT,
+                _ => return Err(format!("unknown modifier '{}' in '{}'", part, s)),
+            }
+        }
+
+        let code = if key.chars().count() == 1 {
+            KeyCode::Char(key.chars().next().unwrap_or(' '))
+        } else {
+            match key.to_ascii_lowercase().as_str() {
+                "enter" | "return" => KeyCode::Enter,
+                "esc" | "escape" => KeyCode::Esc,
+                "tab" if modifiers.contains(KeyModifiers::SHIFT) => KeyCode::BackTab,
+                "tab" => KeyCode::Tab,
+                "backtab" => KeyCode::BackTab,
+                "space" => KeyCode::Char(' '),
+                "backspace" => KeyCode::Backspace,
+                "delete" | "del" => KeyCode::Delete,
+                "insert" | "ins" => KeyCode::Insert,
+                "up" => KeyCode::Up,
+                "down" => KeyCode::Down,
+                "left" => KeyCode::Left,
+                "right" => KeyCode::Right,
+                "home" => KeyCode::Home,
+                "end" => KeyCode::End,
+                "pageup" | "pgup" => KeyCode::PageUp,
+                "pagedown" | "pgdn" => KeyCode::PageDown,
+                f if f.starts_with('f') && f[1..].parse::<u8>().is_ok() => {
+                    KeyCode::F(f[1..].parse().unwrap_or(1))
+                }
+                _ => return Err(format!("unknown key '{}'", s)),
+            }
+        };
+
+        // "shift+a" 等价于 "A"
+        let code = match code {
+            KeyCode::Char(c) if modifiers.contains(KeyModifiers::SHIFT) => {
+                KeyCode::Char(c.to_ascii_uppercase())
+            }
+            other => other,
+        };
+
+        Ok(Self::new(code, modifiers))
+    }
+}
+
+impl From<&KeyEvent> for KeyPress {
+    fn from(key: &KeyEvent) -> Self {
+        Self::new(key.code, key.modifiers)
+    }
+}
+
+impl fmt::Display for KeyPress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.modifiers.contains(KeyModifiers::CONTROL) {
+            write!(f, "Ctrl+")?;
+        }
+        if self.modifiers.contains(KeyModifiers::ALT) {
+            write!(f, "Alt+")?;
+        }
+        if self.modifiers.contains(KeyModifiers::SHIFT) {
+            write!(f, "Shift+")?;
+        }
+        match self.code {
+            KeyCode::Char(' ') => write!(f, "Space"),
+            KeyCode::Char(c) if self.modifiers.contains(KeyModifiers::CONTROL) => {
+                write!(f, "{}", c.to_ascii_uppercase())
+            }
+            KeyCode::Char(c) => write!(f, "{}", c),
+            KeyCode::Enter => write!(f, "Enter"),
+            KeyCode::Esc => write!(f, "Esc"),
+            KeyCode::Tab => write!(f, "Tab"),
+            KeyCode::BackTab => write!(f, "Shift+Tab"),
+            KeyCode::Backspace => write!(f, "Backspace"),
+            KeyCode::Delete => write!(f, "Del"),
+            KeyCode::Insert => write!(f, "Ins"),
+            KeyCode::Up => write!(f, "↑"),
+            KeyCode::Down => write!(f, "↓"),
+            KeyCode::Left => write!(f, "←"),
+            KeyCode::Right => write!(f, "→"),
+            KeyCode::Home => write!(f, "Home"),
+            KeyCode::End => write!(f, "End"),
+            KeyCode::PageUp => write!(f, "PgUp"),
+            KeyCode::PageDown => write!(f, "PgDn"),
+            KeyCode::F(n) => write!(f, "F{}", n),
+            _ => write!(f, "?"),
+        }
+    }
+}
+
+/// 按键序列（单键或多键组合，例如 "g g"、"<leader> d"）
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct KeySequence(pub Vec<KeyPress>);
+
+impl KeySequence {
+    /// 解析按键序列，按空格分隔，"<leader>" 替换为 leader 键
+    /// "<leader>d" 这种不带空格的写法也支持
+    pub fn parse(s: &str, leader: Option<KeyPress>) -> Result<Self, String> {
+        let mut keys = Vec::new();
+        for token in s.split_whitespace() {
+            let mut token = token;
+            while let Some(rest) = token.strip_prefix("<leader>") {
+                keys.push(leader.ok_or_else(|| format!("no leader key configured for '{}'", s))?);
+                token = rest;
+            }
+            if !token.is_empty() {
+                keys.push(KeyPress::parse(token)?);
+            }
+        }
+        // 单独的空格字符串视为 Space 键
+        if keys.is_empty() && s == " " {
+            keys.push(KeyPress::plain(KeyCode::Char(' ')));
+        }
+        if keys.is_empty() {
+            return Err("empty key sequence".to_string());
+        }
+        Ok(Self(keys))
+    }
+
+    /// 是否以指定按键前缀开头（且比前缀更长）
+    pub fn has_prefix(&self, prefix: &[KeyPress]) -> bool {
+        self.0.len() > prefix.len() && self.0.starts_with(prefix)
+    }
+}
+
+impl fmt::Display for KeySequence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, key) in self.0.iter().enumerate() {
+            if i > 0 {
+                write!(f, " ")?;
+            }
+            write!(f, "{}", key)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_single_keys() {
+        assert_eq!(
+            KeyPress::parse("q").unwrap(),
+            KeyPress::plain(KeyCode::Char('q'))
+        );
+        assert_eq!(
+            KeyPress::parse("ctrl+C").unwrap(),
+            KeyPress::new(KeyCode::Char('c'), KeyModifiers::CONTROL)
+        );
+        assert_eq!(
+            KeyPress::parse("shift+r").unwrap(),
+            KeyPress::plain(KeyCode::Char('R'))
+        );
+        assert_eq!(
+            KeyPress::parse("shift+tab").unwrap(),
+            KeyPress::plain(KeyCode::BackTab)
+        );
+        assert_eq!(
+            KeyPress::parse("+").unwrap(),
+            KeyPress::plain(KeyCode::Char('+'))
+        );
+        assert_eq!(
+            KeyPress::parse("pgup").unwrap(),
+            KeyPress::plain(KeyCode::PageUp)
+        );
+        assert!(KeyPress::parse("hyper+x").is_err());
+        assert!(KeyPress::parse("nope").is_err());
+    }
+
+    #[test]
+    fn test_key_event_normalization() {
+        // 终端上报的 Shift+R 与配置中的 "R" 一致
+        let event = KeyEvent::new(KeyCode::Char('R'), KeyModifiers::SHIFT);
+        assert_eq!(KeyPress::from(&event), KeyPress::parse("R").unwrap());
+
+        let event = KeyEvent::new(KeyCode::BackTab, KeyModifiers::SHIFT);
+        assert_eq!(
+            KeyPress::from(&event),
+            KeyPress::parse("shift+tab").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_parse_sequence_with_leader() {
+        let leader = KeyPress::parse("space").ok();
+        let seq = KeySequence::parse("<leader>d", leader).unwrap();
+        assert_eq!(seq.to_string(), "Space d");
+
+        let seq = KeySequence::parse("g g", None).unwrap();
+        assert_eq!(seq.0.len(), 2);
+        assert!(KeySequence::parse("<leader>d", None).is_err());
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(KeyPress::parse("ctrl+q").unwrap().to_string(), "Ctrl+Q");
+        assert_eq!(KeyPress::parse("?").unwrap().to_string(), "?");
+        assert_eq!(
+            KeyPress::parse("shift+tab").unwrap().to_string(),
+            "Shift+Tab"
+        );
+    }
+}
@@ -0,0 +1,404 @@
+//! 快捷键映射模块
+//! 默认绑定 + 配置文件覆盖，支持按模式绑定、多键组合和 leader 键
+//!
+//! 配置示例（~/.devpanel/config.json）：
+//! ```json
+//! "keybindings": {
+//!   "leader": "space",
+//!   "normal": { "delete_project": "D", "stop_dev": ["s", "<leader>s"] },
+//!   "shell": { "focus_sidebar": "ctrl+]" }
+//! }
+//! ```
+//! 配置中出现的动作会替换该动作的全部默认按键，空列表表示解除绑定
+
+#![allow(dead_code)]
+
+mod action;
+mod key;
+
+pub use action::*;
+pub use key::*;
+
+use crate::config::{KeyList, KeybindingsConfig};
+use std::collections::BTreeMap;
+
+/// 默认 leader 键
+pub const DEFAULT_LEADER: &str = "space";
+
+/// 快捷键生效的模式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeymapMode {
+    /// 侧边栏（全局快捷键）
+    Normal,
+    /// Dev Terminal 日志查看
+    DevLog,
+    /// Interactive Shell（未绑定的按键会转发给终端）
+    Shell,
+}
+
+/// 按键序列的解析结果
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyResolution {
+    /// 匹配到动作
+    Action(Action),
+    /// 是某个组合键的前缀，等待后续按键
+    Pending,
+    /// 没有匹配的绑定
+    Unbound,
+}
+
+/// 快捷键映射表
+#[derive(Debug, Clone)]
+pub struct Keymap {
+    leader: Option<KeyPress>,
+    normal: Vec<(KeySequence, Action)>,
+    dev_log: Vec<(KeySequence, Action)>,
+    shell: Vec<(KeySequence, Action)>,
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        let mut normal = vec![
+            ("tab", Action::NextProject),
+            ("j", Action::NextProject),
+            ("down", Action::NextProject),
+            ("shift+tab", Action::PrevProject),
+            ("k", Action::PrevProject),
+            ("up", Action::PrevProject),
+        ];
+        let digits = ["1", "2", "3", "4", "5", "6", "7", "8", "9"];
+        normal.extend(
+            digits
+                .iter()
+                .zip(1..)
+                .map(|(key, n)| (*key, Action::SelectProject(n))),
+        );
+        normal.extend([
+            ("enter", Action::EnterShell),
+            ("r", Action::RunInDev),
+            ("s", Action::StopDev),
+            ("x", Action::InterruptDev),
+            ("p", Action::ToggleSuspend),
+            (".", Action::RerunLast),
+            ("h", Action::OpenHistory),
+            ("E", Action::OpenProblems),
+            ("f", Action::ShowHints),
+            ("o", Action::ShowLinkHints),
+            ("O", Action::OpenDevUrl),
+            ("u", Action::OpenResources),
+            ("t", Action::OpenProcessTree),
+            ("R", Action::RunInShell),
+            ("T", Action::NewShellTab),
+            ("]", Action::NextShellTab),
+            ("[", Action::PrevShellTab),
+            ("a", Action::AddProject),
+            ("e", Action::EditAlias),
+            ("c", Action::AddCommand),
+            ("C", Action::ManageCommands),
+            ("d", Action::DeleteProject),
+            ("g", Action::OpenGit),
+            ("z", Action::ToggleLayout),
+            ("|", Action::TogglePanelOrientation),
+            ("b", Action::ToggleSidebar),
+            ("<", Action::ShrinkSidebar),
+            (">", Action::GrowSidebar),
+            ("-", Action::ShrinkDevPanel),
+            ("+", Action::GrowDevPanel),
+            ("=", Action::GrowDevPanel),
+            (",", Action::OpenSettings),
+            ("q", Action::Quit),
+            ("ctrl+c", Action::Quit),
+            ("ctrl+q", Action::Quit),
+            ("?", Action::Help),
+        ]);
+
+        let dev_log = [
+            ("j", Action::ScrollDown),
+            ("down", Action::ScrollDown),
+            ("k", Action::ScrollUp),
+            ("up", Action::ScrollUp),
+            ("pgup", Action::PageUp),
+            ("pgdn", Action::PageDown),
+            ("home", Action::ScrollToLatest),
+            ("f", Action::ShowHints),
+            ("o", Action::ShowLinkHints),
+            ("O", Action::OpenDevUrl),
+            ("z", Action::ToggleLayout),
+            ("-", Action::ShrinkDevPanel),
+            ("+", Action::GrowDevPanel),
+            ("=", Action::GrowDevPanel),
+            ("esc", Action::FocusSidebar),
+        ];
+
+        let shell = [
+            ("esc", Action::FocusSidebar),
+            ("alt+t", Action::NewShellTab),
+            ("alt+w", Action::CloseShellTab),
+            ("alt+r", Action::RenameShellTab),
+            ("alt+n", Action::NextShellTab),
+            ("alt+p", Action::PrevShellTab),
+        ];
+
+        let build = |bindings: &[(&str, Action)]| {
+            bindings
+                .iter()
+                .filter_map(|(key, action)| Some((KeySequence::parse(key, None).ok()?, *action)))
+                .collect()
+        };
+
+        Self {
+            leader: KeyPress::parse(DEFAULT_LEADER).ok(),
+            normal: build(&normal),
+            dev_log: build(&dev_log),
+            shell: build(&shell),
+        }
+    }
+}
+
+impl Keymap {
+    /// 根据配置构建映射表
+    /// 返回映射表和配置中的错误（无效的条目会被跳过）
+    pub fn from_config(config: &KeybindingsConfig) -> (Self, Vec<String>) {
+        let mut keymap = Self::default();
+        let mut errors = Vec::new();
+
+        if let Some(leader) = &config.leader {
+            match KeyPress::parse(leader) {
+                Ok(key) => keymap.leader = Some(key),
+                Err(e) => errors.push(format!("leader: {}", e)),
+            }
+        }
+
+        let leader = keymap.leader;
+        for (mode, overrides) in [
+            (KeymapMode::Normal, &config.normal),
+            (KeymapMode::DevLog, &config.dev_log),
+            (KeymapMode::Shell, &config.shell),
+        ] {
+            apply_overrides(keymap.bindings_mut(mode), overrides, leader, &mut errors);
+
+            // 完全匹配优先，被遮挡的组合键永远无法触发
+            let bindings = keymap.bindings(mode);
+            for (seq, _) in bindings {
+                if let Some((longer, _)) =
+                    bindings.iter().find(|(other, _)| other.has_prefix(&seq.0))
+                {
+                    errors.push(format!("'{}' shadows '{}'", seq, longer));
+                }
+            }
+        }
+
+        (keymap, errors)
+    }
+
+    /// 当前 leader 键
+    pub fn leader(&self) -> Option<KeyPress> {
+        self.leader
+    }
+
+    /// 获取指定模式的全部绑定（按默认顺序）
+    pub fn bindings(&self, mode: KeymapMode) -> &[(KeySequence, Action)] {
+        match mode {
+            KeymapMode::Normal => &self.normal,
+            KeymapMode::DevLog => &self.dev_log,
+            KeymapMode::Shell => &self.shell,
+        }
+    }
+
+    fn bindings_mut(&mut self, mode: KeymapMode) -> &mut Vec<(KeySequence, Action)> {
+        match mode {
+            KeymapMode::Normal => &mut self.normal,
+            KeymapMode::DevLog => &mut self.dev_log,
+            KeymapMode::Shell => &mut self.shell,
+        }
+    }
+
+    /// 解析已输入的按键序列
+    /// 完全匹配优先于组合键前缀
+    pub fn resolve(&self, mode: KeymapMode, keys: &[KeyPress]) -> KeyResolution {
+        let bindings = self.bindings(mode);
+        if let Some((_, action)) = bindings.iter().find(|(seq, _)| seq.0 == keys) {
+            return KeyResolution::Action(*action);
+        }
+        if bindings.iter().any(|(seq, _)| seq.has_prefix(keys)) {
+            return KeyResolution::Pending;
+        }
+        KeyResolution::Unbound
+    }
+
+    /// 获取动作绑定的所有按键序列
+    pub fn keys_for(&self, mode: KeymapMode, action: Action) -> Vec<&KeySequence> {
+        self.bindings(mode)
+            .iter()
+            .filter(|(_, a)| *a == action)
+            .map(|(seq, _)| seq)
+            .collect()
+    }
+
+    /// 动作的按键显示文本（多个按键用 "/" 连接），未绑定时返回 None
+    pub fn label(&self, mode: KeymapMode, action: Action) -> Option<String> {
+        let keys = self.keys_for(mode, action);
+        if keys.is_empty() {
+            return None;
+        }
+        Some(
+            keys.iter()
+                .map(|seq| seq.to_string())
+                .collect::<Vec<_>>()
+                .join("/"),
+        )
+    }
+
+    /// 动作的首个按键显示文本（用于空间有限的状态栏）
+    pub fn short_label(&self, mode: KeymapMode, action: Action) -> Option<String> {
+        self.keys_for(mode, action)
+            .first()
+            .map(|seq| seq.to_string())
+    }
+}
+
+/// 将配置中的覆盖应用到某个模式的绑定列表
+fn apply_overrides(
+    bindings: &mut Vec<(KeySequence, Action)>,
+    overrides: &BTreeMap<String, KeyList>,
+    leader: Option<KeyPress>,
+    errors: &mut Vec<String>,
+) {
+    for (name, keys) in overrides {
+        let Some(action) = Action::from_name(name) else {
+            errors.push(format!("unknown action '{}'", name));
+            continue;
+        };
+
+        // 新绑定放在该动作原来的位置，保持帮助中的顺序
+        let position = bindings
+            .iter()
+            .position(|(_, a)| *a == action)
+            .unwrap_or(bindings.len());
+        bindings.retain(|(_, a)| *a != action);
+        let mut position = position.min(bindings.len());
+
+        for key in keys.keys() {
+            match KeySequence::parse(key, leader) {
+                Ok(seq) => {
+                    // 同一按键只能绑定一个动作，后配置的覆盖默认
+                    if let Some(existing) = bindings.iter().position(|(s, _)| *s == seq) {
+                        bindings.remove(existing);
+                        if existing < position {
+                            position -= 1;
+                        }
+                    }
+                    bindings.insert(position, (seq, action));
+                    position += 1;
+                }
+                Err(e) => errors.push(format!("{}: {}", name, e)),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::KeyCode;
+
+    fn press(c: char) -> KeyPress {
+        KeyPress::plain(KeyCode::Char(c))
+    }
+
+    #[test]
+    fn test_default_bindings() {
+        let keymap = Keymap::default();
+        assert_eq!(
+            keymap.resolve(KeymapMode::Normal, &[press('q')]),
+            KeyResolution::Action(Action::Quit)
+        );
+        assert_eq!(
+            keymap.resolve(KeymapMode::Normal, &[press('5')]),
+            KeyResolution::Action(Action::SelectProject(5))
+        );
+        assert_eq!(
+            keymap.resolve(KeymapMode::Shell, &[press('q')]),
+            KeyResolution::Unbound
+        );
+        assert_eq!(
+            keymap
+                .label(KeymapMode::Normal, Action::NextProject)
+                .unwrap(),
+            "Tab/j/↓"
+        );
+    }
+
+    #[test]
+    fn test_override_replaces_default_keys() {
+        let config: KeybindingsConfig = serde_json::from_str(
+            r#"{
+                "normal": { "delete_project": "D", "quit": [] },
+                "shell": { "focus_sidebar": "ctrl+]" }
+            }"#,
+        )
+        .unwrap();
+        let (keymap, errors) = Keymap::from_config(&config);
+        assert!(errors.is_empty());
+
+        assert_eq!(
+            keymap.resolve(KeymapMode::Normal, &[press('d')]),
+            KeyResolution::Unbound
+        );
+        assert_eq!(
+            keymap.resolve(KeymapMode::Normal, &[press('D')]),
+            KeyResolution::Action(Action::DeleteProject)
+        );
+        assert!(keymap.keys_for(KeymapMode::Normal, Action::Quit).is_empty());
+        assert_eq!(
+            keymap
+                .label(KeymapMode::Shell, Action::FocusSidebar)
+                .unwrap(),
+            "Ctrl+]"
+        );
+    }
+
+    #[test]
+    fn test_leader_chords() {
+        let config: KeybindingsConfig = serde_json::from_str(
+            r#"{
+                "leader": ",",
+                "normal": { "open_settings": "<leader> <leader>", "stop_dev": "<leader>s" }
+            }"#,
+        )
+        .unwrap();
+        let (keymap, errors) = Keymap::from_config(&config);
+        assert!(errors.is_empty());
+
+        assert_eq!(
+            keymap.resolve(KeymapMode::Normal, &[press(',')]),
+            KeyResolution::Pending
+        );
+        assert_eq!(
+            keymap.resolve(KeymapMode::Normal, &[press(','), press('s')]),
+            KeyResolution::Action(Action::StopDev)
+        );
+        assert_eq!(
+            keymap.resolve(KeymapMode::Normal, &[press(','), press(',')]),
+            KeyResolution::Action(Action::OpenSettings)
+        );
+        assert_eq!(
+            keymap.resolve(KeymapMode::Normal, &[press(','), press('x')]),
+            KeyResolution::Unbound
+        );
+    }
+
+    #[test]
+    fn test_invalid_entries_are_reported() {
+        let config: KeybindingsConfig =
+            serde_json::from_str(r#"{ "normal": { "fly": "f", "quit": ["q", "hyper+q"] } }"#)
+                .unwrap();
+        let (keymap, errors) = Keymap::from_config(&config);
+        assert_eq!(errors.len(), 2);
+        // 有效的按键仍然生效
+        assert_eq!(
+            keymap.resolve(KeymapMode::Normal, &[press('q')]),
+            KeyResolution::Action(Action::Quit)
+        );
+    }
+}
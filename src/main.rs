@@ -7,6 +7,7 @@ mod app;
 mod config;
 mod event;
 mod i18n;
+mod keymap;
 mod platform;
 mod project;
 mod pty;
@@ -20,12 +21,13 @@ use crossterm::{
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
 use futures::StreamExt;
+use platform::EditorCommand;
 use project::Project;
 use pty::{handle_pty_events, PtyManager};
 use ratatui::{backend::CrosstermBackend, Terminal};
 use std::io;
 use std::path::PathBuf;
-use ui::{draw_ui, Theme};
+use ui::draw_ui;
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
@@ -69,6 +71,9 @@ async fn run_app(config: AppConfig) -> anyhow::Result<()> {
                 Ok(mut project) => {
                     project.alias = project_config.alias.clone();
                     project.custom_commands = project_config.custom_commands.clone();
+                    project.worktree_of = project_config.worktree_of.as_ref().map(PathBuf::from);
+                    project.resource_limits = project_config.resource_limits.clone();
+                    project.set_shell_tabs(&project_config.shell_tabs);
                     state.add_project(project);
                 }
                 Err(e) => {
@@ -78,9 +83,6 @@ async fn run_app(config: AppConfig) -> anyhow::Result<()> {
         }
     }
 
-    // 初始化主题
-    let theme = Theme::default();
-
     // 初始化 PTY 管理器
     let pty_manager = PtyManager::new();
 
@@ -95,6 +97,17 @@ async fn run_app(config: AppConfig) -> anyhow::Result<()> {
         // 更新动画帧和过期消息
         state.tick();
 
+        // 未完成的组合键超时后取消（在 Shell 中输入的按键转发给 Shell）
+        if let Err(e) = event::expire_pending_keys(&mut state, false) {
+            log::error!("Failed to forward pending keys: {}", e);
+        }
+
+        // 启动组合命令中等待运行的步骤
+        event::command::advance_composites(&mut state, &pty_manager);
+
+        // 重新运行超过资源上限而停止的命令
+        event::command::restart_over_limit(&mut state, &pty_manager);
+
         // 轮询 PTY 资源清理状态（Windows 专用）
         // 如果资源已释放，执行待处理的命令
         if state.poll_pty_cleanup() {
@@ -123,7 +136,31 @@ async fn run_app(config: AppConfig) -> anyhow::Result<()> {
             }
         }
 
-        // 渲染 UI
+        // 在编辑器中打开文件（问题列表等发起的请求）
+        if let Some(request) = state.pending_editor.take() {
+            let editor = EditorCommand::from_settings(
+                state.config.settings.editor.as_deref(),
+                &request.path,
+                request.line,
+                request.column,
+            );
+            let result = if editor.in_terminal {
+                // 终端编辑器需要暂时退出 TUI，并停止读取终端事件以免抢走编辑器的输入
+                drop(event_stream);
+                let result = run_terminal_editor(&mut terminal, &editor);
+                event_stream = EventStream::new();
+                result
+            } else {
+                editor.spawn().map_err(Into::into)
+            };
+            if let Err(e) = result {
+                log::error!("Failed to open editor {}: {}", editor.program, e);
+                state.set_status(&format!("Error: {}: {}", editor.program, e));
+            }
+        }
+
+        // 渲染 UI（主题可能在设置中实时切换，每帧取当前主题）
+        let theme = state.theme.clone();
         terminal.draw(|frame| {
             draw_ui(frame, &mut state, &theme);
         })?;
@@ -162,3 +199,28 @@ async fn run_app(config: AppConfig) -> anyhow::Result<()> {
 
     Ok(())
 }
+
+/// 暂时退出 TUI，在当前终端中运行编辑器，结束后恢复界面
+fn run_terminal_editor(
+    terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
+    editor: &EditorCommand,
+) -> anyhow::Result<()> {
+    disable_raw_mode()?;
+    execute!(
+        terminal.backend_mut(),
+        LeaveAlternateScreen,
+        DisableMouseCapture
+    )?;
+    terminal.show_cursor()?;
+
+    let result = editor.run_blocking();
+
+    enable_raw_mode()?;
+    execute!(
+        terminal.backend_mut(),
+        EnterAlternateScreen,
+        EnableMouseCapture
+    )?;
+    terminal.clear()?;
+    result.map_err(Into::into)
+}
@@ -0,0 +1,165 @@
+//! 编辑器启动模块
+//! 使用配置的编辑器命令模板，或根据 $VISUAL / $EDITOR 选择编辑器并按编辑器的习惯传递行号和列号
+
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+/// 打开文件的编辑器命令
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EditorCommand {
+    pub program: String,
+    pub args: Vec<String>,
+    /// 是否在当前终端中运行（需要暂时退出 TUI）
+    pub in_terminal: bool,
+}
+
+impl EditorCommand {
+    /// 根据编辑器名称生成打开 file:line:col 的命令
+    pub fn new(editor: &str, path: &Path, line: u32, column: u32) -> Self {
+        let mut parts = editor.split_whitespace().map(str::to_string);
+        let program = parts.next().unwrap_or_else(default_editor);
+        let mut args: Vec<String> = parts.collect();
+        let file = path.to_string_lossy();
+        let name = Path::new(&program)
+            .file_stem()
+            .map(|s| s.to_string_lossy().to_lowercase())
+            .unwrap_or_default();
+
+        let location = format!("{}:{}:{}", file, line, column);
+        match name.as_str() {
+            "code" | "code-insiders" | "codium" | "cursor" | "windsurf" => {
+                args.extend(["-g".to_string(), location])
+            }
+            "subl" | "zed" | "hx" | "helix" => args.push(location),
+            "idea" | "webstorm" | "pycharm" | "goland" | "rustrover" | "clion" => args.extend([
+                "--line".to_string(),
+                line.to_string(),
+                "--column".to_string(),
+                column.to_string(),
+                file.to_string(),
+            ]),
+            "notepad" | "notepad++" => args.push(file.to_string()),
+            // vi、vim、nvim、nano、emacs、micro 等终端编辑器都支持 +line
+            _ => args.extend([format!("+{}", line), file.to_string()]),
+        }
+
+        let in_terminal = !is_gui_editor(&program);
+        Self {
+            program,
+            args,
+            in_terminal,
+        }
+    }
+
+    /// 使用设置中的编辑器命令，未设置时使用 $VISUAL / $EDITOR
+    /// 命令中包含 `{file}`、`{line}`、`{col}` 时按模板替换，否则视为编辑器名称
+    pub fn from_settings(editor: Option<&str>, path: &Path, line: u32, column: u32) -> Self {
+        let editor = editor
+            .filter(|e| !e.trim().is_empty())
+            .map(str::to_string)
+            .or_else(|| std::env::var("VISUAL").ok())
+            .or_else(|| std::env::var("EDITOR").ok())
+            .unwrap_or_else(default_editor);
+        if !editor.contains("{file}") {
+            return Self::new(&editor, path, line, column);
+        }
+
+        // 先按空白拆分再替换，路径中的空格不会拆开参数
+        let file = path.to_string_lossy();
+        let mut parts = editor.split_whitespace().map(|part| {
+            part.replace("{file}", &file)
+                .replace("{line}", &line.to_string())
+                .replace("{col}", &column.to_string())
+        });
+        let program = parts.next().unwrap_or_else(default_editor);
+        let in_terminal = !is_gui_editor(&program);
+        Self {
+            program,
+            args: parts.collect(),
+            in_terminal,
+        }
+    }
+
+    /// 在当前终端中运行编辑器并等待退出
+    pub fn run_blocking(&self) -> std::io::Result<()> {
+        Command::new(&self.program).args(&self.args).status()?;
+        Ok(())
+    }
+
+    /// 在后台启动图形界面编辑器
+    pub fn spawn(&self) -> std::io::Result<()> {
+        let mut child = Command::new(&self.program)
+            .args(&self.args)
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()?;
+        // 在后台等待进程结束，避免留下僵尸进程
+        std::thread::spawn(move || child.wait());
+        Ok(())
+    }
+}
+
+/// 是否为图形界面编辑器（在后台启动，不需要退出 TUI）
+fn is_gui_editor(program: &str) -> bool {
+    let name = Path::new(program)
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+    matches!(
+        name.as_str(),
+        "code"
+            | "code-insiders"
+            | "codium"
+            | "cursor"
+            | "windsurf"
+            | "subl"
+            | "zed"
+            | "idea"
+            | "webstorm"
+            | "pycharm"
+            | "goland"
+            | "rustrover"
+            | "clion"
+            | "notepad"
+            | "notepad++"
+    )
+}
+
+/// 没有配置编辑器时的默认编辑器
+fn default_editor() -> String {
+    if cfg!(windows) {
+        "notepad".to_string()
+    } else {
+        "vi".to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_editor_args() {
+        let path = Path::new("/work/web/src/App.tsx");
+        let code = EditorCommand::new("code --wait", path, 12, 5);
+        assert_eq!(code.program, "code");
+        assert_eq!(code.args, ["--wait", "-g", "/work/web/src/App.tsx:12:5"]);
+        assert!(!code.in_terminal);
+
+        let vim = EditorCommand::new("/usr/bin/nvim", path, 12, 5);
+        assert_eq!(vim.args, ["+12", "/work/web/src/App.tsx"]);
+        assert!(vim.in_terminal);
+
+        let template = EditorCommand::from_settings(
+            Some("emacsclient -n +{line}:{col} {file}"),
+            Path::new("/work/my app/main.rs"),
+            3,
+            9,
+        );
+        assert_eq!(template.program, "emacsclient");
+        assert_eq!(template.args, ["-n", "+3:9", "/work/my app/main.rs"]);
+        assert!(template.in_terminal);
+        assert!(!EditorCommand::from_settings(Some("zed {file}:{line}"), path, 1, 1).in_terminal);
+    }
+}
@@ -1,5 +1,13 @@
 //! 跨平台工具模块
 
+mod editor;
+mod net;
+mod notify;
+mod open;
 mod shell;
 
+pub use editor::*;
+pub use net::*;
+pub use notify::*;
+pub use open::*;
 pub use shell::*;
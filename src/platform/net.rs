@@ -0,0 +1,85 @@
+//! 本机端口检查
+//! 判断监听端口上是否有等待 accept 的连接（进程被暂停时，内核仍会完成握手并把连接放进队列）
+
+/// 监听 port 的套接字是否有等待处理的连接
+#[cfg(target_os = "linux")]
+pub fn has_pending_connections(port: u16) -> bool {
+    ["/proc/net/tcp", "/proc/net/tcp6"].iter().any(|path| {
+        std::fs::read_to_string(path).is_ok_and(|table| proc_net_pending(&table, port) > 0)
+    })
+}
+
+/// 监听 port 的套接字是否有等待处理的连接
+#[cfg(target_os = "macos")]
+pub fn has_pending_connections(port: u16) -> bool {
+    std::process::Command::new("netstat")
+        .args(["-L", "-a", "-n", "-p", "tcp"])
+        .output()
+        .is_ok_and(|out| netstat_pending(&String::from_utf8_lossy(&out.stdout), port) > 0)
+}
+
+/// 其他平台无法读取监听队列
+#[cfg(not(any(target_os = "linux", target_os = "macos")))]
+pub fn has_pending_connections(_port: u16) -> bool {
+    false
+}
+
+/// 解析 /proc/net/tcp：LISTEN（状态 0A）套接字的 rx_queue 为等待 accept 的连接数
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+fn proc_net_pending(table: &str, port: u16) -> u64 {
+    table
+        .lines()
+        .skip(1)
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let local_port = fields.get(1)?.rsplit(':').next()?;
+            let queues = fields.get(4)?;
+            (u16::from_str_radix(local_port, 16).ok()? == port && *fields.get(3)? == "0A")
+                .then(|| u64::from_str_radix(queues.split(':').nth(1)?, 16).ok())
+                .flatten()
+        })
+        .sum()
+}
+
+/// 解析 `netstat -Lan`：每行为 `qlen/incqlen/maxqlen  地址.端口`
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+fn netstat_pending(output: &str, port: u16) -> u64 {
+    output
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            let queues = fields.next()?;
+            let address = fields.next()?;
+            let local_port = address.rsplit('.').next()?.parse::<u16>().ok()?;
+            let qlen = queues.split('/').next()?.parse::<u64>().ok()?;
+            (local_port == port).then_some(qlen)
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_proc_net_pending() {
+        let table = "  sl  local_address rem_address   st tx_queue rx_queue tr tm->when retrnsmt   uid  timeout inode
+   0: 0100007F:1435 00000000:0000 0A 00000000:00000002 00:00000000 00000000  1000        0 1 1 0000000000000000 100 0 0 10 0
+   1: 0100007F:1435 0100007F:C350 01 00000000:00000005 00:00000000 00000000  1000        0 2 1 0000000000000000 20 4 30 10 -1
+   2: 00000000:0BB8 00000000:0000 0A 00000000:00000000 00:00000000 00000000  1000        0 3 1 0000000000000000 100 0 0 10 0";
+        assert_eq!(proc_net_pending(table, 5173), 2);
+        assert_eq!(proc_net_pending(table, 3000), 0);
+        assert_eq!(proc_net_pending(table, 8080), 0);
+    }
+
+    #[test]
+    fn test_netstat_pending() {
+        let output = "Current listen queue sizes (qlen/incqlen/maxqlen)
+Listen         Local Address
+1/0/128        127.0.0.1.5173
+0/0/128        *.3000
+0/0/128        ::1.5173";
+        assert_eq!(netstat_pending(output, 5173), 1);
+        assert_eq!(netstat_pending(output, 3000), 0);
+    }
+}
@@ -0,0 +1,115 @@
+//! 通知发送模块
+//! 通过桌面通知、OSC 9/777 终端通知或终端响铃发送通知
+
+use crate::config::NotifyChannel;
+use std::io::Write;
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+use std::process::{Command, Stdio};
+
+/// 通过配置的方式发送通知（失败只记录日志）
+pub fn send_notification(channels: &[NotifyChannel], title: &str, body: &str) {
+    let mut sequences = String::new();
+    for channel in channels {
+        match channel {
+            NotifyChannel::Desktop => send_desktop_notification(title, body),
+            NotifyChannel::Osc9 => {
+                sequences.push_str(&format!("\x1b]9;{}: {}\x07", clean(title), clean(body)))
+            }
+            NotifyChannel::Osc777 => sequences.push_str(&format!(
+                "\x1b]777;notify;{};{}\x07",
+                clean(title),
+                clean(body)
+            )),
+            NotifyChannel::Bell => sequences.push('\x07'),
+        }
+    }
+    if !sequences.is_empty() {
+        let mut stdout = std::io::stdout();
+        if let Err(e) = stdout
+            .write_all(sequences.as_bytes())
+            .and_then(|_| stdout.flush())
+        {
+            log::warn!("Failed to write terminal notification: {}", e);
+        }
+    }
+}
+
+/// 去掉会提前结束 OSC 序列的控制字符，以及 OSC 777 的字段分隔符
+fn clean(text: &str) -> String {
+    text.chars()
+        .filter(|c| !c.is_control())
+        .map(|c| if c == ';' { ',' } else { c })
+        .collect()
+}
+
+/// 发送桌面通知
+#[cfg(target_os = "linux")]
+fn send_desktop_notification(title: &str, body: &str) {
+    // 优先使用 notify-send，没有安装时直接通过 D-Bus 调用
+    let notify_send = Command::new("notify-send")
+        .args(["--app-name=DevPanel", title, body])
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn();
+    let result = notify_send.or_else(|_| {
+        Command::new("gdbus")
+            .args([
+                "call",
+                "--session",
+                "--dest=org.freedesktop.Notifications",
+                "--object-path=/org/freedesktop/Notifications",
+                "--method=org.freedesktop.Notifications.Notify",
+                "DevPanel",
+                "0",
+                "",
+                title,
+                body,
+                "[]",
+                "{}",
+                "-1",
+            ])
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+    });
+    reap(result);
+}
+
+/// 发送桌面通知
+#[cfg(target_os = "macos")]
+fn send_desktop_notification(title: &str, body: &str) {
+    let script = format!(
+        "display notification {} with title {}",
+        applescript_string(body),
+        applescript_string(title)
+    );
+    let result = Command::new("osascript")
+        .args(["-e", &script])
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn();
+    reap(result);
+}
+
+/// AppleScript 字符串字面量
+#[cfg(target_os = "macos")]
+fn applescript_string(text: &str) -> String {
+    format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// 发送桌面通知（其他平台暂不支持，请使用 OSC 通知）
+#[cfg(not(any(target_os = "linux", target_os = "macos")))]
+fn send_desktop_notification(title: &str, _body: &str) {
+    log::debug!("Desktop notifications not supported: {}", title);
+}
+
+/// 在后台等待通知进程结束，避免留下僵尸进程
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+fn reap(result: std::io::Result<std::process::Child>) {
+    match result {
+        Ok(mut child) => {
+            std::thread::spawn(move || child.wait());
+        }
+        Err(e) => log::warn!("Failed to send desktop notification: {}", e),
+    }
+}
@@ -0,0 +1,38 @@
+//! 系统默认程序打开链接
+
+use std::process::{Command, Stdio};
+
+/// 用系统默认程序（浏览器等）打开链接
+pub fn open_url(url: &str) -> std::io::Result<()> {
+    let mut command = opener(url);
+    let mut child = command
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()?;
+    // 在后台等待进程结束，避免留下僵尸进程
+    std::thread::spawn(move || child.wait());
+    Ok(())
+}
+
+#[cfg(target_os = "macos")]
+fn opener(url: &str) -> Command {
+    let mut command = Command::new("open");
+    command.arg(url);
+    command
+}
+
+#[cfg(windows)]
+fn opener(url: &str) -> Command {
+    // 不经过 cmd，避免 URL 中的 & 等字符被解释
+    let mut command = Command::new("rundll32");
+    command.args(["url.dll,FileProtocolHandler", url]);
+    command
+}
+
+#[cfg(not(any(target_os = "macos", windows)))]
+fn opener(url: &str) -> Command {
+    let mut command = Command::new("xdg-open");
+    command.arg(url);
+    command
+}
@@ -5,6 +5,8 @@
 
 #[cfg(windows)]
 use crate::config::WindowsShell;
+use std::collections::BTreeMap;
+use std::path::Path;
 
 /// 获取系统默认 Shell（不带配置参数的版本，用于兼容现有代码）
 pub fn get_default_shell() -> String {
@@ -164,6 +166,144 @@ pub fn is_powershell(shell: &str) -> bool {
     lower.contains("powershell") || lower.contains("pwsh")
 }
 
+/// 检查 Shell 是否是 cmd.exe
+pub fn is_cmd(shell: &str) -> bool {
+    get_shell_name(shell).to_lowercase().starts_with("cmd")
+}
+
+/// 检查 Shell 是否是 fish
+pub fn is_fish(shell: &str) -> bool {
+    get_shell_name(shell) == "fish"
+}
+
+/// POSIX Shell 单引号转义
+fn posix_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "'\\''"))
+}
+
+/// fish 单引号转义（单引号中的 `\` 和 `'` 需要用反斜杠转义）
+fn fish_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\\', "\\\\").replace('\'', "\\'"))
+}
+
+/// CMD 交互命令行中不加引号的值转义
+/// 特殊字符前加 `^`；`%` 在变量展开阶段就会处理，`^` 无法阻止，
+/// 改为在 `%` 后面插入 `^` 让变量名匹配不上，之后 `^` 会被去掉（同时转义紧跟的字符）
+fn cmd_escape(value: &str) -> String {
+    let mut escaped = String::new();
+    let mut chars = value.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '%' => {
+                escaped.push('%');
+                match chars.peek() {
+                    // 值末尾的 `%` 后面是 `&&`，不能插入 `^`
+                    None => {}
+                    Some('%') => escaped.push('^'),
+                    Some(_) => {
+                        escaped.push('^');
+                        escaped.extend(chars.next());
+                    }
+                }
+            }
+            '^' | '&' | '|' | '<' | '>' | '(' | ')' | '"' => {
+                escaped.push('^');
+                escaped.push(c);
+            }
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// 按目标 Shell 的语法把值转义为单个参数（用于替换到命令行中的路径和用户输入）
+pub fn shell_quote(value: &str, shell: &str) -> String {
+    if is_powershell(shell) {
+        format!("'{}'", value.replace('\'', "''"))
+    } else if is_fish(shell) {
+        fish_quote(value)
+    } else if is_cmd(shell) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        posix_quote(value)
+    }
+}
+
+/// 生成在交互式 Shell 中输入的命令行，附带工作目录和环境变量
+/// POSIX Shell 在子 Shell 中执行，不改变当前目录和环境；
+/// fish 在 begin/end 块中用局部变量执行，执行后回到原目录；
+/// PowerShell / CMD 执行后回到原目录，但环境变量会保留在当前会话
+pub fn shell_input_with_context(
+    command: &str,
+    cwd: Option<&Path>,
+    env: &BTreeMap<String, String>,
+    shell: &str,
+) -> String {
+    if cwd.is_none() && env.is_empty() {
+        return command.to_string();
+    }
+
+    if is_powershell(shell) {
+        let mut parts: Vec<String> = env
+            .iter()
+            .map(|(key, value)| format!("$env:{}='{}'", key, value.replace('\'', "''")))
+            .collect();
+        match cwd {
+            Some(dir) => {
+                let dir = dir.to_string_lossy().replace('\'', "''");
+                parts.push(format!("Push-Location '{}'", dir));
+                parts.push(command.to_string());
+                parts.push("Pop-Location".to_string());
+            }
+            None => parts.push(command.to_string()),
+        }
+        parts.join("; ")
+    } else if is_fish(shell) {
+        let mut parts: Vec<String> = env
+            .iter()
+            .map(|(key, value)| format!("set -lx {} {}", key, fish_quote(value)))
+            .collect();
+        match cwd {
+            Some(dir) => {
+                let dir = fish_quote(&dir.to_string_lossy());
+                parts.push(format!("pushd {}; and begin; {}; popd; end", dir, command));
+            }
+            None => parts.push(command.to_string()),
+        }
+        format!("begin; {}; end", parts.join("; "))
+    } else if is_cmd(shell) {
+        // `set K=V&&` 中间不能有空格，否则空格会成为值的一部分
+        let mut parts: Vec<String> = env
+            .iter()
+            .map(|(key, value)| format!("set {}={}", key, cmd_escape(value)))
+            .collect();
+        if let Some(dir) = cwd {
+            parts.push(format!("pushd \"{}\"", dir.display()));
+        }
+        parts.push(command.to_string());
+        let line = parts.join("&& ");
+        if cwd.is_some() {
+            format!("{} & popd", line)
+        } else {
+            line
+        }
+    } else {
+        let mut parts = Vec::new();
+        if !env.is_empty() {
+            let assignments: Vec<String> = env
+                .iter()
+                .map(|(key, value)| format!("{}={}", key, posix_quote(value)))
+                .collect();
+            parts.push(format!("export {}", assignments.join(" ")));
+        }
+        if let Some(dir) = cwd {
+            parts.push(format!("cd {}", posix_quote(&dir.to_string_lossy())));
+        }
+        parts.push(command.to_string());
+        format!("({})", parts.join(" && "))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -181,4 +321,49 @@ mod tests {
         assert!(!shell.is_empty());
         assert!(!args.is_empty());
     }
+
+    #[test]
+    fn test_shell_quote() {
+        assert_eq!(shell_quote("/my app", "/bin/zsh"), "'/my app'");
+        assert_eq!(
+            shell_quote("a'; rm -rf ~", "/bin/bash"),
+            "'a'\\''; rm -rf ~'"
+        );
+        assert_eq!(shell_quote("it's $(x)", "pwsh.exe"), "'it''s $(x)'");
+        assert_eq!(
+            shell_quote("say \"hi\" & x", "cmd.exe"),
+            "\"say \"\"hi\"\" & x\""
+        );
+    }
+
+    #[test]
+    fn test_shell_input_with_context() {
+        let env = BTreeMap::from([("API_URL".to_string(), "http://x/it's".to_string())]);
+        let dir = Path::new("/work/app/web");
+        assert_eq!(
+            shell_input_with_context("pnpm dev", None, &BTreeMap::new(), "/bin/zsh"),
+            "pnpm dev"
+        );
+        assert_eq!(
+            shell_input_with_context("pnpm dev", Some(dir), &env, "/bin/zsh"),
+            "(export API_URL='http://x/it'\\''s' && cd '/work/app/web' && pnpm dev)"
+        );
+        assert_eq!(
+            shell_input_with_context("pnpm dev", None, &env, "pwsh.exe"),
+            "$env:API_URL='http://x/it''s'; pnpm dev"
+        );
+        assert_eq!(
+            shell_input_with_context("pnpm dev", Some(dir), &BTreeMap::new(), "cmd.exe"),
+            "pushd \"/work/app/web\"&& pnpm dev & popd"
+        );
+        assert_eq!(
+            shell_input_with_context("pnpm dev", Some(dir), &env, "/usr/bin/fish"),
+            "begin; set -lx API_URL 'http://x/it\\'s'; pushd '/work/app/web'; and begin; pnpm dev; popd; end; end"
+        );
+        let env = BTreeMap::from([("Q".to_string(), "50% \"a&b\" ^%PATH%".to_string())]);
+        assert_eq!(
+            shell_input_with_context("pnpm dev", None, &env, "cmd.exe"),
+            "set Q=50%^ ^\"a^&b^\" ^^%^PATH%&& pnpm dev"
+        );
+    }
 }
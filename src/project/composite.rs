@@ -0,0 +1,362 @@
+//! 组合命令模块
+//! 把多个命令组合成一个整体运行：
+//! - 顺序（Sequence）：`lint -> typecheck -> test`，逐个运行，遇到失败即停止
+//! - 并行（Parallel）：`dev:web + dev:api`，同时运行，在 Dev Terminal 中分屏显示
+
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use crate::pty::{PtyHandle, StopStep};
+
+/// 顺序组合的步骤分隔符
+pub const SEQUENCE_SEPARATOR: &str = "->";
+/// 并行组合的步骤分隔符
+pub const PARALLEL_SEPARATOR: &str = "+";
+
+/// 组合方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompositeMode {
+    Sequence,
+    Parallel,
+}
+
+impl CompositeMode {
+    /// 步骤之间的分隔符
+    pub fn separator(&self) -> &'static str {
+        match self {
+            CompositeMode::Sequence => SEQUENCE_SEPARATOR,
+            CompositeMode::Parallel => PARALLEL_SEPARATOR,
+        }
+    }
+}
+
+/// 解析组合命令的步骤名称（如 `lint -> typecheck -> test`）
+pub fn parse_steps(command: &str, mode: CompositeMode) -> Vec<String> {
+    command
+        .split(mode.separator())
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// 单个步骤的状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StepStatus {
+    /// 等待运行
+    Pending,
+    Running,
+    Succeeded,
+    /// 失败（退出码，进程被终止或无法启动时为 None）
+    Failed(Option<i32>),
+    /// 前面的步骤失败，未运行
+    Skipped,
+}
+
+impl StepStatus {
+    /// 状态图标
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            StepStatus::Pending => "·",
+            StepStatus::Running => "●",
+            StepStatus::Succeeded => "✓",
+            StepStatus::Failed(_) => "✗",
+            StepStatus::Skipped => "-",
+        }
+    }
+
+    /// 状态图标，失败时附带退出码（如 `✗ 1`）
+    pub fn label(&self) -> String {
+        match self {
+            StepStatus::Failed(Some(code)) => format!("{} {}", self.symbol(), code),
+            _ => self.symbol().to_string(),
+        }
+    }
+}
+
+/// 组合命令的整体结果
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompositeResult {
+    Running,
+    Succeeded,
+    /// 失败（第一个失败步骤的退出码）
+    Failed(Option<i32>),
+}
+
+/// 组合命令中的一个步骤
+#[derive(Debug)]
+pub struct CompositeStep {
+    pub name: String,
+    /// 实际执行的命令行（无法解析时为空）
+    pub command: String,
+    pub working_dir: PathBuf,
+    pub env: BTreeMap<String, String>,
+    pub status: StepStatus,
+    /// 无法运行的原因（步骤不存在或嵌套了组合命令）
+    pub error: Option<String>,
+    /// 步骤的 PTY（结束后保留以便查看输出）
+    pub pty: Option<PtyHandle>,
+    /// 步骤命令配置的停止序列
+    pub stop: Vec<StopStep>,
+}
+
+impl CompositeStep {
+    /// 可以运行的步骤
+    pub fn new(
+        name: &str,
+        command: String,
+        working_dir: PathBuf,
+        env: BTreeMap<String, String>,
+    ) -> Self {
+        Self {
+            name: name.to_string(),
+            command,
+            working_dir,
+            env,
+            status: StepStatus::Pending,
+            error: None,
+            pty: None,
+            stop: Vec::new(),
+        }
+    }
+
+    /// 无法运行的步骤
+    pub fn invalid(name: &str, error: String) -> Self {
+        Self {
+            error: Some(error),
+            ..Self::new(name, String::new(), PathBuf::new(), BTreeMap::new())
+        }
+    }
+}
+
+/// 正在运行（或已结束）的组合命令
+#[derive(Debug)]
+pub struct CompositeRun {
+    /// 运行 ID（用于记录运行历史）
+    pub id: String,
+    pub name: String,
+    pub mode: CompositeMode,
+    pub steps: Vec<CompositeStep>,
+}
+
+impl CompositeRun {
+    /// 创建组合运行，无法运行的步骤直接标记为失败
+    pub fn new(name: &str, mode: CompositeMode, steps: Vec<CompositeStep>) -> Self {
+        let mut run = Self {
+            id: format!("composite-{}", uuid::Uuid::new_v4()),
+            name: name.to_string(),
+            mode,
+            steps,
+        };
+        for idx in 0..run.steps.len() {
+            if run.steps[idx].error.is_some() && run.steps[idx].status == StepStatus::Pending {
+                run.fail_step(idx, None);
+            }
+        }
+        run
+    }
+
+    /// 现在应该启动的步骤
+    /// 顺序组合在没有步骤运行时启动下一个，并行组合启动所有等待中的步骤
+    pub fn steps_to_start(&self) -> Vec<usize> {
+        let pending = self
+            .steps
+            .iter()
+            .enumerate()
+            .filter(|(_, step)| step.status == StepStatus::Pending)
+            .map(|(idx, _)| idx);
+        match self.mode {
+            CompositeMode::Parallel => pending.collect(),
+            CompositeMode::Sequence => {
+                if self.steps.iter().any(|s| s.status == StepStatus::Running) {
+                    Vec::new()
+                } else {
+                    pending.take(1).collect()
+                }
+            }
+        }
+    }
+
+    /// 步骤已启动
+    pub fn start_step(&mut self, idx: usize, pty: PtyHandle) {
+        if let Some(step) = self.steps.get_mut(idx) {
+            step.pty = Some(pty);
+            step.status = StepStatus::Running;
+        }
+    }
+
+    /// 步骤失败，顺序组合中后续步骤不再运行
+    pub fn fail_step(&mut self, idx: usize, exit_code: Option<i32>) {
+        let Some(step) = self.steps.get_mut(idx) else {
+            return;
+        };
+        step.status = StepStatus::Failed(exit_code);
+        if self.mode == CompositeMode::Sequence {
+            for step in self.steps.iter_mut().skip(idx + 1) {
+                if step.status == StepStatus::Pending {
+                    step.status = StepStatus::Skipped;
+                }
+            }
+        }
+    }
+
+    /// 处理进程退出，返回 PTY 是否属于该组合
+    pub fn on_exit(&mut self, pty_id: &str, exit_code: Option<i32>) -> bool {
+        let Some(idx) = self.steps.iter().position(|step| {
+            step.status == StepStatus::Running
+                && step.pty.as_ref().is_some_and(|pty| pty.id == pty_id)
+        }) else {
+            return false;
+        };
+        // 进程已退出，保留输出但不再持有 PID（避免 Drop 时终止被复用的 PID）
+        if let Some(pty) = self.steps[idx].pty.as_mut() {
+            pty.pid = None;
+            pty.running = false;
+        }
+        if exit_code == Some(0) {
+            self.steps[idx].status = StepStatus::Succeeded;
+        } else {
+            self.fail_step(idx, exit_code);
+        }
+        true
+    }
+
+    /// 整体结果：有步骤未结束时为 Running，否则任一步骤失败即为失败
+    pub fn result(&self) -> CompositeResult {
+        if self
+            .steps
+            .iter()
+            .any(|s| matches!(s.status, StepStatus::Pending | StepStatus::Running))
+        {
+            return CompositeResult::Running;
+        }
+        match self.steps.iter().find_map(|s| match s.status {
+            StepStatus::Failed(code) => Some(code),
+            _ => None,
+        }) {
+            Some(code) => CompositeResult::Failed(code),
+            None => CompositeResult::Succeeded,
+        }
+    }
+
+    /// 是否还有步骤在运行或等待运行
+    pub fn is_running(&self) -> bool {
+        self.result() == CompositeResult::Running
+    }
+
+    /// 顺序组合当前显示的步骤：正在运行的步骤，否则为最后一个运行过的步骤
+    pub fn current_step(&self) -> Option<&CompositeStep> {
+        self.steps
+            .iter()
+            .find(|s| s.status == StepStatus::Running)
+            .or_else(|| self.steps.iter().rev().find(|s| s.pty.is_some()))
+    }
+
+    /// 正在运行的步骤的 PTY
+    pub fn running_ptys(&self) -> impl Iterator<Item = &PtyHandle> {
+        self.steps
+            .iter()
+            .filter(|s| s.status == StepStatus::Running)
+            .filter_map(|s| s.pty.as_ref())
+    }
+
+    /// 正在运行的步骤的 PTY（可变）
+    pub fn running_ptys_mut(&mut self) -> impl Iterator<Item = &mut PtyHandle> {
+        self.steps
+            .iter_mut()
+            .filter(|s| s.status == StepStatus::Running)
+            .filter_map(|s| s.pty.as_mut())
+    }
+
+    /// 第一个无法运行的步骤的原因
+    pub fn first_error(&self) -> Option<&str> {
+        self.steps.iter().find_map(|s| s.error.as_deref())
+    }
+
+    /// 进度描述（如 `lint ✓ → typecheck ● → test ·`）
+    pub fn progress(&self) -> String {
+        let separator = match self.mode {
+            CompositeMode::Sequence => " → ",
+            CompositeMode::Parallel => " + ",
+        };
+        self.steps
+            .iter()
+            .map(|s| format!("{} {}", s.name, s.status.symbol()))
+            .collect::<Vec<_>>()
+            .join(separator)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(mode: CompositeMode, names: &[&str]) -> CompositeRun {
+        let steps = names
+            .iter()
+            .map(|name| CompositeStep::new(name, name.to_string(), PathBuf::new(), BTreeMap::new()))
+            .collect();
+        CompositeRun::new("all", mode, steps)
+    }
+
+    fn start(run: &mut CompositeRun, idx: usize) {
+        run.start_step(idx, PtyHandle::new(&format!("pty-{}", idx), 24, 80));
+    }
+
+    #[test]
+    fn test_parse_steps() {
+        assert_eq!(
+            parse_steps(" lint ->typecheck -> -> test", CompositeMode::Sequence),
+            vec!["lint", "typecheck", "test"]
+        );
+        assert_eq!(
+            parse_steps("dev:web + dev:api", CompositeMode::Parallel),
+            vec!["dev:web", "dev:api"]
+        );
+    }
+
+    #[test]
+    fn test_sequence_stops_on_failure() {
+        let mut seq = run(CompositeMode::Sequence, &["lint", "typecheck", "test"]);
+        assert_eq!(seq.steps_to_start(), vec![0]);
+        start(&mut seq, 0);
+        assert!(seq.steps_to_start().is_empty());
+        assert!(!seq.on_exit("pty-9", Some(0)));
+        assert!(seq.on_exit("pty-0", Some(0)));
+        assert_eq!(seq.steps_to_start(), vec![1]);
+        start(&mut seq, 1);
+        assert!(seq.on_exit("pty-1", Some(2)));
+        assert_eq!(seq.steps[2].status, StepStatus::Skipped);
+        assert!(seq.steps_to_start().is_empty());
+        assert_eq!(seq.result(), CompositeResult::Failed(Some(2)));
+        assert_eq!(
+            seq.current_step().map(|s| s.name.as_str()),
+            Some("typecheck")
+        );
+        assert_eq!(seq.progress(), "lint ✓ → typecheck ✗ → test -");
+    }
+
+    #[test]
+    fn test_parallel_result() {
+        let mut par = run(CompositeMode::Parallel, &["dev:web", "dev:api"]);
+        assert_eq!(par.steps_to_start(), vec![0, 1]);
+        start(&mut par, 0);
+        start(&mut par, 1);
+        assert!(par.on_exit("pty-1", None));
+        assert_eq!(par.result(), CompositeResult::Running);
+        assert!(par.on_exit("pty-0", Some(0)));
+        assert_eq!(par.result(), CompositeResult::Failed(None));
+
+        let mut invalid = CompositeRun::new(
+            "broken",
+            CompositeMode::Sequence,
+            vec![
+                CompositeStep::invalid("missing", "not found".to_string()),
+                CompositeStep::new("test", "test".to_string(), PathBuf::new(), BTreeMap::new()),
+            ],
+        );
+        assert!(invalid.steps_to_start().is_empty());
+        assert!(!invalid.on_exit("pty-0", Some(0)));
+        assert_eq!(invalid.result(), CompositeResult::Failed(None));
+    }
+}
@@ -0,0 +1,486 @@
+//! Git 信息模块
+//! 分支名直接读取 .git 目录获取；工作区状态和拉取、暂存等操作调用 git 命令（在后台线程中运行）
+
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::time::SystemTime;
+
+/// 工作区中的一个变更文件
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GitFileChange {
+    /// 暂存区状态（`M`、`A`、`D`、`R`、`?` 等，未变更为空格）
+    pub index: char,
+    /// 工作区状态
+    pub worktree: char,
+    pub path: String,
+}
+
+impl GitFileChange {
+    /// 两列状态码（如 `M `、` M`、`??`）
+    pub fn code(&self) -> String {
+        format!("{}{}", self.index, self.worktree)
+    }
+}
+
+/// 仓库状态
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GitStatus {
+    /// 当前分支（分离 HEAD 时为提交哈希的前 7 位）
+    pub branch: String,
+    /// 上游分支（如 `origin/main`）
+    pub upstream: Option<String>,
+    /// 领先上游的提交数
+    pub ahead: u32,
+    /// 落后上游的提交数
+    pub behind: u32,
+    /// 变更文件（包括未跟踪文件）
+    pub files: Vec<GitFileChange>,
+}
+
+impl GitStatus {
+    /// 工作区是否有未提交的修改
+    pub fn is_dirty(&self) -> bool {
+        !self.files.is_empty()
+    }
+
+    /// 与上游的差异（如 `↑2↓1`，没有差异时为空）
+    pub fn ahead_behind(&self) -> String {
+        let mut text = String::new();
+        if self.ahead > 0 {
+            text.push_str(&format!("↑{}", self.ahead));
+        }
+        if self.behind > 0 {
+            text.push_str(&format!("↓{}", self.behind));
+        }
+        text
+    }
+}
+
+/// Git 快捷操作
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GitAction {
+    Fetch,
+    Pull,
+    Stash,
+    /// 切换到本地分支
+    Switch(String),
+}
+
+impl GitAction {
+    /// git 命令参数
+    fn args(&self) -> Vec<&str> {
+        match self {
+            GitAction::Fetch => vec!["fetch", "--prune"],
+            GitAction::Pull => vec!["pull", "--ff-only"],
+            GitAction::Stash => vec!["stash", "push", "--include-untracked"],
+            GitAction::Switch(branch) => vec!["switch", branch],
+        }
+    }
+
+    /// 显示用的命令（如 `git pull --ff-only`）
+    pub fn command_line(&self) -> String {
+        format!("git {}", self.args().join(" "))
+    }
+}
+
+/// 查找项目的 Git 目录（支持 worktree 和子模块中 .git 为文件的情况）
+pub fn git_dir(project_path: &Path) -> Option<PathBuf> {
+    let dot_git = project_path.join(".git");
+    if dot_git.is_dir() {
+        return Some(dot_git);
+    }
+    let content = std::fs::read_to_string(&dot_git).ok()?;
+    let dir = content.trim().strip_prefix("gitdir:")?.trim();
+    let dir = Path::new(dir);
+    Some(if dir.is_absolute() {
+        dir.to_path_buf()
+    } else {
+        project_path.join(dir)
+    })
+}
+
+/// 获取当前分支名，分离 HEAD 时返回提交哈希的前 7 位
+pub fn current_branch(project_path: &Path) -> Option<String> {
+    let head = std::fs::read_to_string(git_dir(project_path)?.join("HEAD")).ok()?;
+    parse_head(&head)
+}
+
+/// 仓库的一个工作树（`git worktree list`）
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GitWorktree {
+    pub path: PathBuf,
+    /// 检出的分支（分离 HEAD 时为提交哈希的前 7 位）
+    pub branch: String,
+    /// 是否为主工作树
+    pub is_main: bool,
+}
+
+/// 列出项目所在仓库的所有工作树（第一个为主工作树）
+pub fn list_worktrees(project_path: &Path) -> Vec<GitWorktree> {
+    git_command(project_path)
+        .args(["worktree", "list", "--porcelain"])
+        .output()
+        .map(|output| parse_worktrees(&String::from_utf8_lossy(&output.stdout)))
+        .unwrap_or_default()
+}
+
+/// 为分支创建新的工作树：分支已存在（本地或远程）时直接检出，否则从当前 HEAD 创建
+/// 会阻塞，需在后台线程中调用
+pub fn add_worktree(project_path: &Path, path: &Path, branch: &str) -> Result<(), String> {
+    let exists = git_command(project_path)
+        .args([
+            "for-each-ref",
+            "--format=%(refname)",
+            "refs/heads",
+            "refs/remotes",
+        ])
+        .output()
+        .map(|output| has_branch(&String::from_utf8_lossy(&output.stdout), branch))
+        .unwrap_or(false);
+
+    let mut command = git_command(project_path);
+    command.args(["worktree", "add"]);
+    if exists {
+        command.arg(path).arg(branch);
+    } else {
+        command.args(["-b", branch]).arg(path);
+    }
+    let output = command.output().map_err(|e| e.to_string())?;
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(String::from_utf8_lossy(&output.stderr)
+            .lines()
+            .rev()
+            .find(|line| !line.trim().is_empty())
+            .unwrap_or_default()
+            .trim()
+            .to_string())
+    }
+}
+
+/// 完整引用名列表中是否有该分支：`refs/heads/<分支>` 或 `refs/remotes/<远程>/<分支>`
+fn has_branch(refs: &str, branch: &str) -> bool {
+    refs.lines().any(|r| {
+        r.strip_prefix("refs/heads/") == Some(branch)
+            || r.strip_prefix("refs/remotes/")
+                .and_then(|r| r.split_once('/'))
+                .is_some_and(|(_, name)| name == branch)
+    })
+}
+
+/// 新工作树的默认路径：与主工作树同级，目录名为 `<仓库目录名>-<分支名>`，已存在时追加序号
+pub fn default_worktree_path(main_path: &Path, branch: &str) -> PathBuf {
+    let name = main_path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let branch: String = branch
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() || c == '-' || c == '.' {
+                c
+            } else {
+                '-'
+            }
+        })
+        .collect();
+    let parent = main_path.parent().unwrap_or(main_path);
+    let base = parent.join(format!("{}-{}", name, branch));
+    let mut path = base.clone();
+    let mut n = 2;
+    while path.exists() {
+        path = PathBuf::from(format!("{}-{}", base.to_string_lossy(), n));
+        n += 1;
+    }
+    path
+}
+
+/// 影响仓库状态的文件的修改时间（HEAD、index、FETCH_HEAD），用于判断是否需要刷新
+pub fn git_signature(project_path: &Path) -> Vec<Option<SystemTime>> {
+    let Some(dir) = git_dir(project_path) else {
+        return Vec::new();
+    };
+    ["HEAD", "index", "FETCH_HEAD"]
+        .iter()
+        .map(|name| {
+            std::fs::metadata(dir.join(name))
+                .and_then(|m| m.modified())
+                .ok()
+        })
+        .collect()
+}
+
+/// 读取仓库状态（不是 Git 仓库或 git 命令失败时返回 None，会阻塞，需在后台线程中调用）
+pub fn read_git_status(project_path: &Path) -> Option<GitStatus> {
+    git_dir(project_path)?;
+    let output = git_command(project_path)
+        .args(["status", "--porcelain=v2", "--branch"])
+        // 只读取状态，不抢占 index.lock
+        .env("GIT_OPTIONAL_LOCKS", "0")
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    Some(parse_status(&String::from_utf8_lossy(&output.stdout)))
+}
+
+/// 本地分支列表
+pub fn local_branches(project_path: &Path) -> Vec<String> {
+    git_command(project_path)
+        .args(["for-each-ref", "--format=%(refname:short)", "refs/heads"])
+        .output()
+        .map(|output| {
+            String::from_utf8_lossy(&output.stdout)
+                .lines()
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// 执行快捷操作，返回 git 输出的最后一行（会阻塞，需在后台线程中调用）
+pub fn run_git_action(project_path: &Path, action: &GitAction) -> Result<String, String> {
+    let output = git_command(project_path)
+        .args(action.args())
+        .output()
+        .map_err(|e| e.to_string())?;
+    let last_line = |bytes: &[u8]| {
+        String::from_utf8_lossy(bytes)
+            .lines()
+            .rev()
+            .map(str::trim)
+            .find(|line| !line.is_empty())
+            .unwrap_or_default()
+            .to_string()
+    };
+    if output.status.success() {
+        // git fetch/switch 把进度信息输出到 stderr
+        let stdout = last_line(&output.stdout);
+        Ok(if stdout.is_empty() {
+            last_line(&output.stderr)
+        } else {
+            stdout
+        })
+    } else {
+        Err(last_line(&output.stderr))
+    }
+}
+
+/// 在项目目录中运行的 git 命令（禁止交互式询问凭据，避免后台线程卡住）
+fn git_command(project_path: &Path) -> Command {
+    let mut command = Command::new("git");
+    command
+        .arg("-C")
+        .arg(project_path)
+        .env("GIT_TERMINAL_PROMPT", "0")
+        .stdin(Stdio::null());
+    command
+}
+
+/// 解析 `git worktree list --porcelain` 的输出（每个工作树一段，空行分隔）
+fn parse_worktrees(output: &str) -> Vec<GitWorktree> {
+    let mut worktrees = Vec::new();
+    for block in output.split("\n\n") {
+        let mut path = None;
+        let mut branch = String::new();
+        let mut bare = false;
+        for line in block.lines() {
+            let (key, value) = line.split_once(' ').unwrap_or((line, ""));
+            match key {
+                "worktree" => path = Some(PathBuf::from(value)),
+                "HEAD" if branch.is_empty() => branch = value.chars().take(7).collect(),
+                "branch" => {
+                    branch = value
+                        .strip_prefix("refs/heads/")
+                        .unwrap_or(value)
+                        .to_string()
+                }
+                "bare" => bare = true,
+                _ => {}
+            }
+        }
+        if let (Some(path), false) = (path, bare) {
+            worktrees.push(GitWorktree {
+                path,
+                branch,
+                is_main: worktrees.is_empty(),
+            });
+        }
+    }
+    worktrees
+}
+
+/// 解析 `git status --porcelain=v2 --branch` 的输出
+fn parse_status(output: &str) -> GitStatus {
+    let mut status = GitStatus::default();
+    let mut oid = String::new();
+    for line in output.lines() {
+        if let Some(header) = line.strip_prefix("# ") {
+            let (key, value) = header.split_once(' ').unwrap_or((header, ""));
+            match key {
+                "branch.oid" => oid = value.to_string(),
+                "branch.head" => status.branch = value.to_string(),
+                "branch.upstream" => status.upstream = Some(value.to_string()),
+                "branch.ab" => {
+                    for part in value.split_whitespace() {
+                        if let Some(n) = part.strip_prefix('+') {
+                            status.ahead = n.parse().unwrap_or(0);
+                        } else if let Some(n) = part.strip_prefix('-') {
+                            status.behind = n.parse().unwrap_or(0);
+                        }
+                    }
+                }
+                _ => {}
+            }
+            continue;
+        }
+        // 普通变更 8 个字段后是路径，重命名多一个相似度字段（路径后用 Tab 分隔原路径），冲突有 10 个字段
+        let (kind, rest) = line.split_at(line.len().min(2));
+        let fields = match kind.trim_end() {
+            "1" => 8,
+            "2" => 9,
+            "u" => 10,
+            "?" => {
+                status.files.push(GitFileChange {
+                    index: '?',
+                    worktree: '?',
+                    path: rest.to_string(),
+                });
+                continue;
+            }
+            _ => continue,
+        };
+        let mut parts = rest.splitn(fields, ' ');
+        let xy: Vec<char> = parts.next().unwrap_or_default().chars().collect();
+        let Some(path) = parts.nth(fields - 2) else {
+            continue;
+        };
+        let path = path.split('\t').next().unwrap_or(path);
+        let code = |c: Option<&char>| match c {
+            Some('.') | None => ' ',
+            Some(c) => *c,
+        };
+        status.files.push(GitFileChange {
+            index: code(xy.first()),
+            worktree: code(xy.get(1)),
+            path: path.to_string(),
+        });
+    }
+    if status.branch == "(detached)" {
+        status.branch = oid.chars().take(7).collect();
+    }
+    status
+}
+
+/// 解析 HEAD 文件内容
+fn parse_head(head: &str) -> Option<String> {
+    let head = head.trim();
+    match head.strip_prefix("ref:") {
+        Some(reference) => {
+            let reference = reference.trim();
+            Some(
+                reference
+                    .strip_prefix("refs/heads/")
+                    .unwrap_or(reference)
+                    .to_string(),
+            )
+        }
+        None if head.len() >= 7 => Some(head[..7].to_string()),
+        None => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_head() {
+        assert_eq!(
+            parse_head("ref: refs/heads/feature/login\n"),
+            Some("feature/login".to_string())
+        );
+        assert_eq!(
+            parse_head("3f2a9c1d0e4b5a6978877665544332211aabbccd\n"),
+            Some("3f2a9c1".to_string())
+        );
+        assert_eq!(parse_head(""), None);
+    }
+
+    #[test]
+    fn test_has_branch() {
+        let refs = "refs/heads/main\nrefs/heads/feature/login\nrefs/remotes/origin/fix\nrefs/remotes/origin/team/fix\n";
+        assert!(has_branch(refs, "main"));
+        assert!(has_branch(refs, "feature/login"));
+        assert!(has_branch(refs, "fix"));
+        assert!(has_branch(refs, "team/fix"));
+        assert!(!has_branch(refs, "login"));
+        assert!(!has_branch(refs, "origin/fix"));
+        assert!(!has_branch(refs, "heads/main"));
+    }
+
+    #[test]
+    fn test_parse_status() {
+        let output = "\
+# branch.oid 3f2a9c1d0e4b5a6978877665544332211aabbccd
+# branch.head feature/login
+# branch.upstream origin/feature/login
+# branch.ab +2 -1
+1 .M N... 100644 100644 100644 aaa bbb src/app.ts
+1 A. N... 000000 100644 100644 000 ccc src/new file.ts
+2 R. N... 100644 100644 100644 ddd eee R100 src/renamed.ts\tsrc/old.ts
+u UU N... 100644 100644 100644 100644 fff ggg hhh src/conflict.ts
+? notes.md
+";
+        let status = parse_status(output);
+        assert_eq!(status.branch, "feature/login");
+        assert_eq!(status.upstream.as_deref(), Some("origin/feature/login"));
+        assert_eq!(status.ahead_behind(), "↑2↓1");
+        let files: Vec<_> = status
+            .files
+            .iter()
+            .map(|f| (f.code(), f.path.as_str()))
+            .collect();
+        assert_eq!(
+            files,
+            vec![
+                (" M".to_string(), "src/app.ts"),
+                ("A ".to_string(), "src/new file.ts"),
+                ("R ".to_string(), "src/renamed.ts"),
+                ("UU".to_string(), "src/conflict.ts"),
+                ("??".to_string(), "notes.md"),
+            ]
+        );
+
+        let worktrees = parse_worktrees(
+            "worktree /work/web\nHEAD 3f2a9c1d0e4b\nbranch refs/heads/main\n\n\
+             worktree /work/web-review\nHEAD 8d7e6f5a4b3c\ndetached\n\n\
+             worktree /work/web-fix\nHEAD 1a2b3c4d5e6f\nbranch refs/heads/fix/login\nlocked\n",
+        );
+        let summary: Vec<_> = worktrees
+            .iter()
+            .map(|w| {
+                (
+                    w.path.to_string_lossy().to_string(),
+                    w.branch.as_str(),
+                    w.is_main,
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("/work/web".to_string(), "main", true),
+                ("/work/web-review".to_string(), "8d7e6f5", false),
+                ("/work/web-fix".to_string(), "fix/login", false),
+            ]
+        );
+
+        let detached = parse_status("# branch.oid 3f2a9c1d0e4b\n# branch.head (detached)\n");
+        assert_eq!(detached.branch, "3f2a9c1");
+        assert!(!detached.is_dirty());
+        assert_eq!(detached.ahead_behind(), "");
+    }
+}
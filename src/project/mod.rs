@@ -1,59 +1,197 @@
 //! 项目管理模块
 //! 负责项目数据结构、package.json 解析和项目扫描
 
+mod composite;
+mod git;
+mod node;
 mod package;
+mod placeholder;
+mod problems;
 #[allow(dead_code)]
 mod scanner;
+mod shell;
 
+pub use composite::*;
+pub use git::*;
+pub use node::*;
 pub use package::*;
+pub use placeholder::*;
+pub use problems::*;
 #[allow(unused_imports)]
 pub use scanner::*;
+pub use shell::*;
 
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::path::PathBuf;
-use std::time::Instant;
+use std::time::{Instant, SystemTime};
+use tokio::sync::mpsc::UnboundedSender;
 
-use crate::pty::PtyHandle;
+use crate::config::{
+    load_project_local_config, save_project_local_config, RunTarget, PROJECT_CONFIG_FILE_NAME,
+};
+use crate::pty::{PtyEvent, PtyHandle, ResourceLimits, StopStep};
 
 /// 命令类型枚举
 /// - NpmScript: 通过包管理器执行的 npm scripts
-/// - RawShell: 直接在 Shell 中执行的原始命令
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+/// - RawShell: 直接在 Shell 中执行的原始命令（手写配置省略类型时的默认值）
+/// - Sequence: 依次运行 command 中列出的命令（`lint -> typecheck -> test`）
+/// - Parallel: 同时运行 command 中列出的命令（`dev:web + dev:api`）
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
 pub enum CommandType {
     NpmScript,
+    #[default]
     RawShell,
+    Sequence,
+    Parallel,
+}
+
+impl CommandType {
+    /// 组合命令的组合方式（普通命令为 None）
+    pub fn composite_mode(&self) -> Option<CompositeMode> {
+        match self {
+            CommandType::Sequence => Some(CompositeMode::Sequence),
+            CommandType::Parallel => Some(CompositeMode::Parallel),
+            CommandType::NpmScript | CommandType::RawShell => None,
+        }
+    }
 }
 
 /// 命令条目
 /// 用于存储 npm scripts 或用户自定义命令
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct CommandEntry {
+    #[serde(default = "new_command_id")]
     pub id: String,
     pub name: String,
     pub command: String,
+    #[serde(default)]
     pub cmd_type: CommandType,
+    /// 工作目录（相对项目根目录，为空时使用项目根目录）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cwd: Option<String>,
+    /// 固定的运行位置（为空时由打开命令面板的方式决定）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target: Option<RunTarget>,
+    /// 额外的环境变量
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub env: BTreeMap<String, String>,
+    /// 说明（显示在命令面板中）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// 停止时依次发送的信号（为空时使用全局设置）
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub stop: Vec<StopStep>,
+}
+
+/// 比较两组命令的内容是否相同
+/// 忽略 id：手写配置省略 id 时每次加载都会重新生成
+pub fn same_commands(a: &[CommandEntry], b: &[CommandEntry]) -> bool {
+    a.len() == b.len()
+        && a.iter().zip(b).all(|(a, b)| {
+            CommandEntry {
+                id: String::new(),
+                ..a.clone()
+            } == CommandEntry {
+                id: String::new(),
+                ..b.clone()
+            }
+        })
+}
+
+/// 命令列表中的分组（命令面板中用不同标签区分）
+/// 不同分组可以有同名命令，运行历史和占位符回答按分组和名称一起记录
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default, Serialize, Deserialize,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum CommandGroup {
+    /// package.json scripts（没有记录分组的旧历史按脚本处理）
+    #[default]
+    Script,
+    /// 项目的个人命令
+    Personal,
+    /// 项目共享命令（.devpanel.json）
+    Shared,
+    /// 所有项目共用的全局命令
+    Global,
+}
+
+/// 可编辑命令的来源
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CommandSource {
+    /// 个人命令（保存在 ~/.devpanel/config.json）
+    Personal,
+    /// 项目共享命令（保存在项目的 .devpanel.json）
+    Shared,
+}
+
+impl CommandSource {
+    /// 另一个来源（用于在两者之间转换）
+    pub fn other(&self) -> Self {
+        match self {
+            CommandSource::Personal => CommandSource::Shared,
+            CommandSource::Shared => CommandSource::Personal,
+        }
+    }
+}
+
+/// 生成新的命令 ID（手写配置省略 id 时使用）
+fn new_command_id() -> String {
+    uuid::Uuid::new_v4().to_string()
 }
 
 impl CommandEntry {
     /// 创建新的 NpmScript 类型命令
     pub fn new_npm_script(name: &str, command: &str) -> Self {
         Self {
-            id: uuid::Uuid::new_v4().to_string(),
-            name: name.to_string(),
-            command: command.to_string(),
             cmd_type: CommandType::NpmScript,
+            ..Self::new_raw_shell(name, command)
         }
     }
 
     /// 创建新的 RawShell 类型命令
     pub fn new_raw_shell(name: &str, command: &str) -> Self {
         Self {
-            id: uuid::Uuid::new_v4().to_string(),
+            id: new_command_id(),
             name: name.to_string(),
             command: command.to_string(),
             cmd_type: CommandType::RawShell,
+            cwd: None,
+            target: None,
+            env: BTreeMap::new(),
+            description: None,
+            stop: Vec::new(),
+        }
+    }
+}
+
+/// package.json scripts 重新加载前后的差异统计
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct ScriptsDiff {
+    pub added: usize,
+    pub removed: usize,
+    pub changed: usize,
+}
+
+impl ScriptsDiff {
+    /// 比较新旧 scripts
+    pub fn between(old: &HashMap<String, String>, new: &HashMap<String, String>) -> Self {
+        let mut diff = Self::default();
+        for (name, command) in new {
+            match old.get(name) {
+                None => diff.added += 1,
+                Some(old_command) if old_command != command => diff.changed += 1,
+                Some(_) => {}
+            }
         }
+        diff.removed = old.keys().filter(|name| !new.contains_key(*name)).count();
+        diff
+    }
+
+    /// 是否没有任何变化
+    pub fn is_empty(&self) -> bool {
+        self.added == 0 && self.removed == 0 && self.changed == 0
     }
 }
 
@@ -71,16 +209,44 @@ pub struct Project {
     pub scripts: HashMap<String, String>,
     /// 用户自定义命令
     pub custom_commands: Vec<CommandEntry>,
+    /// 项目本地配置（.devpanel.json）中的命令
+    pub local_commands: Vec<CommandEntry>,
     /// Dev Server PTY 句柄
     pub dev_pty: Option<PtyHandle>,
-    /// 交互式 Shell PTY 句柄
-    pub shell_pty: Option<PtyHandle>,
+    /// 在 Dev Terminal 中运行的组合命令（与 dev_pty 互斥）
+    pub composite: Option<CompositeRun>,
+    /// 正在按停止序列停止的进程（进程树退出后移除）
+    pub stopping: Vec<PtyHandle>,
+    /// 交互式 Shell 标签页
+    pub shells: Vec<ShellTab>,
+    /// 已关闭的 Shell 标签中正在停止的进程（进程树退出后移除）
+    pub stopping_shells: Vec<PtyHandle>,
+    /// 当前选中的 Shell 标签
+    pub active_shell_idx: usize,
     /// Dev Terminal 滚动偏移量（用于查看历史 log）
     pub dev_scroll_offset: usize,
     /// Shell Terminal 滚动偏移量（用于查看历史）
     pub shell_scroll_offset: usize,
     /// Dev Server 启动时间
     pub dev_started_at: Option<Instant>,
+    /// 从 Dev 进程输出中解析出的问题
+    pub problems: ProblemCollector,
+    /// Dev 输出中最近出现的本机访问地址（如 `http://localhost:5173/`）
+    pub dev_url: Option<String>,
+    /// Git 仓库状态（后台刷新，不是 Git 仓库时为 None）
+    pub git: Option<GitStatus>,
+    /// 作为另一个项目的工作树添加时，指向该项目的路径
+    pub worktree_of: Option<PathBuf>,
+    /// 依赖安装状态（定期检查）
+    pub dependencies: DependencyState,
+    /// 用户拒绝或确认安装依赖时 lock 文件的修改时间（lock 文件再次变化前不再提示过期）
+    pub dependencies_acknowledged: Option<SystemTime>,
+    /// Node 版本检查结果（后台刷新，项目没有固定 Node 版本时为 None）
+    pub node: Option<NodeCheck>,
+    /// 包管理器未安装时用户选择的替代启动方式
+    pub runner_fallback: Option<RunnerFallback>,
+    /// 项目自己的资源上限（为 None 时使用全局配置）
+    pub resource_limits: Option<ResourceLimits>,
 }
 
 impl Project {
@@ -98,17 +264,33 @@ impl Project {
             alias: None,
             scripts: HashMap::new(),
             custom_commands: Vec::new(),
+            local_commands: Vec::new(),
             dev_pty: None,
-            shell_pty: None,
+            composite: None,
+            stopping: Vec::new(),
+            shells: Vec::new(),
+            stopping_shells: Vec::new(),
+            active_shell_idx: 0,
             dev_scroll_offset: 0,
             shell_scroll_offset: 0,
             dev_started_at: None,
+            problems: ProblemCollector::default(),
+            dev_url: None,
+            git: None,
+            worktree_of: None,
+            dependencies: DependencyState::default(),
+            dependencies_acknowledged: None,
+            node: None,
+            runner_fallback: None,
+            resource_limits: None,
         }
     }
 
-    /// 记录 Dev Server 启动时间
+    /// 记录 Dev Server 启动时间（同时清空上一次运行的问题）
     pub fn mark_dev_started(&mut self) {
         self.dev_started_at = Some(Instant::now());
+        self.problems.clear();
+        self.dev_url = None;
     }
 
     /// 清除 Dev Server 启动时间
@@ -116,6 +298,43 @@ impl Project {
         self.dev_started_at = None;
     }
 
+    /// 停止 Dev Terminal 中的进程（包括组合命令的所有步骤）
+    /// 仍在运行的进程在后台按停止序列停止，进程树退出前保留在 stopping 中
+    /// 返回是否有进程被停止
+    pub fn stop_dev(&mut self, defaults: &[StopStep], tx: &UnboundedSender<PtyEvent>) -> bool {
+        let was_running = self.is_dev_running();
+        let steps = self
+            .composite
+            .take()
+            .into_iter()
+            .flat_map(|run| run.steps)
+            .filter_map(|step| step.pty);
+        for mut pty in self.dev_pty.take().into_iter().chain(steps) {
+            if pty.running && pty.pid.is_some() {
+                pty.stop(defaults, tx.clone());
+                self.stopping.push(pty);
+            }
+        }
+        self.mark_dev_stopped();
+        was_running
+    }
+
+    /// 是否有进程正在停止
+    pub fn is_stopping(&self) -> bool {
+        !self.stopping.is_empty()
+    }
+
+    /// 停止中的进程树已退出，返回是否属于该项目
+    pub fn finish_stopping(&mut self, pty_id: &str) -> bool {
+        let Some(idx) = self.stopping.iter().position(|pty| pty.id == pty_id) else {
+            return false;
+        };
+        // 进程树已退出，Drop 时不需要再终止（避免终止被复用的 PID）
+        let mut pty = self.stopping.remove(idx);
+        pty.pid = None;
+        true
+    }
+
     /// 获取显示名称（优先使用别名）
     pub fn display_name(&self) -> &str {
         self.alias.as_deref().unwrap_or(&self.name)
@@ -126,7 +345,7 @@ impl Project {
         self.alias = alias.filter(|s| !s.trim().is_empty());
     }
 
-    /// 从路径加载项目，自动解析 package.json
+    /// 从路径加载项目，自动解析 package.json 和项目本地配置
     pub fn load(path: PathBuf) -> anyhow::Result<Self> {
         let mut project = Self::new(path.clone());
 
@@ -139,12 +358,55 @@ impl Project {
             }
         }
 
+        // 项目本地配置解析失败不影响项目加载
+        match load_project_local_config(&path) {
+            Ok(local) => project.local_commands = local.custom_commands,
+            Err(e) => log::warn!("Failed to load {}: {}", PROJECT_CONFIG_FILE_NAME, e),
+        }
+
         Ok(project)
     }
 
-    /// 获取所有可执行命令（npm scripts + 自定义命令）
+    /// 获取项目需要监听变更的文件（package.json 和项目本地配置）
+    pub fn watched_files(&self) -> [PathBuf; 2] {
+        [
+            self.path.join("package.json"),
+            self.path.join(PROJECT_CONFIG_FILE_NAME),
+        ]
+    }
+
+    /// 重新解析 package.json，只替换 scripts 和名称，不影响运行中的进程
+    pub fn reload_package_json(&mut self) -> anyhow::Result<ScriptsDiff> {
+        let pkg = parse_package_json(&self.path.join("package.json"))?;
+        let scripts = pkg.scripts.unwrap_or_default();
+        let diff = ScriptsDiff::between(&self.scripts, &scripts);
+
+        if let Some(name) = pkg.name {
+            self.name = name;
+        }
+        self.scripts = scripts;
+        Ok(diff)
+    }
+
+    /// 重新加载项目本地配置（.devpanel.json）
+    /// 返回命令列表是否发生变化
+    pub fn reload_local_config(&mut self) -> anyhow::Result<bool> {
+        let local = load_project_local_config(&self.path)?;
+        let changed = !same_commands(&local.custom_commands, &self.local_commands);
+        if changed {
+            self.local_commands = local.custom_commands;
+        }
+        Ok(changed)
+    }
+
+    /// 是否有正在运行的 PTY（Dev Server 或 Shell）
+    pub fn has_running_pty(&self) -> bool {
+        self.is_dev_running() || self.shells.iter().any(|tab| tab.pty.is_some())
+    }
+
+    /// 获取所有可执行命令（npm scripts + 自定义命令 + 项目本地命令 + 全局命令）
     /// npm scripts 按名称字母顺序排序，自定义命令按添加顺序排在后面
-    pub fn get_all_commands(&self) -> Vec<CommandEntry> {
+    pub fn get_all_commands(&self, global_commands: &[CommandEntry]) -> Vec<CommandEntry> {
         // 收集并排序 npm scripts（按名称字母顺序）
         let mut script_names: Vec<_> = self.scripts.keys().collect();
         script_names.sort();
@@ -158,30 +420,149 @@ impl Project {
             })
             .collect();
 
-        // 自定义命令按添加顺序追加
+        // 自定义命令按添加顺序追加，项目本地配置的命令排在最后
         commands.extend(self.custom_commands.clone());
+        commands.extend(self.local_commands.clone());
+        commands.extend(global_commands.iter().cloned());
         commands
     }
 
-    /// 添加自定义命令
-    pub fn add_custom_command(&mut self, name: &str, command: &str) {
-        self.custom_commands
-            .push(CommandEntry::new_raw_shell(name, command));
+    /// 命令在 get_all_commands() 中所属的分组
+    pub fn command_group(&self, command_idx: usize) -> CommandGroup {
+        let scripts_end = self.scripts.len();
+        let custom_end = scripts_end + self.custom_commands.len();
+        let local_end = custom_end + self.local_commands.len();
+        if command_idx < scripts_end {
+            CommandGroup::Script
+        } else if command_idx < custom_end {
+            CommandGroup::Personal
+        } else if command_idx < local_end {
+            CommandGroup::Shared
+        } else {
+            CommandGroup::Global
+        }
+    }
+
+    /// 生成命令实际执行的命令行
+    /// npm scripts 通过包管理器运行，自定义命令替换占位符（answers 为 input/pick 的回答，shell 为执行命令的 Shell）
+    /// 组合命令返回步骤列表本身，各步骤运行时再分别生成
+    pub fn command_line(
+        &self,
+        cmd: &CommandEntry,
+        answers: Option<&BTreeMap<String, String>>,
+        runner: &PackageRunner,
+        shell: &str,
+    ) -> String {
+        match cmd.cmd_type {
+            CommandType::NpmScript => runner.script_command(&cmd.name),
+            CommandType::RawShell => {
+                let project_path = self.path.to_string_lossy();
+                let git_branch = cmd
+                    .command
+                    .contains("${git.branch}")
+                    .then(|| current_branch(&self.path))
+                    .flatten();
+                let ctx = PlaceholderContext {
+                    project_path: &project_path,
+                    project_name: &self.name,
+                    git_branch: git_branch.as_deref(),
+                    package_manager: runner.manager.name(),
+                    answers,
+                    shell,
+                };
+                expand_placeholders(&cmd.command, &ctx)
+            }
+            CommandType::Sequence | CommandType::Parallel => cmd.command.clone(),
+        }
+    }
+
+    /// 命令的工作目录（cwd 为相对路径时相对项目根目录）
+    pub fn command_dir(&self, cmd: &CommandEntry) -> PathBuf {
+        match cmd.cwd.as_deref().map(str::trim) {
+            Some(cwd) if !cwd.is_empty() => self.path.join(cwd),
+            _ => self.path.clone(),
+        }
+    }
+
+    /// 指定来源的可编辑命令
+    pub fn commands(&self, source: CommandSource) -> &Vec<CommandEntry> {
+        match source {
+            CommandSource::Personal => &self.custom_commands,
+            CommandSource::Shared => &self.local_commands,
+        }
+    }
+
+    /// 指定来源的可编辑命令（可变）
+    pub fn commands_mut(&mut self, source: CommandSource) -> &mut Vec<CommandEntry> {
+        match source {
+            CommandSource::Personal => &mut self.custom_commands,
+            CommandSource::Shared => &mut self.local_commands,
+        }
+    }
+
+    /// 保存项目共享命令到 .devpanel.json
+    pub fn save_local_config(&self) -> anyhow::Result<()> {
+        let config = ProjectLocalConfig {
+            custom_commands: self.local_commands.clone(),
+        };
+        save_project_local_config(&self.path, &config)
     }
 
     /// 检查 Dev Server 是否正在运行
     pub fn is_dev_running(&self) -> bool {
-        self.dev_pty.is_some()
+        self.dev_pty.is_some() || self.composite.as_ref().is_some_and(|c| c.is_running())
+    }
+
+    /// Dev Terminal 中的进程：dev_pty 以及组合命令中正在运行的步骤
+    pub fn dev_ptys(&self) -> impl Iterator<Item = &PtyHandle> {
+        self.dev_pty
+            .iter()
+            .chain(self.composite.iter().flat_map(|run| run.running_ptys()))
+    }
+
+    /// Dev Terminal 中的进程（可变）
+    pub fn dev_ptys_mut(&mut self) -> impl Iterator<Item = &mut PtyHandle> {
+        self.dev_pty.iter_mut().chain(
+            self.composite
+                .iter_mut()
+                .flat_map(|run| run.running_ptys_mut()),
+        )
+    }
+
+    /// 组合命令中 ID 匹配的正在运行的步骤名称
+    pub fn step_name(&self, pty_id: &str) -> Option<&str> {
+        self.composite
+            .as_ref()?
+            .steps
+            .iter()
+            .find(|s| s.pty.as_ref().is_some_and(|pty| pty.id == pty_id))
+            .map(|s| s.name.as_str())
     }
 }
 
 /// 用于序列化的项目配置（不包含运行时状态）
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ProjectConfig {
     pub path: String,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub alias: Option<String>,
     pub custom_commands: Vec<CommandEntry>,
+    /// 所属的项目路径（仅工作树项目）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub worktree_of: Option<String>,
+    /// 项目自己的资源上限
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resource_limits: Option<ResourceLimits>,
+    /// Shell 标签页
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub shell_tabs: Vec<ShellTabConfig>,
+}
+
+/// 项目本地配置（位于项目根目录的 .devpanel.json，可随仓库提交共享）
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ProjectLocalConfig {
+    #[serde(default)]
+    pub custom_commands: Vec<CommandEntry>,
 }
 
 impl From<&Project> for ProjectConfig {
@@ -190,6 +571,12 @@ impl From<&Project> for ProjectConfig {
             path: project.path.to_string_lossy().to_string(),
             alias: project.alias.clone(),
             custom_commands: project.custom_commands.clone(),
+            worktree_of: project
+                .worktree_of
+                .as_ref()
+                .map(|p| p.to_string_lossy().to_string()),
+            resource_limits: project.resource_limits.clone(),
+            shell_tabs: project.shell_tab_configs(),
         }
     }
 }
@@ -0,0 +1,413 @@
+//! Node 版本检查
+//! 读取项目固定的 Node 版本（.nvmrc、.node-version、package.json 的 volta 和 engines 字段），
+//! 与项目目录下实际解析到的 node 比较，并可通过版本管理器（fnm、nvm、volta、mise）使用固定的版本
+
+use super::{parse_package_json, PackageJson};
+use crate::platform::command_exists;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+/// Node 版本要求的来源
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NodeSource {
+    Nvmrc,
+    NodeVersion,
+    Volta,
+    Engines,
+}
+
+impl NodeSource {
+    /// 显示名称（文件名或 package.json 字段）
+    pub fn label(&self) -> &'static str {
+        match self {
+            NodeSource::Nvmrc => ".nvmrc",
+            NodeSource::NodeVersion => ".node-version",
+            NodeSource::Volta => "volta.node",
+            NodeSource::Engines => "engines.node",
+        }
+    }
+
+    /// 是否固定了具体版本（engines 通常是版本范围，不交给版本管理器）
+    fn is_pinned(&self) -> bool {
+        !matches!(self, NodeSource::Engines)
+    }
+}
+
+/// 项目要求的 Node 版本
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NodeRequirement {
+    pub source: NodeSource,
+    /// 版本或版本范围（如 `18`、`v20.11.1`、`>=18 <21`、`lts/iron`）
+    pub spec: String,
+}
+
+/// Node 版本管理器
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NodeManager {
+    Fnm,
+    Nvm,
+    Volta,
+    Mise,
+}
+
+impl NodeManager {
+    pub fn name(&self) -> &'static str {
+        match self {
+            NodeManager::Fnm => "fnm",
+            NodeManager::Nvm => "nvm",
+            NodeManager::Volta => "volta",
+            NodeManager::Mise => "mise",
+        }
+    }
+
+    /// 通过版本管理器以指定版本运行 node 的命令
+    fn node_command(&self, spec: &str) -> Command {
+        let mut command = match self {
+            NodeManager::Fnm => {
+                let mut c = Command::new("fnm");
+                c.args(["exec", "--using", spec, "--", "node"]);
+                c
+            }
+            NodeManager::Volta => {
+                let mut c = Command::new("volta");
+                c.args(["run", "--node", spec, "--", "node"]);
+                c
+            }
+            NodeManager::Mise => {
+                let mut c = Command::new("mise");
+                c.args(["exec", &format!("node@{}", spec), "--", "node"]);
+                c
+            }
+            // nvm 是 shell 函数，需要先加载 nvm.sh
+            NodeManager::Nvm => {
+                let mut c = Command::new("bash");
+                c.args([
+                    "-c",
+                    r#". "$NVM_DIR/nvm.sh" >/dev/null && nvm exec --silent "$0" node "$@""#,
+                    spec,
+                ]);
+                c
+            }
+        };
+        command.args(["-p", VERSION_SCRIPT]);
+        command
+    }
+}
+
+/// 输出 node 版本和可执行文件路径
+const VERSION_SCRIPT: &str = "process.version + ' ' + process.execPath";
+
+/// 项目的 Node 版本检查结果
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NodeCheck {
+    pub required: NodeRequirement,
+    /// 实际使用的 node 版本（找不到 node 时为 None）
+    pub current: Option<String>,
+    /// 通过版本管理器解析时使用的管理器
+    pub manager: Option<NodeManager>,
+    /// 版本管理器解析出的 node 所在目录（运行命令时加入 PATH 前面）
+    pub bin_dir: Option<PathBuf>,
+}
+
+impl NodeCheck {
+    /// 实际版本是否不满足要求（无法判断的版本别名如 `lts/*` 不算不满足）
+    pub fn is_mismatch(&self) -> bool {
+        match &self.current {
+            Some(current) => version_satisfies(current, &self.required.spec) == Some(false),
+            None => true,
+        }
+    }
+}
+
+/// 读取项目要求的 Node 版本，优先级：.nvmrc > .node-version > volta > engines
+pub fn read_node_requirement(project_path: &Path) -> Option<NodeRequirement> {
+    for (file, source) in [
+        (".nvmrc", NodeSource::Nvmrc),
+        (".node-version", NodeSource::NodeVersion),
+    ] {
+        let spec = std::fs::read_to_string(project_path.join(file))
+            .ok()
+            .and_then(|content| {
+                content
+                    .lines()
+                    .map(|line| line.split('#').next().unwrap_or_default().trim())
+                    .find(|line| !line.is_empty())
+                    .map(str::to_string)
+            });
+        if let Some(spec) = spec {
+            return Some(NodeRequirement { source, spec });
+        }
+    }
+    let pkg = parse_package_json(&project_path.join("package.json")).ok()?;
+    if let Some(spec) = PackageJson::node_field(&pkg.volta) {
+        return Some(NodeRequirement {
+            source: NodeSource::Volta,
+            spec,
+        });
+    }
+    PackageJson::node_field(&pkg.engines).map(|spec| NodeRequirement {
+        source: NodeSource::Engines,
+        spec,
+    })
+}
+
+/// 检查项目的 Node 版本（会运行外部命令，需在后台线程中调用）
+/// use_manager 为 true 时，固定版本通过检测到的版本管理器解析
+pub fn check_node(project_path: &Path, use_manager: bool) -> Option<NodeCheck> {
+    let required = read_node_requirement(project_path)?;
+    if use_manager && required.source.is_pinned() {
+        let managed = detect_node_managers(required.source)
+            .into_iter()
+            .find_map(|manager| {
+                let mut command = manager.node_command(&required.spec);
+                run_version_script(&mut command, project_path).map(|r| (manager, r))
+            });
+        if let Some((manager, (version, exec_path))) = managed {
+            return Some(NodeCheck {
+                required,
+                current: Some(version),
+                manager: Some(manager),
+                bin_dir: exec_path.parent().map(Path::to_path_buf),
+            });
+        }
+    }
+    let mut command = Command::new("node");
+    command.args(["-p", VERSION_SCRIPT]);
+    let current = run_version_script(&mut command, project_path).map(|(version, _)| version);
+    Some(NodeCheck {
+        required,
+        current,
+        manager: None,
+        bin_dir: None,
+    })
+}
+
+/// 运行输出版本和路径的 node 命令
+fn run_version_script(command: &mut Command, dir: &Path) -> Option<(String, PathBuf)> {
+    let output = command
+        .current_dir(dir)
+        .stdin(Stdio::null())
+        .stderr(Stdio::null())
+        .output()
+        .ok()
+        .filter(|o| o.status.success())?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let (version, exec_path) = stdout.lines().last()?.trim().split_once(' ')?;
+    Some((version.to_string(), PathBuf::from(exec_path)))
+}
+
+/// 已安装的版本管理器（按优先级排列；volta 配置优先使用 volta）
+fn detect_node_managers(source: NodeSource) -> Vec<NodeManager> {
+    let mut managers: Vec<_> = [NodeManager::Fnm, NodeManager::Mise, NodeManager::Volta]
+        .into_iter()
+        .filter(|m| command_exists(m.name()))
+        .collect();
+    #[cfg(unix)]
+    if std::env::var_os("NVM_DIR").is_some_and(|dir| Path::new(&dir).join("nvm.sh").exists()) {
+        managers.push(NodeManager::Nvm);
+    }
+    if source == NodeSource::Volta {
+        managers.sort_by_key(|m| *m != NodeManager::Volta);
+    }
+    managers
+}
+
+/// 把版本管理器解析出的 node 目录加到命令环境变量的 PATH 前面
+pub fn prepend_node_bin(
+    mut env: BTreeMap<String, String>,
+    bin_dir: Option<&Path>,
+) -> BTreeMap<String, String> {
+    let Some(bin_dir) = bin_dir else {
+        return env;
+    };
+    let current = env
+        .get("PATH")
+        .map(Into::into)
+        .or_else(|| std::env::var_os("PATH"))
+        .unwrap_or_default();
+    let paths = std::iter::once(bin_dir.to_path_buf()).chain(std::env::split_paths(&current));
+    if let Ok(joined) = std::env::join_paths(paths) {
+        env.insert("PATH".to_string(), joined.to_string_lossy().to_string());
+    }
+    env
+}
+
+/// 判断版本是否满足 npm 风格的版本范围
+/// 支持 `18`、`18.x`、`^18.2`、`~18.2.0`、`>=18 <21`、`16 || 18`、`18 - 20`，
+/// 无法解析的版本别名（如 `lts/*`、`node`）返回 None
+pub fn version_satisfies(version: &str, range: &str) -> Option<bool> {
+    let version = parse_full(version)?;
+    let mut result = false;
+    for alt in range.split("||") {
+        result |= satisfies_all(version, alt.trim())?;
+    }
+    Some(result)
+}
+
+type Version = (u64, u64, u64);
+
+/// 解析完整版本号（忽略 `v` 前缀和预发布后缀）
+fn parse_full(version: &str) -> Option<Version> {
+    let parts = parse_partial(version)?;
+    Some((parts[0]?, parts[1].unwrap_or(0), parts[2].unwrap_or(0)))
+}
+
+/// 解析可能不完整的版本号（`x`、`*` 和缺少的部分为 None）
+fn parse_partial(version: &str) -> Option<[Option<u64>; 3]> {
+    let version = version.trim().trim_start_matches(['v', '=']);
+    let version = version.split(['-', '+']).next().unwrap_or_default();
+    let mut parts = [None; 3];
+    for (idx, part) in version.split('.').enumerate() {
+        if idx >= 3 {
+            return None;
+        }
+        match part {
+            "x" | "X" | "*" => break,
+            _ => parts[idx] = Some(part.parse().ok()?),
+        }
+    }
+    Some(parts)
+}
+
+/// 同时满足空格分隔的所有比较条件（支持 `a - b` 区间）
+fn satisfies_all(version: Version, range: &str) -> Option<bool> {
+    if range.is_empty() || range == "*" {
+        return Some(true);
+    }
+    if let Some((low, high)) = range.split_once(" - ") {
+        let low = parse_partial(low)?;
+        let high = parse_partial(high)?;
+        return Some(version >= lower_bound(low) && below_upper(version, high));
+    }
+    // 运算符和版本之间允许空格（如 `>= 18`）
+    let mut comparators = Vec::new();
+    let mut op = String::new();
+    for token in range.split_whitespace() {
+        if token.chars().all(|c| "<>=^~".contains(c)) {
+            op.push_str(token);
+        } else {
+            comparators.push(format!("{}{}", op, token));
+            op.clear();
+        }
+    }
+    for comparator in comparators {
+        if !satisfies(version, &comparator)? {
+            return Some(false);
+        }
+    }
+    Some(true)
+}
+
+/// 判断单个比较条件
+fn satisfies(version: Version, comparator: &str) -> Option<bool> {
+    let split = comparator
+        .find(|c: char| !"<>=^~".contains(c))
+        .unwrap_or(comparator.len());
+    let (op, partial) = comparator.split_at(split);
+    let partial = parse_partial(partial)?;
+    let lower = lower_bound(partial);
+    let full = partial.iter().all(Option::is_some);
+    let result = match op {
+        "" | "=" => version >= lower && below_upper(version, partial),
+        ">=" => version >= lower,
+        ">" if full => version > lower,
+        ">" => !below_upper(version, partial),
+        "<" => version < lower,
+        "<=" => below_upper(version, partial),
+        "~" => {
+            let upper = match partial {
+                [Some(major), Some(minor), _] => (major, minor + 1, 0),
+                [Some(major), None, _] => (major + 1, 0, 0),
+                _ => return Some(true),
+            };
+            version >= lower && version < upper
+        }
+        "^" => {
+            let upper = match partial {
+                [Some(0), Some(0), Some(patch)] => (0, 0, patch + 1),
+                [Some(0), Some(minor), _] if minor > 0 => (0, minor + 1, 0),
+                [Some(0), Some(0), None] => (0, 1, 0),
+                [Some(major), _, _] => (major + 1, 0, 0),
+                _ => return Some(true),
+            };
+            version >= lower && version < upper
+        }
+        _ => return None,
+    };
+    Some(result)
+}
+
+/// 不完整版本号的下界（缺少的部分补 0）
+fn lower_bound(partial: [Option<u64>; 3]) -> Version {
+    (
+        partial[0].unwrap_or(0),
+        partial[1].unwrap_or(0),
+        partial[2].unwrap_or(0),
+    )
+}
+
+/// 版本是否不超过不完整版本号表示的范围（如 `<=18` 包含所有 18.x）
+fn below_upper(version: Version, partial: [Option<u64>; 3]) -> bool {
+    match partial {
+        [Some(major), Some(minor), Some(patch)] => version <= (major, minor, patch),
+        [Some(major), Some(minor), None] => version < (major, minor + 1, 0),
+        [Some(major), None, _] => version < (major + 1, 0, 0),
+        _ => true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_version_satisfies() {
+        let cases = [
+            ("v18.17.1", "18", Some(true)),
+            ("v20.11.0", "18", Some(false)),
+            ("v18.17.1", "v18.17.1", Some(true)),
+            ("v18.17.1", "18.16", Some(false)),
+            ("v18.17.1", "18.x", Some(true)),
+            ("v20.11.0", ">=18", Some(true)),
+            ("v16.20.2", ">=18", Some(false)),
+            ("v20.11.0", ">= 18 <21", Some(true)),
+            ("v21.0.0", ">=18 <21", Some(false)),
+            ("v20.11.0", "^18.2.0", Some(false)),
+            ("v18.19.0", "^18.2.0", Some(true)),
+            ("v18.3.0", "~18.2", Some(false)),
+            ("v18.2.9", "~18.2", Some(true)),
+            ("v20.11.0", "16 || 18", Some(false)),
+            ("v18.0.0", "16 || 18", Some(true)),
+            ("v20.11.0", "18 - 20", Some(true)),
+            ("v21.1.0", "18 - 20", Some(false)),
+            ("v20.11.0", "<=20", Some(true)),
+            ("v20.11.0", ">20", Some(false)),
+            ("v22.0.0-nightly", "*", Some(true)),
+            ("v20.11.0", "lts/iron", None),
+            ("v20.11.0", "node", None),
+        ];
+        for (version, range, expected) in cases {
+            assert_eq!(
+                version_satisfies(version, range),
+                expected,
+                "{} satisfies {}",
+                version,
+                range
+            );
+        }
+    }
+
+    #[test]
+    fn test_prepend_node_bin() {
+        let mut env = BTreeMap::new();
+        env.insert("PATH".to_string(), "/usr/bin".to_string());
+        let env = prepend_node_bin(env, Some(Path::new("/opt/node/bin")));
+        let paths: Vec<_> = std::env::split_paths(&env["PATH"]).collect();
+        assert_eq!(
+            paths,
+            vec![PathBuf::from("/opt/node/bin"), PathBuf::from("/usr/bin")]
+        );
+        assert!(prepend_node_bin(BTreeMap::new(), None).is_empty());
+    }
+}
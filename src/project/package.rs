@@ -2,9 +2,11 @@
 
 #![allow(dead_code)]
 
+use crate::platform::command_exists;
 use serde::Deserialize;
 use std::collections::HashMap;
 use std::path::Path;
+use std::time::SystemTime;
 
 /// package.json 结构（仅解析需要的字段）
 #[derive(Debug, Deserialize)]
@@ -12,6 +14,32 @@ pub struct PackageJson {
     pub name: Option<String>,
     pub version: Option<String>,
     pub scripts: Option<HashMap<String, String>>,
+    pub dependencies: Option<HashMap<String, serde_json::Value>>,
+    #[serde(rename = "devDependencies")]
+    pub dev_dependencies: Option<HashMap<String, serde_json::Value>>,
+    /// 运行环境要求（如 `"node": ">=18"`）
+    pub engines: Option<HashMap<String, serde_json::Value>>,
+    /// Volta 固定的工具版本
+    pub volta: Option<HashMap<String, serde_json::Value>>,
+    /// Corepack 使用的包管理器（如 `pnpm@9.1.0+sha512...`）
+    #[serde(rename = "packageManager")]
+    pub package_manager: Option<String>,
+    /// npm / yarn / bun 工作区的子包
+    pub workspaces: Option<serde_json::Value>,
+}
+
+impl PackageJson {
+    /// engines 或 volta 中的 node 版本
+    pub fn node_field(field: &Option<HashMap<String, serde_json::Value>>) -> Option<String> {
+        field.as_ref()?.get("node")?.as_str().map(str::to_string)
+    }
+
+    /// 是否声明了需要安装的依赖
+    pub fn has_dependencies(&self) -> bool {
+        [&self.dependencies, &self.dev_dependencies]
+            .into_iter()
+            .any(|deps| deps.as_ref().is_some_and(|d| !d.is_empty()))
+    }
 }
 
 /// 解析 package.json 文件
@@ -21,9 +49,18 @@ pub fn parse_package_json(path: &Path) -> anyhow::Result<PackageJson> {
     Ok(package)
 }
 
+/// 项目所在的工作区根目录：最近的有 pnpm-workspace.yaml 或 package.json 中声明了 workspaces 的上级目录
+pub fn workspace_root(project_path: &Path) -> Option<&Path> {
+    project_path.ancestors().skip(1).find(|dir| {
+        dir.join("pnpm-workspace.yaml").exists()
+            || parse_package_json(&dir.join("package.json"))
+                .is_ok_and(|pkg| pkg.workspaces.is_some())
+    })
+}
+
 /// 检测项目使用的包管理器
 /// 通过检查 lock 文件来判断
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum PackageManager {
     Npm,
     Yarn,
@@ -51,6 +88,57 @@ impl PackageManager {
         }
     }
 
+    /// 从名称解析包管理器
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.trim() {
+            "npm" => Some(PackageManager::Npm),
+            "yarn" => Some(PackageManager::Yarn),
+            "pnpm" => Some(PackageManager::Pnpm),
+            "bun" => Some(PackageManager::Bun),
+            _ => None,
+        }
+    }
+
+    /// 包管理器名称（用于在 Shell 命令中调用）
+    pub fn name(&self) -> &'static str {
+        match self {
+            PackageManager::Npm => "npm",
+            PackageManager::Yarn => "yarn",
+            PackageManager::Pnpm => "pnpm",
+            PackageManager::Bun => "bun",
+        }
+    }
+
+    /// 安装依赖的命令
+    pub fn install_command(&self) -> &'static str {
+        match self {
+            PackageManager::Npm => "npm install",
+            PackageManager::Yarn => "yarn install",
+            PackageManager::Pnpm => "pnpm install",
+            PackageManager::Bun => "bun install",
+        }
+    }
+
+    /// 包管理器的 lock 文件名（bun 1.2 起使用文本格式的 bun.lock）
+    pub fn lockfiles(&self) -> &'static [&'static str] {
+        match self {
+            PackageManager::Npm => &["package-lock.json"],
+            PackageManager::Yarn => &["yarn.lock"],
+            PackageManager::Pnpm => &["pnpm-lock.yaml"],
+            PackageManager::Bun => &["bun.lock", "bun.lockb"],
+        }
+    }
+
+    /// 安装完成时包管理器在 node_modules 中写入的元数据文件
+    fn install_markers(&self) -> &'static [&'static str] {
+        match self {
+            PackageManager::Npm => &[".package-lock.json"],
+            PackageManager::Yarn => &[".yarn-integrity", ".yarn-state.yml"],
+            PackageManager::Pnpm => &[".modules.yaml"],
+            PackageManager::Bun => &[],
+        }
+    }
+
     /// 获取运行脚本的命令前缀
     pub fn run_prefix(&self) -> &'static str {
         match self {
@@ -62,17 +150,225 @@ impl PackageManager {
     }
 }
 
-/// 检测项目目录使用的包管理器
-pub fn detect_package_manager(project_path: &Path) -> PackageManager {
-    // 优先级：pnpm > yarn > bun > npm
-    if project_path.join("pnpm-lock.yaml").exists() {
-        PackageManager::Pnpm
-    } else if project_path.join("yarn.lock").exists() {
-        PackageManager::Yarn
-    } else if project_path.join("bun.lockb").exists() {
-        PackageManager::Bun
-    } else {
-        PackageManager::Npm
+/// 包管理器未安装时的替代启动方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RunnerFallback {
+    /// 通过 Node 自带的 corepack 启动（仅支持 yarn 和 pnpm）
+    Corepack,
+    /// 通过 npx 下载并启动
+    Npx,
+}
+
+impl RunnerFallback {
+    pub fn name(&self) -> &'static str {
+        match self {
+            RunnerFallback::Corepack => "corepack",
+            RunnerFallback::Npx => "npx",
+        }
+    }
+}
+
+/// 项目运行脚本使用的包管理器及其启动方式
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PackageRunner {
+    pub manager: PackageManager,
+    /// packageManager 字段固定的版本
+    pub version: Option<String>,
+    /// 包管理器未安装时改用的启动方式
+    pub fallback: Option<RunnerFallback>,
+}
+
+impl PackageRunner {
+    /// 检测项目的包管理器：packageManager 字段 > lock 文件 > 默认包管理器设置
+    /// root 为 workspace_root 的结果，工作区子包的 lock 文件在工作区根目录
+    pub fn detect(project_path: &Path, root: Option<&Path>, default_runner: &str) -> Self {
+        let pinned = parse_package_json(&project_path.join("package.json"))
+            .ok()
+            .and_then(|pkg| pkg.package_manager)
+            .and_then(|field| parse_package_manager_field(&field));
+        let (manager, version) = match pinned {
+            Some((manager, version)) => (manager, version),
+            None => {
+                // 优先级：pnpm > yarn > bun > npm
+                let by_lockfile = [
+                    PackageManager::Pnpm,
+                    PackageManager::Yarn,
+                    PackageManager::Bun,
+                    PackageManager::Npm,
+                ]
+                .into_iter()
+                .find(|pm| {
+                    [Some(project_path), root]
+                        .into_iter()
+                        .flatten()
+                        .any(|dir| pm.lockfiles().iter().any(|f| dir.join(f).exists()))
+                });
+                let manager = by_lockfile
+                    .or_else(|| PackageManager::from_name(default_runner))
+                    .unwrap_or(PackageManager::Npm);
+                (manager, None)
+            }
+        };
+        Self {
+            manager,
+            version,
+            fallback: None,
+        }
+    }
+
+    /// 启动包管理器的命令（如 `pnpm`、`corepack pnpm`、`npx --yes pnpm@9.1.0`）
+    pub fn invocation(&self) -> String {
+        let name = self.manager.name();
+        match self.fallback {
+            None => name.to_string(),
+            Some(RunnerFallback::Corepack) => format!("corepack {}", name),
+            Some(RunnerFallback::Npx) => format!(
+                "npx --yes {}@{}",
+                name,
+                self.version.as_deref().unwrap_or("latest")
+            ),
+        }
+    }
+
+    /// 运行 package.json 脚本的命令行
+    pub fn script_command(&self, script: &str) -> String {
+        self.with_invocation(self.manager.run_prefix(), script)
+    }
+
+    /// 安装依赖的命令行
+    pub fn install_command(&self) -> String {
+        self.with_invocation(self.manager.install_command(), "")
+    }
+
+    /// 把以包管理器名称开头的命令前缀换成实际的启动命令
+    fn with_invocation(&self, prefix: &str, args: &str) -> String {
+        let rest = prefix.strip_prefix(self.manager.name()).unwrap_or(prefix);
+        format!("{}{} {}", self.invocation(), rest, args)
+            .trim_end()
+            .to_string()
+    }
+
+    /// 包管理器不可用时可以改用的启动方式（都不可用时为 None）
+    pub fn suggest_fallback(&self) -> Option<RunnerFallback> {
+        let corepack = matches!(self.manager, PackageManager::Yarn | PackageManager::Pnpm)
+            && command_exists("corepack");
+        if corepack {
+            Some(RunnerFallback::Corepack)
+        } else if command_exists("npx") {
+            Some(RunnerFallback::Npx)
+        } else {
+            None
+        }
+    }
+
+    /// 包管理器是否可以启动（已安装或已选择替代方式）
+    pub fn is_available(&self) -> bool {
+        self.fallback.is_some() || command_exists(self.manager.name())
+    }
+}
+
+/// 解析 packageManager 字段（`<name>@<version>[+<hash>]`）
+fn parse_package_manager_field(field: &str) -> Option<(PackageManager, Option<String>)> {
+    let (name, version) = match field.trim().split_once('@') {
+        Some((name, version)) => (name, Some(version)),
+        None => (field.trim(), None),
+    };
+    let manager = PackageManager::from_name(name)?;
+    let version = version
+        .and_then(|v| v.split('+').next())
+        .filter(|v| !v.is_empty())
+        .map(str::to_string);
+    Some((manager, version))
+}
+
+/// 项目依赖的安装状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DependencyState {
+    #[default]
+    Installed,
+    /// 声明了依赖但没有 node_modules
+    Missing,
+    /// node_modules 比 lock 文件旧（例如拉取代码后 lock 文件有变化）
+    Stale,
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+/// 项目的 lock 文件修改时间（工作区子包使用工作区根目录 root 的 lock 文件）
+pub fn lockfile_modified(
+    project_path: &Path,
+    root: Option<&Path>,
+    pm: PackageManager,
+) -> Option<SystemTime> {
+    let in_dir = |dir: &Path| {
+        pm.lockfiles()
+            .iter()
+            .filter_map(|f| modified(&dir.join(f)))
+            .max()
+    };
+    in_dir(project_path).or_else(|| root.and_then(in_dir))
+}
+
+/// 检查项目依赖是否需要重新安装
+/// 比较 lock 文件与 node_modules（及包管理器写入的元数据文件）的修改时间；
+/// 工作区子包的依赖可能被提升到上级目录，项目到工作区根目录之间的 node_modules 都算作已安装。
+/// acknowledged 为用户已拒绝或完成安装时 lock 文件的修改时间，lock 文件没有再变化时不算过期
+pub fn check_dependencies(
+    project_path: &Path,
+    root: Option<&Path>,
+    pm: PackageManager,
+    acknowledged: Option<SystemTime>,
+) -> DependencyState {
+    let dirs: Vec<&Path> = match root {
+        Some(root) => project_path
+            .ancestors()
+            .take_while(|dir| *dir != root)
+            .chain([root])
+            .collect(),
+        None => vec![project_path],
+    };
+    // Yarn Plug'n'Play 不使用 node_modules
+    if dirs.iter().any(|dir| dir.join(".pnp.cjs").exists()) {
+        return DependencyState::Installed;
+    }
+
+    let lockfile = lockfile_modified(project_path, root, pm);
+    let installed = dirs
+        .iter()
+        .map(|dir| dir.join("node_modules"))
+        .flat_map(|node_modules| {
+            pm.install_markers()
+                .iter()
+                .filter_map(|marker| modified(&node_modules.join(marker)))
+                .chain(modified(&node_modules))
+                .collect::<Vec<_>>()
+        })
+        .max();
+    // 只有既没有 lock 文件也没有 node_modules 时才需要解析 package.json
+    let has_dependencies = lockfile.is_none()
+        && installed.is_none()
+        && parse_package_json(&project_path.join("package.json"))
+            .is_ok_and(|pkg| pkg.has_dependencies());
+    dependency_state(lockfile, installed, has_dependencies, acknowledged)
+}
+
+/// 根据 lock 文件和安装时间判断依赖状态
+fn dependency_state(
+    lockfile: Option<SystemTime>,
+    installed: Option<SystemTime>,
+    has_dependencies: bool,
+    acknowledged: Option<SystemTime>,
+) -> DependencyState {
+    match (lockfile, installed) {
+        (lockfile, None) if lockfile.is_some() || has_dependencies => DependencyState::Missing,
+        (Some(lockfile), Some(installed))
+            if lockfile > installed && acknowledged != Some(lockfile) =>
+        {
+            DependencyState::Stale
+        }
+        _ => DependencyState::Installed,
     }
 }
 
@@ -85,4 +381,113 @@ mod tests {
         assert_eq!(PackageManager::Pnpm.command(), "pnpm");
         assert_eq!(PackageManager::Yarn.command(), "yarn");
     }
+
+    #[test]
+    fn test_package_runner() {
+        assert_eq!(
+            parse_package_manager_field("pnpm@9.1.0+sha512.abcdef"),
+            Some((PackageManager::Pnpm, Some("9.1.0".to_string())))
+        );
+        assert_eq!(
+            parse_package_manager_field("yarn"),
+            Some((PackageManager::Yarn, None))
+        );
+        assert_eq!(parse_package_manager_field("deno@2.0.0"), None);
+
+        let mut runner = PackageRunner {
+            manager: PackageManager::Pnpm,
+            version: Some("9.1.0".to_string()),
+            fallback: None,
+        };
+        assert_eq!(runner.script_command("dev"), "pnpm dev");
+        runner.fallback = Some(RunnerFallback::Corepack);
+        assert_eq!(runner.script_command("dev"), "corepack pnpm dev");
+        runner.fallback = Some(RunnerFallback::Npx);
+        assert_eq!(runner.install_command(), "npx --yes pnpm@9.1.0 install");
+
+        let npm = PackageRunner {
+            manager: PackageManager::Npm,
+            version: None,
+            fallback: Some(RunnerFallback::Npx),
+        };
+        assert_eq!(
+            npm.script_command("build"),
+            "npx --yes npm@latest run build"
+        );
+    }
+
+    #[test]
+    fn test_dependency_state() {
+        use std::time::Duration;
+        let older = SystemTime::UNIX_EPOCH + Duration::from_secs(100);
+        let newer = SystemTime::UNIX_EPOCH + Duration::from_secs(200);
+
+        assert_eq!(
+            dependency_state(Some(older), Some(newer), true, None),
+            DependencyState::Installed
+        );
+        // 拉取代码后 lock 文件比 node_modules 新
+        assert_eq!(
+            dependency_state(Some(newer), Some(older), true, None),
+            DependencyState::Stale
+        );
+        assert_eq!(
+            dependency_state(Some(older), None, false, None),
+            DependencyState::Missing
+        );
+        assert_eq!(
+            dependency_state(None, None, true, None),
+            DependencyState::Missing
+        );
+        // 没有依赖也没有 lock 文件，不需要安装
+        assert_eq!(
+            dependency_state(None, None, false, None),
+            DependencyState::Installed
+        );
+        // 用户已拒绝或完成安装，lock 文件没有再变化
+        assert_eq!(
+            dependency_state(Some(newer), Some(older), true, Some(newer)),
+            DependencyState::Installed
+        );
+        assert_eq!(
+            dependency_state(Some(newer), Some(older), true, Some(older)),
+            DependencyState::Stale
+        );
+    }
+
+    #[test]
+    fn test_workspace_dependencies() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        let package = root.join("packages").join("web");
+        std::fs::create_dir_all(&package).unwrap();
+        std::fs::write(
+            root.join("package.json"),
+            r#"{"workspaces": ["packages/*"]}"#,
+        )
+        .unwrap();
+        std::fs::write(
+            package.join("package.json"),
+            r#"{"dependencies": {"react": "^18.0.0"}}"#,
+        )
+        .unwrap();
+        std::fs::write(root.join("yarn.lock"), "").unwrap();
+
+        let workspace = workspace_root(&package);
+        assert_eq!(workspace, Some(root));
+        assert_eq!(
+            PackageRunner::detect(&package, workspace, "npm").manager,
+            PackageManager::Yarn
+        );
+        assert_eq!(
+            check_dependencies(&package, workspace, PackageManager::Yarn, None),
+            DependencyState::Missing
+        );
+        // 依赖被提升到工作区根目录的 node_modules
+        std::fs::create_dir(root.join("node_modules")).unwrap();
+        assert_eq!(
+            check_dependencies(&package, workspace, PackageManager::Yarn, None),
+            DependencyState::Installed
+        );
+    }
 }
@@ -0,0 +1,224 @@
+//! 命令占位符模块
+//! 解析自定义命令中的 `${...}` 占位符，并在运行前替换为实际值
+//!
+//! 支持的占位符：
+//! - `${input:标签}`：运行前弹出输入框
+//! - `${pick:标签:a,b,c}`：运行前弹出选择列表
+//! - `${project.path}` / `${project.name}`：当前项目路径和名称
+//! - `${git.branch}`：当前 Git 分支
+//! - `${pm}`：项目使用的包管理器（npm/yarn/pnpm/bun）
+//!
+//! 其他 `${...}`（如 Shell 变量 `${HOME}`）原样保留
+//! 替换进命令行的值按目标 Shell 的语法加引号，路径中的空格和回答中的 `;`、`$(...)` 不会被 Shell 解释
+
+use crate::platform::shell_quote;
+use std::collections::BTreeMap;
+
+/// 需要用户回答的占位符
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Prompt {
+    /// 文本输入
+    Input { label: String },
+    /// 从固定选项中选择
+    Pick { label: String, options: Vec<String> },
+}
+
+impl Prompt {
+    /// 标签（同时作为记住答案时的键）
+    pub fn label(&self) -> &str {
+        match self {
+            Prompt::Input { label } | Prompt::Pick { label, .. } => label,
+        }
+    }
+}
+
+/// 替换占位符时使用的上下文
+#[derive(Debug, Clone, Default)]
+pub struct PlaceholderContext<'a> {
+    pub project_path: &'a str,
+    pub project_name: &'a str,
+    pub git_branch: Option<&'a str>,
+    pub package_manager: &'a str,
+    /// 用户对 input/pick 占位符的回答（键为标签）
+    pub answers: Option<&'a BTreeMap<String, String>>,
+    /// 执行命令的 Shell（决定引号语法）
+    pub shell: &'a str,
+}
+
+/// 单个占位符
+enum Placeholder {
+    Prompt(Prompt),
+    ProjectPath,
+    ProjectName,
+    GitBranch,
+    PackageManager,
+}
+
+/// 解析 `${...}` 内部的内容，不认识的返回 None
+fn parse_placeholder(body: &str) -> Option<Placeholder> {
+    match body {
+        "project.path" => return Some(Placeholder::ProjectPath),
+        "project.name" => return Some(Placeholder::ProjectName),
+        "git.branch" => return Some(Placeholder::GitBranch),
+        "pm" => return Some(Placeholder::PackageManager),
+        _ => {}
+    }
+    if let Some(label) = body.strip_prefix("input:") {
+        let label = label.trim();
+        if !label.is_empty() {
+            return Some(Placeholder::Prompt(Prompt::Input {
+                label: label.to_string(),
+            }));
+        }
+    }
+    if let Some(rest) = body.strip_prefix("pick:") {
+        let (label, options) = rest.split_once(':')?;
+        let options: Vec<String> = options
+            .split(',')
+            .map(|o| o.trim().to_string())
+            .filter(|o| !o.is_empty())
+            .collect();
+        if !label.trim().is_empty() && !options.is_empty() {
+            return Some(Placeholder::Prompt(Prompt::Pick {
+                label: label.trim().to_string(),
+                options,
+            }));
+        }
+    }
+    None
+}
+
+/// 依次处理命令中的占位符，回调返回 Some 时替换，None 时原样保留
+fn replace_placeholders(
+    command: &str,
+    mut f: impl FnMut(Option<Placeholder>) -> Option<String>,
+) -> String {
+    let mut result = String::with_capacity(command.len());
+    let mut rest = command;
+    while let Some(start) = rest.find("${") {
+        let Some(len) = rest[start + 2..].find('}') else {
+            break;
+        };
+        let raw = &rest[start..start + 2 + len + 1];
+        let body = &rest[start + 2..start + 2 + len];
+        result.push_str(&rest[..start]);
+        match f(parse_placeholder(body)) {
+            Some(value) => result.push_str(&value),
+            None => result.push_str(raw),
+        }
+        rest = &rest[start + raw.len()..];
+    }
+    result.push_str(rest);
+    result
+}
+
+/// 收集命令中需要用户回答的占位符（同一标签只出现一次，按出现顺序）
+pub fn command_prompts(command: &str) -> Vec<Prompt> {
+    let mut prompts: Vec<Prompt> = Vec::new();
+    replace_placeholders(command, |placeholder| {
+        if let Some(Placeholder::Prompt(prompt)) = placeholder {
+            if !prompts.iter().any(|p| p.label() == prompt.label()) {
+                prompts.push(prompt);
+            }
+        }
+        None
+    });
+    prompts
+}
+
+/// 替换命令中的占位符
+/// 没有回答的 input/pick 和无法获取的 Git 分支替换为空字符串（加引号后仍占一个参数）
+/// 包管理器名称是固定的命令名，不加引号（PowerShell 不会执行加了引号的命令名）
+pub fn expand_placeholders(command: &str, ctx: &PlaceholderContext) -> String {
+    replace_placeholders(command, |placeholder| {
+        let value = match placeholder? {
+            Placeholder::Prompt(prompt) => ctx
+                .answers
+                .and_then(|answers| answers.get(prompt.label()))
+                .map(String::as_str)
+                .unwrap_or_default(),
+            Placeholder::ProjectPath => ctx.project_path,
+            Placeholder::ProjectName => ctx.project_name,
+            Placeholder::GitBranch => ctx.git_branch.unwrap_or_default(),
+            Placeholder::PackageManager => return Some(ctx.package_manager.to_string()),
+        };
+        Some(shell_quote(value, ctx.shell))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_command_prompts() {
+        let prompts = command_prompts(
+            "vitest run ${input:Test file} --env ${pick:env:dev, staging,prod} ${input:Test file}",
+        );
+        assert_eq!(
+            prompts,
+            vec![
+                Prompt::Input {
+                    label: "Test file".to_string()
+                },
+                Prompt::Pick {
+                    label: "env".to_string(),
+                    options: vec!["dev".into(), "staging".into(), "prod".into()],
+                },
+            ]
+        );
+        assert!(command_prompts("echo ${HOME} ${pick:env} ${input:}").is_empty());
+    }
+
+    #[test]
+    fn test_expand_placeholders() {
+        let answers = BTreeMap::from([("file".to_string(), "src/a.test.ts".to_string())]);
+        let ctx = PlaceholderContext {
+            project_path: "/work/app",
+            project_name: "app",
+            git_branch: Some("main"),
+            package_manager: "pnpm",
+            answers: Some(&answers),
+            shell: "/bin/zsh",
+        };
+        assert_eq!(
+            expand_placeholders(
+                "cd ${project.path} && ${pm} exec vitest ${input:file} # ${git.branch} ${HOME} ${oops",
+                &ctx
+            ),
+            "cd '/work/app' && pnpm exec vitest 'src/a.test.ts' # 'main' ${HOME} ${oops"
+        );
+        assert_eq!(
+            expand_placeholders(
+                "deploy ${pick:env:a,b} ${git.branch}",
+                &PlaceholderContext::default()
+            ),
+            "deploy '' ''"
+        );
+    }
+
+    #[test]
+    fn test_expand_placeholders_quotes_values() {
+        let answers = BTreeMap::from([("msg".to_string(), "x; echo $(whoami)".to_string())]);
+        let ctx = PlaceholderContext {
+            project_path: "/Users/me/My Projects/app",
+            project_name: "app",
+            answers: Some(&answers),
+            shell: "/bin/bash",
+            ..Default::default()
+        };
+        assert_eq!(
+            expand_placeholders("cd ${project.path} && git commit -m ${input:msg}", &ctx),
+            "cd '/Users/me/My Projects/app' && git commit -m 'x; echo $(whoami)'"
+        );
+
+        let ctx = PlaceholderContext {
+            shell: "pwsh.exe",
+            ..ctx
+        };
+        assert_eq!(
+            expand_placeholders("cd ${project.path}", &ctx),
+            "cd '/Users/me/My Projects/app'"
+        );
+    }
+}
@@ -0,0 +1,534 @@
+//! 问题匹配模块
+//! 从 Dev 进程输出中解析错误和警告，内置 tsc、eslint、vite/esbuild、webpack、rustc 和 jest 匹配器，
+//! 并支持配置文件中的自定义正则匹配器
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::LazyLock;
+
+use crate::config::strip_ansi;
+
+/// 每个项目最多保留的问题数量
+const MAX_PROBLEMS: usize = 500;
+/// 没有换行的输出最多缓存的长度（全屏程序的输出可能很长时间没有换行）
+const MAX_PARTIAL_LEN: usize = 8 * 1024;
+
+/// 问题严重程度
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    #[default]
+    Error,
+    Warning,
+}
+
+impl Severity {
+    /// 从输出中的文本解析（error/ERROR/Error、warning/WARNING/Warn 等）
+    fn parse(text: &str) -> Self {
+        if text.to_ascii_lowercase().starts_with("warn") {
+            Severity::Warning
+        } else {
+            Severity::Error
+        }
+    }
+}
+
+/// 解析出的问题
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Problem {
+    pub severity: Severity,
+    /// 文件路径（可能是相对路径）
+    pub file: String,
+    pub line: u32,
+    pub column: Option<u32>,
+    pub message: String,
+    /// 匹配器名称（如 tsc、eslint）
+    pub source: String,
+    /// 产生问题的输出流（PTY ID），重新编译时只清空该输出流的问题
+    pub stream: String,
+}
+
+/// 配置文件中的自定义匹配器
+/// 正则使用命名分组：file、line（必须），col、message、severity（可选）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProblemMatcherConfig {
+    pub name: String,
+    pub pattern: String,
+    /// 正则中没有 severity 分组时使用的严重程度
+    #[serde(default)]
+    pub severity: Severity,
+}
+
+/// 编译后的自定义匹配器
+#[derive(Debug, Clone)]
+pub struct ProblemMatcher {
+    name: String,
+    regex: Regex,
+    severity: Severity,
+}
+
+/// 编译自定义匹配器，返回匹配器和无效配置的错误信息
+pub fn compile_matchers(configs: &[ProblemMatcherConfig]) -> (Vec<ProblemMatcher>, Vec<String>) {
+    let mut matchers = Vec::new();
+    let mut errors = Vec::new();
+    for config in configs {
+        match Regex::new(&config.pattern) {
+            Ok(regex) if has_group(&regex, "file") && has_group(&regex, "line") => {
+                matchers.push(ProblemMatcher {
+                    name: config.name.clone(),
+                    regex,
+                    severity: config.severity,
+                });
+            }
+            Ok(_) => errors.push(format!("{}: missing (?P<file>) or (?P<line>)", config.name)),
+            Err(e) => errors.push(format!("{}: {}", config.name, e)),
+        }
+    }
+    (matchers, errors)
+}
+
+fn has_group(regex: &Regex, name: &str) -> bool {
+    regex.capture_names().any(|n| n == Some(name))
+}
+
+fn regex(pattern: &str) -> Regex {
+    Regex::new(pattern).expect("built-in problem matcher pattern")
+}
+
+/// tsc：`src/a.ts(12,5): error TS2322: ...`
+static TSC: LazyLock<Regex> = LazyLock::new(|| {
+    regex(
+        r"^(?P<file>[^\s(][^(]*)\((?P<line>\d+),(?P<col>\d+)\): (?P<severity>error|warning) (?P<message>.+)$",
+    )
+});
+/// tsc --pretty、vite-plugin-checker：`src/a.ts:12:5 - error TS2322: ...`
+static TSC_PRETTY: LazyLock<Regex> = LazyLock::new(|| {
+    regex(
+        r"^(?P<file>\S+):(?P<line>\d+):(?P<col>\d+) - (?P<severity>error|warning) (?P<message>.+)$",
+    )
+});
+/// eslint unix 格式：`/abs/a.js:12:5: Missing semicolon. [Error/semi]`
+static ESLINT_UNIX: LazyLock<Regex> = LazyLock::new(|| {
+    regex(
+        r"^(?P<file>\S+?):(?P<line>\d+):(?P<col>\d+): (?P<message>.+) \[(?P<severity>Error|Warning)/[^\]]+\]$",
+    )
+});
+/// eslint stylish 格式的文件行（绝对路径单独占一行）
+static ESLINT_FILE: LazyLock<Regex> =
+    LazyLock::new(|| regex(r"^(?P<file>(?:/|[A-Za-z]:[\\/])\S+)$"));
+/// eslint stylish 格式的问题行：`  12:5  error  Missing semicolon  semi`
+static ESLINT_ITEM: LazyLock<Regex> = LazyLock::new(|| {
+    regex(
+        r"^\s+(?P<line>\d+):(?P<col>\d+)\s+(?P<severity>error|warning)\s+(?P<message>.+?)(?:\s{2,}\S+)?$",
+    )
+});
+/// vite 插件错误：`[plugin:vite:react-babel] /abs/App.tsx: Unexpected token (12:5)`
+static VITE_PLUGIN: LazyLock<Regex> = LazyLock::new(|| {
+    regex(r"\[plugin:[^\]]+\] (?P<file>\S+?): (?P<message>.+?) \((?P<line>\d+):(?P<col>\d+)\)")
+});
+/// esbuild（vite 使用）的错误标题：`✘ [ERROR] Expected ";" but found "x"`
+static ESBUILD_HEADER: LazyLock<Regex> =
+    LazyLock::new(|| regex(r"^\s*[✘▲X] \[(?P<severity>ERROR|WARNING)\] (?P<message>.+)$"));
+/// esbuild 的位置行：`    src/main.ts:3:6:`
+static ESBUILD_LOCATION: LazyLock<Regex> =
+    LazyLock::new(|| regex(r"^\s+(?P<file>\S+):(?P<line>\d+):(?P<col>\d+):\s*$"));
+/// webpack：`ERROR in ./src/index.ts 12:5-10`（消息在下一行）
+static WEBPACK: LazyLock<Regex> = LazyLock::new(|| {
+    regex(
+        r"^(?P<severity>ERROR|WARNING) in (?P<file>\S+?)(?::(?P<line>\d+):(?P<col>\d+)| (?P<line2>\d+):(?P<col2>\d+)(?:-\d+)?)?\s*$",
+    )
+});
+/// rustc 的错误标题：`error[E0308]: mismatched types`
+static RUSTC_HEADER: LazyLock<Regex> =
+    LazyLock::new(|| regex(r"^(?P<severity>error|warning)(?:\[\w+\])?: (?P<message>.+)$"));
+/// rustc 的位置行：`  --> src/main.rs:4:5`
+static RUSTC_LOCATION: LazyLock<Regex> =
+    LazyLock::new(|| regex(r"^\s*--> (?P<file>\S+):(?P<line>\d+):(?P<col>\d+)$"));
+/// jest 失败用例标题：`  ● Suite › does something`
+static JEST_HEADER: LazyLock<Regex> = LazyLock::new(|| regex(r"^\s*● (?P<message>.+)$"));
+/// jest 堆栈中的位置：`at Object.<anonymous> (src/a.test.ts:12:5)`
+static JEST_LOCATION: LazyLock<Regex> =
+    LazyLock::new(|| regex(r"^\s*at (?:.*\()?(?P<file>[^()\s]+):(?P<line>\d+):(?P<col>\d+)\)?$"));
+/// 重新编译开始的提示，出现时清空之前的问题
+static RESTART: LazyLock<Regex> = LazyLock::new(|| {
+    regex(
+        r"File change detected|Starting (?:incremental )?compilation|webpack: Compiling|Compiling\.\.\.|\[vite\] (?:hmr update|page reload)",
+    )
+});
+
+/// 等待后续行补全的问题
+#[derive(Debug, Clone)]
+enum Pending {
+    /// rustc 标题，等待 `-->` 位置行
+    Rustc(Severity, String),
+    /// esbuild 标题，等待位置行
+    Esbuild(Severity, String),
+    /// jest 失败用例，等待堆栈中的位置
+    Jest(String),
+    /// webpack 位置，等待消息行
+    Webpack(Problem),
+    /// eslint stylish 当前文件
+    EslintFile(String),
+}
+
+/// 单个输出流的解析状态
+#[derive(Debug, Default)]
+struct StreamState {
+    /// 尚未遇到换行的部分输出（原始字节，跨两次读取的转义序列和 UTF-8 字符在整行到齐后再处理）
+    partial: Vec<u8>,
+    pending: Option<Pending>,
+}
+
+/// 项目的问题列表
+#[derive(Debug, Default)]
+pub struct ProblemCollector {
+    problems: Vec<Problem>,
+    /// 按 PTY ID 区分的解析状态（并行组合命令的输出互不干扰）
+    streams: HashMap<String, StreamState>,
+}
+
+impl ProblemCollector {
+    /// 当前的问题
+    pub fn problems(&self) -> &[Problem] {
+        &self.problems
+    }
+
+    /// 错误和警告的数量
+    pub fn counts(&self) -> (usize, usize) {
+        let errors = self
+            .problems
+            .iter()
+            .filter(|p| p.severity == Severity::Error)
+            .count();
+        (errors, self.problems.len() - errors)
+    }
+
+    /// 清空问题和解析状态（重新运行命令时调用）
+    pub fn clear(&mut self) {
+        self.problems.clear();
+        self.streams.clear();
+    }
+
+    /// 处理一段输出
+    pub fn feed(&mut self, stream_id: &str, data: &[u8], custom: &[ProblemMatcher]) {
+        let mut stream = self.streams.remove(stream_id).unwrap_or_default();
+        stream.partial.extend_from_slice(data);
+        while let Some(pos) = stream.partial.iter().position(|&b| b == b'\n') {
+            let raw: Vec<u8> = stream.partial.drain(..=pos).collect();
+            let text = strip_ansi(&raw);
+            // 进度条等用 \r 覆盖的行只取最后一段
+            let line = text.trim_end_matches(['\n', '\r']);
+            let line = line.rsplit('\r').next().unwrap_or(line);
+            self.feed_line(stream_id, &mut stream, line, custom);
+        }
+        if stream.partial.len() > MAX_PARTIAL_LEN {
+            stream.partial.clear();
+        }
+        self.streams.insert(stream_id.to_string(), stream);
+    }
+
+    fn feed_line(
+        &mut self,
+        stream_id: &str,
+        stream: &mut StreamState,
+        line: &str,
+        custom: &[ProblemMatcher],
+    ) {
+        if RESTART.is_match(line) {
+            // 并行组合中其他步骤的问题保留
+            self.problems.retain(|p| p.stream != stream_id);
+            stream.pending = None;
+            return;
+        }
+        if let Some(problem) = custom.iter().find_map(|m| {
+            let caps = m.regex.captures(line)?;
+            problem_from(&caps, &m.name, m.severity)
+        }) {
+            self.push(stream_id, problem);
+            return;
+        }
+
+        // 先处理等待补全的问题
+        match stream.pending.take() {
+            Some(Pending::Rustc(severity, message)) => {
+                if let Some(caps) = RUSTC_LOCATION.captures(line) {
+                    if let Some(problem) = located(&caps, severity, message, "rustc") {
+                        self.push(stream_id, problem);
+                    }
+                    return;
+                }
+                stream.pending = Some(Pending::Rustc(severity, message));
+            }
+            Some(Pending::Esbuild(severity, message)) => {
+                if let Some(caps) = ESBUILD_LOCATION.captures(line) {
+                    if let Some(problem) = located(&caps, severity, message, "esbuild") {
+                        self.push(stream_id, problem);
+                    }
+                    return;
+                }
+                // 标题和位置之间只有空行
+                if line.trim().is_empty() {
+                    stream.pending = Some(Pending::Esbuild(severity, message));
+                    return;
+                }
+            }
+            Some(Pending::Jest(message)) => {
+                if let Some(caps) = JEST_LOCATION.captures(line) {
+                    if !caps["file"].contains("node_modules") {
+                        if let Some(problem) = located(&caps, Severity::Error, message, "jest") {
+                            self.push(stream_id, problem);
+                        }
+                        return;
+                    }
+                }
+                stream.pending = Some(Pending::Jest(message));
+            }
+            Some(Pending::Webpack(mut problem)) => {
+                if line.trim().is_empty() {
+                    stream.pending = Some(Pending::Webpack(problem));
+                    return;
+                }
+                problem.message = line.trim().to_string();
+                self.push(stream_id, problem);
+                return;
+            }
+            Some(Pending::EslintFile(file)) => {
+                if let Some(caps) = ESLINT_ITEM.captures(line) {
+                    self.push(
+                        stream_id,
+                        Problem {
+                            severity: Severity::parse(&caps["severity"]),
+                            file: file.clone(),
+                            line: caps["line"].parse().unwrap_or(1),
+                            column: caps["col"].parse().ok(),
+                            message: caps["message"].trim().to_string(),
+                            source: "eslint".to_string(),
+                            stream: String::new(),
+                        },
+                    );
+                    stream.pending = Some(Pending::EslintFile(file));
+                    return;
+                }
+            }
+            None => {}
+        }
+
+        let single_line = [
+            (&TSC, "tsc"),
+            (&TSC_PRETTY, "tsc"),
+            (&ESLINT_UNIX, "eslint"),
+            (&VITE_PLUGIN, "vite"),
+        ];
+        for (regex, name) in single_line {
+            if let Some(problem) = regex
+                .captures(line)
+                .and_then(|caps| problem_from(&caps, name, Severity::Error))
+            {
+                self.push(stream_id, problem);
+                return;
+            }
+        }
+
+        if let Some(caps) = WEBPACK.captures(line) {
+            let number = |a: &str, b: &str| {
+                caps.name(a)
+                    .or_else(|| caps.name(b))
+                    .and_then(|m| m.as_str().parse().ok())
+            };
+            stream.pending = Some(Pending::Webpack(Problem {
+                severity: Severity::parse(&caps["severity"]),
+                file: caps["file"].to_string(),
+                line: number("line", "line2").unwrap_or(1),
+                column: number("col", "col2"),
+                message: String::new(),
+                source: "webpack".to_string(),
+                stream: String::new(),
+            }));
+        } else if let Some(caps) = ESBUILD_HEADER.captures(line) {
+            stream.pending = Some(Pending::Esbuild(
+                Severity::parse(&caps["severity"]),
+                caps["message"].to_string(),
+            ));
+        } else if let Some(caps) = RUSTC_HEADER.captures(line) {
+            stream.pending = Some(Pending::Rustc(
+                Severity::parse(&caps["severity"]),
+                caps["message"].to_string(),
+            ));
+        } else if let Some(caps) = JEST_HEADER.captures(line) {
+            stream.pending = Some(Pending::Jest(caps["message"].to_string()));
+        } else if let Some(caps) = ESLINT_FILE.captures(line) {
+            stream.pending = Some(Pending::EslintFile(caps["file"].to_string()));
+        }
+    }
+
+    /// 添加问题（忽略重复的问题）
+    fn push(&mut self, stream_id: &str, mut problem: Problem) {
+        problem.stream = stream_id.to_string();
+        if self.problems.len() < MAX_PROBLEMS && !self.problems.contains(&problem) {
+            self.problems.push(problem);
+        }
+    }
+}
+
+/// 从单行匹配结果构造问题（file 和 line 分组必须存在）
+fn problem_from(caps: &regex::Captures, source: &str, severity: Severity) -> Option<Problem> {
+    let message = caps
+        .name("message")
+        .map(|m| m.as_str().trim().to_string())
+        .unwrap_or_default();
+    let severity = caps
+        .name("severity")
+        .map(|m| Severity::parse(m.as_str()))
+        .unwrap_or(severity);
+    located(caps, severity, message, source)
+}
+
+/// 从位置匹配结果构造问题
+fn located(
+    caps: &regex::Captures,
+    severity: Severity,
+    message: String,
+    source: &str,
+) -> Option<Problem> {
+    Some(Problem {
+        severity,
+        file: caps.name("file")?.as_str().to_string(),
+        line: caps.name("line")?.as_str().parse().ok()?,
+        column: caps.name("col").and_then(|m| m.as_str().parse().ok()),
+        message,
+        source: source.to_string(),
+        stream: String::new(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn collect(output: &str, custom: &[ProblemMatcher]) -> Vec<Problem> {
+        let mut collector = ProblemCollector::default();
+        // 分两段输入，验证跨段的行也能正确解析
+        let (a, b) = output.split_at(output.len() / 2);
+        collector.feed("pty", a.as_bytes(), custom);
+        collector.feed("pty", b.as_bytes(), custom);
+        collector.problems().to_vec()
+    }
+
+    fn summary(problems: &[Problem]) -> Vec<String> {
+        problems
+            .iter()
+            .map(|p| {
+                format!(
+                    "{:?} {}:{}:{} {} [{}]",
+                    p.severity,
+                    p.file,
+                    p.line,
+                    p.column.unwrap_or(0),
+                    p.message,
+                    p.source
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_builtin_matchers() {
+        let output = "\
+src/app.ts(12,5): error TS2322: Type 'string' is not assignable to type 'number'.
+src/b.ts:3:1 - warning TS6133: 'x' is declared but never read.
+
+/work/app/src/c.js
+  4:10  error    'foo' is not defined  no-undef
+  7:1   warning  Unexpected console statement  no-console
+
+[plugin:vite:react-babel] /work/app/src/App.tsx: Unexpected token (8:2)
+\u{1b}[31m✘\u{1b}[0m [ERROR] Expected \";\" but found \"x\"
+
+    src/main.ts:3:6:
+ERROR in ./src/index.ts 5:2-8
+Module not found: Error: Can't resolve './missing'
+error[E0308]: mismatched types
+  --> src/main.rs:4:5
+  ● math › adds numbers
+
+    at Object.<anonymous> (node_modules/expect/build/index.js:1:1)
+    at Object.<anonymous> (src/math.test.ts:9:17)
+src/app.ts(12,5): error TS2322: Type 'string' is not assignable to type 'number'.
+";
+        assert_eq!(
+            summary(&collect(output, &[])),
+            vec![
+                "Error src/app.ts:12:5 TS2322: Type 'string' is not assignable to type 'number'. [tsc]",
+                "Warning src/b.ts:3:1 TS6133: 'x' is declared but never read. [tsc]",
+                "Error /work/app/src/c.js:4:10 'foo' is not defined [eslint]",
+                "Warning /work/app/src/c.js:7:1 Unexpected console statement [eslint]",
+                "Error /work/app/src/App.tsx:8:2 Unexpected token [vite]",
+                "Error src/main.ts:3:6 Expected \";\" but found \"x\" [esbuild]",
+                "Error ./src/index.ts:5:2 Module not found: Error: Can't resolve './missing' [webpack]",
+                "Error src/main.rs:4:5 mismatched types [rustc]",
+                "Error src/math.test.ts:9:17 math › adds numbers [jest]",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_custom_matcher_and_restart() {
+        let (matchers, errors) = compile_matchers(&[
+            ProblemMatcherConfig {
+                name: "lint".to_string(),
+                pattern: r"^LINT (?P<file>\S+) line (?P<line>\d+): (?P<message>.+)$".to_string(),
+                severity: Severity::Warning,
+            },
+            ProblemMatcherConfig {
+                name: "broken".to_string(),
+                pattern: "(".to_string(),
+                severity: Severity::Error,
+            },
+            ProblemMatcherConfig {
+                name: "no-line".to_string(),
+                pattern: r"(?P<file>\S+)".to_string(),
+                severity: Severity::Error,
+            },
+        ]);
+        assert_eq!(matchers.len(), 1);
+        assert_eq!(errors.len(), 2);
+
+        let mut collector = ProblemCollector::default();
+        collector.feed("pty", b"LINT a.css line 3: bad color\n", &matchers);
+        assert_eq!(
+            summary(collector.problems()),
+            vec!["Warning a.css:3:0 bad color [lint]"]
+        );
+        assert_eq!(collector.counts(), (0, 1));
+        collector.feed(
+            "pty",
+            b"[12:00:01] File change detected. Starting incremental compilation...\r\n",
+            &matchers,
+        );
+        assert!(collector.problems().is_empty());
+    }
+
+    #[test]
+    fn test_restart_clears_only_its_stream() {
+        let mut collector = ProblemCollector::default();
+        collector.feed("web", b"src/app.ts(1,1): error TS1: web\n", &[]);
+        collector.feed("api", b"src/api.ts(2,1): error TS2: api\n", &[]);
+        collector.feed("web", b"Compiling...\n", &[]);
+        assert_eq!(
+            summary(collector.problems()),
+            vec!["Error src/api.ts:2:1 TS2: api [tsc]"]
+        );
+    }
+
+    #[test]
+    fn test_escape_sequence_split_across_reads() {
+        let mut collector = ProblemCollector::default();
+        collector.feed("pty", b"\x1b[3", &[]);
+        collector.feed("pty", b"1msrc/app.ts(3,7): error TS2304: x\x1b[0m\n", &[]);
+        assert_eq!(
+            summary(collector.problems()),
+            vec!["Error src/app.ts:3:7 TS2304: x [tsc]"]
+        );
+    }
+}
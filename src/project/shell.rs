@@ -0,0 +1,246 @@
+//! Shell 标签页
+//! 每个项目可以有多个命名的交互式 Shell（如一个用于 git、一个用于 REPL），各自可以设置工作目录和启动后执行的命令
+
+use super::Project;
+use crate::pty::{PtyEvent, PtyHandle, StopStep};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use tokio::sync::mpsc::UnboundedSender;
+
+/// 没有设置名称时使用的标签名
+pub const DEFAULT_SHELL_NAME: &str = "shell";
+
+/// Shell 标签页的配置（保存到配置文件）
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ShellTabConfig {
+    pub name: String,
+    /// 工作目录（相对项目根目录，为空时使用项目根目录）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cwd: Option<String>,
+    /// Shell 启动后执行的命令
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub command: Option<String>,
+}
+
+impl ShellTabConfig {
+    /// 解析新建标签时的输入：`名称 [@目录] [: 命令]`，如 `repl: node`、`api @packages/api: pnpm dev`
+    /// 后面紧跟 `\` 或 `/` 的冒号属于 Windows 盘符（`api @C:\repo\api: pnpm dev`），不作为命令分隔符
+    pub fn parse(input: &str) -> Option<Self> {
+        let separator = input
+            .char_indices()
+            .find(|&(idx, c)| c == ':' && !input[idx + 1..].starts_with(['\\', '/']))
+            .map(|(idx, _)| idx);
+        let (head, command) = match separator {
+            Some(idx) => (&input[..idx], Some(input[idx + 1..].trim())),
+            None => (input, None),
+        };
+        let (name, cwd) = match head.split_once('@') {
+            Some((name, cwd)) => (name.trim(), Some(cwd.trim())),
+            None => (head.trim(), None),
+        };
+        let non_empty = |s: Option<&str>| s.filter(|s| !s.is_empty()).map(str::to_string);
+        let cwd = non_empty(cwd);
+        let command = non_empty(command);
+        if name.is_empty() && cwd.is_none() && command.is_none() {
+            return None;
+        }
+        Some(Self {
+            name: if name.is_empty() {
+                DEFAULT_SHELL_NAME.to_string()
+            } else {
+                name.to_string()
+            },
+            cwd,
+            command,
+        })
+    }
+
+    /// 是否为自动创建的默认标签（不保存到配置文件）
+    pub fn is_plain(&self) -> bool {
+        self.name == DEFAULT_SHELL_NAME && self.cwd.is_none() && self.command.is_none()
+    }
+}
+
+/// Shell 标签页
+#[derive(Debug)]
+pub struct ShellTab {
+    pub config: ShellTabConfig,
+    /// 标签的 PTY（Shell 退出后为空，再次进入时重新启动）
+    pub pty: Option<PtyHandle>,
+}
+
+impl ShellTab {
+    pub fn new(config: ShellTabConfig) -> Self {
+        Self { config, pty: None }
+    }
+}
+
+impl Project {
+    /// 当前选中的 Shell 标签
+    pub fn active_shell(&self) -> Option<&ShellTab> {
+        self.shells.get(self.active_shell_idx)
+    }
+
+    /// 当前选中标签的 PTY
+    pub fn shell_pty(&self) -> Option<&PtyHandle> {
+        self.active_shell().and_then(|tab| tab.pty.as_ref())
+    }
+
+    /// 当前选中标签的 PTY（可变）
+    pub fn shell_pty_mut(&mut self) -> Option<&mut PtyHandle> {
+        self.shells
+            .get_mut(self.active_shell_idx)
+            .and_then(|tab| tab.pty.as_mut())
+    }
+
+    /// 所有标签中 ID 匹配的 PTY
+    pub fn find_shell_pty_mut(&mut self, pty_id: &str) -> Option<&mut PtyHandle> {
+        self.shells
+            .iter_mut()
+            .filter_map(|tab| tab.pty.as_mut())
+            .find(|pty| pty.id == pty_id)
+    }
+
+    /// Shell 的工作目录
+    pub fn shell_dir(&self, config: &ShellTabConfig) -> PathBuf {
+        match config.cwd.as_deref().map(str::trim) {
+            Some(cwd) if !cwd.is_empty() => self.path.join(cwd),
+            _ => self.path.clone(),
+        }
+    }
+
+    /// 添加标签并选中它
+    pub fn add_shell(&mut self, config: ShellTabConfig) {
+        self.shells.push(ShellTab::new(config));
+        self.select_shell(self.shells.len() - 1);
+    }
+
+    /// 确保至少有一个标签（没有时创建默认标签）
+    pub fn ensure_shell(&mut self) {
+        if self.shells.is_empty() {
+            self.add_shell(ShellTabConfig {
+                name: DEFAULT_SHELL_NAME.to_string(),
+                cwd: None,
+                command: None,
+            });
+        }
+    }
+
+    /// 关闭当前标签，返回被关闭的标签名
+    /// 其中的 Shell 在后台按停止序列停止，进程树退出前保留在 stopping_shells 中
+    pub fn close_shell(
+        &mut self,
+        defaults: &[StopStep],
+        tx: &UnboundedSender<PtyEvent>,
+    ) -> Option<String> {
+        if self.active_shell_idx >= self.shells.len() {
+            return None;
+        }
+        let tab = self.shells.remove(self.active_shell_idx);
+        if let Some(mut pty) = tab.pty.filter(|pty| pty.running && pty.pid.is_some()) {
+            pty.stop(defaults, tx.clone());
+            self.stopping_shells.push(pty);
+        }
+        self.select_shell(
+            self.active_shell_idx
+                .min(self.shells.len().saturating_sub(1)),
+        );
+        Some(tab.config.name)
+    }
+
+    /// 已关闭标签中的 Shell 进程树已退出，返回是否属于该项目
+    pub fn finish_shell_stopping(&mut self, pty_id: &str) -> bool {
+        let Some(idx) = self.stopping_shells.iter().position(|pty| pty.id == pty_id) else {
+            return false;
+        };
+        // 进程树已退出，Drop 时不需要再终止
+        let mut pty = self.stopping_shells.remove(idx);
+        pty.pid = None;
+        true
+    }
+
+    /// 切换到指定标签（Shell 终端回到底部）
+    pub fn select_shell(&mut self, idx: usize) {
+        self.active_shell_idx = idx;
+        self.shell_scroll_offset = 0;
+    }
+
+    /// 切换到下一个（delta 为 1）或上一个（delta 为 -1）标签
+    pub fn cycle_shell(&mut self, delta: isize) {
+        let count = self.shells.len() as isize;
+        if count > 1 {
+            let idx = (self.active_shell_idx as isize + delta).rem_euclid(count);
+            self.select_shell(idx as usize);
+        }
+    }
+
+    /// 需要保存的标签配置
+    pub fn shell_tab_configs(&self) -> Vec<ShellTabConfig> {
+        self.shells
+            .iter()
+            .map(|tab| tab.config.clone())
+            .filter(|config| !config.is_plain())
+            .collect()
+    }
+
+    /// 按配置重建标签：同名标签保留正在运行的 Shell，配置中没有但仍在运行的标签也保留
+    pub fn set_shell_tabs(&mut self, configs: &[ShellTabConfig]) {
+        let mut old = std::mem::take(&mut self.shells);
+        for config in configs {
+            let pty = old
+                .iter_mut()
+                .find(|tab| tab.config.name == config.name)
+                .and_then(|tab| tab.pty.take());
+            self.shells.push(ShellTab {
+                config: config.clone(),
+                pty,
+            });
+        }
+        self.shells
+            .extend(old.into_iter().filter(|tab| tab.pty.is_some()));
+        self.active_shell_idx = self
+            .active_shell_idx
+            .min(self.shells.len().saturating_sub(1));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tab(name: &str, cwd: Option<&str>, command: Option<&str>) -> ShellTabConfig {
+        ShellTabConfig {
+            name: name.to_string(),
+            cwd: cwd.map(str::to_string),
+            command: command.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn test_parse_shell_tab() {
+        assert_eq!(ShellTabConfig::parse("git"), Some(tab("git", None, None)));
+        assert_eq!(
+            ShellTabConfig::parse("repl: node"),
+            Some(tab("repl", None, Some("node")))
+        );
+        assert_eq!(
+            ShellTabConfig::parse("api @packages/api: pnpm run dev:api"),
+            Some(tab("api", Some("packages/api"), Some("pnpm run dev:api")))
+        );
+        assert_eq!(
+            ShellTabConfig::parse("@docs"),
+            Some(tab(DEFAULT_SHELL_NAME, Some("docs"), None))
+        );
+        assert_eq!(
+            ShellTabConfig::parse(r"api @C:\repo\api: pnpm dev"),
+            Some(tab("api", Some(r"C:\repo\api"), Some("pnpm dev")))
+        );
+        assert_eq!(
+            ShellTabConfig::parse("@D:/work"),
+            Some(tab(DEFAULT_SHELL_NAME, Some("D:/work"), None))
+        );
+        assert_eq!(ShellTabConfig::parse("  "), None);
+        assert!(tab(DEFAULT_SHELL_NAME, None, None).is_plain());
+        assert!(!tab("git", None, None).is_plain());
+    }
+}
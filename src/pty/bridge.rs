@@ -16,14 +16,27 @@ pub fn handle_pty_events(state: &mut AppState) {
     // 处理收集到的事件
     for event in events {
         match event {
-            PtyEvent::Output { pty_id, data: _ } => {
+            PtyEvent::Output { pty_id, data } => {
                 // 输出数据已经在 PTY reader 任务中更新到 parser 了
-                // 这里可以用于其他处理，如日志记录
+                // 这里解析链接、错误和警告，并检查是否匹配通知规则
                 log::trace!("PTY {} output received", pty_id);
+                state.mark_output(&pty_id);
+                state.track_links(&pty_id, &data);
+                state.collect_problems(&pty_id, &data);
+                state.notify_output(&pty_id, &data);
             }
             PtyEvent::Exited { pty_id, exit_code } => {
                 log::info!("PTY {} exited with code {:?}", pty_id, exit_code);
 
+                // 按配置通知失败退出或长时间命令完成，然后补充运行历史中的时长和退出码
+                state.notify_exit(&pty_id, exit_code);
+                state.finish_run(&pty_id, exit_code);
+
+                // 组合命令的步骤：推进顺序组合或记录整体结果
+                if state.composite_step_exited(&pty_id, exit_code) {
+                    continue;
+                }
+
                 // 查找并更新对应的项目状态
                 let mut status_msg = None;
                 for project in &mut state.projects {
@@ -35,9 +48,10 @@ pub fn handle_pty_events(state: &mut AppState) {
                                 Some(format!("Dev server stopped (exit: {:?})", exit_code));
                         }
                     }
-                    if let Some(ref shell_pty) = project.shell_pty {
-                        if shell_pty.id == pty_id {
-                            project.shell_pty = None;
+                    // Shell 退出后保留标签，再次进入时重新启动
+                    for tab in &mut project.shells {
+                        if tab.pty.as_ref().is_some_and(|pty| pty.id == pty_id) {
+                            tab.pty = None;
                         }
                     }
                 }
@@ -45,6 +59,10 @@ pub fn handle_pty_events(state: &mut AppState) {
                     state.set_status(&msg);
                 }
             }
+            PtyEvent::Stopped { pty_id, outcome } => {
+                // 按停止序列停止的进程树已全部退出
+                state.finish_stopping(&pty_id, outcome);
+            }
             PtyEvent::Error { pty_id, message } => {
                 log::error!("PTY {} error: {}", pty_id, message);
                 state.set_status(&format!("PTY error: {}", message));
@@ -63,10 +81,17 @@ pub fn send_to_active_terminal(state: &mut AppState, data: &[u8]) -> anyhow::Res
             FocusArea::DevTerminal => {
                 if let Some(ref mut pty) = project.dev_pty {
                     pty.send_input(data)?;
+                } else if let Some(pty) = project
+                    .composite
+                    .as_mut()
+                    .and_then(|c| c.running_ptys_mut().next())
+                {
+                    // 组合命令：发送给第一个正在运行的步骤
+                    pty.send_input(data)?;
                 }
             }
             FocusArea::ShellTerminal => {
-                if let Some(ref mut pty) = project.shell_pty {
+                if let Some(pty) = project.shell_pty_mut() {
                     pty.send_input(data)?;
                 }
             }
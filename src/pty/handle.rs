@@ -1,9 +1,12 @@
 //! PTY 句柄模块
 //! 包含 PTY 句柄的定义和操作
 
+use super::links::Hyperlinks;
 use super::process_tree::collect_process_tree;
-use super::resource::ProcessResourceUsage;
+use super::resource::{LimitBreach, ProcessResourceUsage, ResourceHistory, ResourceLimits};
+use super::stop::StopStep;
 use std::sync::Arc;
+use std::time::Instant;
 use tokio::sync::Mutex;
 
 /// PTY 句柄
@@ -15,6 +18,10 @@ pub struct PtyHandle {
     pub running: bool,
     /// 是否已暂停（冻结）
     pub suspended: bool,
+    /// 是否因空闲被自动暂停（与 suspended 同时为 true 时有效）
+    pub auto_suspended: bool,
+    /// 最近一次输出或被查看的时间（用于空闲自动暂停）
+    pub last_activity: Instant,
     /// 子进程 PID（用于发送信号）
     pub pid: Option<u32>,
     /// 终端解析器状态（用于 tui-term 渲染）
@@ -23,6 +30,16 @@ pub struct PtyHandle {
     pub writer: Option<Box<dyn std::io::Write + Send>>,
     /// 进程资源使用信息（包括所有子进程的总和）
     pub resource_usage: ProcessResourceUsage,
+    /// 最近 15 分钟的资源使用采样
+    pub resource_history: ResourceHistory,
+    /// 当前超过的资源上限（回落到上限以下时清除）
+    pub limit_breach: Option<LimitBreach>,
+    /// 输出中的 OSC 8 超链接
+    pub hyperlinks: Hyperlinks,
+    /// 命令配置的停止序列（为空时使用全局设置）
+    pub stop_steps: Vec<StopStep>,
+    /// 开始按停止序列停止的时间（停止期间进程树可能仍在运行）
+    pub stopping_since: Option<Instant>,
 }
 
 impl std::fmt::Debug for PtyHandle {
@@ -44,10 +61,17 @@ impl PtyHandle {
             id: id.to_string(),
             running: false,
             suspended: false,
+            auto_suspended: false,
+            last_activity: Instant::now(),
             pid: None,
             parser: Arc::new(Mutex::new(vt100::Parser::new(rows, cols, 1000))),
             writer: None,
             resource_usage: ProcessResourceUsage::default(),
+            resource_history: ResourceHistory::default(),
+            limit_breach: None,
+            hyperlinks: Hyperlinks::default(),
+            stop_steps: Vec::new(),
+            stopping_since: None,
         }
     }
 
@@ -89,9 +113,29 @@ impl PtyHandle {
 
             self.resource_usage.cpu_percent = normalized_cpu;
             self.resource_usage.memory_bytes = total_memory;
+            self.resource_history
+                .push(Instant::now(), self.resource_usage);
         }
     }
 
+    /// 检查资源上限并更新 limit_breach，返回刚开始超限时的情况
+    /// 暂停的进程不检查（保留暂停前的超限状态）
+    pub fn check_limits(&mut self, limits: &ResourceLimits, now: Instant) -> Option<LimitBreach> {
+        if self.suspended {
+            return None;
+        }
+        let breach = limits.check(&self.resource_history, now);
+        let started = breach.filter(|_| self.limit_breach.is_none());
+        self.limit_breach = breach;
+        started
+    }
+
+    /// 进程恢复后清除超限状态，下一次检查时仍超限会重新执行配置的操作
+    fn mark_resumed(&mut self) {
+        self.suspended = false;
+        self.limit_breach = None;
+    }
+
     /// 向 PTY 发送输入
     pub fn send_input(&mut self, data: &[u8]) -> anyhow::Result<()> {
         if let Some(writer) = &mut self.writer {
@@ -141,13 +185,13 @@ impl PtyHandle {
                     let target_pid = if pgid > 0 { -pgid } else { -(pid as i32) };
 
                     if libc::kill(target_pid, libc::SIGCONT) == 0 {
-                        self.suspended = false;
+                        self.mark_resumed();
                         return Ok(true);
                     }
 
                     // 如果进程组信号失败，尝试直接发送给进程
                     if libc::kill(pid as i32, libc::SIGCONT) == 0 {
-                        self.suspended = false;
+                        self.mark_resumed();
                         return Ok(true);
                     }
                 }
@@ -276,7 +320,7 @@ impl PtyHandle {
                     let _ = CloseHandle(thread_snapshot);
 
                     if resumed_count > 0 {
-                        self.suspended = false;
+                        self.mark_resumed();
                         return Ok(true);
                     }
                 }
@@ -295,7 +339,7 @@ impl PtyHandle {
         }
     }
 
-    /// 终止进程及其所有子进程
+    /// 立即终止进程及其所有子进程（会阻塞，用于退出程序或删除项目时；停止 Dev 进程使用 stop）
     /// 在 Unix 上使用 SIGTERM/SIGKILL 发送给进程组
     /// 在 Windows 上使用 TerminateProcess 终止进程树
     pub fn kill(&mut self) {
@@ -331,49 +375,7 @@ impl PtyHandle {
             }
 
             #[cfg(windows)]
-            {
-                use super::process_tree::get_process_tree;
-                use windows::Win32::Foundation::CloseHandle;
-                use windows::Win32::System::Threading::{
-                    OpenProcess, TerminateProcess, WaitForSingleObject, PROCESS_SYNCHRONIZE,
-                    PROCESS_TERMINATE,
-                };
-
-                unsafe {
-                    // 获取整个进程树
-                    if let Ok(process_tree) = get_process_tree(pid) {
-                        // 收集所有需要等待的进程句柄
-                        let mut handles_to_wait = Vec::new();
-
-                        // 终止进程树中的所有进程
-                        for child_pid in process_tree {
-                            if let Ok(handle) = OpenProcess(
-                                PROCESS_TERMINATE | PROCESS_SYNCHRONIZE,
-                                false,
-                                child_pid,
-                            ) {
-                                let _ = TerminateProcess(handle, 1);
-                                handles_to_wait.push(handle);
-                            }
-                        }
-
-                        // 等待所有进程终止（最多 1000ms）
-                        // 增加等待时间以确保 ConPTY 资源有足够时间释放
-                        for handle in &handles_to_wait {
-                            let _ = WaitForSingleObject(*handle, 1000);
-                        }
-
-                        // 关闭所有句柄
-                        for handle in handles_to_wait {
-                            let _ = CloseHandle(handle);
-                        }
-                    }
-
-                    // 额外等待一段时间，确保 ConPTY 子系统完全释放资源
-                    // 这对于避免快速连续创建 PTY 时的 0xc0000142 错误很重要
-                    std::thread::sleep(std::time::Duration::from_millis(100));
-                }
-            }
+            terminate_tree(pid);
         }
         self.running = false;
         self.suspended = false;
@@ -389,3 +391,87 @@ impl Drop for PtyHandle {
         }
     }
 }
+
+/// 终止整个进程树并等待 ConPTY 释放资源（Windows 没有可以捕获的终止信号）
+#[cfg(windows)]
+pub(super) fn terminate_tree(pid: u32) {
+    use super::process_tree::get_process_tree;
+    use windows::Win32::Foundation::CloseHandle;
+    use windows::Win32::System::Threading::{
+        OpenProcess, TerminateProcess, WaitForSingleObject, PROCESS_SYNCHRONIZE, PROCESS_TERMINATE,
+    };
+
+    unsafe {
+        // 获取整个进程树
+        if let Ok(process_tree) = get_process_tree(pid) {
+            // 收集所有需要等待的进程句柄
+            let mut handles_to_wait = Vec::new();
+
+            // 终止进程树中的所有进程
+            for child_pid in process_tree {
+                if let Ok(handle) =
+                    OpenProcess(PROCESS_TERMINATE | PROCESS_SYNCHRONIZE, false, child_pid)
+                {
+                    let _ = TerminateProcess(handle, 1);
+                    handles_to_wait.push(handle);
+                }
+            }
+
+            // 等待所有进程终止（最多 1000ms）
+            // 增加等待时间以确保 ConPTY 资源有足够时间释放
+            for handle in &handles_to_wait {
+                let _ = WaitForSingleObject(*handle, 1000);
+            }
+
+            // 关闭所有句柄
+            for handle in handles_to_wait {
+                let _ = CloseHandle(handle);
+            }
+        }
+
+        // 额外等待一段时间，确保 ConPTY 子系统完全释放资源
+        // 这对于避免快速连续创建 PTY 时的 0xc0000142 错误很重要
+        std::thread::sleep(std::time::Duration::from_millis(100));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pty::LimitAction;
+    use std::time::Duration;
+
+    #[test]
+    fn test_limit_breach_rearmed_after_resume() {
+        let start = Instant::now();
+        let limits = ResourceLimits {
+            memory_mb: Some(1),
+            cpu_percent: None,
+            cpu_secs: 10,
+            action: LimitAction::Suspend,
+        };
+        let over = ProcessResourceUsage {
+            cpu_percent: 0.0,
+            memory_bytes: 2 * 1024 * 1024,
+        };
+        let mut pty = PtyHandle::new("dev-test", 24, 80);
+        pty.resource_history.push(start, over);
+        assert_eq!(
+            pty.check_limits(&limits, start),
+            Some(LimitBreach::Memory(over.memory_bytes))
+        );
+        // 仍然超限时不重复触发
+        assert_eq!(pty.check_limits(&limits, start), None);
+
+        // 暂停期间不检查，恢复后仍超限会再次触发
+        pty.suspended = true;
+        assert_eq!(pty.check_limits(&limits, start), None);
+        pty.mark_resumed();
+        let now = start + Duration::from_secs(1);
+        pty.resource_history.push(now, over);
+        assert_eq!(
+            pty.check_limits(&limits, now),
+            Some(LimitBreach::Memory(over.memory_bytes))
+        );
+    }
+}
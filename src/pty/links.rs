@@ -0,0 +1,464 @@
+//! 终端输出中的链接识别
+//! - 文件位置引用：`src/foo.ts:42:7`、`src/foo.ts(42,7)`、`File "app.py", line 3`
+//! - URL：纯文本中的 `http(s)://` 链接，以及 OSC 8 超链接
+
+use regex::Regex;
+use std::collections::VecDeque;
+use std::sync::LazyLock;
+use unicode_width::UnicodeWidthStr;
+
+/// 带扩展名的路径后跟 `:line[:col]` 或 `(line,col)`
+static FILE_REF: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"(?P<file>(?:\b[A-Za-z]:)?[\w.@~+\-/\\]*\.[A-Za-z]\w*)(?::(?P<line>\d+)(?::(?P<col>\d+))?|\((?P<pline>\d+),(?P<pcol>\d+)\))",
+    )
+    .unwrap()
+});
+
+/// Python 回溯：`File "app/main.py", line 12`
+static PYTHON_REF: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#"File "(?P<file>[^"]+)", line (?P<line>\d+)"#).unwrap());
+
+/// 纯文本中的 URL
+static URL: LazyLock<Regex> = LazyLock::new(|| Regex::new(r#"\bhttps?://[^\s<>"'`]+"#).unwrap());
+
+/// 本机地址（开发服务器打印的访问地址）
+static LOCAL_URL: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^https?://(?:localhost|127\.0\.0\.1|0\.0\.0\.0|\[::1?\])(?::\d+)?(?:/|$)").unwrap()
+});
+
+/// 是否为 http(s) 链接
+fn is_web_url(url: &str) -> bool {
+    let lower = url.get(..8).unwrap_or(url).to_ascii_lowercase();
+    lower.starts_with("http://") || lower.starts_with("https://")
+}
+
+/// 记住的 OSC 8 超链接数量上限
+const MAX_HYPERLINKS: usize = 200;
+/// OSC 序列和链接文本的长度上限（超过时丢弃，避免异常输出占用内存）
+const MAX_OSC_LEN: usize = 4096;
+/// 链接文本的最少字符数（更短的文本在其他输出中太容易重复出现）
+const MIN_LINK_TEXT_LEN: usize = 2;
+/// 记住的 CSI 参数长度上限
+const MAX_CSI_LEN: usize = 16;
+
+/// 提示标签使用的字符（按手指位置排序）
+const HINT_CHARS: &str = "asdfghjklqwertyuiopzxcvbnm";
+
+/// 文本中的文件位置引用
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileRef {
+    /// 起始列（按显示宽度计算）
+    pub start: usize,
+    /// 占用的列数
+    pub len: usize,
+    pub file: String,
+    pub line: u32,
+    pub column: Option<u32>,
+}
+
+/// 文本中的链接
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UrlRef {
+    /// 起始列（按显示宽度计算）
+    pub start: usize,
+    /// 占用的列数
+    pub len: usize,
+    pub url: String,
+}
+
+/// OSC 8 超链接记录
+/// vt100 解析器会丢弃 OSC 8 序列，这里单独解析输出，记住每个链接的显示文本、目标地址和所在的输出行，
+/// 渲染时只在链接输出的那一行中查找显示文本
+#[derive(Debug, Default)]
+pub struct Hyperlinks {
+    /// 已关闭的链接（最新的在后面）
+    links: VecDeque<Hyperlink>,
+    /// 已输出的换行数
+    lines: usize,
+    state: OscState,
+    /// 正在解析的 OSC 序列
+    osc: Vec<u8>,
+    /// 正在解析的 CSI 参数
+    csi: Vec<u8>,
+    /// 当前打开的链接地址和已输出的文本
+    open: Option<(String, Vec<u8>)>,
+}
+
+/// 一个 OSC 8 超链接
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Hyperlink {
+    text: String,
+    url: String,
+    /// 链接结束时已输出的换行数
+    line: usize,
+}
+
+/// 转义序列解析状态（跨输出块保留）
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+enum OscState {
+    #[default]
+    Text,
+    Escape,
+    Csi,
+    Osc,
+    OscEscape,
+}
+
+impl Hyperlinks {
+    /// 解析一段输出
+    pub fn feed(&mut self, data: &[u8]) {
+        for &byte in data {
+            self.state = match (self.state, byte) {
+                (OscState::Text, 0x1b) => OscState::Escape,
+                (OscState::Text, byte) => {
+                    if byte == b'\n' {
+                        self.lines += 1;
+                    }
+                    if let Some((_, text)) = self.open.as_mut() {
+                        if byte >= 0x20 && text.len() < MAX_OSC_LEN {
+                            text.push(byte);
+                        }
+                    }
+                    OscState::Text
+                }
+                (OscState::Escape, b'[') => {
+                    self.csi.clear();
+                    OscState::Csi
+                }
+                // 终端重置（RIS）后之前的输出都不在屏幕上了
+                (OscState::Escape, b'c') => {
+                    self.links.clear();
+                    OscState::Text
+                }
+                (OscState::Escape, b']') => {
+                    self.osc.clear();
+                    OscState::Osc
+                }
+                (OscState::Escape, _) => OscState::Text,
+                (OscState::Csi, 0x40..=0x7e) => {
+                    // 清屏（`ESC [2J`、`ESC [3J`）
+                    if byte == b'J' && matches!(self.csi.as_slice(), b"2" | b"3") {
+                        self.links.clear();
+                    }
+                    OscState::Text
+                }
+                (OscState::Csi, byte) => {
+                    if self.csi.len() < MAX_CSI_LEN {
+                        self.csi.push(byte);
+                    }
+                    OscState::Csi
+                }
+                (OscState::Osc, 0x07) | (OscState::OscEscape, b'\\') => {
+                    self.finish_osc();
+                    OscState::Text
+                }
+                (OscState::Osc, 0x1b) => OscState::OscEscape,
+                (OscState::Osc, byte) => {
+                    if self.osc.len() < MAX_OSC_LEN {
+                        self.osc.push(byte);
+                    }
+                    OscState::Osc
+                }
+                (OscState::OscEscape, _) => OscState::Text,
+            };
+        }
+    }
+
+    /// OSC 序列结束：`8;参数;地址` 打开链接，地址为空时关闭链接
+    fn finish_osc(&mut self) {
+        let osc = String::from_utf8_lossy(&self.osc);
+        let Some(rest) = osc.strip_prefix("8;") else {
+            return;
+        };
+        let url = rest.split_once(';').map(|(_, url)| url).unwrap_or_default();
+        if let Some((open_url, text)) = self.open.take() {
+            let text = String::from_utf8_lossy(&text).trim().to_string();
+            if text.chars().count() >= MIN_LINK_TEXT_LEN {
+                self.links.push_back(Hyperlink {
+                    text,
+                    url: open_url,
+                    line: self.lines,
+                });
+                if self.links.len() > MAX_HYPERLINKS {
+                    self.links.pop_front();
+                }
+            }
+        }
+        // 只接受网页链接，file:// 等其他协议的目标可能打开或执行本地文件
+        if is_web_url(url) {
+            self.open = Some((url.to_string(), Vec::new()));
+        }
+    }
+
+    /// 查找一行文本中出现的、在这一行输出的超链接文本
+    /// lines_above 为这一行在光标所在行之上的行数（自动换行的折行不计）
+    fn find(&self, text: &str, lines_above: usize) -> Vec<UrlRef> {
+        self.links
+            .iter()
+            .filter(|link| self.lines - link.line == lines_above)
+            .flat_map(|link| {
+                text.match_indices(link.text.as_str())
+                    .map(|(idx, m)| UrlRef {
+                        start: display_column(text, idx),
+                        len: m.width(),
+                        url: link.url.clone(),
+                    })
+            })
+            .collect()
+    }
+}
+
+/// 查找一行文本中的链接（纯文本 URL 和 OSC 8 超链接，重叠时保留先出现的）
+/// lines_above 为这一行在光标所在行之上的行数，为 None 时（光标之下的行）不查找超链接
+pub fn find_urls(text: &str, hyperlinks: &Hyperlinks, lines_above: Option<usize>) -> Vec<UrlRef> {
+    let mut urls: Vec<UrlRef> = URL
+        .find_iter(text)
+        .map(|m| {
+            // 去掉句末标点和未配对的右括号
+            let mut url = m.as_str();
+            while let Some(last) = url.chars().last() {
+                let unbalanced = last == ')' && url.matches('(').count() < url.matches(')').count();
+                if ".,;:!?'".contains(last) || unbalanced {
+                    url = &url[..url.len() - last.len_utf8()];
+                } else {
+                    break;
+                }
+            }
+            UrlRef {
+                start: display_column(text, m.start()),
+                len: url.width(),
+                url: url.to_string(),
+            }
+        })
+        .collect();
+    if let Some(lines_above) = lines_above {
+        urls.extend(hyperlinks.find(text, lines_above));
+    }
+    urls.sort_by_key(|u| u.start);
+    let mut end = 0;
+    urls.retain(|u| {
+        let keep = u.start >= end;
+        if keep {
+            end = u.start + u.len;
+        }
+        keep
+    });
+    urls
+}
+
+/// 开发服务器打印的本机访问地址（`0.0.0.0` 替换为 `localhost`）
+pub fn local_url(text: &str) -> Option<String> {
+    URL.find_iter(text)
+        .map(|m| m.as_str().trim_end_matches(['.', ',', ';', ')']))
+        .find(|url| LOCAL_URL.is_match(url))
+        .map(|url| url.replacen("0.0.0.0", "localhost", 1))
+}
+
+/// 地址中的端口（未写端口时按协议取 80 或 443）
+pub fn url_port(url: &str) -> Option<u16> {
+    let (scheme, rest) = url.split_once("://")?;
+    let host = rest.split('/').next()?;
+    match host.rsplit_once(':') {
+        Some((_, port)) if !port.ends_with(']') => port.parse().ok(),
+        _ => Some(if scheme == "https" { 443 } else { 80 }),
+    }
+}
+
+/// 查找一行文本中的文件位置引用
+pub fn find_file_refs(text: &str) -> Vec<FileRef> {
+    let mut refs = Vec::new();
+    for caps in FILE_REF.captures_iter(text) {
+        let (Some(whole), Some(file)) = (caps.get(0), caps.name("file")) else {
+            continue;
+        };
+        // 跳过 URL（`http://host/app.js:10` 中的 `//host/app.js`）
+        if file.as_str().starts_with("//")
+            || file.as_str().contains("://")
+            || text[..whole.start()].ends_with(':')
+        {
+            continue;
+        }
+        let line = caps.name("line").or_else(|| caps.name("pline"));
+        let Some(line) = line.and_then(|m| m.as_str().parse().ok()) else {
+            continue;
+        };
+        let column = caps
+            .name("col")
+            .or_else(|| caps.name("pcol"))
+            .and_then(|m| m.as_str().parse().ok());
+        refs.push(file_ref(text, whole, file.as_str(), line, column));
+    }
+    for caps in PYTHON_REF.captures_iter(text) {
+        let (Some(whole), Some(file), Some(line)) = (
+            caps.get(0),
+            caps.name("file"),
+            caps.name("line").and_then(|m| m.as_str().parse().ok()),
+        ) else {
+            continue;
+        };
+        // 上面的正则可能已经匹配了引号中带扩展名的部分，只去掉与这个回溯位置重叠的结果
+        let start = display_column(text, whole.start());
+        let end = start + whole.as_str().width();
+        refs.retain(|r| r.start + r.len <= start || r.start >= end);
+        refs.push(file_ref(text, whole, file.as_str(), line, None));
+    }
+    refs.sort_by_key(|r| r.start);
+    refs
+}
+
+/// 为 count 个目标生成提示标签：不超过字符数时用单个字符，否则用两个字符
+pub fn hint_labels(count: usize) -> Vec<String> {
+    let chars: Vec<char> = HINT_CHARS.chars().collect();
+    if count <= chars.len() {
+        return chars.iter().take(count).map(|c| c.to_string()).collect();
+    }
+    chars
+        .iter()
+        .flat_map(|a| chars.iter().map(move |b| format!("{}{}", a, b)))
+        .take(count)
+        .collect()
+}
+
+fn file_ref(
+    text: &str,
+    whole: regex::Match,
+    file: &str,
+    line: u32,
+    column: Option<u32>,
+) -> FileRef {
+    FileRef {
+        start: display_column(text, whole.start()),
+        len: whole.as_str().width(),
+        file: file.to_string(),
+        line,
+        column,
+    }
+}
+
+/// 字节位置转换为终端列（宽字符占两列）
+fn display_column(text: &str, byte_idx: usize) -> usize {
+    text[..byte_idx].width()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn refs(text: &str) -> Vec<(String, u32, Option<u32>)> {
+        find_file_refs(text)
+            .into_iter()
+            .map(|r| (r.file, r.line, r.column))
+            .collect()
+    }
+
+    #[test]
+    fn test_find_file_refs() {
+        assert_eq!(
+            refs("ERROR in src/foo.ts:42:7 and ./lib/a.js:3"),
+            vec![
+                ("src/foo.ts".to_string(), 42, Some(7)),
+                ("./lib/a.js".to_string(), 3, None),
+            ]
+        );
+        assert_eq!(
+            refs("    at render (/app/node_modules/react/index.js:10:5)"),
+            vec![("/app/node_modules/react/index.js".to_string(), 10, Some(5))]
+        );
+        assert_eq!(
+            refs("src/App.tsx(12,3): error TS2322"),
+            vec![("src/App.tsx".to_string(), 12, Some(3))]
+        );
+        assert_eq!(
+            refs(r#"  File "app/main.py", line 8, in <module>"#),
+            vec![("app/main.py".to_string(), 8, None)]
+        );
+        assert!(refs("Local: http://localhost:5173/ on 127.0.0.1:80").is_empty());
+        assert!(refs("see https://example.com/app.js:10").is_empty());
+        assert_eq!(
+            refs(r"C:\work\app\index.ts:5:1"),
+            vec![(r"C:\work\app\index.ts".to_string(), 5, Some(1))]
+        );
+
+        let found = find_file_refs("→ über.rs:1");
+        assert_eq!((found[0].start, found[0].len), (2, 9));
+        // 宽字符占两列
+        let found = find_file_refs("错误 src/组件.ts:3");
+        assert_eq!((found[0].start, found[0].len), (5, 13));
+        // 回溯之后同一行的其他引用保留
+        assert_eq!(
+            refs(r#"File "a.py", line 2, see b.rs:4"#),
+            vec![("a.py".to_string(), 2, None), ("b.rs".to_string(), 4, None)]
+        );
+    }
+
+    #[test]
+    fn test_find_urls() {
+        let mut hyperlinks = Hyperlinks::default();
+        // OSC 8 链接分两段输出，ST 和 BEL 两种结束方式
+        hyperlinks.feed(b"\x1b]8;;https://docs.rs/vt100\x1b\\vt1");
+        hyperlinks.feed(b"00 docs\x1b]8;;\x07 done");
+
+        let text = "see vt100 docs (https://example.com/a_(b)), or http://localhost:5173/.";
+        let urls: Vec<_> = find_urls(text, &hyperlinks, Some(0))
+            .into_iter()
+            .map(|u| (u.start, u.url))
+            .collect();
+        assert_eq!(
+            urls,
+            vec![
+                (4, "https://docs.rs/vt100".to_string()),
+                (16, "https://example.com/a_(b)".to_string()),
+                (47, "http://localhost:5173/".to_string()),
+            ]
+        );
+        assert_eq!(
+            local_url("  ➜  Network: http://0.0.0.0:3000/ docs https://vitejs.dev"),
+            Some("http://localhost:3000/".to_string())
+        );
+        assert_eq!(local_url("see https://vitejs.dev"), None);
+        assert_eq!(url_port("http://localhost:5173/"), Some(5173));
+        assert_eq!(url_port("http://[::1]:3000"), Some(3000));
+        assert_eq!(url_port("https://localhost/app"), Some(443));
+        assert_eq!(url_port("http://[::1]/"), Some(80));
+    }
+
+    #[test]
+    fn test_hyperlinks_scoped_to_their_line() {
+        let mut hyperlinks = Hyperlinks::default();
+        hyperlinks.feed(b"\x1b]8;;https://a.dev\x07docs\x1b]8;;\x07\r\n");
+        // 太短和只有空白的链接文本不记录
+        hyperlinks.feed(
+            b"\x1b]8;;https://b.dev\x07x\x1b]8;;\x07 \x1b]8;;https://c.dev\x07  \x1b]8;;\x07",
+        );
+
+        let urls = |hyperlinks: &Hyperlinks, lines_above| {
+            find_urls("read the docs", hyperlinks, lines_above)
+                .into_iter()
+                .map(|u| u.url)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(urls(&hyperlinks, Some(1)), ["https://a.dev"]);
+        assert!(urls(&hyperlinks, Some(0)).is_empty());
+        assert!(urls(&hyperlinks, None).is_empty());
+        assert!(find_urls("x", &hyperlinks, Some(0)).is_empty());
+
+        // 清屏后之前的链接不再有效
+        hyperlinks.feed(b"\x1b[2J");
+        assert!(urls(&hyperlinks, Some(1)).is_empty());
+
+        // 非 http(s) 的目标不记录
+        hyperlinks.feed(b"\x1b]8;;file:///etc/passwd\x07docs\x1b]8;;\x07");
+        assert!(urls(&hyperlinks, Some(0)).is_empty());
+        assert!(find_urls("file:///tmp/a.html", &hyperlinks, None).is_empty());
+    }
+
+    #[test]
+    fn test_hint_labels() {
+        assert_eq!(hint_labels(3), ["a", "s", "d"]);
+        let many = hint_labels(30);
+        assert_eq!(many.len(), 30);
+        assert_eq!(many[0], "aa");
+        assert_eq!(many[26], "sa");
+    }
+}
@@ -4,6 +4,7 @@
 use super::{PtyEvent, PtyHandle};
 use crate::platform::get_default_shell;
 use portable_pty::{native_pty_system, CommandBuilder, PtySize};
+use std::collections::BTreeMap;
 use std::io::Read;
 use std::path::Path;
 use std::sync::Arc;
@@ -51,7 +52,8 @@ impl PtyManager {
         #[cfg(windows)]
         let args = vec![];
 
-        self.create_pty(id, &shell, &args, working_dir, rows, cols, event_tx)
+        let env = BTreeMap::new();
+        self.create_pty(id, &shell, &args, working_dir, &env, rows, cols, event_tx)
     }
 
     /// 创建执行指定命令的 PTY
@@ -66,17 +68,20 @@ impl PtyManager {
         cols: u16,
         event_tx: mpsc::UnboundedSender<PtyEvent>,
     ) -> anyhow::Result<PtyHandle> {
-        self.create_pty(id, command, args, working_dir, rows, cols, event_tx)
+        let env = BTreeMap::new();
+        self.create_pty(id, command, args, working_dir, &env, rows, cols, event_tx)
     }
 
     /// 通过 Shell 执行命令字符串
+    /// env: 额外的环境变量
     /// shell_config: Windows 上的 Shell 类型配置
-    #[allow(unused_variables)]
+    #[allow(unused_variables, clippy::too_many_arguments)]
     pub fn run_shell_command(
         &self,
         id: &str,
         command: &str,
         working_dir: &Path,
+        env: &BTreeMap<String, String>,
         rows: u16,
         cols: u16,
         event_tx: mpsc::UnboundedSender<PtyEvent>,
@@ -100,7 +105,7 @@ impl PtyManager {
             }
         };
 
-        self.create_pty(id, &shell, &args, working_dir, rows, cols, event_tx)
+        self.create_pty(id, &shell, &args, working_dir, env, rows, cols, event_tx)
     }
 
     /// 内部方法：创建 PTY
@@ -119,6 +124,7 @@ impl PtyManager {
         program: &str,
         args: &[&str],
         working_dir: &Path,
+        env: &BTreeMap<String, String>,
         rows: u16,
         cols: u16,
         event_tx: tokio::sync::mpsc::UnboundedSender<PtyEvent>,
@@ -229,9 +235,14 @@ impl PtyManager {
             }
         }
 
+        // 命令配置的额外环境变量（放在继承之后，可以覆盖 PATH、LANG 等）
+        for (key, value) in env {
+            cmd.env(key, value);
+        }
+
         // 启动子进程（Windows 上添加重试逻辑，专门处理 0xc0000142 错误）
         #[cfg(windows)]
-        let child = {
+        let mut child = {
             // 0xc0000142 作为有符号 32 位整数是 -1073741502
             const STATUS_DLL_INIT_FAILED: i32 = -1073741502;
             let max_retries = 5; // 增加重试次数
@@ -300,7 +311,7 @@ impl PtyManager {
         };
 
         #[cfg(unix)]
-        let child = pair.slave.spawn_command(cmd)?;
+        let mut child = pair.slave.spawn_command(cmd)?;
 
         // 获取子进程 PID（用于后续发送信号）
         let pid = child.process_id();
@@ -326,10 +337,11 @@ impl PtyManager {
             loop {
                 match reader.read(&mut buffer) {
                     Ok(0) => {
-                        // EOF - 进程已退出
+                        // EOF - 进程已退出，回收子进程以获取退出码
+                        let exit_code = child.wait().ok().map(|status| status.exit_code() as i32);
                         let _ = event_tx.send(PtyEvent::Exited {
                             pty_id: pty_id.clone(),
-                            exit_code: None,
+                            exit_code,
                         });
                         break;
                     }
@@ -456,3 +468,48 @@ impl Default for PtyManager {
         Self::new()
     }
 }
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn test_command_env_overrides_inherited_path() {
+        let manager = PtyManager::new();
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let env = BTreeMap::from([(
+            "PATH".to_string(),
+            "/opt/node/bin:/usr/bin:/bin".to_string(),
+        )]);
+        let _handle = manager
+            .create_pty(
+                "env-test",
+                "/bin/sh",
+                &["-c", "echo $PATH"],
+                Path::new("/"),
+                &env,
+                24,
+                80,
+                tx,
+            )
+            .unwrap();
+
+        let mut output = Vec::new();
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while Instant::now() < deadline {
+            match rx.try_recv() {
+                Ok(PtyEvent::Output { data, .. }) => output.extend(data),
+                Ok(PtyEvent::Exited { .. }) => break,
+                Ok(_) => {}
+                Err(_) => std::thread::sleep(Duration::from_millis(10)),
+            }
+        }
+        let output = String::from_utf8_lossy(&output);
+        assert!(
+            output.contains("/opt/node/bin:/usr/bin:/bin"),
+            "unexpected output: {:?}",
+            output
+        );
+    }
+}
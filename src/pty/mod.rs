@@ -5,16 +5,21 @@
 
 mod bridge;
 mod handle;
+mod links;
 mod manager;
 mod process_tree;
 mod resource;
+mod stop;
 
 pub use bridge::*;
 pub use handle::*;
+pub use links::*;
 pub use manager::*;
+pub use process_tree::{process_tree_info, renice, ProcessInfo};
 // ProcessResourceUsage 通过 PtyHandle.resource_usage 字段对外可用
 #[allow(unused_imports)]
 pub use resource::*;
+pub use stop::*;
 
 /// PTY 事件
 /// 用于在异步任务和主线程之间传递 PTY 相关事件
@@ -27,6 +32,11 @@ pub enum PtyEvent {
         pty_id: String,
         exit_code: Option<i32>,
     },
+    /// 按停止序列停止的进程树已退出
+    Stopped {
+        pty_id: String,
+        outcome: StopOutcome,
+    },
     /// 错误发生
     Error { pty_id: String, message: String },
 }
@@ -115,3 +115,173 @@ pub unsafe fn get_process_tree(root_pid: u32) -> anyhow::Result<HashSet<u32>> {
 
     Ok(result)
 }
+
+/// 进程树中的一个进程（用于进程树弹窗）
+#[derive(Debug, Clone)]
+pub struct ProcessInfo {
+    pub pid: u32,
+    /// 在树中的深度（根进程为 0）
+    pub depth: usize,
+    /// 命令行（读取不到时为进程名）
+    pub command: String,
+    /// CPU 使用率（按核心数归一化，与侧边栏一致）
+    pub cpu_percent: f32,
+    pub memory_bytes: u64,
+    pub status: sysinfo::ProcessStatus,
+    /// 运行时长（秒）
+    pub run_time: u64,
+    /// nice 值（Windows 上为 None）
+    pub nice: Option<i32>,
+}
+
+impl ProcessInfo {
+    /// 是否已被暂停（SIGSTOP）
+    pub fn is_stopped(&self) -> bool {
+        self.status == sysinfo::ProcessStatus::Stop
+    }
+}
+
+/// 收集进程树中每个进程的详细信息，按树的先序排列
+pub fn process_tree_info(system: &sysinfo::System, root_pid: sysinfo::Pid) -> Vec<ProcessInfo> {
+    let pids = collect_process_tree(system, root_pid);
+    let nodes: Vec<(u32, Option<u32>)> = pids
+        .iter()
+        .filter_map(|pid| system.process(*pid))
+        .map(|p| (p.pid().as_u32(), p.parent().map(|parent| parent.as_u32())))
+        .collect();
+    let cpu_count = system.cpus().len().max(1) as f32;
+    tree_order(&nodes)
+        .into_iter()
+        .filter_map(|(pid, depth)| {
+            let process = system.process(sysinfo::Pid::from_u32(pid))?;
+            let cmd: Vec<_> = process
+                .cmd()
+                .iter()
+                .map(|arg| arg.to_string_lossy())
+                .collect();
+            let command = if cmd.is_empty() {
+                process.name().to_string_lossy().to_string()
+            } else {
+                cmd.join(" ")
+            };
+            Some(ProcessInfo {
+                pid,
+                depth,
+                command,
+                cpu_percent: process.cpu_usage() / cpu_count,
+                memory_bytes: process.memory(),
+                status: process.status(),
+                run_time: process.run_time(),
+                nice: nice(pid),
+            })
+        })
+        .collect()
+}
+
+/// 把 (pid, 父进程) 列表按树的先序排列，返回 (pid, 深度)
+/// 父进程不在列表中的进程作为根（按出现顺序），子进程按 pid 排序
+fn tree_order(nodes: &[(u32, Option<u32>)]) -> Vec<(u32, usize)> {
+    let pids: HashSet<u32> = nodes.iter().map(|(pid, _)| *pid).collect();
+    let mut children: std::collections::HashMap<u32, Vec<u32>> = Default::default();
+    let mut roots = Vec::new();
+    for &(pid, parent) in nodes {
+        match parent.filter(|p| pids.contains(p) && *p != pid) {
+            Some(parent) => children.entry(parent).or_default().push(pid),
+            None => roots.push(pid),
+        }
+    }
+    let mut result = Vec::new();
+    let mut stack: Vec<(u32, usize)> = roots.into_iter().rev().map(|pid| (pid, 0)).collect();
+    let mut visited = HashSet::new();
+    while let Some((pid, depth)) = stack.pop() {
+        if !visited.insert(pid) {
+            continue;
+        }
+        result.push((pid, depth));
+        if let Some(kids) = children.get_mut(&pid) {
+            kids.sort_unstable();
+            stack.extend(kids.iter().rev().map(|&kid| (kid, depth + 1)));
+        }
+    }
+    result
+}
+
+/// 读取进程的 nice 值
+/// getpriority 出错时也返回 -1，需要先清空 errno 再检查
+#[cfg(unix)]
+fn nice(pid: u32) -> Option<i32> {
+    unsafe {
+        *errno_location() = 0;
+        let nice = libc::getpriority(libc::PRIO_PROCESS, pid as libc::id_t);
+        (nice != -1 || *errno_location() == 0).then_some(nice)
+    }
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+unsafe fn errno_location() -> *mut libc::c_int {
+    libc::__errno_location()
+}
+
+#[cfg(all(unix, not(any(target_os = "linux", target_os = "android"))))]
+unsafe fn errno_location() -> *mut libc::c_int {
+    libc::__error()
+}
+
+#[cfg(windows)]
+fn nice(_pid: u32) -> Option<i32> {
+    None
+}
+
+/// 调整进程的 nice 值（delta 为正表示降低优先级），返回调整后的值
+/// 普通用户通常只能调高 nice 值
+#[cfg(unix)]
+pub fn renice(pid: u32, delta: i32) -> anyhow::Result<i32> {
+    let current = nice(pid).ok_or_else(std::io::Error::last_os_error)?;
+    let nice = (current + delta).clamp(-20, 19);
+    unsafe {
+        if libc::setpriority(libc::PRIO_PROCESS, pid as libc::id_t, nice) != 0 {
+            return Err(std::io::Error::last_os_error().into());
+        }
+    }
+    Ok(nice)
+}
+
+/// Windows 不支持调整 nice 值
+#[cfg(windows)]
+pub fn renice(_pid: u32, _delta: i32) -> anyhow::Result<i32> {
+    anyhow::bail!("renice is not supported on Windows")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tree_order() {
+        // sh(10) -> pnpm(20) -> [vite(40), esbuild(30) -> worker(50)]
+        let nodes = [
+            (10, Some(1)),
+            (40, Some(20)),
+            (20, Some(10)),
+            (50, Some(30)),
+            (30, Some(20)),
+        ];
+        assert_eq!(
+            tree_order(&nodes),
+            vec![(10, 0), (20, 1), (30, 2), (50, 3), (40, 2)]
+        );
+        // 根进程已退出时，剩下的子树各自作为根
+        assert_eq!(
+            tree_order(&[(30, Some(20)), (50, Some(30)), (40, Some(20))]),
+            vec![(30, 0), (50, 1), (40, 0)]
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_nice_missing_process() {
+        assert!(nice(std::process::id()).is_some());
+        // 不存在的进程不能被当成 nice 值 -1
+        assert_eq!(nice(i32::MAX as u32), None);
+    }
+}
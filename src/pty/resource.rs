@@ -1,7 +1,12 @@
 //! 进程资源使用信息模块
+//! 包含最新一次采样、最近 15 分钟的采样历史（用于趋势图）和资源上限检查
+
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
 
 /// 进程资源使用信息
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
 pub struct ProcessResourceUsage {
     /// CPU 使用率（百分比，0.0-100.0+）
     pub cpu_percent: f32,
@@ -46,3 +51,231 @@ impl ProcessResourceUsage {
         }
     }
 }
+
+/// 资源历史保留的时长
+pub const HISTORY_WINDOW: Duration = Duration::from_secs(15 * 60);
+
+/// 一次资源采样
+#[derive(Debug, Clone, Copy)]
+pub struct ResourceSample {
+    pub at: Instant,
+    pub usage: ProcessResourceUsage,
+}
+
+/// 资源使用历史（环形缓冲区，只保留 HISTORY_WINDOW 内的采样）
+#[derive(Debug, Clone, Default)]
+pub struct ResourceHistory {
+    samples: VecDeque<ResourceSample>,
+}
+
+impl ResourceHistory {
+    /// 记录一次采样，并丢弃超出时间窗口的旧采样
+    pub fn push(&mut self, at: Instant, usage: ProcessResourceUsage) {
+        self.samples.push_back(ResourceSample { at, usage });
+        while self
+            .samples
+            .front()
+            .is_some_and(|s| at.duration_since(s.at) > HISTORY_WINDOW)
+        {
+            self.samples.pop_front();
+        }
+    }
+
+    pub fn samples(&self) -> impl Iterator<Item = &ResourceSample> {
+        self.samples.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.samples.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.samples.is_empty()
+    }
+
+    /// 时间窗口内的峰值（CPU 和内存分别取最大值）
+    pub fn peak(&self) -> ProcessResourceUsage {
+        self.samples
+            .iter()
+            .fold(ProcessResourceUsage::default(), |peak, s| {
+                ProcessResourceUsage {
+                    cpu_percent: peak.cpu_percent.max(s.usage.cpu_percent),
+                    memory_bytes: peak.memory_bytes.max(s.usage.memory_bytes),
+                }
+            })
+    }
+
+    /// 最近 duration 内的 CPU 是否一直高于 limit（历史不足 duration 时返回 false）
+    pub fn cpu_above_for(&self, limit: f32, duration: Duration, now: Instant) -> bool {
+        let Some(since) = now.checked_sub(duration) else {
+            return false;
+        };
+        self.samples.front().is_some_and(|s| s.at <= since)
+            && self
+                .samples
+                .iter()
+                .filter(|s| s.at >= since)
+                .all(|s| s.usage.cpu_percent > limit)
+    }
+
+    /// 内存趋势的迷你图（整个时间窗口压缩到 width 个字符）
+    pub fn memory_sparkline(&self, width: usize) -> String {
+        let values: Vec<u64> = self.samples.iter().map(|s| s.usage.memory_bytes).collect();
+        sparkline(&values, width)
+    }
+}
+
+/// 超过资源上限后的处理方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LimitAction {
+    /// 只在侧边栏高亮并在状态栏提示
+    #[default]
+    Warn,
+    /// 同时发送通知（按 notifications 配置）
+    Notify,
+    /// 通知并暂停进程
+    Suspend,
+    /// 通知并重新运行命令
+    Restart,
+}
+
+/// 资源上限（全局配置或单个项目配置）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ResourceLimits {
+    /// 内存上限（MB，包括子进程）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub memory_mb: Option<u64>,
+    /// CPU 上限（占整机的百分比，与侧边栏显示一致）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cpu_percent: Option<f32>,
+    /// CPU 持续超过上限多少秒才算超限
+    #[serde(default = "default_cpu_secs")]
+    pub cpu_secs: u64,
+    /// 超限后的处理方式
+    #[serde(default)]
+    pub action: LimitAction,
+}
+
+fn default_cpu_secs() -> u64 {
+    30
+}
+
+/// 超过的资源上限
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LimitBreach {
+    /// 内存超限（当前内存字节数）
+    Memory(u64),
+    /// CPU 持续超限（当前 CPU 百分比）
+    Cpu(f32),
+}
+
+impl ResourceLimits {
+    /// 按采样历史检查是否超限（内存优先）
+    pub fn check(&self, history: &ResourceHistory, now: Instant) -> Option<LimitBreach> {
+        let latest = history.samples.back()?.usage;
+        if let Some(mb) = self.memory_mb {
+            if latest.memory_bytes > mb * 1024 * 1024 {
+                return Some(LimitBreach::Memory(latest.memory_bytes));
+            }
+        }
+        let limit = self.cpu_percent?;
+        history
+            .cpu_above_for(limit, Duration::from_secs(self.cpu_secs), now)
+            .then_some(LimitBreach::Cpu(latest.cpu_percent))
+    }
+}
+
+/// 把数值序列压缩成 width 个方块字符（每格取区间内最大值，高度相对整体最大值）
+pub fn sparkline(values: &[u64], width: usize) -> String {
+    const BARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+    if values.is_empty() || width == 0 {
+        return String::new();
+    }
+    let width = width.min(values.len());
+    let max = values.iter().copied().max().unwrap_or(0).max(1);
+    (0..width)
+        .map(|i| {
+            let start = i * values.len() / width;
+            let end = ((i + 1) * values.len() / width).max(start + 1);
+            let value = values[start..end].iter().copied().max().unwrap_or(0);
+            BARS[((value * (BARS.len() as u64 - 1)) / max) as usize]
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn usage(cpu_percent: f32, memory_bytes: u64) -> ProcessResourceUsage {
+        ProcessResourceUsage {
+            cpu_percent,
+            memory_bytes,
+        }
+    }
+
+    #[test]
+    fn test_history_window_and_peak() {
+        let start = Instant::now();
+        let mut history = ResourceHistory::default();
+        history.push(start, usage(80.0, 100));
+        history.push(start + Duration::from_secs(60), usage(10.0, 300));
+        history.push(start + Duration::from_secs(120), usage(20.0, 200));
+        assert_eq!(history.len(), 3);
+        assert_eq!(history.peak(), usage(80.0, 300));
+
+        // 超出 15 分钟的采样被丢弃
+        history.push(
+            start + HISTORY_WINDOW + Duration::from_secs(30),
+            usage(5.0, 250),
+        );
+        assert_eq!(history.len(), 3);
+        assert_eq!(history.peak(), usage(20.0, 300));
+    }
+
+    #[test]
+    fn test_resource_limits() {
+        let start = Instant::now();
+        let limits = ResourceLimits {
+            memory_mb: Some(1),
+            cpu_percent: Some(50.0),
+            cpu_secs: 10,
+            action: LimitAction::Warn,
+        };
+        let mut history = ResourceHistory::default();
+        assert_eq!(limits.check(&history, start), None);
+
+        // CPU 超限但还不满 10 秒
+        for secs in 0..=5 {
+            history.push(start + Duration::from_secs(secs), usage(90.0, 1024));
+        }
+        assert_eq!(limits.check(&history, start + Duration::from_secs(5)), None);
+
+        // 持续 10 秒后超限，中间一次回落则重新计时
+        for secs in 6..=10 {
+            history.push(start + Duration::from_secs(secs), usage(90.0, 1024));
+        }
+        let now = start + Duration::from_secs(10);
+        assert_eq!(limits.check(&history, now), Some(LimitBreach::Cpu(90.0)));
+        history.push(start + Duration::from_secs(11), usage(10.0, 1024));
+        history.push(start + Duration::from_secs(12), usage(90.0, 1024));
+        let now = start + Duration::from_secs(12);
+        assert_eq!(limits.check(&history, now), None);
+
+        // 内存立即超限
+        history.push(now, usage(0.0, 2 * 1024 * 1024));
+        assert_eq!(
+            limits.check(&history, now),
+            Some(LimitBreach::Memory(2 * 1024 * 1024))
+        );
+    }
+
+    #[test]
+    fn test_sparkline() {
+        assert_eq!(sparkline(&[], 8), "");
+        assert_eq!(sparkline(&[0, 7], 8), "▁█");
+        assert_eq!(sparkline(&[1, 2, 3, 4, 5, 6, 7, 8], 4), "▂▄▆█");
+        assert_eq!(sparkline(&[5, 5, 5], 3), "███");
+    }
+}
@@ -0,0 +1,280 @@
+//! 优雅停止
+//! 按信号序列（如 SIGINT → SIGTERM → SIGKILL）逐步停止进程组，每步之后等待进程树退出，超时再发送下一个信号
+//! 升级过程在独立线程中进行，进程树完全退出后发送 PtyEvent::Stopped
+
+use super::{PtyEvent, PtyHandle};
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc::UnboundedSender;
+
+/// 停止时发送的信号
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum StopSignal {
+    #[serde(rename = "SIGINT", alias = "INT")]
+    Int,
+    #[serde(rename = "SIGTERM", alias = "TERM")]
+    Term,
+    #[serde(rename = "SIGHUP", alias = "HUP")]
+    Hup,
+    #[serde(rename = "SIGQUIT", alias = "QUIT")]
+    Quit,
+    #[serde(rename = "SIGKILL", alias = "KILL")]
+    Kill,
+}
+
+impl StopSignal {
+    pub fn name(&self) -> &'static str {
+        match self {
+            StopSignal::Int => "SIGINT",
+            StopSignal::Term => "SIGTERM",
+            StopSignal::Hup => "SIGHUP",
+            StopSignal::Quit => "SIGQUIT",
+            StopSignal::Kill => "SIGKILL",
+        }
+    }
+
+    #[cfg(unix)]
+    fn raw(&self) -> i32 {
+        match self {
+            StopSignal::Int => libc::SIGINT,
+            StopSignal::Term => libc::SIGTERM,
+            StopSignal::Hup => libc::SIGHUP,
+            StopSignal::Quit => libc::SIGQUIT,
+            StopSignal::Kill => libc::SIGKILL,
+        }
+    }
+}
+
+impl std::fmt::Display for StopSignal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// 停止序列中的一步
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StopStep {
+    pub signal: StopSignal,
+    /// 发送信号后等待进程树退出的时间（毫秒），超时后执行下一步
+    #[serde(default = "default_wait_ms")]
+    pub wait_ms: u64,
+}
+
+fn default_wait_ms() -> u64 {
+    3000
+}
+
+/// 默认停止序列：SIGINT 等待 3 秒，SIGTERM 等待 3 秒，最后 SIGKILL
+pub fn default_stop_sequence() -> Vec<StopStep> {
+    vec![
+        StopStep {
+            signal: StopSignal::Int,
+            wait_ms: 3000,
+        },
+        StopStep {
+            signal: StopSignal::Term,
+            wait_ms: 3000,
+        },
+        StopStep {
+            signal: StopSignal::Kill,
+            wait_ms: 1000,
+        },
+    ]
+}
+
+/// 实际执行的停止序列：为空时使用默认序列，不以 SIGKILL 结尾时补上一步 SIGKILL，确保进程树最终会退出
+pub fn stop_sequence(steps: &[StopStep]) -> Vec<StopStep> {
+    if steps.is_empty() {
+        return default_stop_sequence();
+    }
+    let mut steps = steps.to_vec();
+    if steps.last().map(|s| s.signal) != Some(StopSignal::Kill) {
+        steps.push(StopStep {
+            signal: StopSignal::Kill,
+            wait_ms: 1000,
+        });
+    }
+    steps
+}
+
+/// 停止序列的结果
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StopOutcome {
+    /// 最后发送的信号（进程在发送任何信号前已退出时为 None）
+    pub signal: Option<StopSignal>,
+    /// 进程树是否已全部退出（为 false 时 SIGKILL 之后仍有进程残留）
+    pub gone: bool,
+    /// 从开始停止到进程树退出的时间
+    pub elapsed: Duration,
+}
+
+impl PtyHandle {
+    /// 在后台线程中按停止序列停止进程树（不阻塞主循环）
+    /// steps 为空时使用 defaults；进程树退出后发送 PtyEvent::Stopped
+    /// pid 保留到收到 Stopped 事件，期间退出程序时 Drop 仍会直接终止进程树
+    pub fn stop(&mut self, defaults: &[StopStep], tx: UnboundedSender<PtyEvent>) {
+        let Some(pid) = self.pid else {
+            return;
+        };
+        let steps = if self.stop_steps.is_empty() {
+            stop_sequence(defaults)
+        } else {
+            stop_sequence(&self.stop_steps)
+        };
+        let suspended = self.suspended;
+        let pty_id = self.id.clone();
+        self.stopping_since = Some(Instant::now());
+        self.suspended = false;
+        self.auto_suspended = false;
+        log::info!("Stopping PTY process tree {} with {:?}", pid, steps);
+
+        std::thread::spawn(move || {
+            let outcome = escalate(pid, &steps, suspended);
+            log::info!("PTY process tree {} stopped: {:?}", pid, outcome);
+            let _ = tx.send(PtyEvent::Stopped { pty_id, outcome });
+        });
+    }
+}
+
+/// 依次发送信号，直到进程组全部退出或序列用完
+#[cfg(unix)]
+fn escalate(pid: u32, steps: &[StopStep], suspended: bool) -> StopOutcome {
+    let started = Instant::now();
+    let pgid = match unsafe { libc::getpgid(pid as i32) } {
+        pgid if pgid > 0 => pgid,
+        _ => pid as i32,
+    };
+
+    // 暂停的进程要先恢复，否则只有 SIGKILL 能生效
+    if suspended {
+        unsafe {
+            libc::kill(-pgid, libc::SIGCONT);
+        }
+    }
+
+    let mut signal = None;
+    for step in steps {
+        if !group_alive(pgid) {
+            break;
+        }
+        unsafe {
+            libc::kill(-pgid, step.signal.raw());
+        }
+        signal = Some(step.signal);
+        let deadline = Instant::now() + Duration::from_millis(step.wait_ms);
+        while group_alive(pgid) && Instant::now() < deadline {
+            std::thread::sleep(Duration::from_millis(100));
+        }
+    }
+    StopOutcome {
+        signal,
+        gone: !group_alive(pgid),
+        elapsed: started.elapsed(),
+    }
+}
+
+/// 进程组中是否还有未退出的进程
+#[cfg(unix)]
+fn group_alive(pgid: i32) -> bool {
+    let exists = unsafe {
+        libc::kill(-pgid, 0) == 0
+            || std::io::Error::last_os_error().raw_os_error() == Some(libc::EPERM)
+    };
+    // 已退出但尚未被回收的僵尸进程也会让 kill 成功，Linux 上通过 /proc 排除
+    #[cfg(target_os = "linux")]
+    if exists {
+        return proc_group_alive(pgid);
+    }
+    exists
+}
+
+#[cfg(target_os = "linux")]
+fn proc_group_alive(pgid: i32) -> bool {
+    let Ok(entries) = std::fs::read_dir("/proc") else {
+        return true;
+    };
+    entries
+        .filter_map(Result::ok)
+        .filter(|entry| {
+            entry
+                .file_name()
+                .to_string_lossy()
+                .bytes()
+                .all(|b| b.is_ascii_digit())
+        })
+        .any(|entry| {
+            std::fs::read_to_string(entry.path().join("stat"))
+                .is_ok_and(|stat| stat_alive_in_group(&stat, pgid))
+        })
+}
+
+/// 解析 /proc/<pid>/stat：命令名（括号内）之后依次为状态、ppid、pgid
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+fn stat_alive_in_group(stat: &str, pgid: i32) -> bool {
+    let Some((_, rest)) = stat.rsplit_once(')') else {
+        return false;
+    };
+    let fields: Vec<&str> = rest.split_whitespace().take(3).collect();
+    match fields.as_slice() {
+        [state, _ppid, group] => *state != "Z" && group.parse() == Ok(pgid),
+        _ => false,
+    }
+}
+
+/// Windows 没有可以捕获的终止信号，直接终止整个进程树
+#[cfg(windows)]
+fn escalate(pid: u32, _steps: &[StopStep], _suspended: bool) -> StopOutcome {
+    let started = Instant::now();
+    super::handle::terminate_tree(pid);
+    StopOutcome {
+        signal: Some(StopSignal::Kill),
+        gone: true,
+        elapsed: started.elapsed(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stop_sequence() {
+        assert_eq!(stop_sequence(&[]), default_stop_sequence());
+
+        let term = StopStep {
+            signal: StopSignal::Term,
+            wait_ms: 5000,
+        };
+        let steps = stop_sequence(&[term]);
+        assert_eq!(steps.len(), 2);
+        assert_eq!(steps[0], term);
+        assert_eq!(steps[1].signal, StopSignal::Kill);
+
+        let kill = StopStep {
+            signal: StopSignal::Kill,
+            wait_ms: 500,
+        };
+        assert_eq!(stop_sequence(&[term, kill]), vec![term, kill]);
+    }
+
+    #[test]
+    fn test_stop_step_serde() {
+        let steps: Vec<StopStep> =
+            serde_json::from_str(r#"[{"signal": "SIGINT", "wait_ms": 5000}, {"signal": "TERM"}]"#)
+                .unwrap();
+        assert_eq!(steps[0].signal, StopSignal::Int);
+        assert_eq!(steps[0].wait_ms, 5000);
+        assert_eq!(steps[1].signal, StopSignal::Term);
+        assert_eq!(steps[1].wait_ms, 3000);
+    }
+
+    #[test]
+    fn test_stat_alive_in_group() {
+        let running = "4242 (node (vite)) S 4200 4200 4200 34817 4200 4194560 1200";
+        assert!(stat_alive_in_group(running, 4200));
+        assert!(!stat_alive_in_group(running, 4242));
+        let zombie = "4243 (esbuild) Z 1 4200 4200 0 -1 4227084 300";
+        assert!(!stat_alive_in_group(zombie, 4200));
+        assert!(!stat_alive_in_group("garbage", 4200));
+    }
+}
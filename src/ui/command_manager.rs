@@ -0,0 +1,200 @@
+//! 命令管理弹窗组件
+//! 包含命令列表（个人 / 项目共享）和新建、编辑命令的表单
+
+use crate::app::{AppState, CommandFormField};
+use crate::project::CommandSource;
+use crate::ui::{centered_rect, Theme};
+use ratatui::{
+    layout::{Alignment, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, List, ListItem, ListState, Paragraph},
+    Frame,
+};
+
+/// 绘制命令管理弹窗
+pub fn draw_command_manager(frame: &mut Frame, state: &AppState, theme: &Theme) {
+    let area = centered_rect(60, 60, frame.area());
+    let i18n = state.i18n();
+
+    frame.render_widget(Clear, area);
+
+    let block = Block::default()
+        .title(i18n.command_manager_title())
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(theme.info))
+        .style(Style::default().bg(theme.bg));
+
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let list_area = Rect {
+        height: inner.height.saturating_sub(2),
+        ..inner
+    };
+    let entries = state.manager_entries();
+    if entries.is_empty() {
+        let empty = Paragraph::new(i18n.no_custom_commands())
+            .style(Style::default().fg(theme.border))
+            .alignment(Alignment::Center);
+        frame.render_widget(empty, list_area);
+    } else {
+        let dim = Style::default().fg(theme.border);
+        let items: Vec<ListItem> = entries
+            .iter()
+            .enumerate()
+            .filter_map(|(pos, (source, idx))| {
+                let cmd = state.manager_command(*source, *idx)?;
+                let is_selected = pos == state.command_manager_idx;
+                let style = if is_selected {
+                    Style::default()
+                        .fg(theme.selection_fg)
+                        .bg(theme.selection)
+                        .add_modifier(Modifier::BOLD)
+                } else {
+                    Style::default().fg(theme.fg)
+                };
+                let prefix = if is_selected { "▸ " } else { "  " };
+                let source_label = match source {
+                    CommandSource::Personal => "[me]    ",
+                    CommandSource::Shared => "[shared]",
+                };
+                Some(ListItem::new(Line::from(vec![
+                    Span::styled(prefix, style),
+                    Span::styled(
+                        format!("{} ", source_label),
+                        Style::default().fg(theme.info),
+                    ),
+                    Span::styled(cmd.name.as_str(), style),
+                    Span::styled(format!("  {}", cmd.command), dim),
+                ])))
+            })
+            .collect();
+        let mut list_state = ListState::default();
+        list_state.select(Some(state.command_manager_idx));
+        frame.render_stateful_widget(List::new(items), list_area, &mut list_state);
+    }
+
+    // 底部提示
+    let hint_area = Rect {
+        y: inner.y + inner.height.saturating_sub(1),
+        height: 1,
+        ..inner
+    };
+    let key_style = Style::default().fg(theme.info).add_modifier(Modifier::BOLD);
+    let desc_style = Style::default().fg(theme.fg);
+    let spans: Vec<Span> = i18n
+        .command_manager_hints()
+        .into_iter()
+        .flat_map(|(key, desc)| [Span::styled(key, key_style), Span::styled(desc, desc_style)])
+        .collect();
+    let hint = Paragraph::new(Line::from(spans)).alignment(Alignment::Center);
+    frame.render_widget(hint, hint_area);
+}
+
+/// 绘制新建/编辑命令表单
+pub fn draw_command_form(frame: &mut Frame, state: &AppState, theme: &Theme) {
+    let Some(form) = state.command_form.as_ref() else {
+        return;
+    };
+    let area = centered_rect(60, 60, frame.area());
+    let i18n = state.i18n();
+
+    frame.render_widget(Clear, area);
+
+    let title = if form.editing.is_some() {
+        i18n.edit_command()
+    } else {
+        i18n.add_command()
+    };
+    let block = Block::default()
+        .title(format!(
+            " {} · {} ",
+            title,
+            i18n.command_source_label(form.source)
+        ))
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(theme.border_focused))
+        .style(Style::default().bg(theme.bg));
+
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    // 每个字段占两行：标签和值
+    let mut lines = Vec::new();
+    for (idx, field) in CommandFormField::ALL.iter().enumerate() {
+        let focused = idx == form.focus;
+        let label_style = if focused {
+            Style::default().fg(theme.info).add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(theme.fg)
+        };
+        let marker = if focused { "▸ " } else { "  " };
+        lines.push(Line::from(Span::styled(
+            format!("{}{}", marker, i18n.command_field_label(*field)),
+            label_style,
+        )));
+
+        let value = match field {
+            CommandFormField::Name => form.name.as_str(),
+            CommandFormField::Kind => i18n.command_type_label(&form.cmd_type),
+            CommandFormField::Command => form.command.as_str(),
+            CommandFormField::Cwd => form.cwd.as_str(),
+            CommandFormField::Env => form.env.as_str(),
+            CommandFormField::Description => form.description.as_str(),
+            CommandFormField::Target => i18n.run_target_label(form.target),
+        };
+        let mut spans = vec![Span::raw("    ")];
+        if matches!(field, CommandFormField::Kind | CommandFormField::Target) {
+            spans.push(Span::styled(
+                format!("‹ {} ›", value),
+                Style::default().fg(theme.fg),
+            ));
+        } else if value.is_empty() && !focused {
+            spans.push(Span::styled(
+                i18n.command_field_placeholder(*field, &form.cmd_type),
+                Style::default().fg(theme.border),
+            ));
+        } else {
+            spans.push(Span::styled(value, Style::default().fg(theme.fg)));
+            if focused {
+                spans.push(Span::styled("█", Style::default().fg(theme.info)));
+            }
+        }
+        lines.push(Line::from(spans));
+    }
+    if let Some(error) = &form.error {
+        lines.push(Line::from(Span::styled(
+            format!("  {}", error),
+            Style::default().fg(theme.error),
+        )));
+    }
+    let fields_area = Rect {
+        height: inner.height.saturating_sub(1),
+        ..inner
+    };
+    frame.render_widget(Paragraph::new(lines), fields_area);
+
+    // 底部提示
+    let hint_area = Rect {
+        y: inner.y + inner.height.saturating_sub(1),
+        height: 1,
+        ..inner
+    };
+    let key_style = Style::default().fg(theme.info).add_modifier(Modifier::BOLD);
+    let desc_style = Style::default().fg(theme.fg);
+    let hint = Paragraph::new(Line::from(vec![
+        Span::styled("Tab", key_style),
+        Span::styled(i18n.form_hint_field(), desc_style),
+        Span::styled("←/→", key_style),
+        Span::styled(i18n.form_hint_change(), desc_style),
+        Span::styled("Enter", key_style),
+        Span::styled(i18n.form_hint_save(), desc_style),
+        Span::styled("Esc", key_style),
+        Span::styled(i18n.theme_hint_cancel(), desc_style),
+    ]))
+    .alignment(Alignment::Center);
+    frame.render_widget(hint, hint_area);
+}
@@ -3,12 +3,13 @@
 
 use crate::app::AppState;
 use crate::i18n::I18n;
-use crate::project::CommandType;
+use crate::project::CommandGroup;
 use crate::ui::{centered_rect, draw_scrollbar, ScrollInfo, Theme};
 use ratatui::{
+    layout::Rect,
     style::{Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, BorderType, Borders, Clear, List, ListItem, ListState},
+    widgets::{Block, BorderType, Borders, Clear, List, ListItem, ListState, Paragraph},
     Frame,
 };
 
@@ -29,37 +30,61 @@ pub fn draw_command_palette(frame: &mut Frame, state: &AppState, i18n: &I18n, th
     let inner = block.inner(area);
     frame.render_widget(block, area);
 
-    // 获取当前项目的命令列表
-    let commands = if let Some(project) = state.active_project() {
-        project.get_all_commands()
+    // 顶部过滤输入行
+    let input_area = Rect { height: 1, ..inner };
+    let list_area = Rect {
+        y: inner.y + 1,
+        height: inner.height.saturating_sub(1),
+        ..inner
+    };
+    let input_line = if state.command_filter.is_empty() {
+        Line::from(vec![
+            Span::styled(" > ", Style::default().fg(theme.info)),
+            Span::styled(i18n.filter_placeholder(), Style::default().fg(theme.border)),
+        ])
     } else {
-        Vec::new()
+        Line::from(vec![
+            Span::styled(" > ", Style::default().fg(theme.info)),
+            Span::styled(state.command_filter.as_str(), Style::default().fg(theme.fg)),
+            Span::styled("▏", Style::default().fg(theme.info)),
+        ])
     };
+    frame.render_widget(Paragraph::new(input_line), input_area);
 
-    if commands.is_empty() {
-        let paragraph = ratatui::widgets::Paragraph::new("No commands available")
+    // 获取当前项目的命令列表
+    let commands = state.active_commands();
+    let Some(project) = state.active_project() else {
+        return;
+    };
+    let matches = state.filtered_commands();
+
+    if matches.is_empty() {
+        let text = if commands.is_empty() {
+            i18n.no_commands()
+        } else {
+            i18n.no_matching_commands()
+        };
+        let paragraph = Paragraph::new(text)
             .style(Style::default().fg(theme.border))
             .alignment(ratatui::layout::Alignment::Center);
-        frame.render_widget(paragraph, inner);
+        frame.render_widget(paragraph, list_area);
         return;
     }
 
     // 构建命令列表
-    let items: Vec<ListItem> = commands
+    let items: Vec<ListItem> = matches
         .iter()
         .enumerate()
-        .map(|(idx, cmd)| {
+        .filter_map(|(idx, m)| {
+            let cmd = commands.get(m.command_idx)?;
             let is_selected = idx == state.command_palette_idx;
 
-            // 类型标签
-            let type_label = match cmd.cmd_type {
-                CommandType::NpmScript => "[npm]",
-                CommandType::RawShell => "[raw]",
-            };
-
-            let type_style = match cmd.cmd_type {
-                CommandType::NpmScript => Style::default().fg(theme.info),
-                CommandType::RawShell => Style::default().fg(theme.warning),
+            // 分组标签（等宽对齐，不同分组使用不同颜色）
+            let (type_label, type_style) = match project.command_group(m.command_idx) {
+                CommandGroup::Script => ("[npm]   ", Style::default().fg(theme.info)),
+                CommandGroup::Personal => ("[raw]   ", Style::default().fg(theme.warning)),
+                CommandGroup::Shared => ("[shared]", Style::default().fg(theme.warning)),
+                CommandGroup::Global => ("[global]", Style::default().fg(theme.success)),
             };
 
             let style = if is_selected {
@@ -70,12 +95,32 @@ pub fn draw_command_palette(frame: &mut Frame, state: &AppState, i18n: &I18n, th
             } else {
                 Style::default().fg(theme.fg)
             };
-
-            ListItem::new(Line::from(vec![
-                Span::styled(type_label, type_style),
-                Span::raw(" "),
-                Span::styled(&cmd.name, style),
-            ]))
+            let highlight = style.fg(theme.warning).add_modifier(Modifier::UNDERLINED);
+
+            let mut spans = vec![Span::styled(type_label, type_style), Span::raw(" ")];
+            spans.extend(highlighted_spans(
+                &cmd.name,
+                &m.name_positions,
+                style,
+                highlight,
+            ));
+            // 命令内容以暗色显示在名称后面，便于查看按内容匹配的结果
+            let dim = Style::default().fg(theme.border);
+            spans.push(Span::styled("  ", dim));
+            spans.extend(highlighted_spans(
+                &cmd.command,
+                &m.command_positions,
+                dim,
+                dim.fg(theme.warning),
+            ));
+            if let Some(description) = &cmd.description {
+                spans.push(Span::styled(
+                    format!("  — {}", description),
+                    dim.add_modifier(Modifier::ITALIC),
+                ));
+            }
+
+            Some(ListItem::new(Line::from(spans)))
         })
         .collect();
 
@@ -83,8 +128,8 @@ pub fn draw_command_palette(frame: &mut Frame, state: &AppState, i18n: &I18n, th
 
     let mut list_state = ListState::default();
     let mut scroll_offset = 0usize;
-    let visible_height = inner.height as usize;
-    let total_items = commands.len();
+    let visible_height = list_area.height as usize;
+    let total_items = matches.len();
 
     let selected_idx = state.command_palette_idx;
     list_state.select(Some(selected_idx));
@@ -103,11 +148,47 @@ pub fn draw_command_palette(frame: &mut Frame, state: &AppState, i18n: &I18n, th
         *list_state.offset_mut() = scroll_offset;
     }
 
-    frame.render_stateful_widget(list, inner, &mut list_state);
+    frame.render_stateful_widget(list, list_area, &mut list_state);
 
     // 绘制滚动条
     if total_items > visible_height {
         let scroll_info = ScrollInfo::new(total_items, visible_height, scroll_offset);
-        draw_scrollbar(frame, inner, &scroll_info, theme);
+        draw_scrollbar(frame, list_area, &scroll_info, theme);
     }
 }
+
+/// 将文本按匹配位置拆分为普通和高亮片段
+fn highlighted_spans<'a>(
+    text: &'a str,
+    positions: &[usize],
+    style: Style,
+    highlight: Style,
+) -> Vec<Span<'a>> {
+    if positions.is_empty() {
+        return vec![Span::styled(text, style)];
+    }
+
+    let mut spans = Vec::new();
+    let mut segment_start = 0;
+    let mut segment_highlighted = false;
+    for (char_idx, (byte_idx, _)) in text.char_indices().enumerate() {
+        let is_match = positions.contains(&char_idx);
+        if is_match != segment_highlighted && byte_idx > segment_start {
+            let segment_style = if segment_highlighted {
+                highlight
+            } else {
+                style
+            };
+            spans.push(Span::styled(&text[segment_start..byte_idx], segment_style));
+            segment_start = byte_idx;
+        }
+        segment_highlighted = is_match;
+    }
+    let segment_style = if segment_highlighted {
+        highlight
+    } else {
+        style
+    };
+    spans.push(Span::styled(&text[segment_start..], segment_style));
+    spans
+}
@@ -1,6 +1,6 @@
 //! 确认对话框模块
 
-use crate::app::AppState;
+use crate::app::{AppState, ConfirmAction};
 use crate::ui::Theme;
 use ratatui::{
     layout::Rect,
@@ -43,7 +43,11 @@ pub fn draw_confirm_popup(frame: &mut Frame, state: &AppState, message: &str, th
     let inner = block.inner(area);
     frame.render_widget(block, area);
 
-    let text = format!("{}\n\n{}", message, i18n.yes_no());
+    let hint = match state.confirm_action {
+        ConfirmAction::InstallDependencies(_) => i18n.install_yes_no(),
+        _ => i18n.yes_no(),
+    };
+    let text = format!("{}\n\n{}", message, hint);
     let paragraph = Paragraph::new(text)
         .style(Style::default().fg(theme.fg))
         .alignment(ratatui::layout::Alignment::Center);
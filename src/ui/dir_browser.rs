@@ -33,11 +33,9 @@ pub fn draw_dir_browser(frame: &mut Frame, state: &AppState, theme: &Theme) {
     let help_items_count = 6; // 6个帮助项
     let avg_item_width = 15; // 平均每个项目宽度（包括分隔符）
     let total_width_needed = help_items_count * avg_item_width;
-    let help_lines_needed = if inner.width > 0 {
-        ((total_width_needed as u16) / inner.width).max(1) + 1
-    } else {
-        2
-    };
+    let help_lines_needed = (total_width_needed as u16)
+        .checked_div(inner.width)
+        .map_or(2, |lines| lines.max(1) + 1);
     let help_height = help_lines_needed.min(3); // 最多3行
 
     // 分割内部区域：路径显示 + 目录列表 + 帮助提示
@@ -0,0 +1,191 @@
+//! Git 详情弹窗组件
+//! 显示分支、上游差异和变更文件，切换分支时显示本地分支列表，也可以查看仓库的工作树
+
+use crate::app::AppState;
+use crate::ui::{centered_rect, Theme};
+use ratatui::{
+    layout::{Alignment, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, List, ListItem, ListState, Paragraph},
+    Frame,
+};
+
+/// 绘制 Git 详情弹窗
+pub fn draw_git_popup(frame: &mut Frame, state: &AppState, theme: &Theme) {
+    let Some(git) = state.active_project().and_then(|p| p.git.as_ref()) else {
+        return;
+    };
+    let area = centered_rect(60, 60, frame.area());
+    let i18n = state.i18n();
+    let popup = &state.git_popup;
+
+    frame.render_widget(Clear, area);
+
+    let project_path = state.active_project().map(|p| p.path.as_path());
+    let title = match (&popup.branches, &popup.worktrees) {
+        (Some(_), _) => format!(" {} ", i18n.git_switch_branch()),
+        (None, Some(_)) => format!(" {} ", i18n.git_worktrees()),
+        (None, None) => format!(" Git · {} ", git.branch),
+    };
+    let block = Block::default()
+        .title(title)
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(theme.info))
+        .style(Style::default().bg(theme.bg));
+
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let selected_style = Style::default()
+        .fg(theme.selection_fg)
+        .bg(theme.selection)
+        .add_modifier(Modifier::BOLD);
+    let dim = Style::default().fg(theme.border);
+
+    let (items, selected): (Vec<ListItem>, usize) = match &popup.branches {
+        // 分支列表，当前分支标记 ●
+        Some(branches) => {
+            let items = branches
+                .iter()
+                .enumerate()
+                .map(|(idx, branch)| {
+                    let is_selected = idx == popup.branch_idx;
+                    let style = if is_selected {
+                        selected_style
+                    } else {
+                        Style::default().fg(theme.fg)
+                    };
+                    let current = if *branch == git.branch { " ●" } else { "" };
+                    ListItem::new(Line::from(vec![
+                        Span::styled(if is_selected { "▸ " } else { "  " }, style),
+                        Span::styled(branch.as_str(), style),
+                        Span::styled(current, Style::default().fg(theme.success)),
+                    ]))
+                })
+                .collect();
+            (items, popup.branch_idx)
+        }
+        // 工作树列表：分支和路径，当前项目所在工作树标记 ●，已添加为项目的标记 ✓
+        None if popup.worktrees.is_some() => {
+            let worktrees = popup.worktrees.as_deref().unwrap_or_default();
+            let items = worktrees
+                .iter()
+                .enumerate()
+                .map(|(idx, worktree)| {
+                    let is_selected = idx == popup.worktree_idx;
+                    let style = if is_selected {
+                        selected_style
+                    } else {
+                        Style::default().fg(theme.fg)
+                    };
+                    let marker = if Some(worktree.path.as_path()) == project_path {
+                        " ●"
+                    } else if state.projects.iter().any(|p| p.path == worktree.path) {
+                        " ✓"
+                    } else {
+                        ""
+                    };
+                    ListItem::new(Line::from(vec![
+                        Span::styled(if is_selected { "▸ " } else { "  " }, style),
+                        Span::styled(format!("⎇ {}", worktree.branch), style),
+                        Span::styled(marker, Style::default().fg(theme.success)),
+                        Span::styled(format!("  {}", worktree.path.display()), dim),
+                    ]))
+                })
+                .collect();
+            (items, popup.worktree_idx)
+        }
+        // 上游信息 + 变更文件
+        None => {
+            let mut header = vec![Span::styled("  ", dim)];
+            match &git.upstream {
+                Some(upstream) => {
+                    header.push(Span::styled(format!("→ {}", upstream), dim));
+                    header.push(Span::styled(
+                        format!(" {}", git.ahead_behind()),
+                        Style::default().fg(theme.warning),
+                    ));
+                }
+                None => header.push(Span::styled(i18n.git_no_upstream(), dim)),
+            }
+            if let Some(action) = &popup.running {
+                header.push(Span::styled(
+                    format!("  {} {}", state.spinner_frame(), action.command_line()),
+                    Style::default().fg(theme.info),
+                ));
+            }
+            let header = Paragraph::new(Line::from(header));
+            frame.render_widget(header, Rect { height: 1, ..inner });
+
+            let items = git
+                .files
+                .iter()
+                .enumerate()
+                .map(|(idx, file)| {
+                    let is_selected = idx == popup.selected;
+                    let style = if is_selected {
+                        selected_style
+                    } else {
+                        Style::default().fg(theme.fg)
+                    };
+                    let code_color = match (file.index, file.worktree) {
+                        ('?', _) => theme.border,
+                        ('U', _) | (_, 'U') => theme.error,
+                        (' ', _) => theme.warning,
+                        _ => theme.success,
+                    };
+                    ListItem::new(Line::from(vec![
+                        Span::styled(if is_selected { "▸ " } else { "  " }, style),
+                        Span::styled(format!("{} ", file.code()), Style::default().fg(code_color)),
+                        Span::styled(file.path.as_str(), style),
+                    ]))
+                })
+                .collect();
+            (items, popup.selected)
+        }
+    };
+
+    // 列表区域（变更文件列表上方留出上游信息行）
+    let top = if popup.branches.is_some() || popup.worktrees.is_some() {
+        0
+    } else {
+        2
+    };
+    let list_area = Rect {
+        y: inner.y + top,
+        height: inner.height.saturating_sub(top + 2),
+        ..inner
+    };
+    if items.is_empty() {
+        let empty = Paragraph::new(i18n.git_clean())
+            .style(Style::default().fg(theme.border))
+            .alignment(Alignment::Center);
+        frame.render_widget(empty, list_area);
+    } else {
+        let mut list_state = ListState::default();
+        list_state.select(Some(selected));
+        frame.render_stateful_widget(List::new(items), list_area, &mut list_state);
+    }
+
+    // 底部提示
+    let hint_area = Rect {
+        y: inner.y + inner.height.saturating_sub(1),
+        height: 1,
+        ..inner
+    };
+    let key_style = Style::default().fg(theme.info).add_modifier(Modifier::BOLD);
+    let desc_style = Style::default().fg(theme.fg);
+    let hints: Vec<(&str, &str)> = match (&popup.branches, &popup.worktrees) {
+        (Some(_), _) => i18n.git_branch_hints().to_vec(),
+        (None, Some(_)) => i18n.git_worktree_hints().to_vec(),
+        (None, None) => i18n.git_hints().to_vec(),
+    };
+    let spans: Vec<Span> = hints
+        .into_iter()
+        .flat_map(|(key, desc)| [Span::styled(key, key_style), Span::styled(desc, desc_style)])
+        .collect();
+    let hint = Paragraph::new(Line::from(spans)).alignment(Alignment::Center);
+    frame.render_widget(hint, hint_area);
+}
@@ -1,6 +1,7 @@
 //! 帮助弹窗模块
 
 use crate::app::AppState;
+use crate::keymap::{Action, HelpSection, KeySequence, Keymap, KeymapMode};
 use crate::ui::{centered_rect, draw_scrollbar, ScrollInfo, Theme};
 use ratatui::{
     style::{Modifier, Style},
@@ -34,85 +35,12 @@ pub fn draw_help_popup(frame: &mut Frame, state: &mut AppState, theme: &Theme) {
         .add_modifier(Modifier::BOLD);
     let divider_style = Style::default().fg(theme.border);
 
-    let sections = match state.language() {
-        crate::i18n::Language::English => vec![
-            ("PROJECT NAVIGATION", "──────────────────"),
-            ("  1-9", "Quick switch to project"),
-            ("  Tab/Shift+Tab", "Switch between projects"),
-            ("  j/k/↑/↓", "Navigate project list"),
-            ("  Enter", "Enter Interactive Shell"),
-            ("", ""),
-            ("DEV SERVER", "──────────"),
-            ("  r", "Run command (opens palette)"),
-            ("  s", "Stop dev server"),
-            ("  x", "Send interrupt (Ctrl+C)"),
-            ("  p", "Pause/Resume (freeze)"),
-            ("", ""),
-            ("DEV LOG VIEW (click Dev panel)", ""),
-            ("  j/k/↑/↓", "Scroll log"),
-            ("  PgUp/PgDn", "Fast scroll"),
-            ("  Home", "Jump to latest"),
-            ("  Esc", "Exit log view"),
-            ("", ""),
-            ("INTERACTIVE SHELL", "─────────────────"),
-            ("  R (Shift+r)", "Run command in shell"),
-            ("  All keys", "Sent to shell directly"),
-            ("  Esc", "Return to sidebar"),
-            ("", ""),
-            ("PROJECT MANAGEMENT", "──────────────────"),
-            ("  a", "Add new project"),
-            ("  e", "Edit project alias"),
-            ("  c", "Add custom command"),
-            ("  d", "Delete project"),
-            ("", ""),
-            ("GENERAL", "───────"),
-            ("  z", "Toggle panel layout"),
-            ("  ,", "Open settings"),
-            ("  q/Ctrl+C", "Quit application"),
-            ("  ?", "Toggle this help"),
-        ],
-        crate::i18n::Language::Chinese => vec![
-            ("项目导航", "────────"),
-            ("  1-9", "快速切换项目"),
-            ("  Tab/Shift+Tab", "切换项目"),
-            ("  j/k/↑/↓", "上下导航"),
-            ("  Enter", "进入交互终端"),
-            ("", ""),
-            ("开发服务", "────────"),
-            ("  r", "运行命令"),
-            ("  s", "停止服务"),
-            ("  x", "发送中断 (Ctrl+C)"),
-            ("  p", "暂停/恢复 (冻结进程)"),
-            ("", ""),
-            ("日志查看 (点击开发服务面板)", ""),
-            ("  j/k/↑/↓", "滚动日志"),
-            ("  PgUp/PgDn", "快速滚动"),
-            ("  Home", "跳到最新"),
-            ("  Esc", "退出查看"),
-            ("", ""),
-            ("交互终端", "────────"),
-            ("  R (Shift+r)", "在终端运行命令"),
-            ("  所有按键", "直接发送给终端"),
-            ("  Esc", "返回侧边栏"),
-            ("", ""),
-            ("项目管理", "────────"),
-            ("  a", "添加项目"),
-            ("  e", "编辑别名"),
-            ("  c", "添加自定义命令"),
-            ("  d", "删除项目"),
-            ("", ""),
-            ("通用", "────"),
-            ("  z", "切换面板布局"),
-            ("  ,", "打开设置"),
-            ("  q/Ctrl+C", "退出程序"),
-            ("  ?", "帮助"),
-        ],
-    };
+    let sections = help_entries(state);
 
     let mut lines: Vec<Line> = Vec::new();
     lines.push(Line::from("")); // 顶部空行
 
-    for (key, desc) in sections {
+    for (key, desc) in &sections {
         if key.is_empty() {
             lines.push(Line::from(""));
         } else if !key.starts_with("  ") {
@@ -123,10 +51,10 @@ pub fn draw_help_popup(frame: &mut Frame, state: &mut AppState, theme: &Theme) {
             ]));
         } else {
             // 快捷键行
-            let padded_key = format!("{:16}", key);
+            let padded_key = format!("{:16}", format!("{} ", key));
             lines.push(Line::from(vec![
                 Span::styled(padded_key, key_style),
-                Span::styled(desc, desc_style),
+                Span::styled(desc.as_str(), desc_style),
             ]));
         }
     }
@@ -173,6 +101,10 @@ pub fn draw_help_popup(frame: &mut Frame, state: &mut AppState, theme: &Theme) {
     let key_hint_style = Style::default().fg(theme.info).add_modifier(Modifier::BOLD);
     let desc_hint_style = Style::default().fg(theme.fg);
 
+    let help_key = state
+        .keymap
+        .short_label(KeymapMode::Normal, Action::Help)
+        .unwrap_or_else(|| "?".to_string());
     let hint_lines = vec![
         Line::from(""),
         Line::from(match state.language() {
@@ -180,7 +112,7 @@ pub fn draw_help_popup(frame: &mut Frame, state: &mut AppState, theme: &Theme) {
                 Span::raw("  Press "),
                 Span::styled("Esc", key_hint_style),
                 Span::styled(" or ", desc_hint_style),
-                Span::styled("?", key_hint_style),
+                Span::styled(help_key, key_hint_style),
                 Span::styled(" to close", desc_hint_style),
             ],
             crate::i18n::Language::Chinese => vec![
@@ -188,7 +120,7 @@ pub fn draw_help_popup(frame: &mut Frame, state: &mut AppState, theme: &Theme) {
                 Span::styled("按 ", desc_hint_style),
                 Span::styled("Esc", key_hint_style),
                 Span::styled(" 或 ", desc_hint_style),
-                Span::styled("?", key_hint_style),
+                Span::styled(help_key, key_hint_style),
                 Span::styled(" 关闭", desc_hint_style),
             ],
         }),
@@ -196,3 +128,96 @@ pub fn draw_help_popup(frame: &mut Frame, state: &mut AppState, theme: &Theme) {
     let hint_paragraph = Paragraph::new(hint_lines);
     frame.render_widget(hint_paragraph, hint_area);
 }
+
+/// 根据当前 keymap 生成帮助内容
+/// 返回 (按键, 描述) 列表：空按键为空行，不以空格开头的为章节标题
+fn help_entries(state: &AppState) -> Vec<(String, String)> {
+    let i18n = state.i18n();
+    let keymap = &state.keymap;
+    let mut entries: Vec<(String, String)> = Vec::new();
+
+    let push_section = |entries: &mut Vec<(String, String)>, title: &str, divider: bool| {
+        if !entries.is_empty() {
+            entries.push((String::new(), String::new()));
+        }
+        let divider = if divider {
+            let width: usize = title
+                .chars()
+                .map(|c| if c.is_ascii() { 1 } else { 2 })
+                .sum();
+            "─".repeat(width)
+        } else {
+            String::new()
+        };
+        entries.push((title.to_string(), divider));
+    };
+
+    for section in HelpSection::ALL {
+        push_section(&mut entries, i18n.help_section_title(section), true);
+        for (keys, action) in grouped_bindings(keymap, KeymapMode::Normal) {
+            if action.help_section() == section {
+                entries.push((
+                    format!("  {}", keys),
+                    i18n.action_description(action).into(),
+                ));
+            }
+        }
+
+        match section {
+            // 日志查看模式紧跟在开发服务之后
+            HelpSection::DevServer => {
+                push_section(&mut entries, i18n.help_dev_log_title(), false);
+                for (keys, action) in grouped_bindings(keymap, KeymapMode::DevLog) {
+                    entries.push((
+                        format!("  {}", keys),
+                        i18n.action_description(action).into(),
+                    ));
+                }
+            }
+            HelpSection::Shell => {
+                let (keys, desc) = i18n.help_shell_passthrough();
+                entries.push((format!("  {}", keys), desc.to_string()));
+                for (keys, action) in grouped_bindings(keymap, KeymapMode::Shell) {
+                    entries.push((
+                        format!("  {}", keys),
+                        i18n.action_description(action).into(),
+                    ));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    entries
+}
+
+/// 按动作合并绑定（按首次出现的顺序），返回 (按键文本, 动作)
+/// 快速切换项目的多个动作合并为一行，例如 "1-9"
+fn grouped_bindings(keymap: &Keymap, mode: KeymapMode) -> Vec<(String, Action)> {
+    let mut groups: Vec<(Action, Vec<&KeySequence>)> = Vec::new();
+    for (seq, action) in keymap.bindings(mode) {
+        let action = match action {
+            Action::SelectProject(_) => Action::SelectProject(1),
+            other => *other,
+        };
+        match groups.iter_mut().find(|(a, _)| *a == action) {
+            Some((_, keys)) => keys.push(seq),
+            None => groups.push((action, vec![seq])),
+        }
+    }
+
+    groups
+        .into_iter()
+        .map(|(action, keys)| {
+            let label = match (action, keys.as_slice()) {
+                (Action::SelectProject(_), [first, .., last]) => format!("{}-{}", first, last),
+                _ => keys
+                    .iter()
+                    .map(|seq| seq.to_string())
+                    .collect::<Vec<_>>()
+                    .join("/"),
+            };
+            (label, action)
+        })
+        .collect()
+}
@@ -0,0 +1,39 @@
+//! 提示模式标签
+//! 在终端面板中的 file:line 引用或链接上显示标签，已输入的部分变暗
+
+use crate::app::AppState;
+use crate::ui::Theme;
+use ratatui::{
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::Paragraph,
+    Frame,
+};
+
+/// 绘制提示标签（只显示与已输入字符匹配的标签）
+pub fn draw_hints(frame: &mut Frame, state: &AppState, theme: &Theme) {
+    let screen = frame.area();
+    let typed_style = Style::default().fg(theme.border).bg(theme.warning);
+    let label_style = Style::default()
+        .fg(theme.bg)
+        .bg(theme.warning)
+        .add_modifier(Modifier::BOLD);
+
+    for hint in state
+        .hints
+        .iter()
+        .filter(|h| h.label.starts_with(&state.hint_input))
+    {
+        let width = hint.label.len() as u16;
+        if hint.x + width > screen.width || hint.y >= screen.height {
+            continue;
+        }
+        let (typed, rest) = hint.label.split_at(state.hint_input.len());
+        let label = Paragraph::new(Line::from(vec![
+            Span::styled(typed, typed_style),
+            Span::styled(rest, label_style),
+        ]));
+        frame.render_widget(label, Rect::new(hint.x, hint.y, width, 1));
+    }
+}
@@ -0,0 +1,136 @@
+//! 命令历史弹窗组件
+//! 列出当前项目运行过的命令，Enter 在原来的位置重新运行
+
+use crate::app::AppState;
+use crate::config::{unix_now, RunRecord, RunTarget};
+use crate::ui::{centered_rect, Theme};
+use ratatui::{
+    layout::{Alignment, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, List, ListItem, ListState, Paragraph},
+    Frame,
+};
+
+/// 绘制命令历史弹窗
+pub fn draw_history_popup(frame: &mut Frame, state: &AppState, theme: &Theme) {
+    let area = centered_rect(70, 60, frame.area());
+    let i18n = state.i18n();
+
+    frame.render_widget(Clear, area);
+
+    let block = Block::default()
+        .title(i18n.history_title())
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(theme.info))
+        .style(Style::default().bg(theme.bg));
+
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let list_area = Rect {
+        height: inner.height.saturating_sub(2),
+        ..inner
+    };
+    let runs = state.history_runs();
+    if runs.is_empty() {
+        let empty = Paragraph::new(i18n.no_history())
+            .style(Style::default().fg(theme.border))
+            .alignment(Alignment::Center);
+        frame.render_widget(empty, list_area);
+    } else {
+        let now = unix_now();
+        let items: Vec<ListItem> = runs
+            .iter()
+            .enumerate()
+            .map(|(idx, run)| {
+                let is_selected = idx == state.history_idx;
+                history_item(run, is_selected, now, i18n.history_running(), theme)
+            })
+            .collect();
+        let mut list_state = ListState::default();
+        list_state.select(Some(state.history_idx));
+        frame.render_stateful_widget(List::new(items), list_area, &mut list_state);
+    }
+
+    // 底部提示
+    let hint_area = Rect {
+        y: inner.y + inner.height.saturating_sub(1),
+        height: 1,
+        ..inner
+    };
+    let key_style = Style::default().fg(theme.info).add_modifier(Modifier::BOLD);
+    let desc_style = Style::default().fg(theme.fg);
+    let hint = Paragraph::new(Line::from(vec![
+        Span::styled("Enter", key_style),
+        Span::styled(i18n.history_hint_rerun(), desc_style),
+        Span::styled("Esc", key_style),
+        Span::styled(i18n.history_hint_close(), desc_style),
+    ]))
+    .alignment(Alignment::Center);
+    frame.render_widget(hint, hint_area);
+}
+
+/// 单条运行记录：时间、位置、名称、时长、退出状态
+fn history_item<'a>(
+    run: &'a RunRecord,
+    is_selected: bool,
+    now: u64,
+    running: &'a str,
+    theme: &Theme,
+) -> ListItem<'a> {
+    let name_style = if is_selected {
+        Style::default()
+            .fg(theme.selection_fg)
+            .bg(theme.selection)
+            .add_modifier(Modifier::BOLD)
+    } else {
+        Style::default().fg(theme.fg)
+    };
+    let dim = Style::default().fg(theme.border);
+    let prefix = if is_selected { "▸ " } else { "  " };
+    let target = match run.target {
+        RunTarget::Dev => "[dev]  ",
+        RunTarget::Shell => "[shell]",
+    };
+
+    let (status, status_style) = match (run.exit_code, run.pty_id.is_some()) {
+        (_, true) => (running.to_string(), Style::default().fg(theme.warning)),
+        (Some(0), _) => ("✓".to_string(), Style::default().fg(theme.success)),
+        (Some(code), _) => (format!("✗ {}", code), Style::default().fg(theme.error)),
+        (None, _) => ("—".to_string(), dim),
+    };
+    let duration = run.duration_ms.map(format_duration).unwrap_or_default();
+
+    ListItem::new(Line::from(vec![
+        Span::styled(prefix, name_style),
+        Span::styled(
+            format!("{:>4} ", format_age(now.saturating_sub(run.started_at))),
+            dim,
+        ),
+        Span::styled(format!("{} ", target), Style::default().fg(theme.info)),
+        Span::styled(run.name.as_str(), name_style),
+        Span::styled(format!("  {} ", duration), dim),
+        Span::styled(status, status_style),
+    ]))
+}
+
+/// 距今时间的简短表示（如 5m、3h、2d）
+fn format_age(secs: u64) -> String {
+    match secs {
+        0..=59 => format!("{}s", secs),
+        60..=3_599 => format!("{}m", secs / 60),
+        3_600..=86_399 => format!("{}h", secs / 3_600),
+        _ => format!("{}d", secs / 86_400),
+    }
+}
+
+/// 运行时长的简短表示（如 850ms、12s、3m20s）
+fn format_duration(ms: u64) -> String {
+    match ms {
+        0..=999 => format!("{}ms", ms),
+        1_000..=59_999 => format!("{}s", ms / 1_000),
+        _ => format!("{}m{}s", ms / 60_000, (ms / 1_000) % 60),
+    }
+}
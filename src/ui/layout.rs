@@ -2,24 +2,61 @@
 //! 负责主界面的布局划分
 
 use crate::app::{AppMode, AppState, FocusArea, PanelLayout};
+use crate::config::{AppSettings, PanelOrientation};
+use crate::project::{CompositeMode, Project};
 use crate::ui::{
-    calculate_status_bar_height, draw_command_palette, draw_confirm_popup, draw_dir_browser,
-    draw_help_popup, draw_input_popup, draw_settings_popup, draw_sidebar, draw_status_bar,
-    draw_terminal_panel, draw_title_bar, Theme,
+    calculate_status_bar_height, draw_command_form, draw_command_manager, draw_command_palette,
+    draw_command_prompt, draw_confirm_popup, draw_dir_browser, draw_git_popup, draw_help_popup,
+    draw_hints, draw_history_popup, draw_input_popup, draw_problems_popup, draw_process_tree_popup,
+    draw_resources_popup, draw_settings_popup, draw_sidebar, draw_status_bar, draw_terminal_panel,
+    draw_theme_picker, draw_title_bar, Theme,
 };
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
     Frame,
 };
 
-/// 绘制主界面
-pub fn draw_ui(frame: &mut Frame, state: &mut AppState, theme: &Theme) {
-    let screen_width = frame.area().width as usize;
+/// 侧边栏最小宽度
+pub const MIN_SIDEBAR_WIDTH: u16 = 20;
+/// 调整侧边栏宽度时工作区至少保留的宽度
+const MIN_WORK_WIDTH: u16 = 20;
+/// Dev Terminal 占比的调整范围（百分比）
+pub const MIN_DEV_PANEL_PERCENT: u16 = 10;
+pub const MAX_DEV_PANEL_PERCENT: u16 = 90;
 
-    // 计算状态栏需要的高度（根据内容和屏幕宽度）
-    let status_height = calculate_status_bar_height(state, screen_width);
+/// 主界面各区域的位置
+/// 绘制和鼠标命中检测使用同一份计算结果，保证调整大小后点击位置仍然准确
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AppLayout {
+    pub title: Rect,
+    /// 侧边栏（隐藏时为 None）
+    pub sidebar: Option<Rect>,
+    /// 右侧工作区（Dev Terminal + Shell Terminal）
+    pub work: Rect,
+    pub dev: Rect,
+    pub shell: Rect,
+    pub status: Rect,
+}
+
+/// 按终端宽度限制侧边栏宽度（工作区至少保留 MIN_WORK_WIDTH 列）
+pub fn clamp_sidebar_width(width: u16, total_width: u16) -> u16 {
+    width
+        .min(total_width.saturating_sub(MIN_WORK_WIDTH))
+        .max(MIN_SIDEBAR_WIDTH)
+        .min(total_width)
+}
 
-    // 主布局：顶部标题 + 中间内容 + 底部状态栏
+pub fn clamp_dev_panel_percent(percent: u16) -> u16 {
+    percent.clamp(MIN_DEV_PANEL_PERCENT, MAX_DEV_PANEL_PERCENT)
+}
+
+/// 计算主界面布局：顶部标题 + 中间内容 + 底部状态栏
+pub fn compute_layout(
+    area: Rect,
+    status_height: u16,
+    settings: &AppSettings,
+    panel_layout: PanelLayout,
+) -> AppLayout {
     let main_chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
@@ -27,34 +64,72 @@ pub fn draw_ui(frame: &mut Frame, state: &mut AppState, theme: &Theme) {
             Constraint::Min(1),                // 主内容区
             Constraint::Length(status_height), // 状态栏（动态高度）
         ])
-        .split(frame.area());
-
-    // 绘制标题栏
-    draw_title_bar(frame, main_chunks[0], state, theme);
+        .split(area);
 
     // 内容区布局：左侧边栏 + 右侧工作区
-    let content_chunks = Layout::default()
-        .direction(Direction::Horizontal)
-        .constraints([
-            Constraint::Length(38), // 侧边栏宽度（增加以显示 CPU/内存信息）
-            Constraint::Min(1),     // 工作区
-        ])
-        .split(main_chunks[1]);
+    let (sidebar, work) = if settings.sidebar_hidden {
+        (None, main_chunks[1])
+    } else {
+        let width = clamp_sidebar_width(settings.sidebar_width, main_chunks[1].width);
+        let content_chunks = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Length(width), Constraint::Min(1)])
+            .split(main_chunks[1]);
+        (Some(content_chunks[0]), content_chunks[1])
+    };
 
-    // 绘制侧边栏
-    draw_sidebar(frame, content_chunks[0], state, theme);
-
-    // 右侧工作区：上方 Dev Terminal + 下方 Shell Terminal
-    // 根据面板布局模式调整高度比例
-    let work_constraints = match state.panel_layout {
-        PanelLayout::Split => [Constraint::Percentage(50), Constraint::Percentage(50)],
-        PanelLayout::DevMax => [Constraint::Min(3), Constraint::Length(3)], // Shell 只显示标题栏
-        PanelLayout::ShellMax => [Constraint::Length(3), Constraint::Min(3)], // Dev 只显示标题栏
+    // 工作区：Dev Terminal + Shell Terminal，上下排列时最大化的另一侧只显示标题栏，左右并排时完全隐藏
+    let (direction, collapsed) = match settings.panel_orientation {
+        PanelOrientation::Vertical => (Direction::Vertical, 3),
+        PanelOrientation::Horizontal => (Direction::Horizontal, 0),
+    };
+    let percent = clamp_dev_panel_percent(settings.dev_panel_percent);
+    let work_constraints = match panel_layout {
+        PanelLayout::Split => [
+            Constraint::Percentage(percent),
+            Constraint::Percentage(100 - percent),
+        ],
+        PanelLayout::DevMax => [Constraint::Min(3), Constraint::Length(collapsed)],
+        PanelLayout::ShellMax => [Constraint::Length(collapsed), Constraint::Min(3)],
     };
     let work_chunks = Layout::default()
-        .direction(Direction::Vertical)
+        .direction(direction)
         .constraints(work_constraints)
-        .split(content_chunks[1]);
+        .split(work);
+
+    AppLayout {
+        title: main_chunks[0],
+        sidebar,
+        work,
+        dev: work_chunks[0],
+        shell: work_chunks[1],
+        status: main_chunks[2],
+    }
+}
+
+/// 按当前状态计算主界面布局
+pub fn app_layout(state: &AppState, area: Rect) -> AppLayout {
+    // 计算状态栏需要的高度（根据内容和屏幕宽度）
+    let status_height = calculate_status_bar_height(state, area.width as usize);
+    compute_layout(
+        area,
+        status_height,
+        &state.config.settings,
+        state.panel_layout,
+    )
+}
+
+/// 绘制主界面
+pub fn draw_ui(frame: &mut Frame, state: &mut AppState, theme: &Theme) {
+    let layout = app_layout(state, frame.area());
+
+    // 绘制标题栏
+    draw_title_bar(frame, layout.title, state, theme);
+
+    // 绘制侧边栏
+    if let Some(sidebar) = layout.sidebar {
+        draw_sidebar(frame, sidebar, state, theme);
+    }
 
     let i18n = state.i18n();
 
@@ -68,9 +143,15 @@ pub fn draw_ui(frame: &mut Frame, state: &mut AppState, theme: &Theme) {
             } else {
                 i18n.dev_server().to_string()
             }
+        } else if let Some(ref run) = project.composite {
+            // 组合命令：显示名称和各步骤进度
+            format!("{} [{}: {}]", i18n.dev_server(), run.name, run.progress())
         } else if let Some(ref pty) = project.dev_pty {
             if pty.suspended {
                 format!("{} [{}]", i18n.dev_server(), i18n.paused())
+            } else if let Some(ref url) = project.dev_url {
+                // 显示开发服务器的访问地址
+                format!("{} · {}", i18n.dev_server(), url)
             } else {
                 i18n.dev_server().to_string()
             }
@@ -82,18 +163,54 @@ pub fn draw_ui(frame: &mut Frame, state: &mut AppState, theme: &Theme) {
         (i18n.dev_server().to_string(), 0)
     };
 
+    // 终端面板的可见文本（提示模式根据它生成目标）
+    let mut visible_rows = Vec::new();
+
     // 绘制 Dev Terminal（只读，不显示光标）
-    draw_terminal_panel(
-        frame,
-        work_chunks[0],
-        &dev_title,
-        state.active_project().and_then(|p| p.dev_pty.as_ref()),
-        state.focus == FocusArea::DevTerminal,
-        false, // Dev Terminal 是只读的，不显示光标
-        dev_scroll_offset,
-        &i18n,
-        theme,
-    );
+    let composite = state.active_project().and_then(|p| p.composite.as_ref());
+    match composite {
+        Some(run) if run.mode == CompositeMode::Parallel && !run.steps.is_empty() => {
+            // 并行组合：每个步骤一个子面板
+            let step_chunks = Layout::default()
+                .direction(Direction::Horizontal)
+                .constraints(vec![
+                    Constraint::Ratio(1, run.steps.len() as u32);
+                    run.steps.len()
+                ])
+                .split(layout.dev);
+            for (step, area) in run.steps.iter().zip(step_chunks.iter()) {
+                visible_rows.extend(draw_terminal_panel(
+                    frame,
+                    *area,
+                    &format!("{} {}", step.name, step.status.label()),
+                    step.pty.as_ref(),
+                    state.focus == FocusArea::DevTerminal,
+                    false,
+                    dev_scroll_offset,
+                    &i18n,
+                    theme,
+                ));
+            }
+        }
+        _ => {
+            // 顺序组合显示当前步骤的输出
+            let pty = match composite {
+                Some(run) => run.current_step().and_then(|s| s.pty.as_ref()),
+                None => state.active_project().and_then(|p| p.dev_pty.as_ref()),
+            };
+            visible_rows.extend(draw_terminal_panel(
+                frame,
+                layout.dev,
+                &dev_title,
+                pty,
+                state.focus == FocusArea::DevTerminal,
+                false, // Dev Terminal 是只读的，不显示光标
+                dev_scroll_offset,
+                &i18n,
+                theme,
+            ));
+        }
+    }
 
     // Shell Terminal 滚动偏移量
     let shell_scroll_offset = state
@@ -102,20 +219,26 @@ pub fn draw_ui(frame: &mut Frame, state: &mut AppState, theme: &Theme) {
         .unwrap_or(0);
 
     // 绘制 Shell Terminal（交互式，聚焦时显示光标）
-    draw_terminal_panel(
+    let shell_title = state
+        .active_project()
+        .map(|p| shell_title(p, i18n.interactive_shell()))
+        .unwrap_or_else(|| i18n.interactive_shell().to_string());
+    visible_rows.extend(draw_terminal_panel(
         frame,
-        work_chunks[1],
-        i18n.interactive_shell(),
-        state.active_project().and_then(|p| p.shell_pty.as_ref()),
+        layout.shell,
+        &shell_title,
+        state.active_project().and_then(|p| p.shell_pty()),
         state.focus == FocusArea::ShellTerminal,
         true, // Shell Terminal 是交互式的，聚焦时显示光标
         shell_scroll_offset,
         &i18n,
         theme,
-    );
+    ));
+
+    state.visible_rows = visible_rows;
 
     // 绘制状态栏
-    draw_status_bar(frame, main_chunks[2], state, theme);
+    draw_status_bar(frame, layout.status, state, theme);
 
     // 根据模式绘制弹窗
     let i18n = state.i18n();
@@ -135,20 +258,44 @@ pub fn draw_ui(frame: &mut Frame, state: &mut AppState, theme: &Theme) {
         AppMode::BrowseDirectory => {
             draw_dir_browser(frame, state, theme);
         }
-        AppMode::AddCommand => {
+        AppMode::CommandManager => {
+            draw_command_manager(frame, state, theme);
+        }
+        AppMode::CommandForm => {
+            draw_command_form(frame, state, theme);
+        }
+        AppMode::EditAlias => {
             draw_input_popup(
                 frame,
-                i18n.add_command(),
-                i18n.command_format_hint(),
+                i18n.edit_alias(),
+                i18n.alias_hint(),
                 &state.input_buffer,
                 theme,
             );
         }
-        AppMode::EditAlias => {
+        AppMode::NewWorktree => {
             draw_input_popup(
                 frame,
-                i18n.edit_alias(),
-                i18n.alias_hint(),
+                i18n.new_worktree(),
+                i18n.new_worktree_hint(),
+                &state.input_buffer,
+                theme,
+            );
+        }
+        AppMode::NewShellTab => {
+            draw_input_popup(
+                frame,
+                i18n.new_shell_tab(),
+                i18n.new_shell_tab_hint(),
+                &state.input_buffer,
+                theme,
+            );
+        }
+        AppMode::RenameShellTab => {
+            draw_input_popup(
+                frame,
+                i18n.rename_shell_tab(),
+                i18n.rename_shell_tab_hint(),
                 &state.input_buffer,
                 theme,
             );
@@ -159,6 +306,30 @@ pub fn draw_ui(frame: &mut Frame, state: &mut AppState, theme: &Theme) {
         AppMode::Settings => {
             draw_settings_popup(frame, state, theme);
         }
+        AppMode::ThemePicker => {
+            draw_theme_picker(frame, state, theme);
+        }
+        AppMode::History => {
+            draw_history_popup(frame, state, theme);
+        }
+        AppMode::CommandPrompt => {
+            draw_command_prompt(frame, state, theme);
+        }
+        AppMode::Problems => {
+            draw_problems_popup(frame, state, theme);
+        }
+        AppMode::Hints => {
+            draw_hints(frame, state, theme);
+        }
+        AppMode::Git => {
+            draw_git_popup(frame, state, theme);
+        }
+        AppMode::Resources => {
+            draw_resources_popup(frame, state, theme);
+        }
+        AppMode::ProcessTree => {
+            draw_process_tree_popup(frame, state, theme);
+        }
         AppMode::Confirm(msg) => {
             draw_confirm_popup(frame, state, msg, theme);
         }
@@ -186,3 +357,68 @@ pub fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
         ])
         .split(popup_layout[1])[1]
 }
+
+/// Shell 面板标题：有多个标签（或自定义标签）时列出标签，当前标签用方括号标出
+/// 例如 `Interactive Shell │ git · [repl]`
+fn shell_title(project: &Project, base: &str) -> String {
+    match project.shells.as_slice() {
+        [] => base.to_string(),
+        [tab] if tab.config.is_plain() => base.to_string(),
+        tabs => {
+            let names: Vec<String> = tabs
+                .iter()
+                .enumerate()
+                .map(|(idx, tab)| {
+                    if idx == project.active_shell_idx {
+                        format!("[{}]", tab.config.name)
+                    } else {
+                        tab.config.name.clone()
+                    }
+                })
+                .collect();
+            format!("{} │ {}", base, names.join(" · "))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compute_layout() {
+        let area = Rect::new(0, 0, 120, 40);
+        let mut settings = AppSettings::default();
+
+        let layout = compute_layout(area, 1, &settings, PanelLayout::Split);
+        assert_eq!(layout.sidebar, Some(Rect::new(0, 1, 38, 38)));
+        assert_eq!(layout.work, Rect::new(38, 1, 82, 38));
+        assert_eq!(layout.dev, Rect::new(38, 1, 82, 19));
+        assert_eq!(layout.shell, Rect::new(38, 20, 82, 19));
+        assert_eq!(layout.status, Rect::new(0, 39, 120, 1));
+
+        settings.sidebar_width = 200;
+        settings.dev_panel_percent = 75;
+        settings.panel_orientation = PanelOrientation::Horizontal;
+        let layout = compute_layout(area, 1, &settings, PanelLayout::Split);
+        assert_eq!(layout.sidebar.map(|s| s.width), Some(100));
+        assert_eq!(layout.dev, Rect::new(100, 1, 15, 38));
+        assert_eq!(layout.shell, Rect::new(115, 1, 5, 38));
+
+        settings.sidebar_hidden = true;
+        let layout = compute_layout(area, 1, &settings, PanelLayout::DevMax);
+        assert_eq!(layout.sidebar, None);
+        assert_eq!(layout.dev, Rect::new(0, 1, 120, 38));
+        assert_eq!(layout.shell.width, 0);
+    }
+
+    #[test]
+    fn test_clamp_layout_sizes() {
+        assert_eq!(clamp_sidebar_width(5, 120), MIN_SIDEBAR_WIDTH);
+        assert_eq!(clamp_sidebar_width(50, 120), 50);
+        assert_eq!(clamp_sidebar_width(50, 60), 40);
+        assert_eq!(clamp_sidebar_width(38, 15), 15);
+        assert_eq!(clamp_dev_panel_percent(0), MIN_DEV_PANEL_PERCENT);
+        assert_eq!(clamp_dev_panel_percent(100), MAX_DEV_PANEL_PERCENT);
+    }
+}
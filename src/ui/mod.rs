@@ -1,12 +1,20 @@
 //! UI 模块
 //! 包含所有 UI 相关组件
 
+mod command_manager;
 mod command_palette;
 mod confirm_popup;
 mod dir_browser;
+mod git_popup;
 mod help_popup;
+mod hints;
+mod history_popup;
 mod input_popup;
 mod layout;
+mod problems_popup;
+mod process_tree_popup;
+mod prompt_popup;
+mod resources_popup;
 mod scrollbar;
 mod settings_popup;
 mod sidebar;
@@ -14,14 +22,24 @@ mod spinner;
 mod status_bar;
 mod terminal;
 mod theme;
+mod theme_file;
+mod theme_picker;
 mod title_bar;
 
+pub use command_manager::*;
 pub use command_palette::*;
 pub use confirm_popup::*;
 pub use dir_browser::*;
+pub use git_popup::*;
 pub use help_popup::*;
+pub use hints::*;
+pub use history_popup::*;
 pub use input_popup::*;
 pub use layout::*;
+pub use problems_popup::*;
+pub use process_tree_popup::*;
+pub use prompt_popup::*;
+pub use resources_popup::*;
 pub use scrollbar::*;
 pub use settings_popup::*;
 pub use sidebar::*;
@@ -29,4 +47,6 @@ pub use spinner::*;
 pub use status_bar::*;
 pub use terminal::*;
 pub use theme::*;
+pub use theme_file::*;
+pub use theme_picker::*;
 pub use title_bar::*;
@@ -0,0 +1,104 @@
+//! 问题列表弹窗组件
+//! 列出当前项目 Dev 输出中解析出的错误和警告，Enter 在编辑器中打开
+
+use crate::app::AppState;
+use crate::project::{Problem, Severity};
+use crate::ui::{centered_rect, Theme};
+use ratatui::{
+    layout::{Alignment, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, List, ListItem, ListState, Paragraph},
+    Frame,
+};
+
+/// 绘制问题列表弹窗
+pub fn draw_problems_popup(frame: &mut Frame, state: &AppState, theme: &Theme) {
+    let area = centered_rect(70, 60, frame.area());
+    let i18n = state.i18n();
+
+    frame.render_widget(Clear, area);
+
+    let problems = state.active_problems();
+    let (errors, warnings) = state
+        .active_project()
+        .map(|p| p.problems.counts())
+        .unwrap_or_default();
+    let block = Block::default()
+        .title(i18n.problems_title(errors, warnings))
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(theme.info))
+        .style(Style::default().bg(theme.bg));
+
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let list_area = Rect {
+        height: inner.height.saturating_sub(2),
+        ..inner
+    };
+    if problems.is_empty() {
+        let empty = Paragraph::new(i18n.no_problems())
+            .style(Style::default().fg(theme.border))
+            .alignment(Alignment::Center);
+        frame.render_widget(empty, list_area);
+    } else {
+        let items: Vec<ListItem> = problems
+            .iter()
+            .enumerate()
+            .map(|(idx, problem)| problem_item(problem, idx == state.problems_idx, theme))
+            .collect();
+        let mut list_state = ListState::default();
+        list_state.select(Some(state.problems_idx));
+        frame.render_stateful_widget(List::new(items), list_area, &mut list_state);
+    }
+
+    // 底部提示
+    let hint_area = Rect {
+        y: inner.y + inner.height.saturating_sub(1),
+        height: 1,
+        ..inner
+    };
+    let key_style = Style::default().fg(theme.info).add_modifier(Modifier::BOLD);
+    let desc_style = Style::default().fg(theme.fg);
+    let hint = Paragraph::new(Line::from(vec![
+        Span::styled("Enter", key_style),
+        Span::styled(i18n.problems_hint_open(), desc_style),
+        Span::styled("Esc", key_style),
+        Span::styled(i18n.history_hint_close(), desc_style),
+    ]))
+    .alignment(Alignment::Center);
+    frame.render_widget(hint, hint_area);
+}
+
+/// 单个问题：严重程度、位置、消息和来源
+fn problem_item<'a>(problem: &'a Problem, is_selected: bool, theme: &Theme) -> ListItem<'a> {
+    let text_style = if is_selected {
+        Style::default()
+            .fg(theme.selection_fg)
+            .bg(theme.selection)
+            .add_modifier(Modifier::BOLD)
+    } else {
+        Style::default().fg(theme.fg)
+    };
+    let dim = Style::default().fg(theme.border);
+    let prefix = if is_selected { "▸ " } else { "  " };
+    let (icon, icon_style) = match problem.severity {
+        Severity::Error => ("✗ ", Style::default().fg(theme.error)),
+        Severity::Warning => ("⚠ ", Style::default().fg(theme.warning)),
+    };
+    let location = match problem.column {
+        Some(col) => format!("{}:{}:{}", problem.file, problem.line, col),
+        None => format!("{}:{}", problem.file, problem.line),
+    };
+
+    ListItem::new(Line::from(vec![
+        Span::styled(prefix, text_style),
+        Span::styled(icon, icon_style),
+        Span::styled(location, Style::default().fg(theme.info)),
+        Span::styled("  ", dim),
+        Span::styled(problem.message.as_str(), text_style),
+        Span::styled(format!("  [{}]", problem.source), dim),
+    ]))
+}
@@ -0,0 +1,141 @@
+//! 进程树弹窗组件
+//! 列出 Dev 进程及其所有子进程的 PID、CPU、内存、状态、运行时长和命令行
+
+use crate::app::AppState;
+use crate::pty::{ProcessInfo, ProcessResourceUsage};
+use crate::ui::{centered_rect, Theme};
+use ratatui::{
+    layout::{Alignment, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, List, ListItem, ListState, Paragraph},
+    Frame,
+};
+
+/// 绘制进程树弹窗
+pub fn draw_process_tree_popup(frame: &mut Frame, state: &AppState, theme: &Theme) {
+    let area = centered_rect(70, 70, frame.area());
+    let i18n = state.i18n();
+
+    frame.render_widget(Clear, area);
+
+    let project_name = state
+        .active_project()
+        .map(|p| p.display_name())
+        .unwrap_or_default();
+    let name = match state.process_tree_current_root() {
+        Some((Some(step), _)) => format!("{} [{}]", project_name, step),
+        _ => project_name.to_string(),
+    };
+    let block = Block::default()
+        .title(i18n.process_tree_title(&name, state.process_tree.len()))
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(theme.info))
+        .style(Style::default().bg(theme.bg));
+
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    // 表头
+    let header = format!(
+        "  {:>7} {:>6} {:>7}  {:<9} {:>7} {:>3}  COMMAND",
+        "PID", "CPU", "MEM", "STATE", "UPTIME", "NI"
+    );
+    frame.render_widget(
+        Paragraph::new(header).style(Style::default().fg(theme.border)),
+        Rect { height: 1, ..inner },
+    );
+
+    let list_area = Rect {
+        y: inner.y + 1,
+        height: inner.height.saturating_sub(3),
+        ..inner
+    };
+    let items: Vec<ListItem> = state
+        .process_tree
+        .iter()
+        .enumerate()
+        .map(|(idx, process)| process_item(process, idx == state.process_tree_idx, theme))
+        .collect();
+    let mut list_state = ListState::default();
+    list_state.select(Some(state.process_tree_idx));
+    frame.render_stateful_widget(List::new(items), list_area, &mut list_state);
+
+    // 底部提示
+    let hint_area = Rect {
+        y: inner.y + inner.height.saturating_sub(1),
+        height: 1,
+        ..inner
+    };
+    let key_style = Style::default().fg(theme.info).add_modifier(Modifier::BOLD);
+    let desc_style = Style::default().fg(theme.fg);
+    let spans: Vec<Span> = i18n
+        .process_tree_hints()
+        .into_iter()
+        .flat_map(|(key, desc)| [Span::styled(key, key_style), Span::styled(desc, desc_style)])
+        .collect();
+    let hint = Paragraph::new(Line::from(spans)).alignment(Alignment::Center);
+    frame.render_widget(hint, hint_area);
+}
+
+/// 单个进程：数值列右对齐，命令行按深度缩进
+fn process_item<'a>(process: &'a ProcessInfo, is_selected: bool, theme: &Theme) -> ListItem<'a> {
+    let text_style = if is_selected {
+        Style::default()
+            .fg(theme.selection_fg)
+            .bg(theme.selection)
+            .add_modifier(Modifier::BOLD)
+    } else {
+        Style::default().fg(theme.fg)
+    };
+    let dim = Style::default().fg(theme.border);
+    let prefix = if is_selected { "▸ " } else { "  " };
+    let memory = ProcessResourceUsage {
+        cpu_percent: 0.0,
+        memory_bytes: process.memory_bytes,
+    }
+    .format_memory();
+    let status_style = if process.is_stopped() {
+        Style::default().fg(theme.warning)
+    } else {
+        dim
+    };
+    let nice = process
+        .nice
+        .map(|n| n.to_string())
+        .unwrap_or_else(|| "-".to_string());
+    let tree = if process.depth == 0 {
+        String::new()
+    } else {
+        format!("{}└ ", "  ".repeat(process.depth - 1))
+    };
+
+    ListItem::new(Line::from(vec![
+        Span::styled(prefix, text_style),
+        Span::styled(
+            format!("{:>7} ", process.pid),
+            Style::default().fg(theme.info),
+        ),
+        Span::styled(
+            format!("{:>5.1}% ", process.cpu_percent),
+            Style::default().fg(theme.warning),
+        ),
+        Span::styled(format!("{:>7}  ", memory), Style::default().fg(theme.info)),
+        Span::styled(format!("{:<9} ", process.status.to_string()), status_style),
+        Span::styled(format!("{:>7} ", format_uptime(process.run_time)), dim),
+        Span::styled(format!("{:>3}  ", nice), dim),
+        Span::styled(tree, dim),
+        Span::styled(process.command.as_str(), text_style),
+    ]))
+}
+
+/// 运行时长的简短表示（如 42s、3m20s、2h05m、3d）
+fn format_uptime(secs: u64) -> String {
+    match secs {
+        0..=59 => format!("{}s", secs),
+        60..=3_599 => format!("{}m{:02}s", secs / 60, secs % 60),
+        3_600..=86_399 => format!("{}h{:02}m", secs / 3_600, (secs / 60) % 60),
+        _ => format!("{}d", secs / 86_400),
+    }
+}
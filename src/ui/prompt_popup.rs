@@ -0,0 +1,113 @@
+//! 命令占位符输入弹窗组件
+//! input 占位符显示输入框，pick 占位符显示选项列表
+
+use crate::app::AppState;
+use crate::project::Prompt;
+use crate::ui::{centered_rect, Theme};
+use ratatui::{
+    layout::{Alignment, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, List, ListItem, ListState, Paragraph},
+    Frame,
+};
+
+/// 绘制命令占位符输入弹窗
+pub fn draw_command_prompt(frame: &mut Frame, state: &AppState, theme: &Theme) {
+    let (Some(prompt), Some(current)) = (state.command_prompt.as_ref(), state.current_prompt())
+    else {
+        return;
+    };
+    let area = centered_rect(60, 40, frame.area());
+    let i18n = state.i18n();
+
+    frame.render_widget(Clear, area);
+
+    let block = Block::default()
+        .title(format!(
+            " {} ({}/{}) ",
+            prompt.command_name,
+            prompt.step + 1,
+            prompt.prompts.len()
+        ))
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(theme.border_focused))
+        .style(Style::default().bg(theme.bg));
+
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    // 标签
+    let label = Paragraph::new(Line::from(Span::styled(
+        current.label(),
+        Style::default().fg(theme.info).add_modifier(Modifier::BOLD),
+    )));
+    frame.render_widget(label, Rect { height: 1, ..inner });
+
+    let body_area = Rect {
+        y: inner.y + 2,
+        height: inner.height.saturating_sub(4),
+        ..inner
+    };
+    match current {
+        Prompt::Input { .. } => {
+            let input = Paragraph::new(format!("> {}█", state.input_buffer))
+                .style(Style::default().fg(theme.fg));
+            frame.render_widget(input, body_area);
+        }
+        Prompt::Pick { options, .. } => {
+            let items: Vec<ListItem> = options
+                .iter()
+                .enumerate()
+                .map(|(idx, option)| {
+                    let is_selected = idx == prompt.pick_idx;
+                    let style = if is_selected {
+                        Style::default()
+                            .fg(theme.selection_fg)
+                            .bg(theme.selection)
+                            .add_modifier(Modifier::BOLD)
+                    } else {
+                        Style::default().fg(theme.fg)
+                    };
+                    let prefix = if is_selected { "▸ " } else { "  " };
+                    ListItem::new(Line::from(vec![
+                        Span::styled(prefix, style),
+                        Span::styled(option.as_str(), style),
+                    ]))
+                })
+                .collect();
+            let mut list_state = ListState::default();
+            list_state.select(Some(prompt.pick_idx));
+            frame.render_stateful_widget(List::new(items), body_area, &mut list_state);
+        }
+    }
+
+    // 底部提示：最后一步 Enter 直接运行
+    let hint_area = Rect {
+        y: inner.y + inner.height.saturating_sub(1),
+        height: 1,
+        ..inner
+    };
+    let key_style = Style::default().fg(theme.info).add_modifier(Modifier::BOLD);
+    let desc_style = Style::default().fg(theme.fg);
+    let mut spans = Vec::new();
+    if matches!(current, Prompt::Pick { .. }) {
+        spans.push(Span::styled("↑/↓", key_style));
+        spans.push(Span::styled(i18n.prompt_hint_select(), desc_style));
+    }
+    let is_last = prompt.step + 1 == prompt.prompts.len();
+    spans.push(Span::styled("Enter", key_style));
+    spans.push(Span::styled(
+        if is_last {
+            i18n.prompt_hint_run()
+        } else {
+            i18n.prompt_hint_next()
+        },
+        desc_style,
+    ));
+    spans.push(Span::styled("Esc", key_style));
+    spans.push(Span::styled(i18n.theme_hint_cancel(), desc_style));
+    let hint = Paragraph::new(Line::from(spans)).alignment(Alignment::Center);
+    frame.render_widget(hint, hint_area);
+}
@@ -0,0 +1,165 @@
+//! 资源使用弹窗组件
+//! 显示当前项目 Dev 进程最近 15 分钟的 CPU 和内存曲线及峰值
+
+use crate::app::AppState;
+use crate::pty::{ProcessResourceUsage, HISTORY_WINDOW};
+use crate::ui::{centered_rect, Theme};
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    symbols::Marker,
+    text::{Line, Span},
+    widgets::{Axis, Block, BorderType, Borders, Chart, Clear, Dataset, GraphType, Paragraph},
+    Frame,
+};
+use std::time::Instant;
+
+/// 绘制资源使用弹窗
+pub fn draw_resources_popup(frame: &mut Frame, state: &AppState, theme: &Theme) {
+    let Some(project) = state.active_project() else {
+        return;
+    };
+    let Some(pty) = project.dev_pty.as_ref() else {
+        return;
+    };
+    let area = centered_rect(70, 70, frame.area());
+    let i18n = state.i18n();
+
+    frame.render_widget(Clear, area);
+
+    let block = Block::default()
+        .title(i18n.resources_title(project.display_name()))
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(theme.info))
+        .style(Style::default().bg(theme.bg));
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let [header_area, cpu_area, memory_area, _, hint_area] = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(2),
+            Constraint::Percentage(50),
+            Constraint::Percentage(50),
+            Constraint::Length(1),
+            Constraint::Length(1),
+        ])
+        .areas(inner);
+
+    // 当前值和峰值
+    let history = &pty.resource_history;
+    let current = pty.resource_usage;
+    let peak = history.peak();
+    let dim = Style::default().fg(theme.border);
+    let cpu_style = Style::default().fg(theme.warning);
+    let mem_style = Style::default().fg(theme.info);
+    let header = Line::from(vec![
+        Span::styled("  CPU ", dim),
+        Span::styled(current.format_cpu(), cpu_style.add_modifier(Modifier::BOLD)),
+        Span::styled(
+            format!(" ({} {})", i18n.resources_peak(), peak.format_cpu()),
+            cpu_style,
+        ),
+        Span::styled("   ", dim),
+        Span::styled(i18n.resources_memory(), dim),
+        Span::styled(" ", dim),
+        Span::styled(
+            current.format_memory(),
+            mem_style.add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(
+            format!(" ({} {})", i18n.resources_peak(), peak.format_memory()),
+            mem_style,
+        ),
+    ]);
+    frame.render_widget(Paragraph::new(header), header_area);
+
+    // 横轴为距现在的秒数（-900 到 0）
+    let now = Instant::now();
+    let points = |value: fn(&ProcessResourceUsage) -> f64| -> Vec<(f64, f64)> {
+        history
+            .samples()
+            .map(|s| {
+                let ago = now.saturating_duration_since(s.at).as_secs_f64();
+                (-ago, value(&s.usage))
+            })
+            .collect()
+    };
+    let cpu_points = points(|u| u.cpu_percent as f64);
+    let memory_points = points(|u| u.memory_bytes as f64 / (1024.0 * 1024.0));
+
+    draw_chart(
+        frame,
+        cpu_area,
+        "CPU %",
+        &cpu_points,
+        (peak.cpu_percent as f64).max(1.0),
+        |v| format!("{:.0}%", v),
+        theme.warning,
+        theme,
+    );
+    draw_chart(
+        frame,
+        memory_area,
+        i18n.resources_memory(),
+        &memory_points,
+        (peak.memory_bytes as f64 / (1024.0 * 1024.0)).max(1.0),
+        |v| format!("{:.0}M", v),
+        theme.info,
+        theme,
+    );
+
+    let key_style = Style::default().fg(theme.info).add_modifier(Modifier::BOLD);
+    let hint = Paragraph::new(Line::from(vec![
+        Span::styled("Esc", key_style),
+        Span::styled(i18n.history_hint_close(), Style::default().fg(theme.fg)),
+    ]))
+    .alignment(Alignment::Center);
+    frame.render_widget(hint, hint_area);
+}
+
+/// 绘制一条随时间变化的曲线（纵轴从 0 到峰值的 1.2 倍）
+#[allow(clippy::too_many_arguments)]
+fn draw_chart(
+    frame: &mut Frame,
+    area: Rect,
+    title: &str,
+    points: &[(f64, f64)],
+    peak: f64,
+    format_value: impl Fn(f64) -> String,
+    color: Color,
+    theme: &Theme,
+) {
+    let max = peak * 1.2;
+    let window = HISTORY_WINDOW.as_secs_f64();
+    let dim = Style::default().fg(theme.border);
+    let dataset = Dataset::default()
+        .marker(Marker::Braille)
+        .graph_type(GraphType::Line)
+        .style(Style::default().fg(color))
+        .data(points);
+    let chart = Chart::new(vec![dataset])
+        .block(
+            Block::default()
+                .title(Span::styled(
+                    format!(" {} ", title),
+                    Style::default().fg(color),
+                ))
+                .borders(Borders::TOP)
+                .border_style(dim),
+        )
+        .style(Style::default().bg(theme.bg))
+        .x_axis(
+            Axis::default()
+                .style(dim)
+                .bounds([-window, 0.0])
+                .labels(["-15m", "-10m", "-5m", "0"]),
+        )
+        .y_axis(Axis::default().style(dim).bounds([0.0, max]).labels([
+            format_value(0.0),
+            format_value(max / 2.0),
+            format_value(max),
+        ]));
+    frame.render_widget(chart, area);
+}
@@ -14,6 +14,9 @@ use ratatui::{
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum SettingItem {
     Language,
+    Theme,
+    NodeVersionManager,
+    IdleSuspend,
     #[cfg(windows)]
     WindowsShell,
 }
@@ -23,11 +26,22 @@ impl SettingItem {
     pub fn all() -> Vec<Self> {
         #[cfg(windows)]
         {
-            vec![SettingItem::Language, SettingItem::WindowsShell]
+            vec![
+                SettingItem::Language,
+                SettingItem::Theme,
+                SettingItem::NodeVersionManager,
+                SettingItem::IdleSuspend,
+                SettingItem::WindowsShell,
+            ]
         }
         #[cfg(not(windows))]
         {
-            vec![SettingItem::Language]
+            vec![
+                SettingItem::Language,
+                SettingItem::Theme,
+                SettingItem::NodeVersionManager,
+                SettingItem::IdleSuspend,
+            ]
         }
     }
 
@@ -57,6 +71,7 @@ pub fn draw_settings_popup(frame: &mut Frame, state: &AppState, theme: &Theme) {
 
     // 构建设置列表
     let setting_items = SettingItem::all();
+    let idle_suspend = i18n.minutes_or_off(state.config.settings.idle_suspend_minutes);
     let items: Vec<ListItem> = setting_items
         .iter()
         .enumerate()
@@ -69,6 +84,21 @@ pub fn draw_settings_popup(frame: &mut Frame, state: &AppState, theme: &Theme) {
                     is_selected,
                     theme,
                 ),
+                SettingItem::Theme => create_setting_item(
+                    i18n.theme(),
+                    &state.config.settings.theme,
+                    is_selected,
+                    theme,
+                ),
+                SettingItem::NodeVersionManager => create_setting_item(
+                    i18n.node_version_manager(),
+                    i18n.on_off(state.config.settings.node_version_manager),
+                    is_selected,
+                    theme,
+                ),
+                SettingItem::IdleSuspend => {
+                    create_setting_item(i18n.idle_suspend(), &idle_suspend, is_selected, theme)
+                }
                 #[cfg(windows)]
                 SettingItem::WindowsShell => create_setting_item(
                     i18n.shell(),
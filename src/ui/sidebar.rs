@@ -2,6 +2,8 @@
 //! 显示项目列表和进程资源使用信息
 
 use crate::app::{AppState, FocusArea};
+use crate::project::{DependencyState, Project};
+use crate::pty::LimitBreach;
 use crate::ui::Theme;
 use ratatui::{
     layout::Rect,
@@ -11,6 +13,24 @@ use ratatui::{
     Frame,
 };
 
+/// 侧边栏内存趋势迷你图的宽度（字符数）
+const SPARKLINE_WIDTH: usize = 12;
+
+/// 项目在侧边栏中占用的行数
+/// Git 仓库或有资源历史的运行中项目多显示一行（分支和内存趋势）
+pub fn sidebar_item_height(project: &Project) -> u16 {
+    let has_history = project.is_dev_running()
+        && project
+            .dev_pty
+            .as_ref()
+            .is_some_and(|pty| pty.resource_history.len() > 1);
+    if project.git.is_some() || has_history {
+        2
+    } else {
+        1
+    }
+}
+
 /// 绘制侧边栏
 pub fn draw_sidebar(frame: &mut Frame, area: Rect, state: &AppState, theme: &Theme) {
     let is_focused = state.focus == FocusArea::Sidebar;
@@ -65,8 +85,29 @@ pub fn draw_sidebar(frame: &mut Frame, area: Rect, state: &AppState, theme: &The
                     .map(|p| p.suspended)
                     .unwrap_or(false);
 
-                let (status_icon, status_style) = if is_suspended {
-                    ("⏸ ", Style::default().fg(theme.warning))
+                // 超过资源上限时状态图标和超限的数值使用错误色
+                let breach = project.dev_pty.as_ref().and_then(|p| p.limit_breach);
+
+                let is_auto_suspended =
+                    is_suspended && project.dev_pty.as_ref().is_some_and(|p| p.auto_suspended);
+
+                // 旧进程树仍在按停止序列退出
+                let is_stopping = project.is_stopping() && !is_running;
+
+                let (status_icon, status_style) = if is_stopping {
+                    ("◌ ", Style::default().fg(theme.warning))
+                } else if is_auto_suspended {
+                    // 空闲自动暂停：选中项目或访问其端口时恢复
+                    ("❄ ", Style::default().fg(theme.info))
+                } else if is_suspended {
+                    let color = if breach.is_some() {
+                        theme.error
+                    } else {
+                        theme.warning
+                    };
+                    ("⏸ ", Style::default().fg(color))
+                } else if breach.is_some() {
+                    ("● ", Style::default().fg(theme.error))
                 } else if is_running {
                     // 运行中时显示绿色圆点
                     ("● ", Style::default().fg(theme.success))
@@ -78,6 +119,43 @@ pub fn draw_sidebar(frame: &mut Frame, area: Rect, state: &AppState, theme: &The
                 // 格式: " 100%|999.9M" 约 12 个字符
                 let resource_info_width = if is_running { 12 } else { 0 };
 
+                // 问题计数（如 "✗2 ⚠1"），没有问题时不显示
+                let (errors, warnings) = project.problems.counts();
+                let mut problem_spans = Vec::new();
+                if is_stopping {
+                    problem_spans.push(Span::styled(
+                        format!("{} ", i18n.stopping()),
+                        Style::default().fg(theme.warning),
+                    ));
+                }
+                if errors > 0 {
+                    problem_spans.push(Span::styled(
+                        format!("✗{} ", errors),
+                        Style::default().fg(theme.error),
+                    ));
+                }
+                if warnings > 0 {
+                    problem_spans.push(Span::styled(
+                        format!("⚠{} ", warnings),
+                        Style::default().fg(theme.warning),
+                    ));
+                }
+                // 依赖需要安装时显示 ⬇（缺少 node_modules 为错误色，过期为警告色）
+                match project.dependencies {
+                    DependencyState::Installed => {}
+                    DependencyState::Missing => {
+                        problem_spans.push(Span::styled("⬇ ", Style::default().fg(theme.error)))
+                    }
+                    DependencyState::Stale => {
+                        problem_spans.push(Span::styled("⬇ ", Style::default().fg(theme.warning)))
+                    }
+                }
+                // Node 版本不满足项目要求时显示 ⬢
+                if project.node.as_ref().is_some_and(|n| n.is_mismatch()) {
+                    problem_spans.push(Span::styled("⬢ ", Style::default().fg(theme.warning)));
+                }
+                let problems_width: usize = problem_spans.iter().map(|s| s.width()).sum();
+
                 // 主样式
                 let style = if is_selected {
                     Style::default()
@@ -106,17 +184,25 @@ pub fn draw_sidebar(frame: &mut Frame, area: Rect, state: &AppState, theme: &The
                 };
 
                 // 资源信息样式 - CPU 使用黄色，内存使用青色
-                let cpu_style = if is_selected {
+                let over_limit = Style::default()
+                    .fg(theme.error)
+                    .add_modifier(Modifier::BOLD);
+                let mut cpu_style = if is_selected {
                     Style::default().fg(theme.warning).bg(theme.selection)
                 } else {
                     Style::default().fg(theme.warning)
                 };
 
-                let mem_style = if is_selected {
+                let mut mem_style = if is_selected {
                     Style::default().fg(theme.info).bg(theme.selection)
                 } else {
                     Style::default().fg(theme.info)
                 };
+                match breach {
+                    Some(LimitBreach::Cpu(_)) => cpu_style = cpu_style.patch(over_limit),
+                    Some(LimitBreach::Memory(_)) => mem_style = mem_style.patch(over_limit),
+                    None => {}
+                }
 
                 let separator_style = if is_selected {
                     Style::default().fg(theme.border).bg(theme.selection)
@@ -126,8 +212,19 @@ pub fn draw_sidebar(frame: &mut Frame, area: Rect, state: &AppState, theme: &The
 
                 // 计算项目名称的最大宽度
                 // 格式: "1 ▶ project_name ● 50%|128M"
-                let fixed_width =
-                    number_badge.len() + prefix.len() + 1 + status_icon.len() + resource_info_width;
+                // 工作树项目在名称前显示 ↳，表示是上方项目的变体
+                let worktree_mark = if project.worktree_of.is_some() {
+                    "↳ "
+                } else {
+                    ""
+                };
+                let fixed_width = number_badge.len()
+                    + prefix.len()
+                    + worktree_mark.chars().count()
+                    + 1
+                    + status_icon.len()
+                    + problems_width
+                    + resource_info_width;
                 let max_name_width = content_width.saturating_sub(fixed_width);
 
                 // 截断项目名称（如果需要）
@@ -142,9 +239,11 @@ pub fn draw_sidebar(frame: &mut Frame, area: Rect, state: &AppState, theme: &The
                 let mut spans = vec![
                     Span::styled(number_badge, badge_style),
                     Span::styled(prefix, selector_style),
+                    Span::styled(worktree_mark, badge_style),
                     Span::styled(truncated_name, style),
                     Span::styled(format!(" {}", status_icon), status_style),
                 ];
+                spans.extend(problem_spans);
 
                 // 如果有资源信息，分别添加 CPU 和内存（不同颜色）
                 if is_running {
@@ -158,7 +257,43 @@ pub fn draw_sidebar(frame: &mut Frame, area: Rect, state: &AppState, theme: &The
                     }
                 }
 
-                ListItem::new(Line::from(spans))
+                // 第二行：Git 分支、未提交修改标记和与上游的差异，右侧为内存趋势迷你图
+                let mut lines = vec![Line::from(spans)];
+                if sidebar_item_height(project) > 1 {
+                    let mut detail_spans = Vec::new();
+                    if let Some(ref git) = project.git {
+                        let branch_style = if is_selected {
+                            Style::default().fg(theme.info).bg(theme.selection)
+                        } else {
+                            Style::default().fg(theme.border)
+                        };
+                        detail_spans
+                            .push(Span::styled(format!("    ⎇ {}", git.branch), branch_style));
+                        if git.is_dirty() {
+                            detail_spans
+                                .push(Span::styled(" *", Style::default().fg(theme.warning)));
+                        }
+                        let ahead_behind = git.ahead_behind();
+                        if !ahead_behind.is_empty() {
+                            detail_spans.push(Span::styled(
+                                format!(" {}", ahead_behind),
+                                Style::default().fg(theme.info),
+                            ));
+                        }
+                    }
+                    if let Some(pty) = project.dev_pty.as_ref().filter(|_| is_running) {
+                        let sparkline = pty.resource_history.memory_sparkline(SPARKLINE_WIDTH);
+                        let used: usize = detail_spans.iter().map(|s| s.width()).sum();
+                        let spark_width = sparkline.chars().count();
+                        if used + spark_width < content_width {
+                            let padding = content_width - used - spark_width - 1;
+                            detail_spans.push(Span::raw(" ".repeat(padding)));
+                            detail_spans.push(Span::styled(sparkline, mem_style));
+                        }
+                    }
+                    lines.push(Line::from(detail_spans));
+                }
+                ListItem::new(lines)
             })
             .collect()
     };
@@ -1,6 +1,7 @@
 //! 状态栏模块
 
 use crate::app::{AppState, FocusArea};
+use crate::keymap::{Action, KeyPress, KeySequence, KeymapMode};
 use crate::ui::Theme;
 use ratatui::{
     layout::Rect,
@@ -11,30 +12,45 @@ use ratatui::{
 };
 
 /// 获取状态栏帮助项（用于计算高度和渲染）
-pub fn get_status_help_items(state: &AppState) -> Vec<(&'static str, &'static str)> {
+/// 按键文本来自当前 keymap，未绑定的动作不显示
+pub fn get_status_help_items(state: &AppState) -> Vec<(String, &'static str)> {
+    let i18n = state.i18n();
+    let keymap = &state.keymap;
+
+    // 组合键输入中：提示已输入的按键
+    if !state.pending_keys.is_empty() {
+        let keys: Vec<KeyPress> = state.pending_keys.iter().map(KeyPress::from).collect();
+        return vec![(KeySequence(keys).to_string(), i18n.pending_keys())];
+    }
+
     match state.focus {
-        FocusArea::Sidebar | FocusArea::DevTerminal => match state.language() {
-            crate::i18n::Language::English => vec![
-                ("Tab", "Project"),
-                ("Enter", "Shell"),
-                ("r/R", "Run"),
-                ("s", "Stop"),
-                ("z", "Layout"),
-                ("?", "Help"),
-            ],
-            crate::i18n::Language::Chinese => vec![
-                ("Tab", "切换项目"),
-                ("Enter", "终端"),
-                ("r/R", "运行"),
-                ("s", "停止"),
-                ("z", "布局"),
-                ("?", "帮助"),
-            ],
-        },
-        FocusArea::ShellTerminal => match state.language() {
-            crate::i18n::Language::English => vec![("Esc", "Back")],
-            crate::i18n::Language::Chinese => vec![("Esc", "返回")],
-        },
+        FocusArea::Sidebar | FocusArea::DevTerminal => {
+            let mode = KeymapMode::Normal;
+            let mut items = Vec::new();
+            for action in [Action::NextProject, Action::EnterShell] {
+                if let Some(key) = keymap.short_label(mode, action) {
+                    items.push((key, i18n.action_hint(action)));
+                }
+            }
+            // r/R 合并显示
+            let run_keys: Vec<String> = [Action::RunInDev, Action::RunInShell]
+                .iter()
+                .filter_map(|action| keymap.short_label(mode, *action))
+                .collect();
+            if !run_keys.is_empty() {
+                items.push((run_keys.join("/"), i18n.action_hint(Action::RunInDev)));
+            }
+            for action in [Action::StopDev, Action::ToggleLayout, Action::Help] {
+                if let Some(key) = keymap.short_label(mode, action) {
+                    items.push((key, i18n.action_hint(action)));
+                }
+            }
+            items
+        }
+        FocusArea::ShellTerminal => keymap
+            .short_label(KeymapMode::Shell, Action::FocusSidebar)
+            .map(|key| vec![(key, i18n.action_hint(Action::FocusSidebar))])
+            .unwrap_or_default(),
     }
 }
 
@@ -52,7 +68,7 @@ pub fn calculate_status_bar_height(state: &AppState, screen_width: usize) -> u16
 
 /// 根据可用宽度构建状态栏帮助提示行（自动换行）
 pub fn build_status_help_lines<'a>(
-    items: &[(&'a str, &'a str)],
+    items: &'a [(String, &'a str)],
     available_width: usize,
 ) -> Vec<Vec<(&'a str, &'a str)>> {
     let mut lines: Vec<Vec<(&'a str, &'a str)>> = Vec::new();
@@ -75,7 +91,7 @@ pub fn build_status_help_lines<'a>(
             current_width = 1;
         }
 
-        current_line.push((key, desc));
+        current_line.push((key.as_str(), desc));
         current_width += item_width;
     }
 
@@ -2,7 +2,7 @@
 //! 用于显示 PTY 输出，支持 ANSI 颜色
 
 use crate::i18n::I18n;
-use crate::pty::PtyHandle;
+use crate::pty::{find_urls, PtyHandle, UrlRef};
 use crate::ui::{draw_scrollbar, ScrollInfo, Theme};
 use ratatui::{
     layout::Rect,
@@ -12,7 +12,18 @@ use ratatui::{
     Frame,
 };
 
-/// 绘制终端面板
+/// 终端面板中可见的一行文本（每个字符对应一个单元格）
+#[derive(Debug, Clone)]
+pub struct VisibleRow {
+    /// 行首在屏幕上的位置
+    pub x: u16,
+    pub y: u16,
+    pub text: String,
+    /// 行中的链接
+    pub links: Vec<UrlRef>,
+}
+
+/// 绘制终端面板，返回可见的文本行（用于提示模式和点击链接）
 /// is_interactive: 是否是交互式终端（Shell），交互式终端在聚焦时显示光标
 #[allow(clippy::too_many_arguments)]
 pub fn draw_terminal_panel(
@@ -25,7 +36,8 @@ pub fn draw_terminal_panel(
     scroll_offset: usize,
     i18n: &I18n,
     theme: &Theme,
-) {
+) -> Vec<VisibleRow> {
+    let mut visible = Vec::new();
     let border_color = if is_focused {
         theme.border_focused
     } else {
@@ -92,37 +104,78 @@ pub fn draw_terminal_panel(
                 base_start.saturating_sub(scroll_offset)
             };
 
-            for row in start_row..screen_rows.min(start_row + visible_rows) {
-                let mut spans: Vec<Span> = Vec::new();
-                let mut current_text = String::new();
-                let mut current_style = Style::default();
+            // 每一行在光标所在行之上的行数（自动换行的折行不计），用于定位 OSC 8 超链接
+            let mut lines_above = vec![None; screen_rows];
+            let mut above = 0;
+            for row in (0..screen_rows.min(cursor_row as usize + 1)).rev() {
+                if row < cursor_row as usize && !screen.row_wrapped(row as u16) {
+                    above += 1;
+                }
+                lines_above[row] = Some(above);
+            }
 
+            for row in start_row..screen_rows.min(start_row + visible_rows) {
+                // 先收集每个单元格的字符和样式（宽字符的第二个单元格为 None）
+                let mut cells: Vec<(Option<char>, Style)> = Vec::new();
+                let mut last_style = Style::default();
                 for col in 0..screen_cols.min(inner.width as usize) {
-                    let cell = screen.cell(row as u16, col as u16);
-                    if let Some(cell) = cell {
+                    if let Some(cell) = screen.cell(row as u16, col as u16) {
+                        if cell.is_wide_continuation() {
+                            cells.push((None, last_style));
+                            continue;
+                        }
                         let char_content = cell.contents();
                         let char_to_add = if char_content.is_empty() {
                             ' '
                         } else {
                             char_content.chars().next().unwrap_or(' ')
                         };
+                        // 转换 vt100 颜色到 ratatui 颜色（按主题重映射 ANSI 调色板）
+                        last_style = vt100_to_ratatui_style(cell, theme);
+                        cells.push((Some(char_to_add), last_style));
+                    } else {
+                        cells.push((Some(' '), last_style));
+                    }
+                }
 
-                        // 转换 vt100 颜色到 ratatui 颜色
-                        let new_style = vt100_to_ratatui_style(cell);
-
-                        // 如果样式变化，保存当前 span 并开始新的
-                        if new_style != current_style && !current_text.is_empty() {
-                            spans.push(Span::styled(current_text.clone(), current_style));
-                            current_text.clear();
-                        }
+                // 链接（URL 和 OSC 8 超链接）加下划线，链接位置为显示列，与单元格一一对应
+                let row_text: String = cells.iter().filter_map(|(c, _)| *c).collect();
+                let links = find_urls(
+                    &row_text,
+                    &pty_handle.hyperlinks,
+                    lines_above.get(row).copied().flatten(),
+                );
+                for link in &links {
+                    for (_, style) in cells.iter_mut().skip(link.start).take(link.len) {
+                        *style = style.add_modifier(Modifier::UNDERLINED);
+                    }
+                }
 
-                        current_style = new_style;
-                        current_text.push(char_to_add);
-                    } else {
-                        current_text.push(' ');
+                // 相同样式的连续单元格合并为一个 span
+                let mut spans: Vec<Span> = Vec::new();
+                let mut current_text = String::new();
+                let mut current_style = Style::default();
+                for (c, style) in cells {
+                    let Some(c) = c else {
+                        continue;
+                    };
+                    if style != current_style && !current_text.is_empty() {
+                        spans.push(Span::styled(
+                            std::mem::take(&mut current_text),
+                            current_style,
+                        ));
                     }
+                    current_style = style;
+                    current_text.push(c);
                 }
 
+                visible.push(VisibleRow {
+                    x: inner.x,
+                    y: inner.y + (row - start_row) as u16,
+                    text: row_text,
+                    links,
+                });
+
                 // 添加剩余文本（保留行，即使是空的）
                 let trimmed = current_text.trim_end();
                 if !trimmed.is_empty() {
@@ -197,17 +250,23 @@ pub fn draw_terminal_panel(
 
         frame.render_widget(paragraph, inner);
     }
+    visible
 }
 
 /// 将 vt100 Cell 的颜色转换为 ratatui Style
-fn vt100_to_ratatui_style(cell: &vt100::Cell) -> Style {
+/// 默认前景色使用主题文字色，避免浅色主题下沿用宿主终端的浅色文字
+fn vt100_to_ratatui_style(cell: &vt100::Cell, theme: &Theme) -> Style {
     let mut style = Style::default();
 
     // 前景色
-    style = style.fg(vt100_color_to_ratatui(cell.fgcolor()));
+    let fg = match vt100_color_to_ratatui(cell.fgcolor()) {
+        Color::Reset => theme.fg,
+        color => theme.terminal_color(color),
+    };
+    style = style.fg(fg);
 
     // 背景色
-    let bg = vt100_color_to_ratatui(cell.bgcolor());
+    let bg = theme.terminal_color(vt100_color_to_ratatui(cell.bgcolor()));
     if bg != Color::Reset {
         style = style.bg(bg);
     }
@@ -1,55 +1,207 @@
 //! 主题模块
-//! 实现 Catppuccin Mocha 配色方案
+//! 内置 Catppuccin 四种配色和高对比度浅色主题，
+//! 并支持从 ~/.devpanel/themes/*.toml 加载自定义主题
 
 #![allow(dead_code)]
 
 use ratatui::style::Color;
 
-/// Catppuccin Mocha 配色
+/// Catppuccin 配色（单个 flavour 的全部颜色）
 /// 参考: https://github.com/catppuccin/catppuccin
-pub struct CatppuccinMocha;
-
-impl CatppuccinMocha {
+pub struct CatppuccinFlavor {
     // 基础色
-    pub const BASE: Color = Color::Rgb(30, 30, 46); // #1e1e2e
-    pub const MANTLE: Color = Color::Rgb(24, 24, 37); // #181825
-    pub const CRUST: Color = Color::Rgb(17, 17, 27); // #11111b
+    pub base: Color,
+    pub mantle: Color,
+    pub crust: Color,
 
     // 表面色
-    pub const SURFACE0: Color = Color::Rgb(49, 50, 68); // #313244
-    pub const SURFACE1: Color = Color::Rgb(69, 71, 90); // #45475a
-    pub const SURFACE2: Color = Color::Rgb(88, 91, 112); // #585b70
+    pub surface0: Color,
+    pub surface1: Color,
+    pub surface2: Color,
 
     // 覆盖色
-    pub const OVERLAY0: Color = Color::Rgb(108, 112, 134); // #6c7086
-    pub const OVERLAY1: Color = Color::Rgb(127, 132, 156); // #7f849c
-    pub const OVERLAY2: Color = Color::Rgb(147, 153, 178); // #9399b2
+    pub overlay0: Color,
+    pub overlay1: Color,
+    pub overlay2: Color,
 
     // 文字色
-    pub const TEXT: Color = Color::Rgb(205, 214, 244); // #cdd6f4
-    pub const SUBTEXT1: Color = Color::Rgb(186, 194, 222); // #bac2de
-    pub const SUBTEXT0: Color = Color::Rgb(166, 173, 200); // #a6adc8
+    pub text: Color,
+    pub subtext1: Color,
+    pub subtext0: Color,
 
     // 强调色
-    pub const ROSEWATER: Color = Color::Rgb(245, 224, 220); // #f5e0dc
-    pub const FLAMINGO: Color = Color::Rgb(242, 205, 205); // #f2cdcd
-    pub const PINK: Color = Color::Rgb(245, 194, 231); // #f5c2e7
-    pub const MAUVE: Color = Color::Rgb(203, 166, 247); // #cba6f7
-    pub const RED: Color = Color::Rgb(243, 139, 168); // #f38ba8
-    pub const MAROON: Color = Color::Rgb(235, 160, 172); // #eba0ac
-    pub const PEACH: Color = Color::Rgb(250, 179, 135); // #fab387
-    pub const YELLOW: Color = Color::Rgb(249, 226, 175); // #f9e2af
-    pub const GREEN: Color = Color::Rgb(166, 227, 161); // #a6e3a1
-    pub const TEAL: Color = Color::Rgb(148, 226, 213); // #94e2d5
-    pub const SKY: Color = Color::Rgb(137, 220, 235); // #89dceb
-    pub const SAPPHIRE: Color = Color::Rgb(116, 199, 236); // #74c7ec
-    pub const BLUE: Color = Color::Rgb(137, 180, 250); // #89b4fa
-    pub const LAVENDER: Color = Color::Rgb(180, 190, 254); // #b4befe
+    pub rosewater: Color,
+    pub flamingo: Color,
+    pub pink: Color,
+    pub mauve: Color,
+    pub red: Color,
+    pub maroon: Color,
+    pub peach: Color,
+    pub yellow: Color,
+    pub green: Color,
+    pub teal: Color,
+    pub sky: Color,
+    pub sapphire: Color,
+    pub blue: Color,
+    pub lavender: Color,
+}
+
+impl CatppuccinFlavor {
+    /// Latte（浅色）
+    pub const LATTE: Self = Self {
+        base: Color::Rgb(239, 241, 245),      // #eff1f5
+        mantle: Color::Rgb(230, 233, 239),    // #e6e9ef
+        crust: Color::Rgb(220, 224, 232),     // #dce0e8
+        surface0: Color::Rgb(204, 208, 218),  // #ccd0da
+        surface1: Color::Rgb(188, 192, 204),  // #bcc0cc
+        surface2: Color::Rgb(172, 176, 190),  // #acb0be
+        overlay0: Color::Rgb(156, 160, 176),  // #9ca0b0
+        overlay1: Color::Rgb(140, 143, 161),  // #8c8fa1
+        overlay2: Color::Rgb(124, 127, 147),  // #7c7f93
+        text: Color::Rgb(76, 79, 105),        // #4c4f69
+        subtext1: Color::Rgb(92, 95, 119),    // #5c5f77
+        subtext0: Color::Rgb(108, 111, 133),  // #6c6f85
+        rosewater: Color::Rgb(220, 138, 120), // #dc8a78
+        flamingo: Color::Rgb(221, 120, 120),  // #dd7878
+        pink: Color::Rgb(234, 118, 203),      // #ea76cb
+        mauve: Color::Rgb(136, 57, 239),      // #8839ef
+        red: Color::Rgb(210, 15, 57),         // #d20f39
+        maroon: Color::Rgb(230, 69, 83),      // #e64553
+        peach: Color::Rgb(254, 100, 11),      // #fe640b
+        yellow: Color::Rgb(223, 142, 29),     // #df8e1d
+        green: Color::Rgb(64, 160, 43),       // #40a02b
+        teal: Color::Rgb(23, 146, 153),       // #179299
+        sky: Color::Rgb(4, 165, 229),         // #04a5e5
+        sapphire: Color::Rgb(32, 159, 181),   // #209fb5
+        blue: Color::Rgb(30, 102, 245),       // #1e66f5
+        lavender: Color::Rgb(114, 135, 253),  // #7287fd
+    };
+
+    /// Frappé
+    pub const FRAPPE: Self = Self {
+        base: Color::Rgb(48, 52, 70),         // #303446
+        mantle: Color::Rgb(41, 44, 60),       // #292c3c
+        crust: Color::Rgb(35, 38, 52),        // #232634
+        surface0: Color::Rgb(65, 69, 89),     // #414559
+        surface1: Color::Rgb(81, 87, 109),    // #51576d
+        surface2: Color::Rgb(98, 104, 128),   // #626880
+        overlay0: Color::Rgb(115, 121, 148),  // #737994
+        overlay1: Color::Rgb(131, 139, 167),  // #838ba7
+        overlay2: Color::Rgb(148, 156, 187),  // #949cbb
+        text: Color::Rgb(198, 208, 245),      // #c6d0f5
+        subtext1: Color::Rgb(181, 191, 226),  // #b5bfe2
+        subtext0: Color::Rgb(165, 173, 206),  // #a5adce
+        rosewater: Color::Rgb(242, 213, 207), // #f2d5cf
+        flamingo: Color::Rgb(238, 190, 190),  // #eebebe
+        pink: Color::Rgb(244, 184, 228),      // #f4b8e4
+        mauve: Color::Rgb(202, 158, 230),     // #ca9ee6
+        red: Color::Rgb(231, 130, 132),       // #e78284
+        maroon: Color::Rgb(234, 153, 156),    // #ea999c
+        peach: Color::Rgb(239, 159, 118),     // #ef9f76
+        yellow: Color::Rgb(229, 200, 144),    // #e5c890
+        green: Color::Rgb(166, 209, 137),     // #a6d189
+        teal: Color::Rgb(129, 200, 190),      // #81c8be
+        sky: Color::Rgb(153, 209, 219),       // #99d1db
+        sapphire: Color::Rgb(133, 193, 220),  // #85c1dc
+        blue: Color::Rgb(140, 170, 238),      // #8caaee
+        lavender: Color::Rgb(186, 187, 241),  // #babbf1
+    };
+
+    /// Macchiato
+    pub const MACCHIATO: Self = Self {
+        base: Color::Rgb(36, 39, 58),         // #24273a
+        mantle: Color::Rgb(30, 32, 48),       // #1e2030
+        crust: Color::Rgb(24, 25, 38),        // #181926
+        surface0: Color::Rgb(54, 58, 79),     // #363a4f
+        surface1: Color::Rgb(73, 77, 100),    // #494d64
+        surface2: Color::Rgb(91, 96, 120),    // #5b6078
+        overlay0: Color::Rgb(110, 115, 141),  // #6e738d
+        overlay1: Color::Rgb(128, 135, 162),  // #8087a2
+        overlay2: Color::Rgb(147, 154, 183),  // #939ab7
+        text: Color::Rgb(202, 211, 245),      // #cad3f5
+        subtext1: Color::Rgb(184, 192, 224),  // #b8c0e0
+        subtext0: Color::Rgb(165, 173, 203),  // #a5adcb
+        rosewater: Color::Rgb(244, 219, 214), // #f4dbd6
+        flamingo: Color::Rgb(240, 198, 198),  // #f0c6c6
+        pink: Color::Rgb(245, 189, 230),      // #f5bde6
+        mauve: Color::Rgb(198, 160, 246),     // #c6a0f6
+        red: Color::Rgb(237, 135, 150),       // #ed8796
+        maroon: Color::Rgb(238, 153, 160),    // #ee99a0
+        peach: Color::Rgb(245, 169, 127),     // #f5a97f
+        yellow: Color::Rgb(238, 212, 159),    // #eed49f
+        green: Color::Rgb(166, 218, 149),     // #a6da95
+        teal: Color::Rgb(139, 213, 202),      // #8bd5ca
+        sky: Color::Rgb(145, 215, 227),       // #91d7e3
+        sapphire: Color::Rgb(125, 196, 228),  // #7dc4e4
+        blue: Color::Rgb(138, 173, 244),      // #8aadf4
+        lavender: Color::Rgb(183, 189, 248),  // #b7bdf8
+    };
+
+    /// Mocha（默认）
+    pub const MOCHA: Self = Self {
+        base: Color::Rgb(30, 30, 46),         // #1e1e2e
+        mantle: Color::Rgb(24, 24, 37),       // #181825
+        crust: Color::Rgb(17, 17, 27),        // #11111b
+        surface0: Color::Rgb(49, 50, 68),     // #313244
+        surface1: Color::Rgb(69, 71, 90),     // #45475a
+        surface2: Color::Rgb(88, 91, 112),    // #585b70
+        overlay0: Color::Rgb(108, 112, 134),  // #6c7086
+        overlay1: Color::Rgb(127, 132, 156),  // #7f849c
+        overlay2: Color::Rgb(147, 153, 178),  // #9399b2
+        text: Color::Rgb(205, 214, 244),      // #cdd6f4
+        subtext1: Color::Rgb(186, 194, 222),  // #bac2de
+        subtext0: Color::Rgb(166, 173, 200),  // #a6adc8
+        rosewater: Color::Rgb(245, 224, 220), // #f5e0dc
+        flamingo: Color::Rgb(242, 205, 205),  // #f2cdcd
+        pink: Color::Rgb(245, 194, 231),      // #f5c2e7
+        mauve: Color::Rgb(203, 166, 247),     // #cba6f7
+        red: Color::Rgb(243, 139, 168),       // #f38ba8
+        maroon: Color::Rgb(235, 160, 172),    // #eba0ac
+        peach: Color::Rgb(250, 179, 135),     // #fab387
+        yellow: Color::Rgb(249, 226, 175),    // #f9e2af
+        green: Color::Rgb(166, 227, 161),     // #a6e3a1
+        teal: Color::Rgb(148, 226, 213),      // #94e2d5
+        sky: Color::Rgb(137, 220, 235),       // #89dceb
+        sapphire: Color::Rgb(116, 199, 236),  // #74c7ec
+        blue: Color::Rgb(137, 180, 250),      // #89b4fa
+        lavender: Color::Rgb(180, 190, 254),  // #b4befe
+    };
+
+    /// 终端 ANSI 16 色映射（与 Catppuccin 官方终端配色一致）
+    /// 浅色 flavour 的黑/白需要互换深浅，否则在浅色背景上不可读
+    pub fn ansi_palette(&self, is_light: bool) -> [Color; 16] {
+        let (black, white, bright_black, bright_white) = if is_light {
+            (self.subtext1, self.surface2, self.subtext0, self.surface1)
+        } else {
+            (self.surface1, self.subtext1, self.surface2, self.subtext0)
+        };
+        [
+            black,
+            self.red,
+            self.green,
+            self.yellow,
+            self.blue,
+            self.pink,
+            self.teal,
+            white,
+            bright_black,
+            self.red,
+            self.green,
+            self.yellow,
+            self.blue,
+            self.pink,
+            self.teal,
+            bright_white,
+        ]
+    }
 }
 
 /// 应用主题
 /// 定义各 UI 元素使用的颜色
+#[derive(Debug, Clone, PartialEq)]
 pub struct Theme {
+    /// 主题名称（内置名称或主题文件名）
+    pub name: String,
     /// 背景色
     pub bg: Color,
     /// 前景色（文字）
@@ -76,6 +228,8 @@ pub struct Theme {
     pub error: Color,
     /// 信息色
     pub info: Color,
+    /// 终端 ANSI 16 色重映射（None 表示沿用宿主终端自身的配色）
+    pub ansi_palette: Option<[Color; 16]>,
 }
 
 impl Default for Theme {
@@ -85,22 +239,109 @@ impl Default for Theme {
 }
 
 impl Theme {
+    /// 默认主题名称
+    pub const DEFAULT_NAME: &'static str = "catppuccin-mocha";
+
+    /// 所有内置主题名称
+    pub const BUILTIN_NAMES: [&'static str; 5] = [
+        "catppuccin-mocha",
+        "catppuccin-macchiato",
+        "catppuccin-frappe",
+        "catppuccin-latte",
+        "high-contrast-light",
+    ];
+
+    /// 根据名称获取内置主题
+    pub fn builtin(name: &str) -> Option<Self> {
+        match name {
+            "catppuccin-mocha" => Some(Self::catppuccin_mocha()),
+            "catppuccin-macchiato" => {
+                Some(Self::catppuccin(name, &CatppuccinFlavor::MACCHIATO, false))
+            }
+            "catppuccin-frappe" => Some(Self::catppuccin(name, &CatppuccinFlavor::FRAPPE, false)),
+            "catppuccin-latte" => Some(Self::catppuccin(name, &CatppuccinFlavor::LATTE, true)),
+            "high-contrast-light" => Some(Self::high_contrast_light()),
+            _ => None,
+        }
+    }
+
+    /// 由 Catppuccin flavour 构建主题
+    fn catppuccin(name: &str, flavor: &CatppuccinFlavor, is_light: bool) -> Self {
+        Self {
+            name: name.to_string(),
+            bg: flavor.base,
+            fg: flavor.text,
+            border: flavor.surface1,
+            border_focused: flavor.lavender,
+            selection: flavor.surface0,
+            selection_fg: flavor.text,
+            status_bg: flavor.mantle,
+            status_fg: flavor.subtext1,
+            title: flavor.mauve,
+            success: flavor.green,
+            warning: flavor.yellow,
+            error: flavor.red,
+            info: flavor.blue,
+            ansi_palette: Some(flavor.ansi_palette(is_light)),
+        }
+    }
+
     /// 创建 Catppuccin Mocha 主题
+    /// 不重映射 ANSI 颜色，保持宿主终端的配色
     pub fn catppuccin_mocha() -> Self {
         Self {
-            bg: CatppuccinMocha::BASE,
-            fg: CatppuccinMocha::TEXT,
-            border: CatppuccinMocha::SURFACE1,
-            border_focused: CatppuccinMocha::LAVENDER,
-            selection: CatppuccinMocha::SURFACE0,
-            selection_fg: CatppuccinMocha::TEXT,
-            status_bg: CatppuccinMocha::MANTLE,
-            status_fg: CatppuccinMocha::SUBTEXT1,
-            title: CatppuccinMocha::MAUVE,
-            success: CatppuccinMocha::GREEN,
-            warning: CatppuccinMocha::YELLOW,
-            error: CatppuccinMocha::RED,
-            info: CatppuccinMocha::BLUE,
+            ansi_palette: None,
+            ..Self::catppuccin(Self::DEFAULT_NAME, &CatppuccinFlavor::MOCHA, false)
+        }
+    }
+
+    /// 创建高对比度浅色主题
+    /// 用于投影仪和明亮环境，所有文字颜色在白色背景上都有足够对比度
+    pub fn high_contrast_light() -> Self {
+        let black = Color::Rgb(0, 0, 0);
+        let white = Color::Rgb(255, 255, 255);
+        Self {
+            name: "high-contrast-light".to_string(),
+            bg: white,
+            fg: black,
+            border: Color::Rgb(89, 89, 89),
+            border_focused: Color::Rgb(0, 0, 192),
+            selection: Color::Rgb(0, 51, 160),
+            selection_fg: white,
+            status_bg: Color::Rgb(230, 230, 230),
+            status_fg: black,
+            title: Color::Rgb(90, 0, 160),
+            success: Color::Rgb(0, 100, 0),
+            warning: Color::Rgb(138, 90, 0),
+            error: Color::Rgb(176, 0, 0),
+            info: Color::Rgb(0, 63, 191),
+            ansi_palette: Some([
+                black,
+                Color::Rgb(176, 0, 0),
+                Color::Rgb(0, 100, 0),
+                Color::Rgb(122, 92, 0),
+                Color::Rgb(0, 51, 204),
+                Color::Rgb(139, 0, 139),
+                Color::Rgb(0, 107, 107),
+                Color::Rgb(89, 89, 89),
+                Color::Rgb(64, 64, 64),
+                Color::Rgb(208, 0, 0),
+                Color::Rgb(0, 122, 0),
+                Color::Rgb(138, 109, 0),
+                Color::Rgb(0, 64, 255),
+                Color::Rgb(160, 0, 160),
+                Color::Rgb(0, 127, 127),
+                Color::Rgb(38, 38, 38),
+            ]),
+        }
+    }
+
+    /// 将终端输出的颜色映射到主题颜色
+    /// 默认前景色使用主题文字色，ANSI 0-15 按主题调色板重映射
+    pub fn terminal_color(&self, color: Color) -> Color {
+        match (color, &self.ansi_palette) {
+            (Color::Indexed(idx), Some(palette)) if idx < 16 => palette[idx as usize],
+            _ => color,
         }
     }
 }
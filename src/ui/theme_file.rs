@@ -0,0 +1,185 @@
+//! 主题文件模块
+//! 负责解析 ~/.devpanel/themes/*.toml 主题文件
+//!
+//! 主题文件示例：
+//! ```toml
+//! base = "catppuccin-latte"   # 可选，未指定的字段沿用该内置主题
+//! bg = "#ffffff"
+//! border_focused = "#0000c0"
+//! ansi_palette = ["#000000", "#b00000", ...]  # 可选，必须是 16 个颜色
+//! ```
+
+use super::Theme;
+use crate::config::get_themes_dir;
+use anyhow::{bail, Context};
+use ratatui::style::Color;
+use serde::Deserialize;
+use std::path::Path;
+
+/// 主题文件结构（所有字段可选，未指定时沿用 base 主题）
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ThemeFile {
+    base: Option<String>,
+    bg: Option<String>,
+    fg: Option<String>,
+    border: Option<String>,
+    border_focused: Option<String>,
+    selection: Option<String>,
+    selection_fg: Option<String>,
+    status_bg: Option<String>,
+    status_fg: Option<String>,
+    title: Option<String>,
+    success: Option<String>,
+    warning: Option<String>,
+    error: Option<String>,
+    info: Option<String>,
+    ansi_palette: Option<Vec<String>>,
+}
+
+/// 解析颜色字符串
+/// 支持 "#rrggbb"、"reset" 和 0-255 的索引色
+pub fn parse_color(value: &str) -> anyhow::Result<Color> {
+    let value = value.trim();
+    if let Some(hex) = value.strip_prefix('#') {
+        if hex.len() != 6 {
+            bail!("invalid color '{}': expected #rrggbb", value);
+        }
+        let channel = |range: std::ops::Range<usize>| {
+            u8::from_str_radix(&hex[range], 16)
+                .with_context(|| format!("invalid color '{}'", value))
+        };
+        return Ok(Color::Rgb(channel(0..2)?, channel(2..4)?, channel(4..6)?));
+    }
+    if value.eq_ignore_ascii_case("reset") {
+        return Ok(Color::Reset);
+    }
+    value
+        .parse::<u8>()
+        .map(Color::Indexed)
+        .with_context(|| format!("invalid color '{}'", value))
+}
+
+/// 从 TOML 内容解析主题
+pub fn parse_theme(name: &str, content: &str) -> anyhow::Result<Theme> {
+    let file: ThemeFile = toml::from_str(content)?;
+
+    let mut theme = match file.base.as_deref() {
+        Some(base) => {
+            Theme::builtin(base).with_context(|| format!("unknown base theme '{}'", base))?
+        }
+        None => Theme::default(),
+    };
+    theme.name = name.to_string();
+
+    let fields = [
+        (&file.bg, &mut theme.bg),
+        (&file.fg, &mut theme.fg),
+        (&file.border, &mut theme.border),
+        (&file.border_focused, &mut theme.border_focused),
+        (&file.selection, &mut theme.selection),
+        (&file.selection_fg, &mut theme.selection_fg),
+        (&file.status_bg, &mut theme.status_bg),
+        (&file.status_fg, &mut theme.status_fg),
+        (&file.title, &mut theme.title),
+        (&file.success, &mut theme.success),
+        (&file.warning, &mut theme.warning),
+        (&file.error, &mut theme.error),
+        (&file.info, &mut theme.info),
+    ];
+    for (value, slot) in fields {
+        if let Some(value) = value {
+            *slot = parse_color(value)?;
+        }
+    }
+
+    if let Some(palette) = &file.ansi_palette {
+        if palette.len() != 16 {
+            bail!("ansi_palette must have 16 colors, got {}", palette.len());
+        }
+        let mut colors = [Color::Reset; 16];
+        for (slot, value) in colors.iter_mut().zip(palette) {
+            *slot = parse_color(value)?;
+        }
+        theme.ansi_palette = Some(colors);
+    }
+
+    Ok(theme)
+}
+
+/// 从主题文件加载主题（主题名称取文件名）
+pub fn load_theme_file(path: &Path) -> anyhow::Result<Theme> {
+    let name = path
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or("custom");
+    let content = std::fs::read_to_string(path)?;
+    parse_theme(name, &content).with_context(|| format!("{}", path.display()))
+}
+
+/// 根据名称加载主题：优先使用主题目录中的同名文件，其次是内置主题
+pub fn load_theme(name: &str) -> anyhow::Result<Theme> {
+    let path = get_themes_dir().join(format!("{}.toml", name));
+    if path.exists() {
+        return load_theme_file(&path);
+    }
+    Theme::builtin(name).with_context(|| format!("theme '{}' not found", name))
+}
+
+/// 列出所有可用主题名称（内置主题在前，主题目录中的文件按名称排序）
+pub fn available_themes() -> Vec<String> {
+    let mut names: Vec<String> = Theme::BUILTIN_NAMES.iter().map(|s| s.to_string()).collect();
+
+    let mut custom: Vec<String> = std::fs::read_dir(get_themes_dir())
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "toml"))
+        .filter_map(|path| path.file_stem()?.to_str().map(str::to_string))
+        .filter(|name| !names.contains(name))
+        .collect();
+    custom.sort();
+
+    names.extend(custom);
+    names
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_color() {
+        assert_eq!(parse_color("#1e1e2e").unwrap(), Color::Rgb(30, 30, 46));
+        assert_eq!(parse_color("reset").unwrap(), Color::Reset);
+        assert_eq!(parse_color("208").unwrap(), Color::Indexed(208));
+        assert!(parse_color("#12345").is_err());
+        assert!(parse_color("blue-ish").is_err());
+    }
+
+    #[test]
+    fn test_parse_theme_overrides_base() {
+        let theme = parse_theme(
+            "projector",
+            r##"
+                base = "catppuccin-latte"
+                bg = "#ffffff"
+            "##,
+        )
+        .unwrap();
+        let latte = Theme::builtin("catppuccin-latte").unwrap();
+
+        assert_eq!(theme.name, "projector");
+        assert_eq!(theme.bg, Color::Rgb(255, 255, 255));
+        assert_eq!(theme.fg, latte.fg);
+        assert_eq!(theme.ansi_palette, latte.ansi_palette);
+    }
+
+    #[test]
+    fn test_parse_theme_rejects_bad_palette() {
+        assert!(parse_theme("bad", r##"ansi_palette = ["#000000"]"##).is_err());
+        assert!(parse_theme("bad", r#"base = "solarized""#).is_err());
+        assert!(parse_theme("bad", r##"background = "#000000""##).is_err());
+    }
+}
@@ -0,0 +1,86 @@
+//! 主题选择弹窗组件
+//! 上下移动时实时预览主题
+
+use crate::app::AppState;
+use crate::ui::{centered_rect, Theme};
+use ratatui::{
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, List, ListItem, ListState, Paragraph},
+    Frame,
+};
+
+/// 绘制主题选择弹窗
+pub fn draw_theme_picker(frame: &mut Frame, state: &AppState, theme: &Theme) {
+    let area = centered_rect(40, 50, frame.area());
+    let i18n = state.i18n();
+
+    frame.render_widget(Clear, area);
+
+    let block = Block::default()
+        .title(i18n.theme_picker_title())
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(theme.info))
+        .style(Style::default().bg(theme.bg));
+
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let items: Vec<ListItem> = state
+        .theme_picker
+        .names
+        .iter()
+        .enumerate()
+        .map(|(idx, name)| {
+            let is_selected = idx == state.theme_picker.selected;
+            let style = if is_selected {
+                Style::default()
+                    .fg(theme.selection_fg)
+                    .bg(theme.selection)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(theme.fg)
+            };
+            let prefix = if is_selected { "▸ " } else { "  " };
+            let builtin = if Theme::builtin(name).is_some() {
+                ""
+            } else {
+                " *"
+            };
+            ListItem::new(Line::from(vec![
+                Span::styled(prefix, style),
+                Span::styled(name.as_str(), style),
+                Span::styled(builtin, Style::default().fg(theme.info)),
+            ]))
+        })
+        .collect();
+
+    let list_area = Rect {
+        height: inner.height.saturating_sub(2),
+        ..inner
+    };
+    let mut list_state = ListState::default();
+    list_state.select(Some(state.theme_picker.selected));
+    frame.render_stateful_widget(List::new(items), list_area, &mut list_state);
+
+    // 底部提示：按键使用信息色，描述使用正常前景色
+    let hint_area = Rect {
+        y: inner.y + inner.height.saturating_sub(1),
+        height: 1,
+        ..inner
+    };
+    let key_style = Style::default().fg(theme.info).add_modifier(Modifier::BOLD);
+    let desc_style = Style::default().fg(theme.fg);
+    let hint = Paragraph::new(Line::from(vec![
+        Span::styled("↑/↓", key_style),
+        Span::styled(i18n.theme_hint_preview(), desc_style),
+        Span::styled("Enter", key_style),
+        Span::styled(i18n.theme_hint_apply(), desc_style),
+        Span::styled("Esc", key_style),
+        Span::styled(i18n.theme_hint_cancel(), desc_style),
+    ]))
+    .alignment(ratatui::layout::Alignment::Center);
+    frame.render_widget(hint, hint_area);
+}